@@ -0,0 +1,93 @@
+//! Minimal MCP server over stdio, for trying out `agent::crew::mcp`
+//!
+//! Offers two tools, `echo` and `add`. Build it and point an agent at it:
+//!
+//! ```yaml
+//! mcp_servers:
+//!   - name: demo
+//!     command: target/debug/examples/mcp_server
+//! ```
+
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    let mut stdout = tokio::io::stdout();
+
+    while let Some(line) = lines.next_line().await? {
+        let Ok(request) = serde_json::from_str::<Value>(&line) else {
+            continue;
+        };
+        // Notifications need no response
+        let Some(id) = request.get("id").cloned() else {
+            continue;
+        };
+
+        let response = match handle(&request["method"], &request["params"]) {
+            Ok(result) => json!({"jsonrpc": "2.0", "id": id, "result": result}),
+            Err(message) => json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": {"code": -32601, "message": message}
+            }),
+        };
+        stdout
+            .write_all(format!("{}\n", response).as_bytes())
+            .await?;
+        stdout.flush().await?;
+    }
+    Ok(())
+}
+
+fn handle(method: &Value, params: &Value) -> Result<Value, String> {
+    match method.as_str().unwrap_or_default() {
+        "initialize" => Ok(json!({
+            "protocolVersion": "2025-03-26",
+            "capabilities": {"tools": {}},
+            "serverInfo": {"name": "example", "version": env!("CARGO_PKG_VERSION")}
+        })),
+        "ping" => Ok(json!({})),
+        "tools/list" => Ok(json!({
+            "tools": [
+                {
+                    "name": "echo",
+                    "description": "Echo the given text back",
+                    "inputSchema": {
+                        "type": "object",
+                        "properties": {"text": {"type": "string"}},
+                        "required": ["text"]
+                    }
+                },
+                {
+                    "name": "add",
+                    "description": "Add two numbers",
+                    "inputSchema": {
+                        "type": "object",
+                        "properties": {"a": {"type": "number"}, "b": {"type": "number"}},
+                        "required": ["a", "b"]
+                    }
+                }
+            ]
+        })),
+        "tools/call" => Ok(call_tool(&params["name"], &params["arguments"])),
+        other => Err(format!("Method not found: {}", other)),
+    }
+}
+
+fn call_tool(name: &Value, arguments: &Value) -> Value {
+    let text = match name.as_str().unwrap_or_default() {
+        "echo" => arguments["text"].as_str().unwrap_or_default().to_string(),
+        "add" => match (arguments["a"].as_f64(), arguments["b"].as_f64()) {
+            (Some(a), Some(b)) => (a + b).to_string(),
+            _ => return tool_error("a and b must be numbers"),
+        },
+        other => return tool_error(&format!("Unknown tool: {}", other)),
+    };
+    json!({"content": [{"type": "text", "text": text}]})
+}
+
+fn tool_error(message: &str) -> Value {
+    json!({"content": [{"type": "text", "text": message}], "isError": true})
+}
@@ -0,0 +1,499 @@
+//! Schema validation of tool arguments
+//!
+//! Tool arguments come from the model, so they are checked against the
+//! tool's JSON schema (`ToolDefinition::parameters`, `args_schema` for crew
+//! tools) before the tool runs. Violations are reported with the path of each
+//! offending value, e.g. `$.limit: expected integer, got string`, and fed
+//! back to the model as the observation so it can correct the call.
+//!
+//! Before validating, arguments can be normalized:
+//! - **coercion**: `"5"` → `5` for numbers, `"true"` → `true` for booleans,
+//!   numbers → strings, a single value → one-element array, a JSON object
+//!   sent as a string → the object, enum values matched case-insensitively
+//! - **defaults**: missing properties with a `default` in the schema are
+//!   filled in
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use agent::arguments::{prepare_arguments, ArgumentOptions};
+//!
+//! let schema = json!({
+//!     "type": "object",
+//!     "properties": {
+//!         "query": { "type": "string" },
+//!         "limit": { "type": "integer", "default": 5 }
+//!     },
+//!     "required": ["query"]
+//! });
+//!
+//! let args = prepare_arguments("search", &schema, json!({"query": "pump"}), &ArgumentOptions::default())?;
+//! assert_eq!(args["limit"], 5);
+//! ```
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Number, Value};
+use thiserror::Error;
+
+use crate::structured::{resolve_ref, validate_in};
+
+// ============================================================================
+// OPTIONS
+// ============================================================================
+
+/// How tool arguments are checked before execution
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ArgumentOptions {
+    /// Reject arguments that do not match the schema
+    pub validate: bool,
+
+    /// Convert mistyped values to the type the schema expects
+    pub coerce: bool,
+
+    /// Fill in missing properties from schema defaults
+    pub apply_defaults: bool,
+}
+
+impl Default for ArgumentOptions {
+    fn default() -> Self {
+        Self {
+            validate: true,
+            coerce: true,
+            apply_defaults: true,
+        }
+    }
+}
+
+impl ArgumentOptions {
+    /// Validate only; arguments are passed on unchanged
+    pub fn strict() -> Self {
+        Self {
+            validate: true,
+            coerce: false,
+            apply_defaults: false,
+        }
+    }
+
+    /// Pass arguments through untouched
+    pub fn disabled() -> Self {
+        Self {
+            validate: false,
+            coerce: false,
+            apply_defaults: false,
+        }
+    }
+}
+
+// ============================================================================
+// ERRORS
+// ============================================================================
+
+/// Tool arguments that do not match the tool's schema
+#[derive(Error, Debug, Clone, PartialEq)]
+#[error("Invalid arguments for tool '{tool_name}': {}", errors.join("; "))]
+pub struct InvalidArguments {
+    /// Name of the tool
+    pub tool_name: String,
+
+    /// One message per violation, prefixed with the JSON path
+    pub errors: Vec<String>,
+}
+
+// ============================================================================
+// PREPARATION
+// ============================================================================
+
+/// Normalize and validate the arguments of a tool call
+///
+/// Returns the arguments to pass to the tool, or every schema violation
+/// found after normalization.
+pub fn prepare_arguments(
+    tool_name: &str,
+    schema: &Value,
+    args: Value,
+    options: &ArgumentOptions,
+) -> Result<Value, InvalidArguments> {
+    let args = if options.coerce || options.apply_defaults {
+        normalize(schema, schema, args, options)
+    } else {
+        args
+    };
+
+    if options.validate {
+        let errors = validate_in(schema, schema, &args);
+        if !errors.is_empty() {
+            return Err(InvalidArguments {
+                tool_name: tool_name.to_string(),
+                errors,
+            });
+        }
+    }
+
+    Ok(args)
+}
+
+/// Coerce mistyped values and apply defaults, recursively
+fn normalize(root: &Value, schema: &Value, value: Value, options: &ArgumentOptions) -> Value {
+    let Some(object) = schema.as_object() else {
+        return value;
+    };
+
+    let mut value = value;
+
+    if let Some(target) = object
+        .get("$ref")
+        .and_then(Value::as_str)
+        .and_then(|r| resolve_ref(root, r))
+    {
+        value = normalize(root, target, value, options);
+    }
+
+    if let Some(all) = object.get("allOf").and_then(Value::as_array) {
+        for sub in all {
+            value = normalize(root, sub, value, options);
+        }
+    }
+
+    // Take the first alternative the value can be made to match
+    for keyword in ["anyOf", "oneOf"] {
+        if let Some(alternatives) = object.get(keyword).and_then(Value::as_array) {
+            if alternatives
+                .iter()
+                .any(|alt| validate_in(root, alt, &value).is_empty())
+            {
+                break;
+            }
+            if let Some(normalized) = alternatives
+                .iter()
+                .map(|alt| (alt, normalize(root, alt, value.clone(), options)))
+                .find(|(alt, v)| validate_in(root, alt, v).is_empty())
+                .map(|(_, v)| v)
+            {
+                value = normalized;
+                break;
+            }
+        }
+    }
+
+    if options.coerce {
+        value = coerce_type(object.get("type"), value);
+        if let Some(allowed) = object.get("enum").and_then(Value::as_array) {
+            value = coerce_enum(allowed, value);
+        }
+    }
+
+    match value {
+        Value::Object(map) => Value::Object(normalize_object(root, object, map, options)),
+        Value::Array(items) => match object.get("items") {
+            Some(item_schema) => Value::Array(
+                items
+                    .into_iter()
+                    .map(|item| normalize(root, item_schema, item, options))
+                    .collect(),
+            ),
+            None => Value::Array(items),
+        },
+        other => other,
+    }
+}
+
+fn normalize_object(
+    root: &Value,
+    schema: &Map<String, Value>,
+    mut map: Map<String, Value>,
+    options: &ArgumentOptions,
+) -> Map<String, Value> {
+    let Some(properties) = schema.get("properties").and_then(Value::as_object) else {
+        return map;
+    };
+
+    for (key, property) in properties {
+        match map.remove(key) {
+            Some(item) => {
+                let item = normalize(root, property, item, options);
+                map.insert(key.clone(), item);
+            }
+            None if options.apply_defaults => {
+                if let Some(default) = default_of(root, property) {
+                    map.insert(key.clone(), default.clone());
+                }
+            }
+            None => {}
+        }
+    }
+
+    map
+}
+
+/// Default of a property schema, following `$ref`s
+fn default_of<'a>(root: &'a Value, schema: &'a Value) -> Option<&'a Value> {
+    schema.get("default").or_else(|| {
+        schema
+            .get("$ref")
+            .and_then(Value::as_str)
+            .and_then(|r| resolve_ref(root, r))
+            .and_then(|target| target.get("default"))
+    })
+}
+
+// ============================================================================
+// COERCION
+// ============================================================================
+
+/// Convert `value` to one of the expected types, if it matches none of them
+fn coerce_type(expected: Option<&Value>, value: Value) -> Value {
+    let types: Vec<&str> = match expected {
+        Some(Value::String(t)) => vec![t.as_str()],
+        Some(Value::Array(ts)) => ts.iter().filter_map(Value::as_str).collect(),
+        _ => return value,
+    };
+
+    if types.iter().any(|t| has_type(t, &value)) {
+        return value;
+    }
+
+    types
+        .iter()
+        .find_map(|t| convert(t, &value))
+        .unwrap_or(value)
+}
+
+fn has_type(expected: &str, value: &Value) -> bool {
+    match expected {
+        "null" => value.is_null(),
+        "boolean" => value.is_boolean(),
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => {
+            value.is_i64() || value.is_u64() || value.as_f64().is_some_and(|n| n.fract() == 0.0)
+        }
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        _ => true,
+    }
+}
+
+fn convert(expected: &str, value: &Value) -> Option<Value> {
+    match (expected, value) {
+        ("integer", Value::String(s)) => {
+            let s = s.trim();
+            s.parse::<i64>()
+                .map(Value::from)
+                .ok()
+                .or_else(|| s.parse::<u64>().map(Value::from).ok())
+                .or_else(|| {
+                    s.parse::<f64>()
+                        .ok()
+                        .filter(|n| n.fract() == 0.0 && n.abs() < i64::MAX as f64)
+                        .map(|n| Value::from(n as i64))
+                })
+        }
+        ("number", Value::String(s)) => {
+            let s = s.trim();
+            s.parse::<i64>().map(Value::from).ok().or_else(|| {
+                s.parse::<f64>()
+                    .ok()
+                    .and_then(Number::from_f64)
+                    .map(Value::Number)
+            })
+        }
+        ("boolean", Value::String(s)) => match s.trim().to_lowercase().as_str() {
+            "true" => Some(Value::Bool(true)),
+            "false" => Some(Value::Bool(false)),
+            _ => None,
+        },
+        ("string", Value::Number(n)) => Some(Value::String(n.to_string())),
+        ("string", Value::Bool(b)) => Some(Value::String(b.to_string())),
+        ("object", Value::Null) => Some(Value::Object(Map::new())),
+        ("object", Value::String(s)) | ("array", Value::String(s)) => {
+            serde_json::from_str::<Value>(s.trim())
+                .ok()
+                .filter(|parsed| has_type(expected, parsed))
+                .or_else(|| (expected == "array").then(|| Value::Array(vec![value.clone()])))
+        }
+        ("array", Value::Null) => None,
+        ("array", other) => Some(Value::Array(vec![other.clone()])),
+        _ => None,
+    }
+}
+
+/// Match a string to an enum value ignoring case
+fn coerce_enum(allowed: &[Value], value: Value) -> Value {
+    let Value::String(s) = &value else {
+        return value;
+    };
+    if allowed.contains(&value) {
+        return value;
+    }
+    allowed
+        .iter()
+        .find(|a| a.as_str().is_some_and(|a| a.eq_ignore_ascii_case(s.trim())))
+        .cloned()
+        .unwrap_or(value)
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn search_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "query": { "type": "string" },
+                "limit": { "type": "integer", "default": 5 },
+                "max_price": { "type": ["number", "null"] },
+                "in_stock": { "type": "boolean" },
+                "tags": { "type": "array", "items": { "type": "string" } },
+                "sort": { "type": "string", "enum": ["price", "name"] }
+            },
+            "required": ["query"],
+            "additionalProperties": false
+        })
+    }
+
+    #[test]
+    fn test_valid_arguments_pass_and_get_defaults() {
+        let args = prepare_arguments(
+            "search",
+            &search_schema(),
+            json!({"query": "pump"}),
+            &ArgumentOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(args, json!({"query": "pump", "limit": 5}));
+    }
+
+    #[test]
+    fn test_coerces_mistyped_values() {
+        let args = prepare_arguments(
+            "search",
+            &search_schema(),
+            json!({
+                "query": 42,
+                "limit": "10",
+                "max_price": "1500.50",
+                "in_stock": "TRUE",
+                "tags": "garden",
+                "sort": "Price"
+            }),
+            &ArgumentOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(args["query"], "42");
+        assert_eq!(args["limit"], 10);
+        assert_eq!(args["max_price"], 1500.5);
+        assert_eq!(args["in_stock"], true);
+        assert_eq!(args["tags"], json!(["garden"]));
+        assert_eq!(args["sort"], "price");
+    }
+
+    #[test]
+    fn test_parses_stringified_object() {
+        let args = prepare_arguments(
+            "search",
+            &search_schema(),
+            json!("{\"query\": \"pump\"}"),
+            &ArgumentOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(args["query"], "pump");
+    }
+
+    #[test]
+    fn test_reports_every_violation_with_path() {
+        let err = prepare_arguments(
+            "search",
+            &search_schema(),
+            json!({"limit": "ten", "color": "red"}),
+            &ArgumentOptions::default(),
+        )
+        .unwrap_err();
+
+        assert_eq!(err.tool_name, "search");
+        assert!(err
+            .errors
+            .contains(&"$: missing required property 'query'".to_string()));
+        assert!(err
+            .errors
+            .contains(&"$.limit: expected integer, got string".to_string()));
+        assert!(err
+            .errors
+            .contains(&"$.color: unknown property".to_string()));
+        assert!(err
+            .to_string()
+            .starts_with("Invalid arguments for tool 'search': "));
+    }
+
+    #[test]
+    fn test_strict_does_not_coerce() {
+        let err = prepare_arguments(
+            "search",
+            &search_schema(),
+            json!({"query": "pump", "limit": "10"}),
+            &ArgumentOptions::strict(),
+        )
+        .unwrap_err();
+
+        assert_eq!(err.errors, vec!["$.limit: expected integer, got string"]);
+    }
+
+    #[test]
+    fn test_disabled_passes_through() {
+        let args = json!({"limit": "ten"});
+        let prepared = prepare_arguments(
+            "search",
+            &search_schema(),
+            args.clone(),
+            &ArgumentOptions::disabled(),
+        )
+        .unwrap();
+
+        assert_eq!(prepared, args);
+    }
+
+    #[test]
+    fn test_follows_refs_in_nested_schemas() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "item": { "$ref": "#/definitions/Item" }
+            },
+            "definitions": {
+                "Item": {
+                    "type": "object",
+                    "properties": {
+                        "quantity": { "type": "integer", "minimum": 1, "default": 1 }
+                    }
+                }
+            }
+        });
+
+        let args = prepare_arguments(
+            "quote",
+            &schema,
+            json!({"item": {}}),
+            &ArgumentOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(args, json!({"item": {"quantity": 1}}));
+
+        let err = prepare_arguments(
+            "quote",
+            &schema,
+            json!({"item": {"quantity": "0"}}),
+            &ArgumentOptions::default(),
+        )
+        .unwrap_err();
+        assert_eq!(err.errors, vec!["$.item.quantity: must be at least 1"]);
+    }
+}
@@ -0,0 +1,259 @@
+//! Serializable checkpoints for pausable, resumable ReAct runs
+//!
+//! A [`ReActCheckpoint`] holds everything [`ReActAgent`](crate::ReActAgent)
+//! needs to continue a run: the query, retrieved context, scratchpad, trace,
+//! iteration count, usage so far, and the tool calls waiting for approval.
+//!
+//! Runs pause before tools listed in `ReActConfig::pause_before_tools`. The
+//! checkpoint can be stored (e.g. in Redis by the worker) and the run resumed
+//! later with an [`ApprovalDecision`]. When a [`CheckpointStore`] is attached,
+//! the agent also saves a checkpoint after every iteration, so a run can be
+//! picked up again after a worker restart, and deletes it once the run
+//! finishes.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use agent::checkpoint::ApprovalDecision;
+//! use agent::react_agent::ReActOutcome;
+//!
+//! let config = ReActConfig::builder()
+//!     .pause_before_tool("create_quote")
+//!     .build();
+//! let mut agent = ReActAgent::new(config).with_tools(tools);
+//!
+//! if let ReActOutcome::Paused(checkpoint) = agent.start("Quote 10 pumps", None).await? {
+//!     let json = serde_json::to_string(&checkpoint)?;
+//!     // ... later, possibly in another process
+//!     let checkpoint = serde_json::from_str(&json)?;
+//!     let outcome = agent.resume(checkpoint, ApprovalDecision::Approve).await?;
+//! }
+//! ```
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use uuid::Uuid;
+
+use crate::react_agent::{ReActError, ReActStep, RetrievedSource, ToolInvocation};
+use crate::usage::UsageReport;
+
+// ============================================================================
+// CHECKPOINT
+// ============================================================================
+
+/// Execution state of a ReAct run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReActCheckpoint {
+    /// ID of the execution (reported as `ReActResponse::id`)
+    pub execution_id: Uuid,
+
+    /// The original question
+    pub query: String,
+
+    /// Conversation the run answers in, if any
+    #[serde(default)]
+    pub conversation_id: Option<Uuid>,
+
+    /// Retrieved RAG context, if any
+    #[serde(default)]
+    pub context: Option<Vec<String>>,
+
+    /// Retriever results behind `context`, when the agent retrieved it
+    #[serde(default)]
+    pub sources: Vec<RetrievedSource>,
+
+    /// Thought/Action/Observation text of completed steps
+    pub scratchpad: Vec<String>,
+
+    /// Reasoning trace so far
+    pub trace: Vec<ReActStep>,
+
+    /// Number of iterations started so far
+    pub iteration: usize,
+
+    /// Tool calls waiting for approval
+    #[serde(default)]
+    pub pending: Option<PendingAction>,
+
+    /// Number of draft answers sent back by the critic
+    #[serde(default)]
+    pub revisions: usize,
+
+    /// Decisions taken on paused tool calls, oldest first
+    #[serde(default)]
+    pub reviews: Vec<Review>,
+
+    /// Token usage and cost so far
+    #[serde(default)]
+    pub usage: UsageReport,
+
+    /// Active execution time so far in milliseconds
+    pub elapsed_ms: u64,
+
+    /// When the checkpoint was taken
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ReActCheckpoint {
+    /// Initial state of a run
+    pub fn new(query: impl Into<String>) -> Self {
+        Self {
+            execution_id: Uuid::new_v4(),
+            query: query.into(),
+            conversation_id: None,
+            context: None,
+            sources: Vec::new(),
+            scratchpad: Vec::new(),
+            trace: Vec::new(),
+            iteration: 0,
+            pending: None,
+            revisions: 0,
+            reviews: Vec::new(),
+            usage: UsageReport::default(),
+            elapsed_ms: 0,
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// Use a specific execution ID (e.g. the ID of the job running it)
+    pub fn with_execution_id(mut self, execution_id: Uuid) -> Self {
+        self.execution_id = execution_id;
+        self
+    }
+
+    /// Set the conversation the run answers in
+    pub fn with_conversation_id(mut self, conversation_id: Option<Uuid>) -> Self {
+        self.conversation_id = conversation_id;
+        self
+    }
+
+    /// Set the retrieved RAG context
+    pub fn with_context(mut self, context: Option<Vec<String>>) -> Self {
+        self.context = context;
+        self
+    }
+
+    /// Whether the run is waiting for approval
+    pub fn is_paused(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Names of the tools waiting for approval
+    pub fn pending_tool_names(&self) -> Vec<String> {
+        self.pending
+            .as_ref()
+            .map(|p| p.calls.iter().map(|c| c.tool_name.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Record who decided on the pending calls, before resuming with `decision`
+    ///
+    /// Does nothing if the run is not waiting for approval.
+    pub fn record_review(&mut self, reviewer: impl Into<String>, decision: &ApprovalDecision) {
+        if !self.is_paused() {
+            return;
+        }
+        self.reviews.push(Review {
+            reviewer: reviewer.into(),
+            tool_names: self.pending_tool_names(),
+            decision: decision.clone(),
+            decided_at: Utc::now(),
+        });
+    }
+}
+
+/// Tool calls of one step, held back until approved
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingAction {
+    /// Thought preceding the calls
+    pub thought: Option<String>,
+
+    /// The requested tool calls
+    pub calls: Vec<ToolInvocation>,
+
+    /// Provider and model that requested the calls
+    #[serde(default)]
+    pub model: Option<String>,
+}
+
+/// Decision on the pending tool calls of a paused run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "decision", rename_all = "snake_case")]
+pub enum ApprovalDecision {
+    /// Execute the pending tool calls
+    Approve,
+
+    /// Skip the pending tool calls; the model observes the rejection
+    Reject { reason: Option<String> },
+}
+
+/// Who approved or rejected a run's paused tool calls, and when
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Review {
+    pub reviewer: String,
+    pub tool_names: Vec<String>,
+    pub decision: ApprovalDecision,
+    pub decided_at: DateTime<Utc>,
+}
+
+// ============================================================================
+// STORAGE
+// ============================================================================
+
+/// Storage for checkpoints, keyed by execution ID
+#[async_trait]
+pub trait CheckpointStore: Send + Sync {
+    /// Save (or overwrite) a checkpoint
+    async fn save(&self, checkpoint: &ReActCheckpoint) -> Result<(), ReActError>;
+
+    /// Load the latest checkpoint of an execution
+    async fn load(&self, execution_id: &Uuid) -> Result<Option<ReActCheckpoint>, ReActError>;
+
+    /// Remove the checkpoint of a finished execution
+    async fn delete(&self, execution_id: &Uuid) -> Result<(), ReActError>;
+}
+
+/// Process-local checkpoint store
+#[derive(Default)]
+pub struct InMemoryCheckpointStore {
+    checkpoints: Mutex<HashMap<Uuid, ReActCheckpoint>>,
+}
+
+impl InMemoryCheckpointStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of stored checkpoints
+    pub fn len(&self) -> usize {
+        self.checkpoints.lock().unwrap().len()
+    }
+
+    /// Whether no checkpoint is stored
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[async_trait]
+impl CheckpointStore for InMemoryCheckpointStore {
+    async fn save(&self, checkpoint: &ReActCheckpoint) -> Result<(), ReActError> {
+        self.checkpoints
+            .lock()
+            .unwrap()
+            .insert(checkpoint.execution_id, checkpoint.clone());
+        Ok(())
+    }
+
+    async fn load(&self, execution_id: &Uuid) -> Result<Option<ReActCheckpoint>, ReActError> {
+        Ok(self.checkpoints.lock().unwrap().get(execution_id).cloned())
+    }
+
+    async fn delete(&self, execution_id: &Uuid) -> Result<(), ReActError> {
+        self.checkpoints.lock().unwrap().remove(execution_id);
+        Ok(())
+    }
+}
@@ -0,0 +1,455 @@
+//! Token counting and context-window management
+//!
+//! Long ReAct sessions and large retrieval results can overflow the model's
+//! context window. This module provides:
+//!
+//! - [`TokenCounter`]: BPE-backed token counts per [`ModelFamily`]
+//! - [`ContextBudgeter`]: fits retrieved context, scratchpad steps and
+//!   conversation history into the window, reserving room for the reply
+//!
+//! ```text
+//! ┌──────────────────────── context window ────────────────────────┐
+//! │ fixed prompt │ context (best first) │ history / scratchpad │ reply │
+//! │  (never cut) │  (lowest rank cut)   │  (oldest summarized) │ (max_tokens)
+//! └────────────────────────────────────────────────────────────────┘
+//! ```
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use agent::context::ContextBudgeter;
+//!
+//! let budgeter = ContextBudgeter::for_model("gpt-4", 1024);
+//! let available = budgeter.available_for(&fixed_prompt);
+//! let steps = budgeter.fit_oldest_first(&scratchpad_steps, available);
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+use tiktoken_rs::CoreBPE;
+
+// ============================================================================
+// MODEL FAMILIES
+// ============================================================================
+
+/// Model families with distinct tokenizers and context windows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelFamily {
+    /// gpt-4o, gpt-4.1, o1/o3 (o200k_base)
+    Gpt4o,
+    /// gpt-4, gpt-4-turbo (cl100k_base)
+    Gpt4,
+    /// gpt-3.5-turbo (cl100k_base)
+    Gpt35,
+    /// Anthropic Claude
+    Claude,
+    /// Google Gemini
+    Gemini,
+    /// Llama, Mistral and other local models
+    Open,
+}
+
+impl ModelFamily {
+    /// Detect the family from a model name
+    pub fn from_model(model: &str) -> Self {
+        // Routed responses use "<provider>/<model>"
+        let name = model.rsplit('/').next().unwrap_or(model).to_lowercase();
+
+        if name.starts_with("gpt-4o")
+            || name.starts_with("gpt-4.1")
+            || name.starts_with("gpt-5")
+            || name.starts_with("o1")
+            || name.starts_with("o3")
+            || name.starts_with("o4")
+        {
+            ModelFamily::Gpt4o
+        } else if name.starts_with("gpt-4") {
+            ModelFamily::Gpt4
+        } else if name.starts_with("gpt-3.5") {
+            ModelFamily::Gpt35
+        } else if name.starts_with("claude") {
+            ModelFamily::Claude
+        } else if name.starts_with("gemini") {
+            ModelFamily::Gemini
+        } else {
+            ModelFamily::Open
+        }
+    }
+
+    /// Context window size in tokens for a model of this family
+    pub fn context_window(model: &str) -> usize {
+        let name = model.to_lowercase();
+        match Self::from_model(model) {
+            ModelFamily::Gpt4o => 128_000,
+            ModelFamily::Gpt4 if name.contains("turbo") || name.contains("preview") => 128_000,
+            ModelFamily::Gpt4 if name.contains("32k") => 32_768,
+            ModelFamily::Gpt4 => 8_192,
+            ModelFamily::Gpt35 => 16_385,
+            ModelFamily::Claude => 200_000,
+            ModelFamily::Gemini => 1_000_000,
+            ModelFamily::Open => 8_192,
+        }
+    }
+
+    /// Whether counts for this family are exact or approximated with cl100k
+    pub fn is_exact(&self) -> bool {
+        matches!(
+            self,
+            ModelFamily::Gpt4o | ModelFamily::Gpt4 | ModelFamily::Gpt35
+        )
+    }
+}
+
+// ============================================================================
+// TOKEN COUNTER
+// ============================================================================
+
+static CL100K: OnceLock<Option<CoreBPE>> = OnceLock::new();
+static O200K: OnceLock<Option<CoreBPE>> = OnceLock::new();
+
+fn cl100k() -> Option<&'static CoreBPE> {
+    CL100K
+        .get_or_init(|| tiktoken_rs::cl100k_base().ok())
+        .as_ref()
+}
+
+fn o200k() -> Option<&'static CoreBPE> {
+    O200K
+        .get_or_init(|| tiktoken_rs::o200k_base().ok())
+        .as_ref()
+}
+
+/// Token counter for a model family
+///
+/// OpenAI families are counted exactly with their BPE. Other families are
+/// approximated with cl100k plus a safety margin, since their tokenizers are
+/// not public.
+#[derive(Debug, Clone, Copy)]
+pub struct TokenCounter {
+    family: ModelFamily,
+}
+
+/// Safety margin applied to approximated counts
+const APPROXIMATION_MARGIN: f64 = 1.15;
+
+/// Fixed per-message overhead of chat formats (role markers, separators)
+pub const MESSAGE_OVERHEAD_TOKENS: usize = 4;
+
+impl TokenCounter {
+    /// Create a counter for a model family
+    pub fn new(family: ModelFamily) -> Self {
+        Self { family }
+    }
+
+    /// Create a counter for a model name
+    pub fn for_model(model: &str) -> Self {
+        Self::new(ModelFamily::from_model(model))
+    }
+
+    /// The model family being counted
+    pub fn family(&self) -> ModelFamily {
+        self.family
+    }
+
+    /// Count the tokens in `text`
+    pub fn count(&self, text: &str) -> usize {
+        if text.is_empty() {
+            return 0;
+        }
+
+        let bpe = match self.family {
+            ModelFamily::Gpt4o => o200k(),
+            _ => cl100k(),
+        };
+
+        match bpe {
+            Some(bpe) => {
+                let count = bpe.encode_with_special_tokens(text).len();
+                if self.family.is_exact() {
+                    count
+                } else {
+                    (count as f64 * APPROXIMATION_MARGIN).ceil() as usize
+                }
+            }
+            None => estimate_tokens(text),
+        }
+    }
+
+    /// Count the tokens of a list of chat messages, including overhead
+    pub fn count_messages<'a>(&self, messages: impl IntoIterator<Item = &'a str>) -> usize {
+        messages
+            .into_iter()
+            .map(|m| self.count(m) + MESSAGE_OVERHEAD_TOKENS)
+            .sum()
+    }
+
+    /// Truncate `text` to at most `max_tokens` tokens, appending a marker
+    pub fn truncate(&self, text: &str, max_tokens: usize) -> String {
+        if self.count(text) <= max_tokens {
+            return text.to_string();
+        }
+
+        const MARKER: &str = " …[truncated]";
+        let budget = max_tokens.saturating_sub(self.count(MARKER));
+        let chars: Vec<char> = text.chars().collect();
+
+        // Binary search the longest prefix that fits
+        let (mut lo, mut hi) = (0usize, chars.len());
+        while lo < hi {
+            let mid = (lo + hi).div_ceil(2);
+            let prefix: String = chars[..mid].iter().collect();
+            if self.count(&prefix) <= budget {
+                lo = mid;
+            } else {
+                hi = mid - 1;
+            }
+        }
+
+        let mut truncated: String = chars[..lo].iter().collect();
+        truncated.push_str(MARKER);
+        truncated
+    }
+}
+
+/// Character-based estimate used when no BPE is available
+///
+/// Latin text averages ~4 characters per token; Thai and other non-ASCII
+/// scripts are closer to one token per character.
+pub fn estimate_tokens(text: &str) -> usize {
+    let (ascii, other) = text.chars().fold((0usize, 0usize), |(a, o), c| {
+        if c.is_ascii() {
+            (a + 1, o)
+        } else {
+            (a, o + 1)
+        }
+    });
+    ascii.div_ceil(4) + other
+}
+
+// ============================================================================
+// CONTEXT BUDGETER
+// ============================================================================
+
+/// Fits prompt components into a model's context window
+#[derive(Debug, Clone)]
+pub struct ContextBudgeter {
+    counter: TokenCounter,
+    context_window: usize,
+    reserved_output: usize,
+}
+
+impl ContextBudgeter {
+    /// Create a budgeter for a model, reserving `max_tokens` for the reply
+    pub fn for_model(model: &str, max_tokens: usize) -> Self {
+        Self {
+            counter: TokenCounter::for_model(model),
+            context_window: ModelFamily::context_window(model),
+            reserved_output: max_tokens,
+        }
+    }
+
+    /// Override the context window size
+    pub fn with_context_window(mut self, tokens: usize) -> Self {
+        self.context_window = tokens;
+        self
+    }
+
+    /// The token counter in use
+    pub fn counter(&self) -> &TokenCounter {
+        &self.counter
+    }
+
+    /// Context window size in tokens
+    pub fn context_window(&self) -> usize {
+        self.context_window
+    }
+
+    /// Tokens available for the prompt (window minus the reply reservation)
+    pub fn prompt_budget(&self) -> usize {
+        self.context_window.saturating_sub(self.reserved_output)
+    }
+
+    /// Tokens left for variable content once `fixed` is in the prompt
+    pub fn available_for(&self, fixed: &str) -> usize {
+        self.prompt_budget()
+            .saturating_sub(self.counter.count(fixed))
+    }
+
+    /// Keep items in order (best first) until the budget is used up
+    ///
+    /// Used for retrieved context, which is already ranked by relevance; an
+    /// item that doesn't fit whole is truncated if at least a quarter of it
+    /// still fits, otherwise dropped along with everything after it.
+    pub fn fit_ranked(&self, items: &[String], budget: usize) -> Vec<String> {
+        let mut kept = Vec::new();
+        let mut used = 0;
+
+        for item in items {
+            let tokens = self.counter.count(item);
+            let remaining = budget.saturating_sub(used);
+            if tokens <= remaining {
+                used += tokens;
+                kept.push(item.clone());
+            } else {
+                if remaining >= tokens / 4 && remaining > 0 {
+                    kept.push(self.counter.truncate(item, remaining));
+                }
+                break;
+            }
+        }
+
+        kept
+    }
+
+    /// Keep the newest items that fit, summarizing the dropped oldest ones
+    ///
+    /// Used for scratchpad steps and conversation history. Dropped items are
+    /// replaced by a single note listing the first line of each, so the model
+    /// still knows what was already tried.
+    pub fn fit_oldest_first(&self, items: &[String], budget: usize) -> Vec<String> {
+        let total: usize = items.iter().map(|i| self.counter.count(i)).sum();
+        if total <= budget {
+            return items.to_vec();
+        }
+
+        // Leave a slice of the budget for the summary note
+        let summary_budget = (budget / 5).min(512);
+        let mut kept: Vec<String> = Vec::new();
+        let mut used = 0;
+
+        for item in items.iter().rev() {
+            let tokens = self.counter.count(item);
+            if used + tokens > budget.saturating_sub(summary_budget) {
+                break;
+            }
+            used += tokens;
+            kept.push(item.clone());
+        }
+        kept.reverse();
+
+        let dropped = &items[..items.len() - kept.len()];
+        if !dropped.is_empty() && summary_budget > 0 {
+            let summary = Self::summarize(dropped);
+            kept.insert(0, self.counter.truncate(&summary, summary_budget));
+        }
+
+        kept
+    }
+
+    /// Extractive summary of dropped items
+    fn summarize(items: &[String]) -> String {
+        let mut summary = format!("[{} earlier step(s) omitted to fit context:", items.len());
+        for item in items {
+            let first_line = item.trim().lines().next().unwrap_or_default();
+            let short: String = first_line.chars().take(80).collect();
+            summary.push_str(&format!("\n- {}", short));
+        }
+        summary.push(']');
+        summary
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_model_family_detection() {
+        assert_eq!(ModelFamily::from_model("gpt-4"), ModelFamily::Gpt4);
+        assert_eq!(ModelFamily::from_model("gpt-4o-mini"), ModelFamily::Gpt4o);
+        assert_eq!(ModelFamily::from_model("gpt-3.5-turbo"), ModelFamily::Gpt35);
+        assert_eq!(
+            ModelFamily::from_model("anthropic/claude-3-haiku"),
+            ModelFamily::Claude
+        );
+        assert_eq!(ModelFamily::from_model("llama3"), ModelFamily::Open);
+    }
+
+    #[test]
+    fn test_context_window() {
+        assert_eq!(ModelFamily::context_window("gpt-4"), 8_192);
+        assert_eq!(ModelFamily::context_window("gpt-4-turbo"), 128_000);
+        assert_eq!(ModelFamily::context_window("claude-3-opus"), 200_000);
+    }
+
+    #[test]
+    fn test_token_counter_counts() {
+        let counter = TokenCounter::for_model("gpt-4");
+        assert_eq!(counter.count(""), 0);
+        assert!(counter.count("Hello, world!") > 0);
+        assert!(counter.count(&"word ".repeat(100)) >= 100);
+    }
+
+    #[test]
+    fn test_approximate_families_add_margin() {
+        let text = "The quick brown fox jumps over the lazy dog. ".repeat(20);
+        let exact = TokenCounter::for_model("gpt-4").count(&text);
+        let approx = TokenCounter::for_model("claude-3-opus").count(&text);
+        assert!(approx > exact);
+    }
+
+    #[test]
+    fn test_estimate_tokens_thai() {
+        assert_eq!(estimate_tokens("abcd"), 1);
+        assert_eq!(estimate_tokens("สวัสดี"), 6);
+    }
+
+    #[test]
+    fn test_truncate() {
+        let counter = TokenCounter::for_model("gpt-4");
+        let text = "token ".repeat(200);
+        let truncated = counter.truncate(&text, 20);
+        assert!(counter.count(&truncated) <= 20);
+        assert!(truncated.ends_with("[truncated]"));
+
+        assert_eq!(counter.truncate("short", 20), "short");
+    }
+
+    #[test]
+    fn test_budget_reserves_output() {
+        let budgeter = ContextBudgeter::for_model("gpt-4", 2048);
+        assert_eq!(budgeter.prompt_budget(), 8_192 - 2048);
+        assert!(budgeter.available_for("Hello") < budgeter.prompt_budget());
+    }
+
+    #[test]
+    fn test_fit_ranked_keeps_best_first() {
+        let budgeter = ContextBudgeter::for_model("gpt-4", 0);
+        let items: Vec<String> = (0..10)
+            .map(|i| format!("chunk {} ", i).repeat(50))
+            .collect();
+        let per_item = budgeter.counter().count(&items[0]);
+
+        let kept = budgeter.fit_ranked(&items, per_item * 3);
+        assert_eq!(kept.len(), 3);
+        assert_eq!(kept[0], items[0]);
+    }
+
+    #[test]
+    fn test_fit_oldest_first_summarizes_dropped() {
+        let budgeter = ContextBudgeter::for_model("gpt-4", 0);
+        let steps: Vec<String> = (0..20)
+            .map(|i| format!("Action: search\nObservation: {}", "result ".repeat(40 + i)))
+            .collect();
+        let budget = budgeter.counter().count(&steps[19]) * 4;
+
+        let kept = budgeter.fit_oldest_first(&steps, budget);
+        assert!(kept[0].contains("earlier step(s) omitted"));
+        assert_eq!(kept.last(), steps.last());
+
+        let used: usize = kept.iter().map(|s| budgeter.counter().count(s)).sum();
+        assert!(used <= budget);
+    }
+
+    #[test]
+    fn test_fit_oldest_first_no_trim_when_fits() {
+        let budgeter = ContextBudgeter::for_model("gpt-4", 0);
+        let steps = vec!["a".to_string(), "b".to_string()];
+        assert_eq!(budgeter.fit_oldest_first(&steps, 100), steps);
+    }
+}
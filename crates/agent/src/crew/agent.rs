@@ -12,7 +12,13 @@ use thiserror::Error;
 use uuid::Uuid;
 
 use super::memory::{Memory, MemoryConfig, MemoryType};
+use crate::arguments::{prepare_arguments, ArgumentOptions};
+use crate::rig_integration::CompletionClient;
+use crate::structured::{StructuredCompletion, StructuredError, DEFAULT_MAX_REPAIRS};
+use crate::tools::policy::{run_bounded, CancellationToken};
 use crate::tools::{Tool, ToolDefinition, ToolResult};
+use crate::usage::UsageReport;
+use common::{global_pricing, PricingConfig};
 
 /// Errors that can occur during agent operations
 #[derive(Error, Debug)]
@@ -34,6 +40,12 @@ pub enum AgentError {
 
     #[error("Memory error: {0}")]
     MemoryError(String),
+
+    #[error("Invalid structured output: {0}")]
+    InvalidOutput(String),
+
+    #[error("Execution cancelled")]
+    Cancelled,
 }
 
 /// Configuration for an agent
@@ -134,6 +146,12 @@ pub struct ExecutionContext {
 
     /// Maximum allowed iterations
     pub max_iterations: usize,
+
+    /// JSON schema the output must conform to
+    pub output_schema: Option<serde_json::Value>,
+
+    /// Cancels the execution and its tool calls
+    pub cancellation: CancellationToken,
 }
 
 /// Result of an agent's execution
@@ -148,6 +166,10 @@ pub struct AgentExecutionResult {
     /// Reasoning/thought process (if verbose)
     pub reasoning: Option<String>,
 
+    /// Output parsed and validated against the task's schema
+    #[serde(default)]
+    pub structured_output: Option<serde_json::Value>,
+
     /// Execution metadata
     pub metadata: ExecutionMetadata,
 }
@@ -186,6 +208,10 @@ pub struct ExecutionMetadata {
     /// Token usage (if available)
     pub tokens_used: Option<TokenUsage>,
 
+    /// Token usage and cost per model
+    #[serde(default)]
+    pub usage: UsageReport,
+
     /// Timestamp when execution started
     pub started_at: chrono::DateTime<chrono::Utc>,
 
@@ -201,6 +227,16 @@ pub struct TokenUsage {
     pub total_tokens: usize,
 }
 
+impl From<&UsageReport> for TokenUsage {
+    fn from(usage: &UsageReport) -> Self {
+        Self {
+            prompt_tokens: usage.prompt_tokens as usize,
+            completion_tokens: usage.completion_tokens as usize,
+            total_tokens: usage.total_tokens as usize,
+        }
+    }
+}
+
 /// Trait for agent execution behavior
 #[async_trait]
 pub trait AgentExecutor: Send + Sync {
@@ -246,6 +282,12 @@ pub struct Agent {
 
     /// Agent memory
     memory: Option<Memory>,
+
+    /// LLM client (placeholder responses when unset)
+    llm_client: Option<Arc<dyn CompletionClient>>,
+
+    /// Pricing table for cost accounting
+    pricing: Arc<PricingConfig>,
 }
 
 impl Agent {
@@ -265,9 +307,23 @@ impl Agent {
             config,
             tools: HashMap::new(),
             memory,
+            llm_client: None,
+            pricing: global_pricing().clone(),
         }
     }
 
+    /// Set the LLM client used for task execution
+    pub fn with_llm_client(mut self, client: Arc<dyn CompletionClient>) -> Self {
+        self.llm_client = Some(client);
+        self
+    }
+
+    /// Use a custom pricing table for cost accounting
+    pub fn with_pricing(mut self, pricing: PricingConfig) -> Self {
+        self.pricing = Arc::new(pricing);
+        self
+    }
+
     /// Get the agent's unique identifier
     pub fn id(&self) -> &str {
         &self.config.id
@@ -310,19 +366,29 @@ impl Agent {
         self.tools.values().map(|t| t.definition()).collect()
     }
 
-    /// Execute a tool by name
+    /// Execute a tool by name, stopping early when `cancel` is cancelled
     pub async fn execute_tool(
         &self,
         tool_name: &str,
         args: serde_json::Value,
+        cancel: &CancellationToken,
     ) -> Result<ToolResult, AgentError> {
         let tool = self
             .tools
             .get(tool_name)
             .ok_or_else(|| AgentError::ToolNotFound(tool_name.to_string()))?;
 
-        tool.execute(args)
+        let args = prepare_arguments(
+            tool_name,
+            &tool.definition().parameters,
+            args,
+            &ArgumentOptions::default(),
+        )
+        .map_err(|e| AgentError::ToolExecutionFailed(e.to_string()))?;
+
+        run_bounded(tool.execute(args), None, cancel)
             .await
+            .map_err(|_| AgentError::Cancelled)?
             .map_err(|e| AgentError::ToolExecutionFailed(e.to_string()))
     }
 
@@ -376,6 +442,68 @@ impl AgentExecutor for Agent {
             }
         }
 
+        if let Some(client) = &self.llm_client {
+            let system = self.system_prompt();
+            let mut usage = UsageReport::default();
+            let (output, structured_output) = match &context.output_schema {
+                Some(schema) => {
+                    let structured = client
+                        .complete_json_value(&system, &prompt, schema, DEFAULT_MAX_REPAIRS)
+                        .await
+                        .map_err(|e| match e {
+                            StructuredError::Llm(e) => AgentError::LlmError(e.to_string()),
+                            invalid => AgentError::InvalidOutput(invalid.to_string()),
+                        })?;
+                    let (prompt_tokens, completion_tokens) = structured
+                        .usage
+                        .as_ref()
+                        .map(|u| (u.prompt_tokens as u64, u.completion_tokens as u64))
+                        .unwrap_or_default();
+                    usage.record_calls(
+                        &structured.model,
+                        structured.attempts,
+                        prompt_tokens,
+                        completion_tokens,
+                        &self.pricing,
+                    );
+                    (structured.raw, Some(structured.value))
+                }
+                None => {
+                    let response = client
+                        .complete_with_system(&system, &prompt)
+                        .await
+                        .map_err(|e| AgentError::LlmError(e.to_string()))?;
+                    let full_prompt = format!("{}\n\n{}", system, prompt);
+                    usage.record_response(&full_prompt, &response, &self.pricing);
+                    (response.content, None)
+                }
+            };
+
+            let completed_at = chrono::Utc::now();
+            return Ok(AgentExecutionResult {
+                output,
+                tool_calls,
+                reasoning,
+                structured_output,
+                metadata: ExecutionMetadata {
+                    iterations: 1,
+                    execution_time_ms: (completed_at - started_at).num_milliseconds() as u64,
+                    llm_calls: usage.llm_calls,
+                    tokens_used: Some(TokenUsage::from(&usage)),
+                    usage,
+                    started_at,
+                    completed_at,
+                },
+            });
+        }
+
+        if context.output_schema.is_some() {
+            return Err(AgentError::ConfigError(format!(
+                "Agent '{}' has no LLM client, which tasks with an output schema require",
+                self.config.id
+            )));
+        }
+
         // TODO: Integrate with rig for actual LLM calls
         // For now, this is a placeholder that demonstrates the structure
         let output = format!(
@@ -407,11 +535,13 @@ impl AgentExecutor for Agent {
             output,
             tool_calls,
             reasoning,
+            structured_output: None,
             metadata: ExecutionMetadata {
                 iterations,
                 execution_time_ms,
-                llm_calls: 1,
+                llm_calls: 0,
                 tokens_used: None,
+                usage: UsageReport::default(),
                 started_at,
                 completed_at,
             },
@@ -427,6 +557,8 @@ impl AgentExecutor for Agent {
 pub struct AgentBuilder {
     config: AgentConfig,
     tools: Vec<Arc<dyn Tool>>,
+    llm_client: Option<Arc<dyn CompletionClient>>,
+    pricing: Option<PricingConfig>,
 }
 
 impl AgentBuilder {
@@ -435,6 +567,8 @@ impl AgentBuilder {
         Self {
             config: AgentConfig::default(),
             tools: Vec::new(),
+            llm_client: None,
+            pricing: None,
         }
     }
 
@@ -558,9 +692,25 @@ impl AgentBuilder {
         self
     }
 
+    /// Set the LLM client
+    pub fn llm_client(mut self, client: Arc<dyn CompletionClient>) -> Self {
+        self.llm_client = Some(client);
+        self
+    }
+
+    /// Set the pricing table used for cost accounting
+    pub fn pricing(mut self, pricing: PricingConfig) -> Self {
+        self.pricing = Some(pricing);
+        self
+    }
+
     /// Build the agent
     pub fn build(self) -> Agent {
         let mut agent = Agent::new(self.config);
+        agent.llm_client = self.llm_client;
+        if let Some(pricing) = self.pricing {
+            agent.pricing = Arc::new(pricing);
+        }
         for tool in self.tools {
             agent.add_tool(tool);
         }
@@ -48,6 +48,10 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::Path;
+use std::sync::Arc;
+
+use super::http_tools::HttpToolSpec;
+use super::mcp::{McpClient, McpError, McpServerConfig};
 
 /// YAML configuration for an agent (matching CrewAI Python pattern)
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -70,6 +74,14 @@ pub struct AgentYamlConfig {
     #[serde(default)]
     pub tools: Vec<String>,
 
+    /// MCP servers whose tools this agent can use
+    #[serde(default)]
+    pub mcp_servers: Vec<McpServerConfig>,
+
+    /// HTTP endpoints this agent can call as tools
+    #[serde(default)]
+    pub http_tools: Vec<HttpToolSpec>,
+
     /// Whether the agent can delegate tasks
     #[serde(default)]
     pub allow_delegation: bool,
@@ -140,6 +152,10 @@ pub struct TaskYamlConfig {
     #[serde(default)]
     pub tools: Vec<String>,
 
+    /// JSON schema the task output must conform to
+    #[serde(default)]
+    pub output_schema: Option<serde_json::Value>,
+
     /// Additional metadata
     #[serde(flatten)]
     pub extra: HashMap<String, serde_json::Value>,
@@ -255,10 +271,16 @@ impl AgentsConfig {
         Self::from_yaml(&content)
     }
 
-    /// Parse agents from YAML string
+    /// Parse agents from YAML string, validating their HTTP tools
     pub fn from_yaml(content: &str) -> Result<Self, ConfigError> {
-        serde_yaml::from_str(content)
-            .map_err(|e| ConfigError::ParseError(format!("Failed to parse agents YAML: {}", e)))
+        let config: Self = serde_yaml::from_str(content)
+            .map_err(|e| ConfigError::ParseError(format!("Failed to parse agents YAML: {}", e)))?;
+        for agent in config.agents.values() {
+            for spec in &agent.http_tools {
+                spec.validate()?;
+            }
+        }
+        Ok(config)
     }
 }
 
@@ -338,8 +360,27 @@ impl AgentYamlConfig {
             builder = builder.tool_name(tool);
         }
 
+        for spec in &self.http_tools {
+            match spec.to_tool() {
+                Ok(tool) => builder = builder.tool(Arc::new(tool)),
+                Err(e) => tracing::error!(agent = %id, error = %e, "Skipping HTTP tool"),
+            }
+        }
+
         builder.build()
     }
+
+    /// Convert to Agent, connecting to its MCP servers and adding their tools
+    pub async fn to_agent_with_mcp(&self, id: &str) -> Result<Agent, McpError> {
+        let mut agent = self.to_agent(id);
+        for server in &self.mcp_servers {
+            let client = McpClient::connect(server).await?;
+            for tool in client.tools() {
+                agent.add_tool(Arc::new(tool));
+            }
+        }
+        Ok(agent)
+    }
 }
 
 impl TaskYamlConfig {
@@ -365,6 +406,10 @@ impl TaskYamlConfig {
             builder = builder.tool(tool);
         }
 
+        if let Some(schema) = &self.output_schema {
+            builder = builder.output_schema(schema.clone());
+        }
+
         builder.build()
     }
 }
@@ -529,6 +574,70 @@ allow_delegation: true
         assert_eq!(agent.backstory(), "Test Backstory");
     }
 
+    #[test]
+    fn test_agent_mcp_servers() {
+        let yaml = r#"
+role: Test Role
+goal: Test Goal
+backstory: Test Backstory
+mcp_servers:
+  - name: demo
+    command: ./mcp_server
+    tools: [echo]
+"#;
+        let config: AgentYamlConfig = serde_yaml::from_str(yaml).unwrap();
+
+        assert_eq!(
+            config.mcp_servers,
+            vec![McpServerConfig::stdio("demo", "./mcp_server").with_tools(["echo"])]
+        );
+        assert!(config.extra.is_empty());
+    }
+
+    #[test]
+    fn test_agent_http_tools() {
+        let yaml = r#"
+role: Test Role
+goal: Test Goal
+backstory: Test Backstory
+http_tools:
+  - name: stock_lookup
+    description: Stock by SKU
+    url: http://erp.internal/api/stock/{sku}
+    parameters:
+      type: object
+      properties:
+        sku: { type: string }
+      required: [sku]
+"#;
+        let config: AgentYamlConfig = serde_yaml::from_str(yaml).unwrap();
+        let agent = config.to_agent("test-agent");
+
+        let definitions = agent.tool_definitions();
+        assert_eq!(definitions.len(), 1);
+        assert_eq!(definitions[0].name, "stock_lookup");
+        assert_eq!(
+            definitions[0].parameters["required"],
+            serde_json::json!(["sku"])
+        );
+        assert!(config.extra.is_empty());
+
+        let invalid = AgentsConfig::from_yaml(
+            r#"
+clerk:
+  role: Clerk
+  goal: Look up stock
+  backstory: Knows the ERP
+  http_tools:
+    - name: stock_lookup
+      description: Stock by SKU
+      url: http://erp.internal/api/stock/{sku}
+      response_path: $.items[x
+"#,
+        );
+        assert!(matches!(invalid, Err(ConfigError::ValidationError(_))));
+    }
+
     #[test]
     fn test_task_to_builder() {
         let yaml = r#"
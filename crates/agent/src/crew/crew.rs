@@ -13,10 +13,13 @@ use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
 use super::agent::{Agent, AgentError, AgentExecutor, ExecutionContext};
-use super::config::{AgentsConfig, CrewYamlConfig, TasksConfig};
+use super::config::{AgentYamlConfig, AgentsConfig, CrewYamlConfig, TasksConfig};
+use super::mcp::McpError;
 use super::memory::{CrewMemory, MemoryConfig};
 use super::process::{Process, ProcessConfig};
 use super::task::{Task, TaskError, TaskOutput};
+use crate::tools::policy::{run_bounded, CancellationToken};
+use crate::usage::UsageReport;
 
 /// Errors that can occur during crew operations
 #[derive(Error, Debug)]
@@ -50,6 +53,9 @@ pub enum CrewError {
 
     #[error("Validation failed: {0}")]
     ValidationFailed(String),
+
+    #[error("Crew execution cancelled")]
+    Cancelled,
 }
 
 /// Configuration for a crew
@@ -144,6 +150,14 @@ pub struct ExecutionStats {
     /// Total tokens used (if available)
     pub total_tokens: Option<usize>,
 
+    /// Token usage and cost across all agents
+    #[serde(default)]
+    pub usage: UsageReport,
+
+    /// Token usage and cost per agent ID
+    #[serde(default)]
+    pub usage_by_agent: HashMap<String, UsageReport>,
+
     /// Timestamp when execution started
     pub started_at: chrono::DateTime<chrono::Utc>,
 
@@ -192,6 +206,12 @@ pub struct Crew {
 
     /// Completed task outputs (for context passing)
     completed_outputs: RwLock<HashMap<String, TaskOutput>>,
+
+    /// LLM usage of the current execution, per agent
+    usage_by_agent: HashMap<String, UsageReport>,
+
+    /// Cancels running and remaining tasks
+    cancellation: CancellationToken,
 }
 
 impl Crew {
@@ -211,9 +231,25 @@ impl Crew {
             memory,
             listeners: Vec::new(),
             completed_outputs: RwLock::new(HashMap::new()),
+            usage_by_agent: HashMap::new(),
+            cancellation: CancellationToken::new(),
         }
     }
 
+    /// Use a cancellation token shared with the caller
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation = token;
+        self
+    }
+
+    /// Token aborting the crew's execution when cancelled
+    ///
+    /// The running task is stopped, its agent's tool calls included, no
+    /// further task starts and the result reports the cancellation.
+    pub fn cancellation_token(&self) -> CancellationToken {
+        self.cancellation.clone()
+    }
+
     /// Get crew ID
     pub fn id(&self) -> &str {
         &self.config.id
@@ -338,6 +374,7 @@ impl Crew {
             let mut outputs = self.completed_outputs.write().await;
             outputs.clear();
         }
+        self.usage_by_agent.clear();
 
         // Execute based on process type
         let result = match self.config.process.process_type {
@@ -351,6 +388,8 @@ impl Crew {
 
         let completed_at = chrono::Utc::now();
         let total_time_ms = (completed_at - started_at).num_milliseconds() as u64;
+        let usage: UsageReport = self.usage_by_agent.values().sum();
+        let total_tokens = (!usage.is_empty()).then_some(usage.total_tokens as usize);
 
         // Build result
         let crew_result = match result {
@@ -369,8 +408,10 @@ impl Crew {
                     tasks_succeeded: outputs.len(),
                     tasks_failed: self.tasks.len() - outputs.len(),
                     tasks_skipped: 0,
-                    total_llm_calls: outputs.len(), // Placeholder
-                    total_tokens: None,
+                    total_llm_calls: usage.llm_calls,
+                    total_tokens,
+                    usage,
+                    usage_by_agent: self.usage_by_agent.clone(),
                     started_at,
                     completed_at,
                 };
@@ -398,8 +439,10 @@ impl Crew {
                         tasks_succeeded: 0,
                         tasks_failed: 1,
                         tasks_skipped: 0,
-                        total_llm_calls: 0,
-                        total_tokens: None,
+                        total_llm_calls: usage.llm_calls,
+                        total_tokens,
+                        usage,
+                        usage_by_agent: self.usage_by_agent.clone(),
                         started_at,
                         completed_at,
                     },
@@ -478,12 +521,43 @@ impl Crew {
                 shared_state: HashMap::new(),
                 iteration: 0,
                 max_iterations: agent.config().max_iterations,
+                output_schema: task.output_schema().cloned(),
+                cancellation: self.cancellation.clone(),
             };
 
-            // Execute the agent
-            match agent.execute(context).await {
-                Ok(result) => {
+            // Execute the agent and check the output against the task's schema
+            let result = match run_bounded(agent.execute(context), None, &self.cancellation).await
+            {
+                Ok(result) if !self.cancellation.is_cancelled() => result,
+                _ => {
+                    warn!(task_id = %task.id(), "Crew execution cancelled");
+                    task.cancel();
+                    return Err(CrewError::Cancelled);
+                }
+            };
+            let outcome = result
+                .map_err(|e| e.to_string())
+                .and_then(|result| {
+                    self.usage_by_agent
+                        .entry(agent.id().to_string())
+                        .or_default()
+                        .merge(&result.metadata.usage);
+
+                    let structured = match result.structured_output {
+                        Some(value) => Some(value),
+                        None => task
+                            .validate_output(&result.output)
+                            .map_err(|e| e.to_string())?,
+                    };
                     let output = TaskOutput::new(&result.output);
+                    Ok(match structured {
+                        Some(data) => output.with_structured_data(data),
+                        None => output,
+                    })
+                });
+
+            match outcome {
+                Ok(output) => {
                     task.complete(output.clone());
 
                     // Notify listeners
@@ -497,11 +571,11 @@ impl Crew {
                 }
                 Err(e) => {
                     error!(task_id = %task.id(), error = %e, "Task execution failed");
-                    task.fail(&e.to_string());
+                    task.fail(&e);
 
                     // Notify listeners
                     for listener in &self.listeners {
-                        listener.on_task_fail(task.id(), &e.to_string()).await;
+                        listener.on_task_fail(task.id(), &e).await;
                     }
 
                     if self.config.process.fail_fast {
@@ -735,11 +809,23 @@ impl CrewLoader {
 
     /// Get agent by ID (with variable substitution)
     pub fn agent(&self, id: &str) -> Option<Agent> {
+        Some(self.agent_config(id)?.to_agent(id))
+    }
+
+    /// Get agent by ID with the tools of its MCP servers
+    pub async fn agent_with_mcp(&self, id: &str) -> Result<Option<Agent>, McpError> {
+        match self.agent_config(id) {
+            Some(cfg) => cfg.to_agent_with_mcp(id).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn agent_config(&self, id: &str) -> Option<AgentYamlConfig> {
         let mut cfg = self.agents.agents.get(id)?.clone();
         cfg.role = super::config::substitute_variables(&cfg.role, &self.vars);
         cfg.goal = super::config::substitute_variables(&cfg.goal, &self.vars);
         cfg.backstory = super::config::substitute_variables(&cfg.backstory, &self.vars);
-        Some(cfg.to_agent(id))
+        Some(cfg)
     }
 
     /// Get task by ID (with variable substitution)
@@ -769,6 +855,31 @@ impl CrewLoader {
         builder.build()
     }
 
+    /// Build crew like [`CrewLoader::build`], connecting agents to their MCP servers
+    pub async fn build_with_mcp(
+        &self,
+        name: &str,
+        agent_ids: &[&str],
+        task_ids: &[&str],
+        process: Process,
+    ) -> Result<Crew, McpError> {
+        let mut builder = Crew::builder().id(name).name(name).process(process);
+
+        for id in agent_ids {
+            if let Some(agent) = self.agent_with_mcp(id).await? {
+                builder = builder.agent(agent);
+            }
+        }
+
+        for id in task_ids {
+            if let Some(task) = self.task(id) {
+                builder = builder.task(task);
+            }
+        }
+
+        Ok(builder.build())
+    }
+
     /// Build crew with all agents and tasks from config
     pub fn build_all(&self, name: &str, process: Process) -> Crew {
         let agent_ids: Vec<&str> = self.agents.agents.keys().map(|s| s.as_str()).collect();
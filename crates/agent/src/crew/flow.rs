@@ -14,6 +14,7 @@ use tracing::{debug, error, info};
 use uuid::Uuid;
 
 use super::crew::{Crew, CrewError, CrewResult};
+use crate::usage::UsageReport;
 
 /// Errors that can occur in flow execution
 #[derive(Error, Debug)]
@@ -292,6 +293,10 @@ pub struct FlowStats {
     /// Number of crews executed
     pub crews_executed: usize,
 
+    /// Token usage and cost across all crews
+    #[serde(default)]
+    pub usage: UsageReport,
+
     /// Timestamp when execution started
     pub started_at: chrono::DateTime<chrono::Utc>,
 
@@ -555,6 +560,8 @@ impl Flow {
             .collect::<Vec<_>>()
             .join("\n\n---\n\n");
 
+        let usage = crew_results.iter().map(|r| &r.stats.usage).sum();
+
         let result = FlowResult {
             execution_id,
             final_state: context.current_state.clone(),
@@ -565,6 +572,7 @@ impl Flow {
                 states_visited: context.history.len(),
                 transitions_made: context.history.len() - 1,
                 crews_executed: iterations,
+                usage,
                 started_at,
                 completed_at,
             },
@@ -0,0 +1,1420 @@
+//! Declarative HTTP Tools
+//!
+//! Turns HTTP endpoints into [`DynamicTool`]s without writing Rust, either
+//! from declarative YAML specs or from the operations of an OpenAPI 3
+//! document. Each spec gives the method, a URL template, headers, a JSON
+//! schema for the arguments and optionally a JSONPath selecting the part of
+//! the response the model sees.
+//!
+//! Arguments are sent as follows:
+//! - named in the URL template as `{name}`: substituted into the path
+//! - listed in `query_params`: added to the query string
+//! - named by `body_param`: sent as the JSON body
+//! - anything else: query string for GET, HEAD and DELETE, otherwise fields of
+//!   a JSON body object (query string when `body_param` is set)
+//!
+//! Secrets stay out of the config: `${VAR}` in URLs and header values and the
+//! `auth` section are read from environment variables when the tool runs.
+//! Redirects are not followed, and only the first MiB of a response is read.
+//!
+//! # Example http_tools.yaml
+//!
+//! ```yaml
+//! tools:
+//!   - name: stock_lookup
+//!     description: Current stock level of a product by SKU
+//!     method: GET
+//!     url: ${ERP_URL}/api/stock/{sku}
+//!     parameters:
+//!       type: object
+//!       properties:
+//!         sku: { type: string, description: Product SKU }
+//!         warehouse: { type: string }
+//!       required: [sku]
+//!     response_path: $.data.available
+//!     auth:
+//!       type: bearer
+//!       token_env: ERP_TOKEN
+//! ```
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use agent::crew::http_tools::{HttpAuth, OpenApiOptions};
+//! use agent::crew::ToolRegistry;
+//!
+//! let mut registry = ToolRegistry::new();
+//! registry.register_http_tools_file("config/http_tools.yaml")?;
+//!
+//! let options = OpenApiOptions::new()
+//!     .with_operations(["getOrder", "listOrders"])
+//!     .with_auth(HttpAuth::bearer("ERP_TOKEN"));
+//! registry.register_openapi_file("config/erp.openapi.yaml", &options)?;
+//! ```
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::config::ConfigError;
+use super::tools::{DynamicTool, ToolError, ToolRegistry};
+
+/// Default request timeout
+const DEFAULT_TIMEOUT_SECS: u64 = 30;
+
+/// Characters of an error response body kept in the error message
+const MAX_ERROR_BODY_CHARS: usize = 500;
+
+/// Bytes of a response body read; the rest is discarded
+const MAX_RESPONSE_BYTES: usize = 1024 * 1024;
+
+// ============================================================================
+// SPECS
+// ============================================================================
+
+/// A tool calling one HTTP endpoint
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HttpToolSpec {
+    /// Tool name shown to the model
+    pub name: String,
+
+    /// What the tool does
+    pub description: String,
+
+    /// HTTP method
+    #[serde(default = "default_method")]
+    pub method: String,
+
+    /// URL with `{arg}` path placeholders and `${VAR}` environment variables
+    pub url: String,
+
+    /// Extra headers; values may contain `${VAR}`
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+
+    /// JSON schema of the arguments
+    #[serde(default = "default_parameters")]
+    pub parameters: Value,
+
+    /// Arguments always sent in the query string
+    #[serde(default)]
+    pub query_params: Vec<String>,
+
+    /// Argument sent as the whole JSON body
+    #[serde(default)]
+    pub body_param: Option<String>,
+
+    /// JSONPath selecting the output from a JSON response, e.g. `$.items[*].sku`
+    #[serde(default)]
+    pub response_path: Option<String>,
+
+    /// Credentials read from the environment
+    #[serde(default)]
+    pub auth: Option<HttpAuth>,
+
+    /// Request timeout in seconds
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+}
+
+fn default_method() -> String {
+    "GET".to_string()
+}
+
+fn default_parameters() -> Value {
+    json!({"type": "object", "properties": {}})
+}
+
+impl HttpToolSpec {
+    pub fn new(
+        name: impl Into<String>,
+        description: impl Into<String>,
+        method: impl Into<String>,
+        url: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            description: description.into(),
+            method: method.into(),
+            url: url.into(),
+            headers: HashMap::new(),
+            parameters: default_parameters(),
+            query_params: Vec::new(),
+            body_param: None,
+            response_path: None,
+            auth: None,
+            timeout_secs: None,
+        }
+    }
+
+    /// Set the JSON schema of the arguments
+    pub fn with_parameters(mut self, parameters: Value) -> Self {
+        self.parameters = parameters;
+        self
+    }
+
+    /// Add a header
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.insert(name.into(), value.into());
+        self
+    }
+
+    /// Select the output from the JSON response
+    pub fn with_response_path(mut self, path: impl Into<String>) -> Self {
+        self.response_path = Some(path.into());
+        self
+    }
+
+    /// Authenticate requests with credentials from the environment
+    pub fn with_auth(mut self, auth: HttpAuth) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
+    pub fn with_timeout_secs(mut self, secs: u64) -> Self {
+        self.timeout_secs = Some(secs);
+        self
+    }
+
+    /// Check the spec without making a request
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.name.trim().is_empty() {
+            return Err(ConfigError::MissingField("name".to_string()));
+        }
+        if self.url.trim().is_empty() {
+            return Err(ConfigError::ValidationError(format!(
+                "HTTP tool '{}' has no url",
+                self.name
+            )));
+        }
+        if reqwest::Method::from_bytes(self.method.to_uppercase().as_bytes()).is_err() {
+            return Err(ConfigError::ValidationError(format!(
+                "HTTP tool '{}' has invalid method '{}'",
+                self.name, self.method
+            )));
+        }
+        if let Some(name) = placeholder_outside_path(&self.url) {
+            return Err(ConfigError::ValidationError(format!(
+                "HTTP tool '{}' has placeholder '{{{}}}' outside the URL path",
+                self.name, name
+            )));
+        }
+        if let Some(path) = &self.response_path {
+            parse_json_path(path).map_err(|e| {
+                ConfigError::ValidationError(format!(
+                    "HTTP tool '{}' has invalid response_path: {}",
+                    self.name, e
+                ))
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Validate the spec and build the tool
+    pub fn to_tool(&self) -> Result<DynamicTool, ConfigError> {
+        self.validate()?;
+        let spec = Arc::new(self.clone());
+        // Redirects are not followed, so header and query credentials are
+        // only ever sent to the configured host
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(
+                self.timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS),
+            ))
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .map_err(|e| {
+                ConfigError::ValidationError(format!(
+                    "HTTP tool '{}' cannot create its HTTP client: {}",
+                    self.name, e
+                ))
+            })?;
+
+        Ok(
+            DynamicTool::new(&self.name, &self.description, move |args| {
+                let spec = spec.clone();
+                let client = client.clone();
+                async move { call(&spec, &client, args).await }
+            })
+            .with_schema(self.parameters.clone()),
+        )
+    }
+}
+
+/// A file of declarative HTTP tools
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HttpToolsConfig {
+    #[serde(default)]
+    pub tools: Vec<HttpToolSpec>,
+}
+
+impl HttpToolsConfig {
+    /// Load from a YAML file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
+        let content = std::fs::read_to_string(path.as_ref())
+            .map_err(|e| ConfigError::IoError(format!("Failed to read HTTP tools file: {}", e)))?;
+        Self::from_yaml(&content)
+    }
+
+    /// Parse from YAML and validate every spec
+    pub fn from_yaml(content: &str) -> Result<Self, ConfigError> {
+        let config: Self = serde_yaml::from_str(content).map_err(|e| {
+            ConfigError::ParseError(format!("Failed to parse HTTP tools YAML: {}", e))
+        })?;
+        for spec in &config.tools {
+            spec.validate()?;
+        }
+        Ok(config)
+    }
+}
+
+// ============================================================================
+// AUTH
+// ============================================================================
+
+/// Credentials injected into requests, read from environment variables
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum HttpAuth {
+    /// `Authorization: Bearer <token>`
+    Bearer { token_env: String },
+
+    /// HTTP basic authentication
+    Basic {
+        username_env: String,
+        password_env: String,
+    },
+
+    /// A custom header, e.g. `X-Api-Key`
+    Header { name: String, value_env: String },
+
+    /// A query string parameter, e.g. `api_key`
+    Query { name: String, value_env: String },
+}
+
+impl HttpAuth {
+    pub fn bearer(token_env: impl Into<String>) -> Self {
+        Self::Bearer {
+            token_env: token_env.into(),
+        }
+    }
+
+    pub fn header(name: impl Into<String>, value_env: impl Into<String>) -> Self {
+        Self::Header {
+            name: name.into(),
+            value_env: value_env.into(),
+        }
+    }
+
+    fn apply(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<reqwest::RequestBuilder, ToolError> {
+        Ok(match self {
+            Self::Bearer { token_env } => request.bearer_auth(env_var(token_env)?),
+            Self::Basic {
+                username_env,
+                password_env,
+            } => request.basic_auth(env_var(username_env)?, Some(env_var(password_env)?)),
+            Self::Header { name, value_env } => request.header(name, env_var(value_env)?),
+            Self::Query { name, value_env } => request.query(&[(name, env_var(value_env)?)]),
+        })
+    }
+}
+
+fn env_var(name: &str) -> Result<String, ToolError> {
+    std::env::var(name).map_err(|_| {
+        ToolError::ExecutionFailed(format!("environment variable {} is not set", name))
+    })
+}
+
+/// Replace `${VAR}` with the value of environment variable `VAR`
+fn expand_env(template: &str) -> Result<String, ToolError> {
+    let mut result = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("${") {
+        let Some(end) = rest[start..].find('}') else {
+            break;
+        };
+        result.push_str(&rest[..start]);
+        result.push_str(&env_var(&rest[start + 2..start + end])?);
+        rest = &rest[start + end + 1..];
+    }
+    result.push_str(rest);
+    Ok(result)
+}
+
+// ============================================================================
+// EXECUTION
+// ============================================================================
+
+async fn call(
+    spec: &HttpToolSpec,
+    client: &reqwest::Client,
+    args: Value,
+) -> Result<String, ToolError> {
+    let method = reqwest::Method::from_bytes(spec.method.to_uppercase().as_bytes())
+        .map_err(|_| ToolError::InvalidInput(format!("invalid method '{}'", spec.method)))?;
+    let args = match args {
+        Value::Object(args) => args,
+        Value::Null => Map::new(),
+        other => {
+            return Err(ToolError::InvalidInput(format!(
+                "expected an object of arguments, got {}",
+                other
+            )))
+        }
+    };
+
+    let mut url = expand_env(&spec.url)?;
+    let mut in_path = HashSet::new();
+    for (name, value) in &args {
+        let placeholder = format!("{{{}}}", name);
+        if url.contains(&placeholder) {
+            url = url.replace(
+                &placeholder,
+                &encode_path_segment(name, &value_text(value))?,
+            );
+            in_path.insert(name.as_str());
+        }
+    }
+    if let Some(name) = unfilled_placeholder(&url) {
+        return Err(ToolError::MissingArgument(name.to_string()));
+    }
+
+    let sends_body = !matches!(
+        method,
+        reqwest::Method::GET | reqwest::Method::HEAD | reqwest::Method::DELETE
+    );
+    let mut query: Vec<(String, String)> = Vec::new();
+    let mut body = None;
+    let mut body_fields = Map::new();
+    for (name, value) in &args {
+        if in_path.contains(name.as_str()) {
+            continue;
+        }
+        if spec.body_param.as_deref() == Some(name.as_str()) {
+            body = Some(value.clone());
+        } else if spec.query_params.contains(name) || !sends_body || spec.body_param.is_some() {
+            push_query(&mut query, name, value);
+        } else {
+            body_fields.insert(name.clone(), value.clone());
+        }
+    }
+    if body.is_none() && !body_fields.is_empty() {
+        body = Some(Value::Object(body_fields));
+    }
+
+    let mut request = client.request(method, &url).query(&query);
+    for (name, value) in &spec.headers {
+        request = request.header(name, expand_env(value)?);
+    }
+    if let Some(auth) = &spec.auth {
+        request = auth.apply(request)?;
+    }
+    if let Some(body) = &body {
+        request = request.json(body);
+    }
+
+    // Errors leave out the URL, which may hold query credentials
+    let response = request.send().await.map_err(|e| {
+        if e.is_timeout() {
+            ToolError::Timeout(Duration::from_secs(
+                spec.timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS),
+            ))
+        } else {
+            ToolError::NetworkError(e.without_url().to_string())
+        }
+    })?;
+    let status = response.status();
+    let (text, truncated) = read_body(response).await?;
+
+    if !status.is_success() {
+        let body: String = text.chars().take(MAX_ERROR_BODY_CHARS).collect();
+        return Err(ToolError::ExecutionFailed(format!(
+            "HTTP {}: {}",
+            status.as_u16(),
+            body.trim()
+        )));
+    }
+
+    match &spec.response_path {
+        Some(_) if truncated => Err(ToolError::ExecutionFailed(format!(
+            "response is larger than {} bytes",
+            MAX_RESPONSE_BYTES
+        ))),
+        Some(path) => {
+            let json: Value = serde_json::from_str(&text)
+                .map_err(|e| ToolError::ParseError(format!("response is not JSON: {}", e)))?;
+            select_output(path, &json)
+        }
+        None if truncated => Ok(format!("{}\n[response truncated]", text)),
+        None => Ok(text),
+    }
+}
+
+/// Up to [`MAX_RESPONSE_BYTES`] of the body, and whether more was discarded
+async fn read_body(response: reqwest::Response) -> Result<(String, bool), ToolError> {
+    let (body, truncated) = read_capped(response, MAX_RESPONSE_BYTES)
+        .await
+        .map_err(|e| ToolError::NetworkError(e.without_url().to_string()))?;
+    Ok((String::from_utf8_lossy(&body).into_owned(), truncated))
+}
+
+/// Up to `limit` bytes of a response body, and whether more was discarded
+///
+/// The rest of the body is not read, so a large response costs no more
+/// memory than `limit`.
+pub(super) async fn read_capped(
+    mut response: reqwest::Response,
+    limit: usize,
+) -> Result<(Vec<u8>, bool), reqwest::Error> {
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        if body.len() + chunk.len() > limit {
+            body.extend_from_slice(&chunk[..limit - body.len()]);
+            return Ok((body, true));
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok((body, false))
+}
+
+/// Text of an argument in a path or query string
+fn value_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn push_query(query: &mut Vec<(String, String)>, name: &str, value: &Value) {
+    match value {
+        Value::Null => {}
+        Value::Array(items) => {
+            for item in items {
+                query.push((name.to_string(), value_text(item)));
+            }
+        }
+        other => query.push((name.to_string(), value_text(other))),
+    }
+}
+
+/// Percent-encode an argument as one path segment
+///
+/// `.` and `..` are refused: URL parsing would resolve them and let the
+/// argument reach another path on the server.
+fn encode_path_segment(name: &str, value: &str) -> Result<String, ToolError> {
+    if matches!(value, "" | "." | "..") {
+        return Err(ToolError::InvalidInput(format!(
+            "'{}' is not a valid path segment for {}",
+            value, name
+        )));
+    }
+
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    Ok(encoded)
+}
+
+/// First `{name}` placeholder of a URL template outside its path
+///
+/// Arguments may only fill path segments: one in the host would let the
+/// model pick the server the tool's credentials are sent to. `${VAR}`
+/// environment variables are set by the operator and allowed anywhere.
+fn placeholder_outside_path(url: &str) -> Option<&str> {
+    let authority = url.find("://").map_or(0, |i| i + 3);
+    let path_start = url[authority..]
+        .find(['/', '?', '#'])
+        .map_or(url.len(), |i| authority + i);
+    let path_end = url[path_start..]
+        .find(['?', '#'])
+        .map_or(url.len(), |i| path_start + i);
+
+    let mut rest = 0;
+    while let Some(offset) = url[rest..].find('{') {
+        let start = rest + offset;
+        let end = start + url[start..].find('}')?;
+        let is_env = url[..start].ends_with('$');
+        if !is_env && (start < path_start || end >= path_end) {
+            return Some(&url[start + 1..end]);
+        }
+        rest = end + 1;
+    }
+    None
+}
+
+/// First `{name}` left in a URL
+fn unfilled_placeholder(url: &str) -> Option<&str> {
+    let start = url.find('{')?;
+    let end = url[start..].find('}')?;
+    Some(&url[start + 1..start + end])
+}
+
+// ============================================================================
+// JSONPATH
+// ============================================================================
+//
+// The subset needed to pick values out of API responses: `$`, `.key`,
+// `['key']`, `[index]` (negative counts from the end), `[*]` and `.*`.
+
+#[derive(Debug, Clone, PartialEq)]
+enum PathSegment {
+    Key(String),
+    Index(i64),
+    Wildcard,
+}
+
+fn parse_json_path(path: &str) -> Result<Vec<PathSegment>, String> {
+    let path = path.trim();
+    let mut rest = path.strip_prefix('$').unwrap_or(path);
+    let mut segments = Vec::new();
+
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('.') {
+            let end = after.find(['.', '[']).unwrap_or(after.len());
+            let key = &after[..end];
+            match key {
+                "" => return Err(format!("empty key in '{}'", path)),
+                "*" => segments.push(PathSegment::Wildcard),
+                key => segments.push(PathSegment::Key(key.to_string())),
+            }
+            rest = &after[end..];
+        } else if let Some(after) = rest.strip_prefix('[') {
+            let end = after
+                .find(']')
+                .ok_or_else(|| format!("unclosed '[' in '{}'", path))?;
+            let inner = after[..end].trim();
+            let segment = if inner == "*" {
+                PathSegment::Wildcard
+            } else if let Some(key) = inner
+                .strip_prefix('\'')
+                .and_then(|s| s.strip_suffix('\''))
+                .or_else(|| inner.strip_prefix('"').and_then(|s| s.strip_suffix('"')))
+            {
+                PathSegment::Key(key.to_string())
+            } else {
+                PathSegment::Index(
+                    inner
+                        .parse()
+                        .map_err(|_| format!("invalid index '{}' in '{}'", inner, path))?,
+                )
+            };
+            segments.push(segment);
+            rest = &after[end + 1..];
+        } else {
+            // A bare leading key, as in `data.items`
+            let end = rest.find(['.', '[']).unwrap_or(rest.len());
+            segments.push(PathSegment::Key(rest[..end].to_string()));
+            rest = &rest[end..];
+        }
+    }
+    Ok(segments)
+}
+
+fn select_json_path<'a>(segments: &[PathSegment], value: &'a Value) -> Vec<&'a Value> {
+    let mut current = vec![value];
+    for segment in segments {
+        current = current
+            .into_iter()
+            .flat_map(|value| -> Vec<&Value> {
+                match (segment, value) {
+                    (PathSegment::Key(key), Value::Object(map)) => {
+                        map.get(key).into_iter().collect()
+                    }
+                    (PathSegment::Index(index), Value::Array(items)) => {
+                        let index = if *index < 0 {
+                            items.len() as i64 + index
+                        } else {
+                            *index
+                        };
+                        usize::try_from(index)
+                            .ok()
+                            .and_then(|i| items.get(i))
+                            .into_iter()
+                            .collect()
+                    }
+                    (PathSegment::Wildcard, Value::Array(items)) => items.iter().collect(),
+                    (PathSegment::Wildcard, Value::Object(map)) => map.values().collect(),
+                    _ => Vec::new(),
+                }
+            })
+            .collect();
+    }
+    current
+}
+
+/// Output for the model: a single string as is, anything else as JSON
+fn select_output(path: &str, json: &Value) -> Result<String, ToolError> {
+    let segments = parse_json_path(path).map_err(ToolError::InvalidInput)?;
+    let wildcard = segments.contains(&PathSegment::Wildcard);
+    let matches = select_json_path(&segments, json);
+
+    match matches.as_slice() {
+        [] if wildcard => Ok("[]".to_string()),
+        [] => Err(ToolError::ExecutionFailed(format!(
+            "response has nothing at '{}'",
+            path
+        ))),
+        [Value::String(s)] if !wildcard => Ok(s.clone()),
+        [value] if !wildcard => Ok(value.to_string()),
+        values => Ok(Value::Array(values.iter().map(|v| (*v).clone()).collect()).to_string()),
+    }
+}
+
+// ============================================================================
+// OPENAPI
+// ============================================================================
+
+/// Which operations of an OpenAPI document to import, and how
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OpenApiOptions {
+    /// Server URL, overriding the document's first `servers` entry
+    #[serde(default)]
+    pub base_url: Option<String>,
+
+    /// operationIds (or generated names) to import; empty imports all
+    #[serde(default)]
+    pub operations: Vec<String>,
+
+    /// Headers added to every request
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+
+    /// Credentials for every request
+    #[serde(default)]
+    pub auth: Option<HttpAuth>,
+
+    /// Prefix for tool names, e.g. `erp` gives `erp_getOrder`
+    #[serde(default)]
+    pub tool_prefix: Option<String>,
+
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+}
+
+impl OpenApiOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    pub fn with_operations<I, S>(mut self, operations: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.operations = operations.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.insert(name.into(), value.into());
+        self
+    }
+
+    pub fn with_auth(mut self, auth: HttpAuth) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
+    pub fn with_tool_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.tool_prefix = Some(prefix.into());
+        self
+    }
+}
+
+const OPENAPI_METHODS: [&str; 5] = ["get", "post", "put", "patch", "delete"];
+
+/// `$ref`s are followed this deep; recursive schemas are cut off there
+const MAX_REF_DEPTH: usize = 8;
+
+/// Build tool specs from an OpenAPI 3 document in JSON or YAML
+pub fn openapi_tool_specs(
+    document: &str,
+    options: &OpenApiOptions,
+) -> Result<Vec<HttpToolSpec>, ConfigError> {
+    let doc: Value = serde_yaml::from_str(document)
+        .map_err(|e| ConfigError::ParseError(format!("Failed to parse OpenAPI document: {}", e)))?;
+
+    let version = doc["openapi"].as_str().unwrap_or_default();
+    if !version.starts_with('3') {
+        return Err(ConfigError::ValidationError(
+            "only OpenAPI 3 documents are supported".to_string(),
+        ));
+    }
+
+    let base_url = match &options.base_url {
+        Some(url) => url.clone(),
+        None => server_url(&doc).ok_or_else(|| {
+            ConfigError::ValidationError(
+                "OpenAPI document has no absolute server URL; set base_url".to_string(),
+            )
+        })?,
+    };
+    let base_url = base_url.trim_end_matches('/');
+
+    let paths = doc["paths"]
+        .as_object()
+        .ok_or_else(|| ConfigError::MissingField("paths".to_string()))?;
+
+    let mut specs = Vec::new();
+    let mut imported = HashSet::new();
+    for (path, item) in paths {
+        let item = resolve(&doc, item, 0);
+        for method in OPENAPI_METHODS {
+            let Some(operation) = item.get(method) else {
+                continue;
+            };
+            let operation_id = operation["operationId"]
+                .as_str()
+                .map(tool_name)
+                .unwrap_or_else(|| generated_name(method, path));
+            if !options.operations.is_empty() && !options.operations.contains(&operation_id) {
+                continue;
+            }
+
+            imported.insert(operation_id.clone());
+            let mut spec = operation_spec(&doc, path, method, &item, operation)?;
+            spec.name = match &options.tool_prefix {
+                Some(prefix) => format!("{}_{}", prefix, operation_id),
+                None => operation_id,
+            };
+            spec.url = format!("{}{}", base_url, path);
+            spec.headers = options.headers.clone();
+            spec.auth = options.auth.clone();
+            spec.timeout_secs = options.timeout_secs;
+            spec.validate()?;
+            specs.push(spec);
+        }
+    }
+
+    for wanted in &options.operations {
+        if !imported.contains(wanted) {
+            return Err(ConfigError::ValidationError(format!(
+                "OpenAPI document has no operation '{}'",
+                wanted
+            )));
+        }
+    }
+    Ok(specs)
+}
+
+/// The first server URL, with variables set to their defaults
+fn server_url(doc: &Value) -> Option<String> {
+    let server = doc["servers"].get(0)?;
+    let mut url = server["url"].as_str()?.to_string();
+    if let Some(variables) = server["variables"].as_object() {
+        for (name, variable) in variables {
+            if let Some(default) = variable["default"].as_str() {
+                url = url.replace(&format!("{{{}}}", name), default);
+            }
+        }
+    }
+    url.contains("://").then_some(url)
+}
+
+fn operation_spec(
+    doc: &Value,
+    path: &str,
+    method: &str,
+    item: &Value,
+    operation: &Value,
+) -> Result<HttpToolSpec, ConfigError> {
+    let description = operation["summary"]
+        .as_str()
+        .or_else(|| operation["description"].as_str())
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("{} {}", method.to_uppercase(), path));
+
+    let mut spec = HttpToolSpec::new("", description, method.to_uppercase(), path);
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+
+    // Operation parameters override path-level ones with the same name and location
+    let mut parameters: Vec<Value> = Vec::new();
+    for parameter in item["parameters"]
+        .as_array()
+        .into_iter()
+        .chain(operation["parameters"].as_array())
+        .flatten()
+    {
+        let parameter = resolve(doc, parameter, 0);
+        parameters.retain(|p| p["name"] != parameter["name"] || p["in"] != parameter["in"]);
+        parameters.push(parameter);
+    }
+
+    for parameter in parameters {
+        let Some(name) = parameter["name"].as_str() else {
+            continue;
+        };
+        match parameter["in"].as_str() {
+            Some("path") => required.push(name.to_string()),
+            Some("query") => {
+                spec.query_params.push(name.to_string());
+                if parameter["required"].as_bool() == Some(true) {
+                    required.push(name.to_string());
+                }
+            }
+            // Header and cookie parameters are left to `headers` and `auth`
+            _ => continue,
+        }
+
+        let mut schema = parameter
+            .get("schema")
+            .cloned()
+            .unwrap_or_else(|| json!({"type": "string"}));
+        if let (Some(description), Value::Object(schema)) =
+            (parameter["description"].as_str(), &mut schema)
+        {
+            schema.insert("description".to_string(), json!(description));
+        }
+        properties.insert(name.to_string(), schema);
+    }
+
+    if let Some(body) = operation.get("requestBody") {
+        let body = resolve(doc, body, 0);
+        if let Some(schema) = body["content"]["application/json"].get("schema") {
+            let mut schema = schema.clone();
+            if let (Some(description), Value::Object(schema)) =
+                (body["description"].as_str(), &mut schema)
+            {
+                schema
+                    .entry("description")
+                    .or_insert_with(|| json!(description));
+            }
+            properties.insert("body".to_string(), schema);
+            spec.body_param = Some("body".to_string());
+            if body["required"].as_bool() == Some(true) {
+                required.push("body".to_string());
+            }
+        }
+    }
+
+    let mut parameters = json!({"type": "object", "properties": properties});
+    if !required.is_empty() {
+        parameters["required"] = json!(required);
+    }
+    spec.parameters = parameters;
+    Ok(spec)
+}
+
+/// Inline local `$ref`s such as `#/components/schemas/Order`
+fn resolve(doc: &Value, value: &Value, depth: usize) -> Value {
+    match value {
+        Value::Object(map) => {
+            if let Some(reference) = map.get("$ref").and_then(Value::as_str) {
+                if depth >= MAX_REF_DEPTH {
+                    return json!({});
+                }
+                return match reference
+                    .strip_prefix('#')
+                    .and_then(|pointer| doc.pointer(pointer))
+                {
+                    Some(target) => resolve(doc, target, depth + 1),
+                    None => json!({}),
+                };
+            }
+            Value::Object(
+                map.iter()
+                    .map(|(key, value)| (key.clone(), resolve(doc, value, depth)))
+                    .collect(),
+            )
+        }
+        Value::Array(items) => Value::Array(items.iter().map(|v| resolve(doc, v, depth)).collect()),
+        other => other.clone(),
+    }
+}
+
+/// Keep characters tool names allow
+fn tool_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Name for an operation without an operationId, e.g. `get_orders_id`
+fn generated_name(method: &str, path: &str) -> String {
+    let segments: Vec<String> = path
+        .split('/')
+        .map(|segment| tool_name(segment.trim_matches(|c| c == '{' || c == '}')))
+        .filter(|segment| !segment.is_empty())
+        .collect();
+    format!("{}_{}", method, segments.join("_"))
+}
+
+// ============================================================================
+// REGISTRY
+// ============================================================================
+
+impl ToolRegistry {
+    /// Register a declarative HTTP tool
+    pub fn register_http_tool(&mut self, spec: &HttpToolSpec) -> Result<(), ConfigError> {
+        self.register(spec.to_tool()?);
+        Ok(())
+    }
+
+    /// Register the HTTP tools in a YAML document, returning their names
+    pub fn register_http_tools_yaml(&mut self, yaml: &str) -> Result<Vec<String>, ConfigError> {
+        let config = HttpToolsConfig::from_yaml(yaml)?;
+        self.register_specs(&config.tools)
+    }
+
+    /// Register the HTTP tools in a YAML file, returning their names
+    pub fn register_http_tools_file<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+    ) -> Result<Vec<String>, ConfigError> {
+        let config = HttpToolsConfig::from_file(path)?;
+        self.register_specs(&config.tools)
+    }
+
+    /// Register operations of an OpenAPI 3 document, returning their names
+    pub fn register_openapi(
+        &mut self,
+        document: &str,
+        options: &OpenApiOptions,
+    ) -> Result<Vec<String>, ConfigError> {
+        let specs = openapi_tool_specs(document, options)?;
+        self.register_specs(&specs)
+    }
+
+    /// Register operations of an OpenAPI 3 file, returning their names
+    pub fn register_openapi_file<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        options: &OpenApiOptions,
+    ) -> Result<Vec<String>, ConfigError> {
+        let document = std::fs::read_to_string(path.as_ref())
+            .map_err(|e| ConfigError::IoError(format!("Failed to read OpenAPI file: {}", e)))?;
+        self.register_openapi(&document, options)
+    }
+
+    /// Register the specs, or none of them if one is invalid
+    fn register_specs(&mut self, specs: &[HttpToolSpec]) -> Result<Vec<String>, ConfigError> {
+        let tools = specs
+            .iter()
+            .map(HttpToolSpec::to_tool)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(tools
+            .into_iter()
+            .map(|tool| {
+                let name = tool.name().to_string();
+                self.register(tool);
+                name
+            })
+            .collect())
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Answer one request with `status` and `body`, returning the raw request
+    async fn serve_once(status: &str, body: &str) -> (String, tokio::task::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let response = format!(
+            "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        );
+
+        let handle = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            loop {
+                let n = stream.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..n]);
+                let text = String::from_utf8_lossy(&request).to_string();
+                if let Some(head_end) = text.find("\r\n\r\n") {
+                    let length = text[..head_end]
+                        .lines()
+                        .find_map(|line| {
+                            let (name, value) = line.split_once(':')?;
+                            name.eq_ignore_ascii_case("content-length")
+                                .then(|| value.trim().parse::<usize>().ok())?
+                        })
+                        .unwrap_or(0);
+                    if request.len() >= head_end + 4 + length || n == 0 {
+                        break;
+                    }
+                }
+                if n == 0 {
+                    break;
+                }
+            }
+            stream.write_all(response.as_bytes()).await.unwrap();
+            String::from_utf8_lossy(&request).to_string()
+        });
+        (base_url, handle)
+    }
+
+    #[test]
+    fn test_parse_http_tools_yaml() {
+        let config = HttpToolsConfig::from_yaml(
+            r#"
+tools:
+  - name: stock_lookup
+    description: Stock by SKU
+    url: http://erp/api/stock/{sku}
+    response_path: $.data.available
+    auth:
+      type: header
+      name: X-Api-Key
+      value_env: ERP_KEY
+"#,
+        )
+        .unwrap();
+
+        let spec = &config.tools[0];
+        assert_eq!(spec.method, "GET");
+        assert_eq!(spec.parameters, default_parameters());
+        assert_eq!(spec.auth, Some(HttpAuth::header("X-Api-Key", "ERP_KEY")));
+
+        let invalid = HttpToolsConfig::from_yaml(
+            "tools:\n  - name: bad\n    description: x\n    url: http://erp\n    response_path: $.items[x\n",
+        );
+        assert!(matches!(invalid, Err(ConfigError::ValidationError(_))));
+    }
+
+    #[test]
+    fn test_placeholders_only_fill_the_path() {
+        let spec = |url: &str| HttpToolSpec::new("lookup", "Lookup", "GET", url);
+
+        assert!(spec("https://erp/api/{sku}/stock").validate().is_ok());
+        assert!(spec("${ERP_URL}/api/{sku}?format=json").validate().is_ok());
+        assert!(spec("https://${ERP_HOST}/api/{sku}").validate().is_ok());
+
+        for url in [
+            "https://{host}/api",
+            "https://erp.{domain}/api",
+            "https://erp:{port}/api",
+            "{base}/api",
+            "https://erp/api?sku={sku}",
+            "https://erp/api#{section}",
+        ] {
+            let err = spec(url).validate().unwrap_err();
+            assert!(err.to_string().contains("outside the URL path"), "{}", url);
+        }
+
+        let mut registry = ToolRegistry::new();
+        let result = registry.register_http_tools_yaml(
+            "tools:\n  - name: ok\n    description: x\n    url: http://erp/a\n  - name: bad\n    description: x\n    url: http://{host}/a\n",
+        );
+        assert!(matches!(result, Err(ConfigError::ValidationError(_))));
+        assert!(registry.get("ok").is_none());
+
+        // A server variable without a default would put the host in the model's hands
+        let api = ORDERS_API.replace("      host: { default: erp.example.com }\n", "");
+        let result = openapi_tool_specs(&api, &OpenApiOptions::new());
+        assert!(matches!(result, Err(ConfigError::ValidationError(_))));
+    }
+
+    #[test]
+    fn test_json_path_selection() {
+        let json = json!({
+            "data": {
+                "items": [
+                    {"sku": "P-1", "qty": 4},
+                    {"sku": "P-2", "qty": 0}
+                ],
+                "total": 2
+            }
+        });
+
+        assert_eq!(select_output("$.data.total", &json).unwrap(), "2");
+        assert_eq!(select_output("$.data.items[0].sku", &json).unwrap(), "P-1");
+        assert_eq!(
+            select_output("$['data'].items[-1].qty", &json).unwrap(),
+            "0"
+        );
+        assert_eq!(
+            select_output("$.data.items[*].sku", &json).unwrap(),
+            r#"["P-1","P-2"]"#
+        );
+        assert_eq!(select_output("$.data.missing[*]", &json).unwrap(), "[]");
+        assert!(select_output("$.data.missing", &json).is_err());
+        assert!(parse_json_path("$.data..items").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_get_with_path_query_and_auth() {
+        std::env::set_var("HTTP_TOOLS_TEST_TOKEN", "secret-token");
+        let (base_url, server) = serve_once("200 OK", r#"{"data": {"available": 12}}"#).await;
+
+        let tool = HttpToolSpec::new(
+            "stock_lookup",
+            "Stock by SKU",
+            "GET",
+            format!("{}/api/stock/{{sku}}", base_url),
+        )
+        .with_parameters(json!({
+            "type": "object",
+            "properties": {"sku": {"type": "string"}, "warehouse": {"type": "string"}},
+            "required": ["sku"]
+        }))
+        .with_response_path("$.data.available")
+        .with_auth(HttpAuth::bearer("HTTP_TOOLS_TEST_TOKEN"))
+        .to_tool()
+        .unwrap();
+
+        let output = tool
+            .execute(json!({"sku": "P 1/2", "warehouse": "BKK"}))
+            .await
+            .unwrap();
+        assert_eq!(output, "12");
+
+        let request = server.await.unwrap();
+        assert!(request.starts_with("GET /api/stock/P%201%2F2?warehouse=BKK HTTP/1.1"));
+        assert!(request
+            .to_lowercase()
+            .contains("authorization: bearer secret-token"));
+
+        let missing = tool.execute(json!({"warehouse": "BKK"})).await;
+        assert!(matches!(missing, Err(ToolError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn test_path_segments_cannot_traverse() {
+        assert_eq!(encode_path_segment("sku", "P 1/2").unwrap(), "P%201%2F2");
+        assert_eq!(encode_path_segment("sku", "v1.2").unwrap(), "v1.2");
+        for value in ["", ".", ".."] {
+            assert!(matches!(
+                encode_path_segment("sku", value),
+                Err(ToolError::InvalidInput(_))
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_redirects_are_not_followed() {
+        std::env::set_var("HTTP_TOOLS_TEST_KEY", "secret-key");
+        let (base_url, server) =
+            serve_once("302 Found\r\nLocation: http://127.0.0.1:9/steal", "").await;
+
+        let result = HttpToolSpec::new("stock", "Stock", "GET", format!("{}/stock", base_url))
+            .with_auth(HttpAuth::Query {
+                name: "api_key".to_string(),
+                value_env: "HTTP_TOOLS_TEST_KEY".to_string(),
+            })
+            .to_tool()
+            .unwrap()
+            .execute(json!({}))
+            .await;
+
+        assert!(matches!(result, Err(ToolError::ExecutionFailed(m)) if m.starts_with("HTTP 302")));
+        server.await.unwrap();
+
+        // Connection errors leave out the URL and its credentials
+        let unreachable = HttpToolSpec::new("stock", "Stock", "GET", "http://127.0.0.1:9/stock")
+            .with_auth(HttpAuth::Query {
+                name: "api_key".to_string(),
+                value_env: "HTTP_TOOLS_TEST_KEY".to_string(),
+            })
+            .to_tool()
+            .unwrap()
+            .execute(json!({}))
+            .await;
+        assert!(
+            matches!(unreachable, Err(ToolError::NetworkError(m)) if !m.contains("secret-key"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_large_responses_are_truncated() {
+        let body = "x".repeat(MAX_RESPONSE_BYTES + 10);
+        let (base_url, _server) = serve_once("200 OK", &body).await;
+        let spec = HttpToolSpec::new("dump", "Dump", "GET", format!("{}/dump", base_url));
+
+        let output = spec.to_tool().unwrap().execute(json!({})).await.unwrap();
+        assert_eq!(
+            output.len(),
+            MAX_RESPONSE_BYTES + "\n[response truncated]".len()
+        );
+
+        let (base_url, _server) = serve_once("200 OK", &body).await;
+        let selected = HttpToolSpec::new("dump", "Dump", "GET", format!("{}/dump", base_url))
+            .with_response_path("$.data")
+            .to_tool()
+            .unwrap()
+            .execute(json!({}))
+            .await;
+        assert!(matches!(selected, Err(ToolError::ExecutionFailed(_))));
+    }
+
+    #[tokio::test]
+    async fn test_post_sends_json_body_and_reports_errors() {
+        let (base_url, server) =
+            serve_once("422 Unprocessable Entity", r#"{"error": "bad qty"}"#).await;
+
+        let tool = HttpToolSpec::new(
+            "create_order",
+            "Create an order",
+            "POST",
+            format!("{}/orders", base_url),
+        )
+        .with_header("X-Source", "agent")
+        .to_tool()
+        .unwrap();
+
+        let result = tool.execute(json!({"sku": "P-1", "qty": 3})).await;
+        match result {
+            Err(ToolError::ExecutionFailed(message)) => {
+                assert_eq!(message, r#"HTTP 422: {"error": "bad qty"}"#)
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        let request = server.await.unwrap();
+        assert!(request.starts_with("POST /orders HTTP/1.1"));
+        assert!(request.to_lowercase().contains("x-source: agent"));
+        assert!(request.ends_with(r#"{"qty":3,"sku":"P-1"}"#));
+
+        let unset = HttpToolSpec::new("t", "t", "GET", "http://localhost/${HTTP_TOOLS_TEST_UNSET}")
+            .to_tool()
+            .unwrap()
+            .execute(json!({}))
+            .await;
+        assert!(
+            matches!(unset, Err(ToolError::ExecutionFailed(m)) if m.contains("HTTP_TOOLS_TEST_UNSET"))
+        );
+    }
+
+    const ORDERS_API: &str = r##"
+openapi: 3.0.3
+info: { title: ERP, version: "1.0" }
+servers:
+  - url: https://{host}/v1
+    variables:
+      host: { default: erp.example.com }
+paths:
+  /orders/{orderId}:
+    parameters:
+      - $ref: "#/components/parameters/OrderId"
+    get:
+      operationId: getOrder
+      summary: Fetch an order
+      parameters:
+        - name: expand
+          in: query
+          schema: { type: boolean }
+    patch:
+      operationId: updateOrder
+      requestBody:
+        required: true
+        content:
+          application/json:
+            schema: { $ref: "#/components/schemas/OrderUpdate" }
+  /stock:
+    get:
+      parameters:
+        - name: sku
+          in: query
+          required: true
+          description: Product SKU
+          schema: { type: string }
+components:
+  parameters:
+    OrderId:
+      name: orderId
+      in: path
+      required: true
+      schema: { type: string }
+  schemas:
+    OrderUpdate:
+      type: object
+      properties:
+        status: { type: string }
+"##;
+
+    #[test]
+    fn test_openapi_import() {
+        let specs = openapi_tool_specs(ORDERS_API, &OpenApiOptions::new()).unwrap();
+        let names: Vec<&str> = specs.iter().map(|spec| spec.name.as_str()).collect();
+        assert_eq!(names, ["getOrder", "updateOrder", "get_stock"]);
+
+        let get_order = &specs[0];
+        assert_eq!(get_order.method, "GET");
+        assert_eq!(get_order.url, "https://erp.example.com/v1/orders/{orderId}");
+        assert_eq!(get_order.description, "Fetch an order");
+        assert_eq!(get_order.query_params, ["expand"]);
+        assert_eq!(get_order.parameters["required"], json!(["orderId"]));
+
+        let update = &specs[1];
+        assert_eq!(update.body_param.as_deref(), Some("body"));
+        assert_eq!(
+            update.parameters["properties"]["body"]["properties"]["status"],
+            json!({"type": "string"})
+        );
+        assert_eq!(update.parameters["required"], json!(["orderId", "body"]));
+
+        assert_eq!(
+            specs[2].parameters["properties"]["sku"],
+            json!({"type": "string", "description": "Product SKU"})
+        );
+
+        let missing = openapi_tool_specs(
+            ORDERS_API,
+            &OpenApiOptions::new().with_operations(["deleteOrder"]),
+        );
+        assert!(matches!(missing, Err(ConfigError::ValidationError(_))));
+        assert!(openapi_tool_specs("swagger: '2.0'\npaths: {}", &OpenApiOptions::new()).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_register_openapi_operations() {
+        let (base_url, server) = serve_once("200 OK", r#"{"id": "42", "status": "shipped"}"#).await;
+
+        let mut registry = ToolRegistry::new();
+        let names = registry
+            .register_openapi(
+                ORDERS_API,
+                &OpenApiOptions::new()
+                    .with_base_url(base_url)
+                    .with_operations(["updateOrder"])
+                    .with_tool_prefix("erp"),
+            )
+            .unwrap();
+        assert_eq!(names, ["erp_updateOrder"]);
+
+        let output = registry
+            .get("erp_updateOrder")
+            .unwrap()
+            .execute(json!({"orderId": "42", "body": {"status": "shipped"}}))
+            .await
+            .unwrap();
+        assert_eq!(output, r#"{"id": "42", "status": "shipped"}"#);
+
+        let request = server.await.unwrap();
+        assert!(request.starts_with("PATCH /orders/42 HTTP/1.1"));
+        assert!(request.ends_with(r#"{"status":"shipped"}"#));
+    }
+}
@@ -0,0 +1,990 @@
+//! Model Context Protocol (MCP) Client
+//!
+//! Connects to MCP servers, discovers their tools with `tools/list` and wraps
+//! each one as a [`DynamicTool`] carrying the server's JSON schema, so MCP
+//! tools run like any other tool: in the [`ToolRegistry`], on a crew agent or
+//! with `ReActAgent::with_tool`.
+//!
+//! Two transports are supported:
+//! - **stdio**: the server is started as a child process and exchanges
+//!   newline-delimited JSON-RPC messages over stdin/stdout
+//! - **HTTP**: JSON-RPC requests are POSTed to the server's endpoint
+//!   (streamable HTTP); replies may be plain JSON or an event stream
+//!
+//! `examples/mcp_server.rs` is a small stdio server to try it with:
+//! `cargo build -p agent --example mcp_server` and point a config at
+//! `target/debug/examples/mcp_server`.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use agent::crew::mcp::{McpClient, McpServerConfig};
+//! use agent::crew::ToolRegistry;
+//!
+//! let config = McpServerConfig::stdio("demo", "target/debug/examples/mcp_server")
+//!     .with_tool_prefix("demo");
+//! let client = McpClient::connect(&config).await?;
+//!
+//! let mut registry = ToolRegistry::new();
+//! registry.register_mcp(&client);
+//! let output = registry.get("demo_echo").unwrap().execute(json!({"text": "hi"})).await?;
+//! ```
+//!
+//! # Example agents.yaml
+//!
+//! ```yaml
+//! researcher:
+//!   role: Senior Data Researcher
+//!   goal: Find what customers bought last year
+//!   backstory: You know the CRM inside out.
+//!   mcp_servers:
+//!     - name: files
+//!       command: npx
+//!       args: ["-y", "@modelcontextprotocol/server-filesystem", "/srv/reports"]
+//!     - name: crm
+//!       url: http://crm.internal:8080/mcp
+//!       headers:
+//!         Authorization: Bearer token
+//!       tools: [lookup_customer]
+//!       tool_prefix: crm
+//! ```
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::process::{Child, Command};
+use tokio::sync::Mutex;
+
+use super::http_tools::read_capped;
+use super::tools::{DynamicTool, ToolError, ToolRegistry};
+
+/// Protocol revision requested during the handshake
+pub const PROTOCOL_VERSION: &str = "2025-03-26";
+
+/// Header carrying the session assigned by an HTTP server
+const SESSION_HEADER: &str = "Mcp-Session-Id";
+
+/// Most `tools/list` pages fetched from one server
+const MAX_TOOL_PAGES: usize = 100;
+
+/// Largest message read from a server, as one stdio line or HTTP body
+const MAX_MESSAGE_BYTES: usize = 4 * 1024 * 1024;
+
+/// Errors talking to an MCP server
+#[derive(Error, Debug)]
+pub enum McpError {
+    #[error("Failed to start MCP server: {0}")]
+    Spawn(String),
+
+    #[error("MCP transport error: {0}")]
+    Transport(String),
+
+    #[error("MCP protocol error: {0}")]
+    Protocol(String),
+
+    #[error("MCP server error {code}: {message}")]
+    Server { code: i64, message: String },
+
+    #[error("MCP tool failed: {0}")]
+    ToolFailed(String),
+
+    #[error("MCP request timed out after {0:?}")]
+    Timeout(Duration),
+}
+
+impl From<McpError> for ToolError {
+    fn from(e: McpError) -> Self {
+        match e {
+            McpError::Timeout(limit) => ToolError::Timeout(limit),
+            McpError::Transport(message) => ToolError::NetworkError(message),
+            McpError::ToolFailed(message) => ToolError::ExecutionFailed(message),
+            other => ToolError::ExecutionFailed(other.to_string()),
+        }
+    }
+}
+
+// ============================================================================
+// CONFIGURATION
+// ============================================================================
+
+/// How to reach an MCP server
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum McpTransportConfig {
+    /// Start the server as a child process and talk over stdin/stdout
+    Stdio {
+        command: String,
+        #[serde(default)]
+        args: Vec<String>,
+        /// Extra environment variables for the server process
+        #[serde(default)]
+        env: HashMap<String, String>,
+    },
+
+    /// POST JSON-RPC requests to a streamable HTTP endpoint
+    Http {
+        url: String,
+        /// Extra request headers, e.g. `Authorization`
+        #[serde(default)]
+        headers: HashMap<String, String>,
+    },
+}
+
+/// An MCP server whose tools an agent can use
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct McpServerConfig {
+    /// Name used in logs and errors
+    pub name: String,
+
+    #[serde(flatten)]
+    pub transport: McpTransportConfig,
+
+    /// Only load these tools (all of the server's tools when empty)
+    #[serde(default)]
+    pub tools: Vec<String>,
+
+    /// Expose tools as `{prefix}_{tool}` to avoid name clashes
+    #[serde(default)]
+    pub tool_prefix: Option<String>,
+
+    /// Timeout for each request to the server
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_timeout_secs() -> u64 {
+    30
+}
+
+impl McpServerConfig {
+    /// A server started with `command`
+    pub fn stdio(name: impl Into<String>, command: impl Into<String>) -> Self {
+        Self::new(
+            name,
+            McpTransportConfig::Stdio {
+                command: command.into(),
+                args: Vec::new(),
+                env: HashMap::new(),
+            },
+        )
+    }
+
+    /// A server reachable at `url`
+    pub fn http(name: impl Into<String>, url: impl Into<String>) -> Self {
+        Self::new(
+            name,
+            McpTransportConfig::Http {
+                url: url.into(),
+                headers: HashMap::new(),
+            },
+        )
+    }
+
+    fn new(name: impl Into<String>, transport: McpTransportConfig) -> Self {
+        Self {
+            name: name.into(),
+            transport,
+            tools: Vec::new(),
+            tool_prefix: None,
+            timeout_secs: default_timeout_secs(),
+        }
+    }
+
+    /// Add command-line arguments (stdio servers only)
+    pub fn with_args<I, S>(mut self, new_args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        if let McpTransportConfig::Stdio { args, .. } = &mut self.transport {
+            args.extend(new_args.into_iter().map(Into::into));
+        }
+        self
+    }
+
+    /// Add a request header (HTTP servers only)
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        if let McpTransportConfig::Http { headers, .. } = &mut self.transport {
+            headers.insert(name.into(), value.into());
+        }
+        self
+    }
+
+    /// Only load the named tools
+    pub fn with_tools<I, S>(mut self, tools: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.tools = tools.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Expose tools as `{prefix}_{tool}`
+    pub fn with_tool_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.tool_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Set the per-request timeout
+    pub fn with_timeout_secs(mut self, seconds: u64) -> Self {
+        self.timeout_secs = seconds;
+        self
+    }
+}
+
+// ============================================================================
+// TRANSPORTS
+// ============================================================================
+
+#[async_trait]
+trait Transport: Send + Sync {
+    /// Send a request and wait for the response with the same id
+    async fn request(&self, message: Value) -> Result<Value, McpError>;
+
+    /// Send a notification; no response is expected
+    async fn notify(&self, message: Value) -> Result<(), McpError>;
+}
+
+type Reader = BufReader<Box<dyn AsyncRead + Send + Unpin>>;
+type Writer = Box<dyn AsyncWrite + Send + Unpin>;
+
+/// Newline-delimited JSON-RPC over a pair of byte streams
+struct StdioTransport {
+    io: Mutex<(Writer, Reader)>,
+    /// The server process, killed when the client is dropped
+    _child: Option<Child>,
+}
+
+impl StdioTransport {
+    fn spawn(
+        command: &str,
+        args: &[String],
+        env: &HashMap<String, String>,
+    ) -> Result<Self, McpError> {
+        let mut child = Command::new(command)
+            .args(args)
+            .envs(env)
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::inherit())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| McpError::Spawn(format!("{}: {}", command, e)))?;
+
+        let stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| McpError::Spawn("no stdin".into()))?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| McpError::Spawn("no stdout".into()))?;
+        Ok(Self::new(stdout, stdin, Some(child)))
+    }
+
+    fn new<R, W>(reader: R, writer: W, child: Option<Child>) -> Self
+    where
+        R: AsyncRead + Send + Unpin + 'static,
+        W: AsyncWrite + Send + Unpin + 'static,
+    {
+        let reader: Box<dyn AsyncRead + Send + Unpin> = Box::new(reader);
+        let writer: Writer = Box::new(writer);
+        Self {
+            io: Mutex::new((writer, BufReader::new(reader))),
+            _child: child,
+        }
+    }
+}
+
+async fn write_message(writer: &mut Writer, message: &Value) -> Result<(), McpError> {
+    let mut line = serde_json::to_string(message).map_err(|e| McpError::Protocol(e.to_string()))?;
+    line.push('\n');
+    writer
+        .write_all(line.as_bytes())
+        .await
+        .map_err(|e| McpError::Transport(e.to_string()))?;
+    writer
+        .flush()
+        .await
+        .map_err(|e| McpError::Transport(e.to_string()))
+}
+
+#[async_trait]
+impl Transport for StdioTransport {
+    async fn request(&self, message: Value) -> Result<Value, McpError> {
+        let id = message["id"].clone();
+        let mut io = self.io.lock().await;
+        let (writer, reader) = &mut *io;
+        write_message(writer, &message).await?;
+
+        loop {
+            let Some(line) = read_line_limited(reader, MAX_MESSAGE_BYTES).await? else {
+                return Err(McpError::Transport("server closed its output".to_string()));
+            };
+            let line = String::from_utf8_lossy(&line);
+
+            let Ok(incoming) = serde_json::from_str::<Value>(line.trim()) else {
+                tracing::debug!(
+                    line = line.trim(),
+                    "Ignoring non-JSON output from MCP server"
+                );
+                continue;
+            };
+            if incoming.get("method").is_some() {
+                // Notifications are dropped; requests from the server get an answer
+                if let Some(reply) = reply_to_server(&incoming) {
+                    write_message(writer, &reply).await?;
+                }
+                continue;
+            }
+            // Responses to requests abandoned after a timeout are skipped
+            if incoming.get("id") == Some(&id) {
+                return Ok(incoming);
+            }
+        }
+    }
+
+    async fn notify(&self, message: Value) -> Result<(), McpError> {
+        let mut io = self.io.lock().await;
+        write_message(&mut io.0, &message).await
+    }
+}
+
+/// Read one line of at most `max` bytes, without its newline, or `None` at
+/// the end of the stream
+///
+/// A longer line is consumed and discarded, so the next read starts at the
+/// following message, and reported as an error.
+async fn read_line_limited<R>(reader: &mut R, max: usize) -> Result<Option<Vec<u8>>, McpError>
+where
+    R: AsyncBufRead + Unpin,
+{
+    let mut line = Vec::new();
+    let mut too_long = false;
+    loop {
+        let available = reader
+            .fill_buf()
+            .await
+            .map_err(|e| McpError::Transport(e.to_string()))?;
+        if available.is_empty() {
+            if line.is_empty() && !too_long {
+                return Ok(None);
+            }
+            break;
+        }
+
+        let (chunk, used, ends) = match available.iter().position(|&byte| byte == b'\n') {
+            Some(end) => (&available[..end], end + 1, true),
+            None => (available, available.len(), false),
+        };
+        if !too_long && line.len() + chunk.len() > max {
+            too_long = true;
+            line = Vec::new();
+        }
+        if !too_long {
+            line.extend_from_slice(chunk);
+        }
+        reader.consume(used);
+        if ends {
+            break;
+        }
+    }
+
+    if too_long {
+        return Err(McpError::Protocol(format!(
+            "message from server is larger than {} bytes",
+            max
+        )));
+    }
+    Ok(Some(line))
+}
+
+/// Answer a request sent by the server: `ping` is supported, nothing else
+fn reply_to_server(request: &Value) -> Option<Value> {
+    let id = request.get("id")?;
+    Some(if request["method"] == "ping" {
+        json!({"jsonrpc": "2.0", "id": id, "result": {}})
+    } else {
+        json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": {"code": -32601, "message": "Method not found"}
+        })
+    })
+}
+
+/// JSON-RPC over streamable HTTP
+struct HttpTransport {
+    client: reqwest::Client,
+    url: String,
+    headers: HashMap<String, String>,
+    session_id: std::sync::Mutex<Option<String>>,
+}
+
+impl HttpTransport {
+    fn new(url: &str, headers: &HashMap<String, String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url: url.to_string(),
+            headers: headers.clone(),
+            session_id: std::sync::Mutex::new(None),
+        }
+    }
+
+    async fn post(&self, message: &Value) -> Result<reqwest::Response, McpError> {
+        let mut request = self
+            .client
+            .post(&self.url)
+            .header(
+                reqwest::header::ACCEPT,
+                "application/json, text/event-stream",
+            )
+            .json(message);
+        for (name, value) in &self.headers {
+            request = request.header(name, value);
+        }
+        let session_id = self.session_id.lock().unwrap().clone();
+        if let Some(session_id) = session_id {
+            request = request.header(SESSION_HEADER, session_id);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| McpError::Transport(e.to_string()))?;
+        if let Some(session_id) = response
+            .headers()
+            .get(SESSION_HEADER)
+            .and_then(|value| value.to_str().ok())
+        {
+            *self.session_id.lock().unwrap() = Some(session_id.to_string());
+        }
+        if !response.status().is_success() {
+            return Err(McpError::Transport(format!("HTTP {}", response.status())));
+        }
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl Transport for HttpTransport {
+    async fn request(&self, message: Value) -> Result<Value, McpError> {
+        let response = self.post(&message).await?;
+        let is_stream = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.starts_with("text/event-stream"));
+        let (body, truncated) = read_capped(response, MAX_MESSAGE_BYTES)
+            .await
+            .map_err(|e| McpError::Transport(e.to_string()))?;
+        if truncated {
+            return Err(McpError::Protocol(format!(
+                "response is larger than {} bytes",
+                MAX_MESSAGE_BYTES
+            )));
+        }
+        let body = String::from_utf8_lossy(&body);
+
+        if is_stream {
+            event_stream_messages(&body)
+                .into_iter()
+                .find(|incoming| incoming.get("id") == Some(&message["id"]))
+                .ok_or_else(|| McpError::Protocol("no response in event stream".to_string()))
+        } else {
+            serde_json::from_str(&body).map_err(|e| McpError::Protocol(e.to_string()))
+        }
+    }
+
+    async fn notify(&self, message: Value) -> Result<(), McpError> {
+        self.post(&message).await.map(|_| ())
+    }
+}
+
+/// JSON messages in the `data` fields of a server-sent event stream
+fn event_stream_messages(body: &str) -> Vec<Value> {
+    let mut messages = Vec::new();
+    let mut data: Vec<&str> = Vec::new();
+
+    for line in body.lines().chain(std::iter::once("")) {
+        if line.is_empty() {
+            if !data.is_empty() {
+                if let Ok(message) = serde_json::from_str(&data.join("\n")) {
+                    messages.push(message);
+                }
+                data.clear();
+            }
+        } else if let Some(value) = line.strip_prefix("data:") {
+            data.push(value.strip_prefix(' ').unwrap_or(value));
+        }
+    }
+    messages
+}
+
+// ============================================================================
+// CLIENT
+// ============================================================================
+
+/// A tool advertised by an MCP server
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct McpToolInfo {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(rename = "inputSchema", default = "object_schema")]
+    pub input_schema: Value,
+}
+
+fn object_schema() -> Value {
+    json!({"type": "object"})
+}
+
+/// A connected MCP server and the tools it offers
+pub struct McpClient {
+    name: String,
+    transport: Box<dyn Transport>,
+    next_id: AtomicU64,
+    timeout: Duration,
+    tool_prefix: Option<String>,
+    tools: Vec<McpToolInfo>,
+}
+
+impl McpClient {
+    /// Connect to a server, run the handshake and discover its tools
+    pub async fn connect(config: &McpServerConfig) -> Result<Arc<Self>, McpError> {
+        let transport: Box<dyn Transport> = match &config.transport {
+            McpTransportConfig::Stdio { command, args, env } => {
+                Box::new(StdioTransport::spawn(command, args, env)?)
+            }
+            McpTransportConfig::Http { url, headers } => Box::new(HttpTransport::new(url, headers)),
+        };
+        Self::start(config, transport).await
+    }
+
+    async fn start(
+        config: &McpServerConfig,
+        transport: Box<dyn Transport>,
+    ) -> Result<Arc<Self>, McpError> {
+        let mut client = Self {
+            name: config.name.clone(),
+            transport,
+            next_id: AtomicU64::new(1),
+            timeout: Duration::from_secs(config.timeout_secs),
+            tool_prefix: config.tool_prefix.clone(),
+            tools: Vec::new(),
+        };
+
+        client
+            .request(
+                "initialize",
+                json!({
+                    "protocolVersion": PROTOCOL_VERSION,
+                    "capabilities": {},
+                    "clientInfo": {
+                        "name": "agentic-rust",
+                        "version": env!("CARGO_PKG_VERSION")
+                    }
+                }),
+            )
+            .await?;
+        client
+            .transport
+            .notify(json!({"jsonrpc": "2.0", "method": "notifications/initialized"}))
+            .await?;
+
+        let mut tools = client.list_tools().await?;
+        if !config.tools.is_empty() {
+            tools.retain(|tool| config.tools.contains(&tool.name));
+        }
+        tracing::info!(server = %config.name, tools = tools.len(), "Connected to MCP server");
+        client.tools = tools;
+
+        Ok(Arc::new(client))
+    }
+
+    /// Name of the server from its config
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Tools discovered on the server, after filtering
+    pub fn server_tools(&self) -> &[McpToolInfo] {
+        &self.tools
+    }
+
+    /// Wrap the discovered tools as [`DynamicTool`]s that call the server
+    pub fn tools(self: &Arc<Self>) -> Vec<DynamicTool> {
+        self.tools
+            .iter()
+            .map(|info| {
+                let client = self.clone();
+                let remote_name = info.name.clone();
+                let description = info.description.clone().unwrap_or_else(|| {
+                    format!("{} (from the {} MCP server)", info.name, self.name)
+                });
+
+                DynamicTool::new(self.tool_name(&info.name), description, move |input| {
+                    let client = client.clone();
+                    let remote_name = remote_name.clone();
+                    async move { Ok(client.call_tool(&remote_name, input).await?) }
+                })
+                .with_schema(info.input_schema.clone())
+            })
+            .collect()
+    }
+
+    /// Call a tool by its name on the server, returning its text output
+    pub async fn call_tool(&self, name: &str, arguments: Value) -> Result<String, McpError> {
+        let result = self
+            .request("tools/call", json!({"name": name, "arguments": arguments}))
+            .await?;
+        let output = tool_output(&result);
+
+        if result["isError"].as_bool().unwrap_or(false) {
+            Err(McpError::ToolFailed(output))
+        } else {
+            Ok(output)
+        }
+    }
+
+    fn tool_name(&self, name: &str) -> String {
+        match &self.tool_prefix {
+            Some(prefix) => format!("{}_{}", prefix, name),
+            None => name.to_string(),
+        }
+    }
+
+    /// All tools on the server, following `nextCursor` pagination
+    ///
+    /// Fails on a cursor seen before or after [`MAX_TOOL_PAGES`] pages, so a
+    /// misbehaving server cannot keep the client paging forever.
+    async fn list_tools(&self) -> Result<Vec<McpToolInfo>, McpError> {
+        let mut tools = Vec::new();
+        let mut cursor: Option<String> = None;
+        let mut seen = HashSet::new();
+
+        for _ in 0..MAX_TOOL_PAGES {
+            let params = match &cursor {
+                Some(cursor) => json!({"cursor": cursor}),
+                None => json!({}),
+            };
+            let result = self.request("tools/list", params).await?;
+            let page: Vec<McpToolInfo> = serde_json::from_value(result["tools"].clone())
+                .map_err(|e| McpError::Protocol(format!("invalid tools/list result: {}", e)))?;
+            tools.extend(page);
+
+            match result["nextCursor"].as_str() {
+                Some(next) if !seen.insert(next.to_string()) => {
+                    return Err(McpError::Protocol(format!(
+                        "tools/list repeated cursor {}",
+                        next
+                    )));
+                }
+                Some(next) => cursor = Some(next.to_string()),
+                None => return Ok(tools),
+            }
+        }
+        Err(McpError::Protocol(format!(
+            "tools/list returned more than {} pages",
+            MAX_TOOL_PAGES
+        )))
+    }
+
+    async fn request(&self, method: &str, params: Value) -> Result<Value, McpError> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let message = json!({"jsonrpc": "2.0", "id": id, "method": method, "params": params});
+
+        let response = tokio::time::timeout(self.timeout, self.transport.request(message))
+            .await
+            .map_err(|_| McpError::Timeout(self.timeout))??;
+
+        if let Some(error) = response.get("error") {
+            return Err(McpError::Server {
+                code: error["code"].as_i64().unwrap_or(0),
+                message: error["message"].as_str().unwrap_or_default().to_string(),
+            });
+        }
+        response
+            .get("result")
+            .cloned()
+            .ok_or_else(|| McpError::Protocol(format!("{} response has no result", method)))
+    }
+}
+
+/// Text of a `tools/call` result; non-text content is described briefly
+fn tool_output(result: &Value) -> String {
+    let parts: Vec<String> = result["content"]
+        .as_array()
+        .map(|content| {
+            content
+                .iter()
+                .map(|item| match item["type"].as_str() {
+                    Some("text") => item["text"].as_str().unwrap_or_default().to_string(),
+                    Some("resource") => match item["resource"]["text"].as_str() {
+                        Some(text) => text.to_string(),
+                        None => format!("[resource: {}]", item["resource"]["uri"]),
+                    },
+                    Some("resource_link") => format!("[resource: {}]", item["uri"]),
+                    Some(kind) => format!("[{}: {}]", kind, item["mimeType"]),
+                    None => item.to_string(),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    if parts.is_empty() {
+        if let Some(structured) = result.get("structuredContent") {
+            return structured.to_string();
+        }
+    }
+    parts.join("\n")
+}
+
+/// Connect to each server in turn
+pub async fn connect_all(servers: &[McpServerConfig]) -> Result<Vec<Arc<McpClient>>, McpError> {
+    let mut clients = Vec::with_capacity(servers.len());
+    for server in servers {
+        clients.push(McpClient::connect(server).await?);
+    }
+    Ok(clients)
+}
+
+impl ToolRegistry {
+    /// Register every tool of a connected MCP server
+    pub fn register_mcp(&mut self, client: &Arc<McpClient>) {
+        for tool in client.tools() {
+            self.register(tool);
+        }
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::DuplexStream;
+
+    /// In-process stand-in for an MCP server process
+    ///
+    /// The first `tools/list` page points to `first_cursor`; the cursor
+    /// `loop` keeps pointing to itself.
+    async fn fake_server(
+        input: DuplexStream,
+        mut output: DuplexStream,
+        first_cursor: &'static str,
+    ) {
+        let mut lines = BufReader::new(input).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            let request: Value = serde_json::from_str(&line).unwrap();
+            let result = match request["method"].as_str().unwrap() {
+                "initialize" => json!({
+                    "protocolVersion": PROTOCOL_VERSION,
+                    "capabilities": {"tools": {}},
+                    "serverInfo": {"name": "fake", "version": "1.0"}
+                }),
+                "tools/list" if request["params"]["cursor"] == "loop" => json!({
+                    "tools": [],
+                    "nextCursor": "loop"
+                }),
+                "tools/list" if request["params"]["cursor"].is_null() => json!({
+                    "tools": [{
+                        "name": "echo",
+                        "description": "Echo text back",
+                        "inputSchema": {
+                            "type": "object",
+                            "properties": {"text": {"type": "string"}},
+                            "required": ["text"]
+                        }
+                    }],
+                    "nextCursor": first_cursor
+                }),
+                "tools/list" => json!({
+                    "tools": [{"name": "fail"}, {"name": "hidden"}]
+                }),
+                "tools/call" => {
+                    // Chatter the client has to skip over
+                    let noise = json!({"jsonrpc": "2.0", "method": "notifications/message"});
+                    output
+                        .write_all(format!("{}\n", noise).as_bytes())
+                        .await
+                        .unwrap();
+
+                    match request["params"]["name"].as_str().unwrap() {
+                        "echo" => json!({
+                            "content": [{"type": "text", "text": request["params"]["arguments"]["text"]}]
+                        }),
+                        _ => json!({
+                            "content": [{"type": "text", "text": "boom"}],
+                            "isError": true
+                        }),
+                    }
+                }
+                _ => continue,
+            };
+            let response = json!({"jsonrpc": "2.0", "id": request["id"], "result": result});
+            output
+                .write_all(format!("{}\n", response).as_bytes())
+                .await
+                .unwrap();
+        }
+    }
+
+    async fn start_fake(
+        config: &McpServerConfig,
+        first_cursor: &'static str,
+    ) -> Result<Arc<McpClient>, McpError> {
+        let (client_out, server_in) = tokio::io::duplex(4096);
+        let (server_out, client_in) = tokio::io::duplex(4096);
+        tokio::spawn(fake_server(server_in, server_out, first_cursor));
+
+        let transport = StdioTransport::new(client_in, client_out, None);
+        McpClient::start(config, Box::new(transport)).await
+    }
+
+    async fn connect_fake(config: &McpServerConfig) -> Arc<McpClient> {
+        start_fake(config, "page-2").await.unwrap()
+    }
+
+    #[test]
+    fn test_server_config_from_yaml() {
+        let yaml = r#"
+- name: files
+  command: npx
+  args: ["-y", "server-filesystem"]
+- name: crm
+  url: http://localhost:8080/mcp
+  headers:
+    Authorization: Bearer secret
+  tools: [lookup_customer]
+  tool_prefix: crm
+"#;
+        let servers: Vec<McpServerConfig> = serde_yaml::from_str(yaml).unwrap();
+
+        assert_eq!(
+            servers[0],
+            McpServerConfig::stdio("files", "npx").with_args(["-y", "server-filesystem"])
+        );
+        assert_eq!(
+            servers[1],
+            McpServerConfig::http("crm", "http://localhost:8080/mcp")
+                .with_header("Authorization", "Bearer secret")
+                .with_tools(["lookup_customer"])
+                .with_tool_prefix("crm")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_discovers_and_calls_tools() {
+        let client = connect_fake(&McpServerConfig::stdio("fake", "unused")).await;
+
+        let names: Vec<&str> = client
+            .server_tools()
+            .iter()
+            .map(|t| t.name.as_str())
+            .collect();
+        assert_eq!(names, ["echo", "fail", "hidden"]);
+
+        let mut registry = ToolRegistry::new();
+        registry.register_mcp(&client);
+
+        let echo = registry.get("echo").unwrap();
+        assert_eq!(echo.description(), "Echo text back");
+        assert_eq!(echo.definition().args_schema["required"], json!(["text"]));
+        assert_eq!(
+            echo.execute(json!({"text": "hello"})).await.unwrap(),
+            "hello"
+        );
+        assert!(matches!(
+            echo.execute(json!({})).await,
+            Err(ToolError::InvalidInput(_))
+        ));
+
+        let fail = registry.get("fail").unwrap();
+        assert_eq!(fail.description(), "fail (from the fake MCP server)");
+        assert!(matches!(
+            fail.execute(json!({})).await,
+            Err(ToolError::ExecutionFailed(message)) if message == "boom"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_repeated_tools_cursor() {
+        let result = start_fake(&McpServerConfig::stdio("fake", "unused"), "loop").await;
+
+        assert!(matches!(result, Err(McpError::Protocol(_))));
+    }
+
+    #[tokio::test]
+    async fn test_filters_and_prefixes_tools() {
+        let config = McpServerConfig::stdio("fake", "unused")
+            .with_tools(["echo", "fail"])
+            .with_tool_prefix("fake");
+        let client = connect_fake(&config).await;
+
+        let mut names: Vec<String> = client
+            .tools()
+            .iter()
+            .map(|t| t.name().to_string())
+            .collect();
+        names.sort();
+        assert_eq!(names, ["fake_echo", "fake_fail"]);
+    }
+
+    #[tokio::test]
+    async fn test_mcp_tools_run_in_react_agent() {
+        use crate::react_agent::{ReActAgent, ReActConfig};
+        use crate::replay::ScriptedClient;
+
+        let client = connect_fake(&McpServerConfig::stdio("fake", "unused")).await;
+        let llm = Arc::new(ScriptedClient::new("gpt-4").with_responses([
+            "Action: echo\nAction Input: {\"text\": \"from mcp\"}",
+            "Final Answer: done",
+        ]));
+        let mut agent = ReActAgent::new(ReActConfig::default()).with_llm_client(llm);
+        for tool in client.tools() {
+            agent = agent.with_tool(tool);
+        }
+
+        let response = agent.run("Echo something").await.unwrap();
+        let action = response.trace.unwrap()[0].action.clone().unwrap();
+        assert!(action.success);
+        assert_eq!(action.tool_output.unwrap(), "from mcp");
+    }
+
+    #[tokio::test]
+    async fn test_read_line_limited() {
+        let mut input: &[u8] = b"short\nmuch too long\n{}\nlast";
+
+        let line = read_line_limited(&mut input, 5).await.unwrap();
+        assert_eq!(line.unwrap(), b"short");
+        let too_long = read_line_limited(&mut input, 5).await;
+        assert!(matches!(too_long, Err(McpError::Protocol(_))));
+        let line = read_line_limited(&mut input, 5).await.unwrap();
+        assert_eq!(line.unwrap(), b"{}");
+        let line = read_line_limited(&mut input, 5).await.unwrap();
+        assert_eq!(line.unwrap(), b"last");
+        assert!(read_line_limited(&mut input, 5).await.unwrap().is_none());
+    }
+
+    #[test]
+    fn test_event_stream_messages() {
+        let body = "event: message\r\ndata: {\"jsonrpc\":\"2.0\",\"method\":\"notifications/progress\"}\r\n\r\nid: 2\ndata: {\"jsonrpc\":\"2.0\",\ndata: \"id\":1,\"result\":{}}\n";
+        let messages = event_stream_messages(body);
+
+        assert_eq!(messages.len(), 2);
+        assert_eq!(
+            messages[1],
+            json!({"jsonrpc": "2.0", "id": 1, "result": {}})
+        );
+    }
+}
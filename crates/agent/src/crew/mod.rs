@@ -10,6 +10,8 @@
 //! - **Crew**: Teams of agents that collaborate to complete tasks
 //! - **Process**: How tasks are executed (Sequential, Hierarchical)
 //! - **Flow**: Event-driven workflows for complex orchestration
+//! - **MCP**: Tools loaded from Model Context Protocol servers
+//! - **HTTP tools**: Tools declared in YAML or imported from OpenAPI documents
 //!
 //! # Example
 //!
@@ -60,10 +62,13 @@ pub mod config;
 pub mod crew;
 pub mod examples;
 pub mod flow;
+pub mod http_tools;
 pub mod integration;
+pub mod mcp;
 pub mod memory;
 pub mod process;
 pub mod prompts;
+pub mod sandbox;
 pub mod task;
 pub mod tools;
 
@@ -73,8 +78,11 @@ mod tests;
 pub use agent::{Agent, AgentBuilder as CrewAgentBuilder, AgentConfig as CrewAgentConfig};
 pub use crew::{Crew, CrewBuilder, CrewConfig, CrewLoader, CrewResult};
 pub use flow::{Flow, FlowBuilder, FlowState, StateTransition, TransitionCondition};
+pub use http_tools::{HttpAuth, HttpToolSpec, HttpToolsConfig, OpenApiOptions};
+pub use mcp::{McpClient, McpError, McpServerConfig, McpTransportConfig};
 pub use memory::{Memory, MemoryConfig, MemoryType};
 pub use process::{Process, ProcessConfig};
+pub use sandbox::{ExecutionResult, Sandbox, SandboxConfig, SandboxError, SandboxFile};
 pub use task::{Task, TaskBuilder, TaskConfig, TaskContext, TaskOutput, TaskStatus};
 
 // Re-export example crews
@@ -0,0 +1,1186 @@
+//! Sandboxed Code Execution
+//!
+//! Runs code in a subprocess interpreter (Python by default) inside a fresh
+//! temporary working directory, which is removed afterwards. The process gets
+//! a cleared environment and, on Unix, resource limits:
+//!
+//! - **CPU time** and **memory** (`RLIMIT_CPU`, `RLIMIT_AS`)
+//! - **file size** (`RLIMIT_FSIZE`), bounding every file it writes
+//! - **processes** (`RLIMIT_NPROC`), stopping fork bombs
+//! - **wall-clock** timeout, after which its whole process group is killed;
+//!   the group is also killed when a run is dropped before it finishes
+//! - **output**: stdout and stderr are each capped at `max_output_bytes`
+//!
+//! On Linux the process is also moved into new user and network namespaces,
+//! leaving it with only a loopback interface that is down. Where namespaces
+//! are not permitted the run fails, unless `require_network_isolation` is
+//! turned off to accept running with network access. The result records
+//! which applied.
+//!
+//! The code runs as the worker's user, so on Linux the filesystem is confined
+//! with Landlock: it may read and execute only under `read_paths` (system
+//! directories by default), use a few devices such as `/dev/null`, and write
+//! only in its working directory. The worker's files, including its `.env`,
+//! configuration and `/proc`, are out of reach. As with the network, the run
+//! fails where Landlock is not available, unless
+//! `require_filesystem_isolation` is turned off. An interpreter installed
+//! outside the system directories needs its location added to `read_paths`.
+//!
+//! On Linux x86_64 and aarch64 a seccomp filter then refuses system calls
+//! that reach past those boundaries: tracing other processes, mounting,
+//! entering or creating namespaces, kernel modules and keyrings, BPF and
+//! io_uring.
+//!
+//! Regular files the code creates in its working directory are captured in
+//! the result; symlinks are ignored.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use agent::crew::sandbox::{Sandbox, SandboxConfig};
+//!
+//! let sandbox = Sandbox::new(SandboxConfig::default().with_timeout_ms(10_000));
+//! let result = sandbox.run("import statistics\nprint(statistics.mean([3, 5, 10]))").await?;
+//! assert_eq!(result.stdout.trim(), "6");
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use thiserror::Error;
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio::process::{Child, Command};
+
+use super::tools::ToolError;
+
+/// How long output is still read after the process is gone
+const OUTPUT_GRACE: Duration = Duration::from_millis(500);
+
+/// Errors from setting up or running the sandbox
+#[derive(Error, Debug)]
+pub enum SandboxError {
+    #[error("Sandbox IO error: {0}")]
+    Io(String),
+
+    #[error("Failed to start {command}: {message}")]
+    Spawn { command: String, message: String },
+
+    #[error("Network isolation unavailable: {0}")]
+    NetworkIsolation(String),
+
+    #[error("Filesystem isolation unavailable: {0}")]
+    FilesystemIsolation(String),
+}
+
+impl From<std::io::Error> for SandboxError {
+    fn from(e: std::io::Error) -> Self {
+        SandboxError::Io(e.to_string())
+    }
+}
+
+impl From<SandboxError> for ToolError {
+    fn from(e: SandboxError) -> Self {
+        ToolError::ExecutionFailed(e.to_string())
+    }
+}
+
+// ============================================================================
+// CONFIGURATION
+// ============================================================================
+
+/// Interpreter and limits for sandboxed runs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SandboxConfig {
+    /// Interpreter to run, looked up on `PATH`
+    pub command: String,
+
+    /// Arguments before the script path
+    pub args: Vec<String>,
+
+    /// Name the code is saved under in the working directory
+    pub file_name: String,
+
+    /// Wall-clock limit in milliseconds
+    pub timeout_ms: u64,
+
+    /// CPU time limit in seconds
+    pub cpu_time_secs: u64,
+
+    /// Address space limit in bytes
+    pub memory_bytes: u64,
+
+    /// Bytes kept from each of stdout and stderr
+    pub max_output_bytes: usize,
+
+    /// Largest file the code may write, in bytes
+    pub max_file_bytes: u64,
+
+    /// Processes and threads the code may run at once
+    pub max_processes: u64,
+
+    /// Created files captured in the result
+    pub max_files: usize,
+
+    /// Skip network isolation
+    pub allow_network: bool,
+
+    /// Refuse to run when the network cannot be isolated, rather than run
+    /// with network access
+    pub require_network_isolation: bool,
+
+    /// Paths the code may read and execute, besides its working directory
+    pub read_paths: Vec<String>,
+
+    /// Refuse to run when the filesystem cannot be confined, rather than
+    /// run with the worker's access to it
+    pub require_filesystem_isolation: bool,
+
+    /// Extra environment variables
+    pub env: HashMap<String, String>,
+}
+
+impl Default for SandboxConfig {
+    fn default() -> Self {
+        Self {
+            command: "python3".to_string(),
+            // Isolated mode (no user site-packages, PYTHON* vars ignored), unbuffered
+            args: vec!["-I".to_string(), "-u".to_string()],
+            file_name: "main.py".to_string(),
+            timeout_ms: 30_000,
+            cpu_time_secs: 10,
+            memory_bytes: 512 * 1024 * 1024,
+            max_output_bytes: 64 * 1024,
+            max_file_bytes: 10 * 1024 * 1024,
+            max_processes: 128,
+            max_files: 32,
+            allow_network: false,
+            require_network_isolation: true,
+            read_paths: [
+                "/usr",
+                "/bin",
+                "/sbin",
+                "/lib",
+                "/lib32",
+                "/lib64",
+                "/etc/ld.so.cache",
+                "/etc/localtime",
+                "/etc/ssl",
+                "/etc/fonts",
+            ]
+            .map(String::from)
+            .to_vec(),
+            require_filesystem_isolation: true,
+            env: HashMap::new(),
+        }
+    }
+}
+
+impl SandboxConfig {
+    /// Run code with `command`, saved as `file_name`
+    pub fn new(command: impl Into<String>, file_name: impl Into<String>) -> Self {
+        Self {
+            command: command.into(),
+            args: Vec::new(),
+            file_name: file_name.into(),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.args = args.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn with_timeout_ms(mut self, ms: u64) -> Self {
+        self.timeout_ms = ms;
+        self
+    }
+
+    pub fn with_cpu_time_secs(mut self, secs: u64) -> Self {
+        self.cpu_time_secs = secs;
+        self
+    }
+
+    pub fn with_memory_bytes(mut self, bytes: u64) -> Self {
+        self.memory_bytes = bytes;
+        self
+    }
+
+    pub fn with_max_output_bytes(mut self, bytes: usize) -> Self {
+        self.max_output_bytes = bytes;
+        self
+    }
+
+    pub fn with_max_file_bytes(mut self, bytes: u64) -> Self {
+        self.max_file_bytes = bytes;
+        self
+    }
+
+    pub fn with_max_processes(mut self, processes: u64) -> Self {
+        self.max_processes = processes;
+        self
+    }
+
+    /// Let the code use the network
+    pub fn with_network(mut self, allow: bool) -> Self {
+        self.allow_network = allow;
+        self
+    }
+
+    /// Run with network access where the network cannot be isolated,
+    /// instead of failing
+    pub fn with_network_isolation_fallback(mut self) -> Self {
+        self.require_network_isolation = false;
+        self
+    }
+
+    /// Let the code read and execute under `path`, e.g. a virtualenv
+    pub fn with_read_path(mut self, path: impl Into<String>) -> Self {
+        self.read_paths.push(path.into());
+        self
+    }
+
+    /// Run with the worker's filesystem access where it cannot be confined,
+    /// instead of failing
+    pub fn with_filesystem_isolation_fallback(mut self) -> Self {
+        self.require_filesystem_isolation = false;
+        self
+    }
+
+    pub fn with_env(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.env.insert(name.into(), value.into());
+        self
+    }
+}
+
+// ============================================================================
+// RESULTS
+// ============================================================================
+
+/// A file created by sandboxed code
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SandboxFile {
+    /// Path relative to the working directory
+    pub path: String,
+    pub size: u64,
+    pub contents: Vec<u8>,
+}
+
+/// Outcome of a sandboxed run
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExecutionResult {
+    pub stdout: String,
+    pub stderr: String,
+    /// Exit code, when the process exited normally
+    pub exit_code: Option<i32>,
+    /// Signal that ended the process, e.g. SIGXCPU at the CPU limit
+    pub signal: Option<i32>,
+    pub timed_out: bool,
+    /// Whether stdout or stderr was cut at `max_output_bytes`
+    pub output_truncated: bool,
+    pub network_isolated: bool,
+    pub filesystem_isolated: bool,
+    pub files: Vec<SandboxFile>,
+    pub duration_ms: u64,
+}
+
+impl ExecutionResult {
+    /// Whether the code ran to completion with exit code 0
+    pub fn success(&self) -> bool {
+        self.exit_code == Some(0) && !self.timed_out
+    }
+
+    /// Text report for a model: output, created files and how the run ended
+    pub fn summary(&self) -> String {
+        let mut sections = Vec::new();
+        if !self.stdout.trim().is_empty() {
+            sections.push(self.stdout.trim_end().to_string());
+        }
+        if !self.stderr.trim().is_empty() {
+            sections.push(format!("stderr:\n{}", self.stderr.trim_end()));
+        }
+        if self.output_truncated {
+            sections.push("[output truncated]".to_string());
+        }
+        if !self.files.is_empty() {
+            let files: Vec<String> = self
+                .files
+                .iter()
+                .map(|file| format!("{} ({} bytes)", file.path, file.size))
+                .collect();
+            sections.push(format!("files created: {}", files.join(", ")));
+        }
+        if !self.network_isolated {
+            sections.push("ran without network isolation".to_string());
+        }
+        if !self.filesystem_isolated {
+            sections.push("ran without filesystem isolation".to_string());
+        }
+        if self.timed_out {
+            sections.push(format!("timed out after {} ms", self.duration_ms));
+        } else if let Some(signal) = self.signal {
+            sections.push(format!("killed by signal {}", signal));
+        } else if !self.success() {
+            sections.push(format!("exit code: {}", self.exit_code.unwrap_or(-1)));
+        }
+
+        if sections.is_empty() {
+            "(no output)".to_string()
+        } else {
+            sections.join("\n\n")
+        }
+    }
+}
+
+// ============================================================================
+// SANDBOX
+// ============================================================================
+
+/// Runs code in a limited subprocess
+#[derive(Debug, Clone, Default)]
+pub struct Sandbox {
+    config: SandboxConfig,
+}
+
+impl Sandbox {
+    pub fn new(config: SandboxConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn config(&self) -> &SandboxConfig {
+        &self.config
+    }
+
+    /// Run `code` in a fresh working directory
+    pub async fn run(&self, code: &str) -> Result<ExecutionResult, SandboxError> {
+        let workdir = tempfile::Builder::new()
+            .prefix("agent-sandbox-")
+            .tempdir()?;
+        tokio::fs::write(workdir.path().join(&self.config.file_name), code).await?;
+
+        let started = Instant::now();
+        let (mut child, isolation) = self.spawn(workdir.path())?;
+        let mut group = ProcessGroup(child.id());
+
+        let stdout = LimitedBuffer::capture(child.stdout.take(), self.config.max_output_bytes);
+        let stderr = LimitedBuffer::capture(child.stderr.take(), self.config.max_output_bytes);
+
+        let timeout = Duration::from_millis(self.config.timeout_ms);
+        let status = match tokio::time::timeout(timeout, child.wait()).await {
+            Ok(status) => Some(status?),
+            Err(_) => None,
+        };
+        // Also ends anything the code left running in the background
+        group.kill();
+        if status.is_none() {
+            let _ = child.kill().await;
+        }
+        let duration_ms = started.elapsed().as_millis() as u64;
+
+        let (stdout, stdout_truncated) = stdout.finish().await;
+        let (stderr, stderr_truncated) = stderr.finish().await;
+        let files = collect_files(workdir.path(), &self.config)?;
+
+        Ok(ExecutionResult {
+            stdout,
+            stderr,
+            exit_code: status.and_then(|status| status.code()),
+            signal: status.and_then(exit_signal),
+            timed_out: status.is_none(),
+            output_truncated: stdout_truncated || stderr_truncated,
+            network_isolated: isolation.network,
+            filesystem_isolated: isolation.filesystem,
+            files,
+            duration_ms,
+        })
+    }
+
+    /// Start the interpreter, isolating the filesystem and network when
+    /// possible
+    fn spawn(&self, workdir: &Path) -> Result<(Child, Isolation), SandboxError> {
+        let ruleset = match filesystem_ruleset(workdir, &self.config) {
+            Ok(ruleset) => Some(ruleset),
+            Err(e) if self.config.require_filesystem_isolation => {
+                return Err(SandboxError::FilesystemIsolation(e.to_string()))
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "Filesystem isolation unavailable, running without it");
+                None
+            }
+        };
+        let ruleset = ruleset.as_ref();
+        let isolation = |network| Isolation {
+            network,
+            filesystem: ruleset.is_some(),
+        };
+        let start = |isolate| {
+            self.start(workdir, isolate, ruleset)
+                .map_err(|e| self.spawn_error(e))
+        };
+        if self.config.allow_network {
+            return Ok((start(false)?, isolation(false)));
+        }
+        if !cfg!(target_os = "linux") {
+            if self.config.require_network_isolation {
+                return Err(SandboxError::NetworkIsolation(
+                    "network namespaces are only available on Linux".to_string(),
+                ));
+            }
+            return Ok((start(false)?, isolation(false)));
+        }
+
+        match self.start(workdir, true, ruleset) {
+            Ok(child) => Ok((child, isolation(true))),
+            Err(e) if is_exec_error(&e) => Err(self.spawn_error(e)),
+            Err(e) if self.config.require_network_isolation => {
+                Err(SandboxError::NetworkIsolation(e.to_string()))
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "Network isolation unavailable, running without it");
+                Ok((start(false)?, isolation(false)))
+            }
+        }
+    }
+
+    fn start(
+        &self,
+        workdir: &Path,
+        isolate_network: bool,
+        ruleset: Option<&Ruleset>,
+    ) -> std::io::Result<Child> {
+        let mut command = Command::new(&self.config.command);
+        command
+            .args(&self.config.args)
+            .arg(&self.config.file_name)
+            .current_dir(workdir)
+            .env_clear()
+            .env(
+                "PATH",
+                std::env::var("PATH").unwrap_or_else(|_| "/usr/local/bin:/usr/bin:/bin".into()),
+            )
+            .env("HOME", workdir)
+            .env("TMPDIR", workdir)
+            .env("LANG", "C.UTF-8")
+            .env("PYTHONDONTWRITEBYTECODE", "1")
+            .env("MPLBACKEND", "Agg")
+            .envs(&self.config.env)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+
+        #[cfg(unix)]
+        limits::apply(
+            &mut command,
+            &self.config,
+            isolate_network,
+            ruleset.map(std::os::fd::AsRawFd::as_raw_fd),
+        );
+        #[cfg(not(unix))]
+        let _ = (isolate_network, ruleset);
+
+        command.spawn()
+    }
+
+    fn spawn_error(&self, e: std::io::Error) -> SandboxError {
+        SandboxError::Spawn {
+            command: self.config.command.clone(),
+            message: e.to_string(),
+        }
+    }
+}
+
+/// Which isolation a run got
+struct Isolation {
+    network: bool,
+    filesystem: bool,
+}
+
+#[cfg(unix)]
+type Ruleset = std::os::fd::OwnedFd;
+#[cfg(not(unix))]
+type Ruleset = ();
+
+/// Landlock ruleset confining a run to `workdir` and the configured paths
+#[cfg(target_os = "linux")]
+fn filesystem_ruleset(workdir: &Path, config: &SandboxConfig) -> std::io::Result<Ruleset> {
+    limits::landlock::ruleset(workdir, &config.read_paths)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn filesystem_ruleset(_workdir: &Path, _config: &SandboxConfig) -> std::io::Result<Ruleset> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "Landlock is only available on Linux",
+    ))
+}
+
+/// Whether starting failed at exec, rather than while isolating the process
+#[cfg(unix)]
+fn is_exec_error(e: &std::io::Error) -> bool {
+    matches!(
+        e.raw_os_error(),
+        Some(libc::ENOENT | libc::EACCES | libc::ENOEXEC | libc::ENOTDIR)
+    )
+}
+
+#[cfg(not(unix))]
+fn is_exec_error(e: &std::io::Error) -> bool {
+    e.kind() == std::io::ErrorKind::NotFound
+}
+
+#[cfg(unix)]
+mod limits {
+    use super::SandboxConfig;
+    use tokio::process::Command;
+
+    #[cfg(all(target_os = "linux", target_env = "gnu"))]
+    type Resource = libc::__rlimit_resource_t;
+    #[cfg(not(all(target_os = "linux", target_env = "gnu")))]
+    type Resource = libc::c_int;
+
+    /// Limit the child between fork and exec, in its own process group
+    ///
+    /// `ruleset` is a Landlock ruleset the child restricts itself with.
+    pub(super) fn apply(
+        command: &mut Command,
+        config: &SandboxConfig,
+        isolate_network: bool,
+        ruleset: Option<std::os::fd::RawFd>,
+    ) {
+        let cpu = config.cpu_time_secs;
+        let memory = config.memory_bytes;
+        let file_size = config.max_file_bytes;
+        let processes = config.max_processes;
+        // Built before the fork: the child must not allocate
+        #[cfg(all(
+            target_os = "linux",
+            any(target_arch = "x86_64", target_arch = "aarch64")
+        ))]
+        let filter = seccomp::filter();
+
+        command.process_group(0);
+        // SAFETY: the closure only makes async-signal-safe system calls
+        unsafe {
+            command.pre_exec(move || {
+                set_limit(libc::RLIMIT_CPU, cpu)?;
+                set_limit(libc::RLIMIT_AS, memory)?;
+                set_limit(libc::RLIMIT_FSIZE, file_size)?;
+                set_limit(libc::RLIMIT_NPROC, processes)?;
+                set_limit(libc::RLIMIT_CORE, 0)?;
+                #[cfg(target_os = "linux")]
+                if isolate_network && libc::unshare(libc::CLONE_NEWUSER | libc::CLONE_NEWNET) != 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+                #[cfg(target_os = "linux")]
+                if let Some(ruleset) = ruleset {
+                    landlock::restrict(ruleset)?;
+                }
+                #[cfg(not(target_os = "linux"))]
+                let _ = (isolate_network, ruleset);
+                // Last, as it refuses `unshare`
+                #[cfg(all(
+                    target_os = "linux",
+                    any(target_arch = "x86_64", target_arch = "aarch64")
+                ))]
+                seccomp::install(&filter)?;
+                Ok(())
+            });
+        }
+    }
+
+    fn set_limit(resource: Resource, value: u64) -> std::io::Result<()> {
+        let limit = libc::rlimit {
+            rlim_cur: value as libc::rlim_t,
+            rlim_max: value as libc::rlim_t,
+        };
+        // SAFETY: `limit` is a valid rlimit for the duration of the call
+        if unsafe { libc::setrlimit(resource, &limit) } != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    /// Landlock ruleset limiting which files the code can reach
+    #[cfg(target_os = "linux")]
+    pub(super) mod landlock {
+        use std::os::fd::{AsRawFd, FromRawFd, OwnedFd, RawFd};
+        use std::os::unix::fs::OpenOptionsExt;
+        use std::path::Path;
+
+        const CREATE_RULESET_VERSION: libc::c_uint = 1;
+        const RULE_PATH_BENEATH: libc::c_int = 1;
+
+        const EXECUTE: u64 = 1 << 0;
+        const WRITE_FILE: u64 = 1 << 1;
+        const READ_FILE: u64 = 1 << 2;
+        const READ_DIR: u64 = 1 << 3;
+        /// Every right of ABI version 1: reading, writing, creating and
+        /// removing entries
+        const ABI_V1: u64 = (1 << 13) - 1;
+        /// Linking and renaming across directories, from ABI version 2
+        const REFER: u64 = 1 << 13;
+        /// Truncating files, from ABI version 3
+        const TRUNCATE: u64 = 1 << 14;
+        /// Rights that apply to files rather than directories
+        const FILE_RIGHTS: u64 = EXECUTE | WRITE_FILE | READ_FILE | TRUNCATE;
+
+        /// Devices the code may read and write
+        const DEVICES: &[&str] = &["/dev/null", "/dev/zero", "/dev/random", "/dev/urandom"];
+
+        #[repr(C)]
+        struct RulesetAttr {
+            handled_access_fs: u64,
+        }
+
+        #[repr(C, packed)]
+        struct PathBeneathAttr {
+            allowed_access: u64,
+            parent_fd: i32,
+        }
+
+        /// Ruleset allowing everything in `workdir`, reading and executing
+        /// under `read_paths` and using [`DEVICES`]; paths that do not exist
+        /// are skipped
+        pub(in crate::crew::sandbox) fn ruleset(
+            workdir: &Path,
+            read_paths: &[String],
+        ) -> std::io::Result<OwnedFd> {
+            // SAFETY: with the version flag and no attributes, only queries
+            // the supported ABI
+            let abi = unsafe {
+                libc::syscall(
+                    libc::SYS_landlock_create_ruleset,
+                    std::ptr::null::<RulesetAttr>(),
+                    0usize,
+                    CREATE_RULESET_VERSION,
+                )
+            };
+            if abi < 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            let mut handled = ABI_V1;
+            if abi >= 2 {
+                handled |= REFER;
+            }
+            if abi >= 3 {
+                handled |= TRUNCATE;
+            }
+
+            let attr = RulesetAttr {
+                handled_access_fs: handled,
+            };
+            // SAFETY: `attr` is a valid ruleset attribute of the given size
+            let fd = unsafe {
+                libc::syscall(
+                    libc::SYS_landlock_create_ruleset,
+                    &attr as *const RulesetAttr,
+                    std::mem::size_of::<RulesetAttr>(),
+                    0 as libc::c_uint,
+                )
+            };
+            if fd < 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            // SAFETY: the kernel just returned this descriptor, owned by no one else
+            let ruleset = unsafe { OwnedFd::from_raw_fd(fd as RawFd) };
+
+            allow(&ruleset, workdir, handled)?;
+            for path in read_paths {
+                allow(&ruleset, Path::new(path), EXECUTE | READ_FILE | READ_DIR)?;
+            }
+            for device in DEVICES {
+                allow(
+                    &ruleset,
+                    Path::new(device),
+                    (READ_FILE | WRITE_FILE | TRUNCATE) & handled,
+                )?;
+            }
+            Ok(ruleset)
+        }
+
+        /// Grant `access` beneath `path`, if it exists
+        fn allow(ruleset: &OwnedFd, path: &Path, access: u64) -> std::io::Result<()> {
+            let file = match std::fs::OpenOptions::new()
+                .read(true)
+                .custom_flags(libc::O_PATH)
+                .open(path)
+            {
+                Ok(file) => file,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+                Err(e) => return Err(e),
+            };
+            let access = if file.metadata()?.is_dir() {
+                access
+            } else {
+                access & FILE_RIGHTS
+            };
+            let attr = PathBeneathAttr {
+                allowed_access: access,
+                parent_fd: file.as_raw_fd(),
+            };
+            // SAFETY: `attr` is a valid rule and `file` stays open for the call
+            let added = unsafe {
+                libc::syscall(
+                    libc::SYS_landlock_add_rule,
+                    ruleset.as_raw_fd(),
+                    RULE_PATH_BENEATH,
+                    &attr as *const PathBeneathAttr,
+                    0 as libc::c_uint,
+                )
+            };
+            if added != 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            Ok(())
+        }
+
+        /// Restrict the calling process with `ruleset`; async-signal-safe
+        pub(in crate::crew::sandbox) fn restrict(ruleset: RawFd) -> std::io::Result<()> {
+            // SAFETY: plain system calls on a descriptor the caller keeps open
+            unsafe {
+                if libc::prctl(libc::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0) != 0
+                    || libc::syscall(libc::SYS_landlock_restrict_self, ruleset, 0 as libc::c_uint)
+                        != 0
+                {
+                    return Err(std::io::Error::last_os_error());
+                }
+            }
+            Ok(())
+        }
+    }
+
+    /// Seccomp filter refusing system calls that escape the sandbox
+    #[cfg(all(
+        target_os = "linux",
+        any(target_arch = "x86_64", target_arch = "aarch64")
+    ))]
+    pub(super) mod seccomp {
+        use libc::sock_filter;
+
+        #[cfg(target_arch = "x86_64")]
+        const AUDIT_ARCH: u32 = 0xC000_003E;
+        #[cfg(target_arch = "aarch64")]
+        const AUDIT_ARCH: u32 = 0xC000_00B7;
+
+        /// Offsets of the fields of `struct seccomp_data`
+        const NR_OFFSET: u32 = 0;
+        const ARCH_OFFSET: u32 = 4;
+
+        /// System call numbers at or above this are the x32 ABI
+        #[cfg(target_arch = "x86_64")]
+        const X32_SYSCALL_BIT: u32 = 0x4000_0000;
+
+        /// Refused with `EPERM`
+        const DENIED: &[libc::c_long] = &[
+            libc::SYS_ptrace,
+            libc::SYS_process_vm_readv,
+            libc::SYS_process_vm_writev,
+            libc::SYS_mount,
+            libc::SYS_umount2,
+            libc::SYS_pivot_root,
+            libc::SYS_chroot,
+            libc::SYS_unshare,
+            libc::SYS_setns,
+            libc::SYS_init_module,
+            libc::SYS_finit_module,
+            libc::SYS_delete_module,
+            libc::SYS_kexec_load,
+            libc::SYS_reboot,
+            libc::SYS_swapon,
+            libc::SYS_swapoff,
+            libc::SYS_keyctl,
+            libc::SYS_add_key,
+            libc::SYS_request_key,
+            libc::SYS_bpf,
+            libc::SYS_perf_event_open,
+            libc::SYS_userfaultfd,
+            libc::SYS_open_by_handle_at,
+            libc::SYS_io_uring_setup,
+        ];
+
+        /// BPF program: kill on a foreign architecture, refuse [`DENIED`],
+        /// allow the rest
+        pub(in crate::crew::sandbox) fn filter() -> Vec<sock_filter> {
+            let ld = libc::BPF_LD | libc::BPF_W | libc::BPF_ABS;
+            let jeq = libc::BPF_JMP | libc::BPF_JEQ | libc::BPF_K;
+            let ret = libc::BPF_RET | libc::BPF_K;
+            let denied = DENIED.len() as u8;
+
+            let mut program = vec![
+                statement(ld, ARCH_OFFSET),
+                jump(jeq, AUDIT_ARCH, 1, 0),
+                statement(ret, libc::SECCOMP_RET_KILL_PROCESS),
+                statement(ld, NR_OFFSET),
+            ];
+            #[cfg(target_arch = "x86_64")]
+            program.push(jump(
+                libc::BPF_JMP | libc::BPF_JGE | libc::BPF_K,
+                X32_SYSCALL_BIT,
+                denied + 1,
+                0,
+            ));
+            // Each check jumps over the remaining ones and the allow
+            for (i, nr) in DENIED.iter().enumerate() {
+                program.push(jump(jeq, *nr as u32, denied - i as u8, 0));
+            }
+            program.push(statement(ret, libc::SECCOMP_RET_ALLOW));
+            program.push(statement(ret, libc::SECCOMP_RET_ERRNO | libc::EPERM as u32));
+            program
+        }
+
+        fn statement(code: u32, k: u32) -> sock_filter {
+            jump(code, k, 0, 0)
+        }
+
+        fn jump(code: u32, k: u32, jt: u8, jf: u8) -> sock_filter {
+            sock_filter {
+                code: code as u16,
+                jt,
+                jf,
+                k,
+            }
+        }
+
+        /// Install `filter` on the calling process; async-signal-safe
+        pub(in crate::crew::sandbox) fn install(filter: &[sock_filter]) -> std::io::Result<()> {
+            let program = libc::sock_fprog {
+                len: filter.len() as u16,
+                filter: filter.as_ptr() as *mut sock_filter,
+            };
+            // SAFETY: `program` points into `filter`, which outlives the calls
+            unsafe {
+                if libc::prctl(libc::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0) != 0
+                    || libc::prctl(
+                        libc::PR_SET_SECCOMP,
+                        libc::SECCOMP_MODE_FILTER,
+                        &program as *const libc::sock_fprog,
+                    ) != 0
+                {
+                    return Err(std::io::Error::last_os_error());
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Process group of a sandboxed run, killed at the latest when dropped
+///
+/// Dropping the future of [`Sandbox::run`] kills only the interpreter itself
+/// (`kill_on_drop`); the guard also ends whatever it started.
+struct ProcessGroup(Option<u32>);
+
+impl ProcessGroup {
+    fn kill(&mut self) {
+        kill_process_group(self.0.take());
+    }
+}
+
+impl Drop for ProcessGroup {
+    fn drop(&mut self) {
+        self.kill();
+    }
+}
+
+#[cfg(unix)]
+fn kill_process_group(pid: Option<u32>) {
+    if let Some(pid) = pid.and_then(|pid| i32::try_from(pid).ok()) {
+        // SAFETY: signals the group created for the child; harmless if it is gone
+        unsafe {
+            libc::kill(-pid, libc::SIGKILL);
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn kill_process_group(_pid: Option<u32>) {}
+
+#[cfg(unix)]
+fn exit_signal(status: std::process::ExitStatus) -> Option<i32> {
+    use std::os::unix::process::ExitStatusExt;
+    status.signal()
+}
+
+#[cfg(not(unix))]
+fn exit_signal(_status: std::process::ExitStatus) -> Option<i32> {
+    None
+}
+
+/// Output read in the background, keeping at most `limit` bytes
+struct LimitedBuffer {
+    buffer: Arc<Mutex<(Vec<u8>, bool)>>,
+    reader: Option<tokio::task::JoinHandle<()>>,
+}
+
+impl LimitedBuffer {
+    fn capture<R>(stream: Option<R>, limit: usize) -> Self
+    where
+        R: AsyncRead + Unpin + Send + 'static,
+    {
+        let buffer = Arc::new(Mutex::new((Vec::new(), false)));
+        let reader = stream.map(|mut stream| {
+            let buffer = buffer.clone();
+            tokio::spawn(async move {
+                let mut chunk = [0u8; 8192];
+                while let Ok(n) = stream.read(&mut chunk).await {
+                    if n == 0 {
+                        break;
+                    }
+                    let mut buffer = buffer.lock().unwrap();
+                    let room = limit.saturating_sub(buffer.0.len());
+                    buffer.0.extend_from_slice(&chunk[..n.min(room)]);
+                    buffer.1 |= n > room;
+                }
+            })
+        });
+        Self { buffer, reader }
+    }
+
+    /// Text read so far and whether it was truncated
+    async fn finish(self) -> (String, bool) {
+        if let Some(mut reader) = self.reader {
+            // A process that escaped the group may still hold the pipe open
+            if tokio::time::timeout(OUTPUT_GRACE, &mut reader)
+                .await
+                .is_err()
+            {
+                reader.abort();
+            }
+        }
+        let buffer = self.buffer.lock().unwrap();
+        (String::from_utf8_lossy(&buffer.0).into_owned(), buffer.1)
+    }
+}
+
+/// Regular files under `workdir` other than the script, in path order
+fn collect_files(workdir: &Path, config: &SandboxConfig) -> Result<Vec<SandboxFile>, SandboxError> {
+    let mut files = Vec::new();
+    let mut pending = vec![workdir.to_path_buf()];
+
+    while let Some(dir) = pending.pop() {
+        for entry in std::fs::read_dir(&dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            // symlink_metadata, so links out of the sandbox are never followed
+            let metadata = std::fs::symlink_metadata(&path)?;
+            if metadata.is_dir() {
+                pending.push(path);
+            } else if metadata.is_file() {
+                let relative = path
+                    .strip_prefix(workdir)
+                    .unwrap_or(&path)
+                    .to_string_lossy()
+                    .replace('\\', "/");
+                if relative != config.file_name {
+                    files.push((relative, path, metadata.len()));
+                }
+            }
+        }
+    }
+
+    files.sort();
+    files
+        .into_iter()
+        .take(config.max_files)
+        .map(|(relative, path, size)| {
+            Ok(SandboxFile {
+                path: relative,
+                size,
+                contents: std::fs::read(path)?,
+            })
+        })
+        .collect()
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn shell() -> SandboxConfig {
+        SandboxConfig::new("sh", "main.sh")
+    }
+
+    #[tokio::test]
+    async fn test_captures_output_and_exit_code() {
+        let sandbox = Sandbox::new(shell().with_env("GREETING", "hello"));
+
+        let result = sandbox
+            .run("echo $GREETING; echo oops >&2; test \"$HOME\" = \"$(pwd)\" && exit 3")
+            .await
+            .unwrap();
+        assert_eq!(result.stdout, "hello\n");
+        assert_eq!(result.stderr, "oops\n");
+        assert_eq!(result.exit_code, Some(3));
+        assert!(!result.success());
+        assert_eq!(result.summary(), "hello\n\nstderr:\noops\n\nexit code: 3");
+    }
+
+    #[tokio::test]
+    async fn test_captures_created_files() {
+        let sandbox = Sandbox::new(shell());
+
+        let result = sandbox
+            .run("printf 'a,b\\n' > out.csv; mkdir -p plots; printf xy > plots/p.png; ln -s /etc/hostname leak")
+            .await
+            .unwrap();
+        assert!(result.success());
+        let paths: Vec<&str> = result.files.iter().map(|file| file.path.as_str()).collect();
+        assert_eq!(paths, ["out.csv", "plots/p.png"]);
+        assert_eq!(result.files[0].contents, b"a,b\n");
+        assert_eq!(
+            result.summary(),
+            "files created: out.csv (4 bytes), plots/p.png (2 bytes)"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_enforces_limits() {
+        let result = Sandbox::new(shell().with_timeout_ms(300))
+            .run("sleep 5 & sleep 5")
+            .await
+            .unwrap();
+        assert!(result.timed_out);
+        assert!(result.duration_ms < 5_000);
+
+        let result = Sandbox::new(shell().with_max_output_bytes(1_000))
+            .run("i=0; while [ $i -lt 500 ]; do echo 0123456789; i=$((i+1)); done")
+            .await
+            .unwrap();
+        assert_eq!(result.stdout.len(), 1_000);
+        assert!(result.output_truncated);
+
+        let result = Sandbox::new(shell().with_cpu_time_secs(1).with_timeout_ms(20_000))
+            .run("while :; do :; done")
+            .await
+            .unwrap();
+        assert!(!result.timed_out);
+        assert!(result.signal.is_some());
+
+        let result = Sandbox::new(shell().with_max_file_bytes(100))
+            .run("head -c 1000 /dev/zero > big.bin")
+            .await
+            .unwrap();
+        assert!(!result.success());
+        assert_eq!(result.files[0].size, 100);
+    }
+
+    #[tokio::test]
+    async fn test_process_limit() {
+        // The kernel does not apply RLIMIT_NPROC to root
+        if unsafe { libc::geteuid() } == 0 {
+            return;
+        }
+
+        let result = Sandbox::new(shell().with_max_processes(4))
+            .run("for i in 1 2 3 4 5 6 7 8; do sleep 1 & done; wait")
+            .await
+            .unwrap();
+        assert!(!result.stderr.is_empty());
+        assert!(result.duration_ms < 5_000);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_reports_network_isolation() {
+        let result = Sandbox::new(shell()).run("true").await.unwrap();
+        assert!(result.network_isolated);
+        assert_eq!(result.summary(), "(no output)");
+
+        let result = Sandbox::new(shell().with_network(true))
+            .run("true")
+            .await
+            .unwrap();
+        assert!(!result.network_isolated);
+        assert_eq!(result.summary(), "ran without network isolation");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_confines_filesystem_to_workdir() {
+        let outside = tempfile::tempdir().unwrap();
+        let secret = outside.path().join(".env");
+        std::fs::write(&secret, "DATABASE_URL=postgres://secret").unwrap();
+
+        let script = format!(
+            "cat {secret}; cat /proc/self/environ; echo x > {dir}/new; echo ok > inside; cat inside",
+            secret = secret.display(),
+            dir = outside.path().display()
+        );
+        let result = Sandbox::new(shell()).run(&script).await.unwrap();
+
+        assert!(result.filesystem_isolated);
+        assert_eq!(result.stdout, "ok\n");
+        assert!(!result.stderr.contains("secret"));
+        assert_eq!(result.stderr.matches("Permission denied").count(), 3);
+        assert!(!outside.path().join("new").exists());
+        assert_eq!(result.files[0].path, "inside");
+    }
+
+    #[cfg(all(
+        target_os = "linux",
+        any(target_arch = "x86_64", target_arch = "aarch64")
+    ))]
+    #[test]
+    fn test_seccomp_filter_refuses_denied_calls() {
+        // Filters apply per thread, so the test's own thread takes it
+        std::thread::spawn(|| {
+            limits::seccomp::install(&limits::seccomp::filter()).unwrap();
+
+            let source = [7u8];
+            let mut target = [0u8];
+            let local = libc::iovec {
+                iov_base: target.as_mut_ptr().cast(),
+                iov_len: 1,
+            };
+            let remote = libc::iovec {
+                iov_base: source.as_ptr() as *mut libc::c_void,
+                iov_len: 1,
+            };
+            // SAFETY: both iovecs point at live one-byte buffers
+            let read = unsafe { libc::process_vm_readv(libc::getpid(), &local, 1, &remote, 1, 0) };
+            assert_eq!(read, -1);
+            assert_eq!(
+                std::io::Error::last_os_error().raw_os_error(),
+                Some(libc::EPERM)
+            );
+            assert_eq!(target, [0]);
+        })
+        .join()
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_dropped_run_kills_process_group() {
+        let marker = tempfile::NamedTempFile::new().unwrap();
+        let path = marker.path().to_string_lossy().to_string();
+        let code = format!("(sleep 1; echo late > {}) & sleep 5", path);
+
+        let sandbox = Sandbox::new(shell());
+        let _ = tokio::time::timeout(Duration::from_millis(300), sandbox.run(&code)).await;
+        tokio::time::sleep(Duration::from_millis(1_500)).await;
+
+        assert_eq!(std::fs::read_to_string(marker.path()).unwrap(), "");
+    }
+
+    #[tokio::test]
+    async fn test_missing_interpreter() {
+        let sandbox = Sandbox::new(SandboxConfig::new("no-such-interpreter", "main.x"));
+        assert!(matches!(
+            sandbox.run("1").await,
+            Err(SandboxError::Spawn { .. })
+        ));
+    }
+}
@@ -3,11 +3,14 @@
 //! Tasks are specific assignments given to agents. They define what needs
 //! to be done, the expected output, and can depend on other tasks.
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use thiserror::Error;
 use uuid::Uuid;
 
+use crate::structured::{parse_json, schema_for, validate};
+
 /// Errors that can occur during task operations
 #[derive(Error, Debug)]
 pub enum TaskError {
@@ -98,6 +101,10 @@ pub struct TaskConfig {
     /// Retry configuration
     pub max_retries: usize,
 
+    /// JSON schema the output must conform to (fills `TaskOutput::structured_data`)
+    #[serde(default)]
+    pub output_schema: Option<serde_json::Value>,
+
     /// Additional metadata
     pub metadata: HashMap<String, serde_json::Value>,
 }
@@ -119,6 +126,7 @@ impl Default for TaskConfig {
             context_instructions: None,
             include_in_output: true,
             max_retries: 0,
+            output_schema: None,
             metadata: HashMap::new(),
         }
     }
@@ -278,6 +286,11 @@ impl Task {
         self.config.is_async
     }
 
+    /// Get the declared output schema
+    pub fn output_schema(&self) -> Option<&serde_json::Value> {
+        self.config.output_schema.as_ref()
+    }
+
     /// Get current status
     pub fn status(&self) -> TaskStatus {
         self.status
@@ -288,6 +301,23 @@ impl Task {
         self.output.as_ref()
     }
 
+    /// Parse and validate raw output against the declared schema
+    ///
+    /// Returns `Ok(None)` when the task declares no schema.
+    pub fn validate_output(&self, output: &str) -> Result<Option<serde_json::Value>, TaskError> {
+        let Some(schema) = &self.config.output_schema else {
+            return Ok(None);
+        };
+
+        let value = parse_json(output).map_err(TaskError::ValidationFailed)?;
+        let errors = validate(schema, &value);
+        if errors.is_empty() {
+            Ok(Some(value))
+        } else {
+            Err(TaskError::ValidationFailed(errors.join("; ")))
+        }
+    }
+
     /// Get task context
     pub fn context(&self) -> &[TaskContext] {
         &self.context
@@ -342,6 +372,12 @@ impl Task {
         self.completed_at = Some(chrono::Utc::now());
     }
 
+    /// Mark task as cancelled
+    pub fn cancel(&mut self) {
+        self.status = TaskStatus::Cancelled;
+        self.completed_at = Some(chrono::Utc::now());
+    }
+
     /// Mark task as skipped
     pub fn skip(&mut self) {
         self.status = TaskStatus::Skipped;
@@ -500,6 +536,17 @@ impl TaskBuilder {
         self
     }
 
+    /// Require the output to conform to a JSON schema
+    pub fn output_schema(mut self, schema: serde_json::Value) -> Self {
+        self.config.output_schema = Some(schema);
+        self
+    }
+
+    /// Require the output to conform to the JSON schema of `T`
+    pub fn output_type<T: JsonSchema>(self) -> Self {
+        self.output_schema(schema_for::<T>())
+    }
+
     /// Add metadata
     pub fn metadata(mut self, key: impl Into<String>, value: serde_json::Value) -> Self {
         self.config.metadata.insert(key.into(), value);
@@ -588,4 +635,34 @@ mod tests {
         assert!(prompt.contains("Previous output"));
         assert!(prompt.contains("Focus on trends"));
     }
+
+    #[test]
+    fn test_task_output_type() {
+        #[derive(JsonSchema)]
+        #[allow(dead_code)]
+        struct Report {
+            title: String,
+            findings: Vec<String>,
+        }
+
+        let task = Task::builder()
+            .description("Summarize the findings")
+            .expected_output("A report")
+            .output_type::<Report>()
+            .build();
+
+        let schema = task.output_schema().unwrap();
+        assert_eq!(schema["properties"]["findings"]["type"], "array");
+        // Schema instructions are added by the structured completion, not here
+        assert!(!task.build_prompt().contains("JSON schema"));
+
+        let data = task
+            .validate_output(r#"{"title": "Q3", "findings": ["growth"]}"#)
+            .unwrap();
+        assert_eq!(data.unwrap()["title"], "Q3");
+        assert!(matches!(
+            task.validate_output(r#"{"title": "Q3"}"#),
+            Err(TaskError::ValidationFailed(_))
+        ));
+    }
 }
@@ -87,6 +87,8 @@ mod integration_tests {
             shared_state: HashMap::new(),
             iteration: 0,
             max_iterations: 10,
+            output_schema: None,
+            cancellation: Default::default(),
         };
 
         let result = agent.execute(context).await;
@@ -850,6 +852,253 @@ mod integration_tests {
         assert_eq!(listener.task_completes.load(Ordering::SeqCst), 2);
     }
 
+    // ==================== Structured Output Tests ====================
+
+    use crate::rig_integration::{ChatMessage, CompletionClient, LlmError, LlmResponse, Provider};
+
+    /// Client that always replies with the same JSON document
+    struct JsonClient(&'static str);
+
+    #[async_trait]
+    impl CompletionClient for JsonClient {
+        async fn complete(&self, _prompt: &str) -> Result<LlmResponse, LlmError> {
+            Ok(LlmResponse {
+                content: self.0.to_string(),
+                model: "json".to_string(),
+                usage: None,
+                finish_reason: None,
+            })
+        }
+
+        async fn complete_with_system(
+            &self,
+            _system: &str,
+            prompt: &str,
+        ) -> Result<LlmResponse, LlmError> {
+            self.complete(prompt).await
+        }
+
+        async fn chat(&self, _messages: Vec<ChatMessage>) -> Result<LlmResponse, LlmError> {
+            self.complete("").await
+        }
+
+        fn model(&self) -> &str {
+            "json"
+        }
+
+        fn provider(&self) -> Provider {
+            Provider::Custom
+        }
+    }
+
+    fn rating_schema() -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {"score": {"type": "integer", "minimum": 1, "maximum": 5}},
+            "required": ["score"]
+        })
+    }
+
+    #[tokio::test]
+    async fn test_crew_task_structured_output() {
+        let agent = Agent::builder()
+            .id("rater")
+            .role("Rater")
+            .goal("Rate things")
+            .backstory("Rater")
+            .llm_client(Arc::new(JsonClient(r#"{"score": 4}"#)))
+            .build();
+
+        let task = Task::builder()
+            .id("rate")
+            .description("Rate the product")
+            .expected_output("A score")
+            .agent("rater")
+            .output_schema(rating_schema())
+            .build();
+
+        let mut crew = Crew::builder().agent(agent).task(task).build();
+        let result = crew.kickoff().await.unwrap();
+
+        let output = &result.task_outputs["rate"];
+        assert_eq!(output.structured_data.as_ref().unwrap()["score"], 4);
+    }
+
+    #[tokio::test]
+    async fn test_crew_task_rejects_invalid_structured_output() {
+        let agent = Agent::builder()
+            .id("rater")
+            .role("Rater")
+            .goal("Rate things")
+            .backstory("Rater")
+            .llm_client(Arc::new(JsonClient(r#"{"score": 9}"#)))
+            .build();
+
+        let task = Task::builder()
+            .id("rate")
+            .description("Rate the product")
+            .expected_output("A score")
+            .agent("rater")
+            .output_schema(rating_schema())
+            .build();
+
+        let mut crew = Crew::builder().agent(agent).task(task).build();
+        let result = crew.kickoff().await.unwrap();
+
+        assert!(!result.task_outputs.contains_key("rate"));
+        assert_eq!(crew.tasks()[0].status(), TaskStatus::Failed);
+        let error = crew.tasks()[0].error().unwrap();
+        assert!(error.contains("$.score: must be at most 5"));
+    }
+
+    #[tokio::test]
+    async fn test_crew_cancellation_stops_running_task() {
+        let agent = Agent::builder()
+            .id("slow")
+            .role("Slow")
+            .goal("Take forever")
+            .backstory("Slow")
+            .llm_client(Arc::new(
+                crate::replay::ScriptedClient::new("gpt-4").with_response("never"),
+            ))
+            .build();
+        let task = Task::builder()
+            .id("wait")
+            .description("Wait")
+            .expected_output("Nothing")
+            .agent("slow")
+            .build();
+
+        let mut crew = Crew::builder().agent(agent).task(task).build();
+        let cancel = crew.cancellation_token();
+        cancel.cancel();
+
+        let result = crew.kickoff().await.unwrap();
+        assert!(!result.success);
+        assert_eq!(result.error.as_deref(), Some("Crew execution cancelled"));
+        assert_eq!(crew.tasks()[0].status(), TaskStatus::Cancelled);
+    }
+
+    #[tokio::test]
+    async fn test_crew_task_structured_output_needs_llm_client() {
+        let agent = Agent::builder()
+            .id("rater")
+            .role("Rater")
+            .goal("Rate things")
+            .backstory("Rater")
+            .build();
+
+        let task = Task::builder()
+            .id("rate")
+            .description("Rate the product")
+            .expected_output("A score")
+            .agent("rater")
+            .output_schema(rating_schema())
+            .build();
+
+        let mut crew = Crew::builder().agent(agent).task(task).build();
+        crew.kickoff().await.unwrap();
+
+        assert_eq!(crew.tasks()[0].status(), TaskStatus::Failed);
+        assert!(crew.tasks()[0].error().unwrap().contains("no LLM client"));
+    }
+
+    #[tokio::test]
+    async fn test_crew_aggregates_usage_per_agent() {
+        let priced = |id: &str| {
+            Agent::builder()
+                .id(id)
+                .role("Writer")
+                .goal("Write")
+                .backstory("Writer")
+                .llm_client(Arc::new(JsonClient(r#"{"text": "hello"}"#)))
+                .pricing(common::PricingConfig {
+                    currency: "USD".to_string(),
+                    models: HashMap::from([(
+                        "json".to_string(),
+                        common::ModelPricing::new(1_000_000.0, 1_000_000.0),
+                    )]),
+                })
+                .build()
+        };
+
+        let task = |id: &str, agent: &str| {
+            Task::builder()
+                .id(id)
+                .description("Write something")
+                .expected_output("Text")
+                .agent(agent)
+                .build()
+        };
+
+        let mut crew = Crew::builder()
+            .agent(priced("writer"))
+            .agent(priced("editor"))
+            .task(task("draft", "writer"))
+            .task(task("edit", "editor"))
+            .task(task("polish", "editor"))
+            .build();
+
+        let stats = crew.kickoff().await.unwrap().stats;
+
+        assert_eq!(stats.total_llm_calls, 3);
+        assert_eq!(stats.usage.llm_calls, 3);
+        assert_eq!(stats.total_tokens, Some(stats.usage.total_tokens as usize));
+        assert_eq!(stats.usage_by_agent["writer"].llm_calls, 1);
+        assert_eq!(stats.usage_by_agent["editor"].llm_calls, 2);
+        assert_eq!(stats.usage.cost.as_units(), stats.usage.total_tokens as f64);
+    }
+
+    #[tokio::test]
+    async fn test_crew_scripted_pipeline_passes_context() {
+        use crate::replay::ScriptedClient;
+
+        let client = Arc::new(ScriptedClient::new("gpt-4").with_responses([
+            "Rust has ownership and borrowing.",
+            "Rust keeps memory safe without a GC.",
+        ]));
+        let agent = Agent::builder()
+            .id("writer")
+            .role("Writer")
+            .goal("Write")
+            .backstory("Writer")
+            .llm_client(client.clone())
+            .build();
+
+        let research = Task::builder()
+            .id("research")
+            .description("Research Rust")
+            .expected_output("Notes")
+            .agent("writer")
+            .build();
+        let summary = Task::builder()
+            .id("summary")
+            .description("Summarize the notes")
+            .expected_output("One sentence")
+            .agent("writer")
+            .depends_on("research")
+            .build();
+
+        let mut crew = Crew::builder()
+            .agent(agent)
+            .task(research)
+            .task(summary)
+            .build();
+        let result = crew.kickoff().await.unwrap();
+
+        assert_eq!(
+            result.task_outputs["summary"].result,
+            "Rust keeps memory safe without a GC."
+        );
+        assert_eq!(client.remaining(), 0);
+
+        let requests = client.requests();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[1]
+            .prompt()
+            .contains("Rust has ownership and borrowing."));
+    }
+
     // ==================== Complex Scenarios ====================
 
     #[tokio::test]
@@ -3,6 +3,11 @@
 //! Tools provide agents with capabilities to interact with the external world.
 //! This module implements the BaseTool pattern from CrewAI Python.
 //!
+//! Crew tools and [`crate::tools::Tool`]s are interchangeable:
+//! [`DynamicTool::from_base_tool`] and [`DynamicTool::from_tool`] wrap either
+//! kind for the [`ToolRegistry`], and a [`DynamicTool`] can be passed to
+//! `ReActAgent::with_tool` or a crew agent as a `Tool`.
+//!
 //! # Example
 //!
 //! ```rust,ignore
@@ -47,6 +52,12 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use thiserror::Error;
 
+use crate::arguments::{prepare_arguments, ArgumentOptions, InvalidArguments};
+use crate::tools::policy::{run_bounded, CancellationToken, Interrupted, ToolPolicy};
+use crate::tools::{Tool, ToolDefinition, ToolResult};
+
+use super::sandbox::{Sandbox, SandboxConfig};
+
 /// Errors that can occur during tool operations
 #[derive(Error, Debug)]
 pub enum ToolError {
@@ -71,8 +82,11 @@ pub enum ToolError {
     #[error("Permission denied: {0}")]
     PermissionDenied(String),
 
-    #[error("Timeout after {0} seconds")]
-    Timeout(u64),
+    #[error("Timeout after {0:?}")]
+    Timeout(std::time::Duration),
+
+    #[error("Tool execution cancelled")]
+    Cancelled,
 }
 
 /// Trait for tool input schemas (like Pydantic BaseModel)
@@ -106,14 +120,45 @@ pub trait BaseTool: Send + Sync {
         Self::Input::json_schema()
     }
 
+    /// Timeouts and output limits for this tool
+    fn policy(&self) -> ToolPolicy {
+        ToolPolicy::default()
+    }
+
     /// Execute the tool with typed input
     async fn run(&self, input: Self::Input) -> Result<String, ToolError>;
 
     /// Execute the tool with JSON input (for dynamic invocation)
+    ///
+    /// The input is validated against `args_schema` first, with coercion and
+    /// schema defaults applied.
     async fn run_json(&self, input: serde_json::Value) -> Result<String, ToolError> {
+        self.run_json_with_cancellation(input, &CancellationToken::new())
+            .await
+    }
+
+    /// Execute the tool with JSON input, stopping early when `cancel` is cancelled
+    async fn run_json_with_cancellation(
+        &self,
+        input: serde_json::Value,
+        cancel: &CancellationToken,
+    ) -> Result<String, ToolError> {
+        let input = prepare_arguments(
+            self.name(),
+            &self.args_schema(),
+            input,
+            &ArgumentOptions::default(),
+        )?;
         let typed_input: Self::Input = serde_json::from_value(input)
             .map_err(|e| ToolError::InvalidInput(e.to_string()))?;
-        self.run(typed_input).await
+        let policy = self.policy();
+        let output = bounded(
+            self.run(typed_input),
+            policy.timeout_for(self.name()),
+            cancel,
+        )
+        .await?;
+        Ok(policy.limit_output(output).0)
     }
 
     /// Get tool definition for agent context
@@ -126,6 +171,28 @@ pub trait BaseTool: Send + Sync {
     }
 }
 
+/// Run a tool call within a timeout, mapping interruptions to [`ToolError`]s
+async fn bounded<F>(
+    call: F,
+    timeout: Option<std::time::Duration>,
+    cancel: &CancellationToken,
+) -> Result<String, ToolError>
+where
+    F: std::future::Future<Output = Result<String, ToolError>>,
+{
+    match run_bounded(call, timeout, cancel).await {
+        Ok(result) => result,
+        Err(Interrupted::TimedOut(limit)) => Err(ToolError::Timeout(limit)),
+        Err(Interrupted::Cancelled) => Err(ToolError::Cancelled),
+    }
+}
+
+impl From<InvalidArguments> for ToolError {
+    fn from(e: InvalidArguments) -> Self {
+        ToolError::InvalidInput(e.errors.join("; "))
+    }
+}
+
 /// Tool definition for serialization
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CrewToolDefinition {
@@ -139,6 +206,8 @@ pub struct DynamicTool {
     name: String,
     description: String,
     args_schema: serde_json::Value,
+    arguments: ArgumentOptions,
+    policy: ToolPolicy,
     handler: Box<dyn Fn(serde_json::Value) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<String, ToolError>> + Send>> + Send + Sync>,
 }
 
@@ -157,6 +226,8 @@ impl DynamicTool {
             name: name.into(),
             description: description.into(),
             args_schema: serde_json::json!({"type": "object"}),
+            arguments: ArgumentOptions::default(),
+            policy: ToolPolicy::default(),
             handler: Box::new(move |input| Box::pin(handler(input))),
         }
     }
@@ -167,6 +238,18 @@ impl DynamicTool {
         self
     }
 
+    /// Set how arguments are checked against the args schema
+    pub fn with_argument_options(mut self, options: ArgumentOptions) -> Self {
+        self.arguments = options;
+        self
+    }
+
+    /// Set timeouts and output limits
+    pub fn with_policy(mut self, policy: ToolPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
     /// Get tool name
     pub fn name(&self) -> &str {
         &self.name
@@ -177,9 +260,22 @@ impl DynamicTool {
         &self.description
     }
 
-    /// Execute the tool
+    /// Execute the tool, after checking the input against the args schema
     pub async fn execute(&self, input: serde_json::Value) -> Result<String, ToolError> {
-        (self.handler)(input).await
+        self.execute_with_cancellation(input, &CancellationToken::new())
+            .await
+    }
+
+    /// Execute the tool, stopping early when `cancel` is cancelled
+    pub async fn execute_with_cancellation(
+        &self,
+        input: serde_json::Value,
+        cancel: &CancellationToken,
+    ) -> Result<String, ToolError> {
+        let input = prepare_arguments(&self.name, &self.args_schema, input, &self.arguments)?;
+        let timeout = self.policy.timeout_for(&self.name);
+        let output = bounded((self.handler)(input), timeout, cancel).await?;
+        Ok(self.policy.limit_output(output).0)
     }
 
     /// Get tool definition
@@ -192,6 +288,109 @@ impl DynamicTool {
     }
 }
 
+// ============================================================================
+// ADAPTERS
+// ============================================================================
+//
+// Any tool can run anywhere: `BaseTool`s and `tools::Tool`s convert into a
+// `DynamicTool` for the `ToolRegistry`, and a `DynamicTool` is itself a
+// `tools::Tool`, usable with `ReActAgent::with_tool` and crew agents.
+
+impl DynamicTool {
+    /// Wrap a typed [`BaseTool`], keeping its schema and policy
+    pub fn from_base_tool<T: BaseTool + 'static>(tool: T) -> Self {
+        let tool = Arc::new(tool);
+        let handler_tool = tool.clone();
+
+        Self {
+            name: tool.name().to_string(),
+            description: tool.description().to_string(),
+            args_schema: tool.args_schema(),
+            arguments: ArgumentOptions::default(),
+            policy: tool.policy(),
+            handler: Box::new(move |input| {
+                let tool = handler_tool.clone();
+                Box::pin(async move {
+                    let typed_input: T::Input = serde_json::from_value(input)
+                        .map_err(|e| ToolError::InvalidInput(e.to_string()))?;
+                    tool.run(typed_input).await
+                })
+            }),
+        }
+    }
+
+    /// Wrap a [`crate::tools::Tool`]; unsuccessful results become errors
+    pub fn from_tool(tool: Arc<dyn Tool>) -> Self {
+        let definition = tool.definition();
+
+        Self {
+            name: definition.name,
+            description: definition.description,
+            args_schema: definition.parameters,
+            arguments: ArgumentOptions::default(),
+            policy: ToolPolicy::default(),
+            handler: Box::new(move |input| {
+                let tool = tool.clone();
+                Box::pin(async move {
+                    match tool.execute(input).await {
+                        Ok(result) if result.success => Ok(result.output),
+                        Ok(result) => Err(ToolError::ExecutionFailed(result.output)),
+                        Err(e) => Err(ToolError::ExecutionFailed(e.to_string())),
+                    }
+                })
+            }),
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for DynamicTool {
+    fn definition(&self) -> ToolDefinition {
+        self.definition().into()
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> common::Result<ToolResult> {
+        let output = DynamicTool::execute(self, args).await?;
+        Ok(ToolResult {
+            tool_name: self.name.clone(),
+            output,
+            success: true,
+        })
+    }
+}
+
+impl From<ToolError> for common::Error {
+    fn from(e: ToolError) -> Self {
+        match e {
+            ToolError::InvalidInput(_) | ToolError::MissingArgument(_) => {
+                common::Error::Validation(e.to_string())
+            }
+            ToolError::Timeout(limit) => common::Error::Timeout(limit),
+            other => common::Error::Internal(other.to_string()),
+        }
+    }
+}
+
+impl From<CrewToolDefinition> for ToolDefinition {
+    fn from(definition: CrewToolDefinition) -> Self {
+        Self {
+            name: definition.name,
+            description: definition.description,
+            parameters: definition.args_schema,
+        }
+    }
+}
+
+impl From<ToolDefinition> for CrewToolDefinition {
+    fn from(definition: ToolDefinition) -> Self {
+        Self {
+            name: definition.name,
+            description: definition.description,
+            args_schema: definition.parameters,
+        }
+    }
+}
+
 // ============================================================================
 // BUILT-IN TOOLS (matching data-analyst-agent)
 // ============================================================================
@@ -334,8 +533,28 @@ impl ToolInput for ReplInput {
 }
 
 /// Python REPL tool (like the repl tool in data-analyst-agent)
-/// Note: This is a placeholder - actual implementation would use PyO3 or subprocess
-pub struct ReplTool;
+///
+/// Runs each snippet in a [`Sandbox`]: a fresh working directory with CPU,
+/// memory, process, wall-clock and output limits, no network and no access
+/// to files outside it but system directories. A snippet that times out
+/// still reports the output it produced.
+#[derive(Debug, Clone, Default)]
+pub struct ReplTool {
+    sandbox: Sandbox,
+}
+
+impl ReplTool {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run code with a different interpreter or limits
+    pub fn with_config(config: SandboxConfig) -> Self {
+        Self {
+            sandbox: Sandbox::new(config),
+        }
+    }
+}
 
 #[async_trait]
 impl BaseTool for ReplTool {
@@ -346,21 +565,12 @@ impl BaseTool for ReplTool {
     }
 
     fn description(&self) -> &str {
-        "Execute Python code in an interactive REPL environment. Use this to run calculations, process data, or test code snippets."
+        "Execute Python code in a sandbox. Use this to run calculations, process data, or test code snippets. Print the values you need; files written to the working directory are listed in the result."
     }
 
     async fn run(&self, input: Self::Input) -> Result<String, ToolError> {
-        // Placeholder implementation - would use python subprocess
-        // In production, this would:
-        // 1. Spawn a Python subprocess
-        // 2. Execute the code
-        // 3. Capture stdout/stderr
-        // 4. Return the result
-
-        Ok(format!(
-            "[REPL] Would execute Python code:\n```python\n{}\n```\n\nNote: Python execution not implemented in this demo.",
-            input.code
-        ))
+        let result = self.sandbox.run(&input.code).await?;
+        Ok(result.summary())
     }
 }
 
@@ -452,7 +662,7 @@ impl ToolRegistry {
         registry.register_typed::<FileWriteTool>(FileWriteTool);
 
         // Add code execution
-        registry.register_typed::<ReplTool>(ReplTool);
+        registry.register_typed::<ReplTool>(ReplTool::new());
 
         // Add web search
         registry.register_typed::<WebSearchTool>(WebSearchTool);
@@ -462,30 +672,12 @@ impl ToolRegistry {
 
     /// Register a typed tool
     pub fn register_typed<T: BaseTool + 'static>(&mut self, tool: T) {
-        let name = tool.name().to_string();
-        let description = tool.description().to_string();
-        let schema = tool.args_schema();
-
-        let dynamic = DynamicTool {
-            name: name.clone(),
-            description,
-            args_schema: schema,
-            handler: Box::new(move |input| {
-                let typed_input: T::Input = match serde_json::from_value(input) {
-                    Ok(v) => v,
-                    Err(e) => return Box::pin(async move {
-                        Err(ToolError::InvalidInput(e.to_string()))
-                    }),
-                };
-                // Note: This is a simplified implementation
-                // In production, we'd need proper async handling
-                Box::pin(async move {
-                    Ok(format!("Tool '{}' executed", std::any::type_name::<T>()))
-                })
-            }),
-        };
+        self.register(DynamicTool::from_base_tool(tool));
+    }
 
-        self.tools.insert(name, Arc::new(dynamic));
+    /// Register a tool implementing [`crate::tools::Tool`], e.g. a sales tool
+    pub fn register_tool<T: Tool + 'static>(&mut self, tool: T) {
+        self.register(DynamicTool::from_tool(Arc::new(tool)));
     }
 
     /// Register a dynamic tool
@@ -565,6 +757,28 @@ mod tests {
         assert!(names.contains(&"file_read".to_string()));
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_repl_tool_runs_code() {
+        let tool = ReplTool::with_config(SandboxConfig::new("sh", "main.sh").with_timeout_ms(300));
+
+        let output = tool
+            .run(ReplInput {
+                code: "echo $((6 * 7))".to_string(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(output, "42");
+
+        let output = tool
+            .run(ReplInput {
+                code: "echo started; sleep 5".to_string(),
+            })
+            .await
+            .unwrap();
+        assert!(output.starts_with("started\n\ntimed out after "));
+    }
+
     #[test]
     fn test_dynamic_tool() {
         let tool = simple_tool(
@@ -591,6 +805,121 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_dynamic_tool_validates_input() {
+        let tool = simple_tool("echo", "Echo the count", |input: serde_json::Value| {
+            Ok(input["count"].to_string())
+        })
+        .with_schema(serde_json::json!({
+            "type": "object",
+            "properties": { "count": { "type": "integer", "default": 1 } }
+        }));
+
+        assert_eq!(tool.execute(serde_json::json!({"count": "3"})).await.unwrap(), "3");
+        assert_eq!(tool.execute(serde_json::json!({})).await.unwrap(), "1");
+
+        let err = tool
+            .execute(serde_json::json!({"count": "many"}))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid input: $.count: expected integer, got string"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_json_reports_missing_argument() {
+        let err = FileReadTool
+            .run_json(serde_json::json!({}))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, ToolError::InvalidInput(msg) if msg.contains("'file_path'")));
+    }
+
+    #[tokio::test]
+    async fn test_dynamic_tool_policy() {
+        let slow = DynamicTool::new("slow", "Never finishes", |_| async {
+            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+            Ok("done".to_string())
+        })
+        .with_policy(ToolPolicy::new().with_timeout_ms(10));
+        let err = slow.execute(serde_json::json!({})).await.unwrap_err();
+        assert!(
+            matches!(err, ToolError::Timeout(limit) if limit == std::time::Duration::from_millis(10))
+        );
+        assert_eq!(err.to_string(), "Timeout after 10ms");
+
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        let err = slow
+            .execute_with_cancellation(serde_json::json!({}), &cancel)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ToolError::Cancelled));
+
+        let chatty = simple_tool("chatty", "Talks a lot", |_| Ok("a".repeat(100)))
+            .with_policy(ToolPolicy::new().with_max_output_chars(10));
+        let output = chatty.execute(serde_json::json!({})).await.unwrap();
+        assert_eq!(output, format!("{}\n[... truncated 90 characters]", "a".repeat(10)));
+    }
+
+    #[tokio::test]
+    async fn test_registry_runs_typed_tools() {
+        let path = std::env::temp_dir().join(format!("registry-{}.txt", uuid::Uuid::new_v4()));
+        tokio::fs::write(&path, "hello").await.unwrap();
+
+        let registry = ToolRegistry::with_defaults();
+        let output = registry
+            .get("file_read")
+            .unwrap()
+            .execute(serde_json::json!({"file_path": path.to_string_lossy()}))
+            .await
+            .unwrap();
+        assert_eq!(output, "hello");
+
+        tokio::fs::remove_file(&path).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_registry_accepts_agent_tools() {
+        let mut registry = ToolRegistry::new();
+        registry.register_tool(crate::tools::SearchTool::new());
+
+        let search = registry.get("search").unwrap();
+        assert!(search.definition().args_schema["properties"]["query"].is_object());
+        assert_eq!(
+            search
+                .execute(serde_json::json!({"query": "rust"}))
+                .await
+                .unwrap(),
+            "Searched for 'rust' with limit 5"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_crew_tools_run_in_react_agent() {
+        use crate::react_agent::{ReActAgent, ReActConfig};
+        use crate::replay::ScriptedClient;
+
+        let client = Arc::new(ScriptedClient::new("gpt-4").with_responses([
+            "Action: web_search\nAction Input: {\"query\": \"rust\"}",
+            "Final Answer: done",
+        ]));
+        let mut agent = ReActAgent::new(ReActConfig::default())
+            .with_tool(DynamicTool::from_base_tool(WebSearchTool))
+            .with_llm_client(client.clone());
+
+        let response = agent.run("Search the web").await.unwrap();
+        let action = response.trace.unwrap()[0].action.clone().unwrap();
+        assert!(action.success);
+        assert!(action
+            .tool_output
+            .unwrap()
+            .starts_with("[Web Search] Query: 'rust' (max 5 results)"));
+    }
+
     #[test]
     fn test_input_schema() {
         let schema = FileReadInput::json_schema();
@@ -0,0 +1,182 @@
+//! Live step events from ReAct executions
+//!
+//! [`ReActAgent`](crate::ReActAgent) emits a [`ReActEvent`] as each phase
+//! of the loop happens (thinking, acting, observing, reflecting, final
+//! answer, errors), so chat UIs can show progress like "searching
+//! products..." and workers can publish job progress while the run is still
+//! going.
+//!
+//! Events go to every attached [`ReActEventListener`]; [`ReActAgent::subscribe`]
+//! returns a channel receiving them instead.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use agent::events::ReActEventKind;
+//!
+//! let mut agent = ReActAgent::new(config).with_tools(tools);
+//! let mut events = agent.subscribe();
+//!
+//! tokio::spawn(async move {
+//!     while let Some(event) = events.recv().await {
+//!         if let ReActEventKind::Acting { tool_name, .. } = &event.kind {
+//!             println!("using {}...", tool_name);
+//!         }
+//!     }
+//! });
+//!
+//! let response = agent.run("Find me a water pump").await?;
+//! ```
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+// ============================================================================
+// EVENTS
+// ============================================================================
+
+/// Something that happened during a ReAct execution
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReActEvent {
+    /// Execution the event belongs to
+    pub execution_id: Uuid,
+
+    /// Iteration of the loop (0 before the first one)
+    pub iteration: usize,
+
+    /// When the event happened
+    pub timestamp: DateTime<Utc>,
+
+    /// What happened
+    #[serde(flatten)]
+    pub kind: ReActEventKind,
+}
+
+/// Kind of [`ReActEvent`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ReActEventKind {
+    /// Execution started or resumed
+    Started { query: String },
+
+    /// Waiting for the LLM to decide the next step
+    Thinking,
+
+    /// The LLM produced a thought without acting
+    Thought { content: String, duration_ms: u64 },
+
+    /// A tool call started
+    Acting {
+        tool_name: String,
+        tool_input: serde_json::Value,
+    },
+
+    /// A tool call finished
+    Observation {
+        tool_name: String,
+        output: String,
+        success: bool,
+        duration_ms: u64,
+    },
+
+    /// Execution paused before tools that need approval
+    Paused { tool_names: Vec<String> },
+
+    /// The critic reviewed a draft answer
+    Reflection { approved: bool, feedback: String },
+
+    /// Execution finished with an answer
+    FinalAnswer {
+        answer: String,
+        total_duration_ms: u64,
+    },
+
+    /// Execution failed
+    Error { message: String },
+}
+
+impl ReActEventKind {
+    /// Name of the kind, as serialized in the `type` field
+    pub fn name(&self) -> &'static str {
+        match self {
+            ReActEventKind::Started { .. } => "started",
+            ReActEventKind::Thinking => "thinking",
+            ReActEventKind::Thought { .. } => "thought",
+            ReActEventKind::Acting { .. } => "acting",
+            ReActEventKind::Observation { .. } => "observation",
+            ReActEventKind::Paused { .. } => "paused",
+            ReActEventKind::Reflection { .. } => "reflection",
+            ReActEventKind::FinalAnswer { .. } => "final_answer",
+            ReActEventKind::Error { .. } => "error",
+        }
+    }
+
+    /// How long the step took, for kinds that report it
+    pub fn duration_ms(&self) -> Option<u64> {
+        match self {
+            ReActEventKind::Thought { duration_ms, .. }
+            | ReActEventKind::Observation { duration_ms, .. } => Some(*duration_ms),
+            ReActEventKind::FinalAnswer {
+                total_duration_ms, ..
+            } => Some(*total_duration_ms),
+            _ => None,
+        }
+    }
+}
+
+impl ReActEvent {
+    pub fn new(execution_id: Uuid, iteration: usize, kind: ReActEventKind) -> Self {
+        Self {
+            execution_id,
+            iteration,
+            timestamp: Utc::now(),
+            kind,
+        }
+    }
+
+    /// Short human-readable progress message, e.g. "Using product_search..."
+    pub fn progress_message(&self) -> String {
+        match &self.kind {
+            ReActEventKind::Started { .. } => "Starting...".to_string(),
+            ReActEventKind::Thinking => "Thinking...".to_string(),
+            ReActEventKind::Thought { .. } => "Reasoning...".to_string(),
+            ReActEventKind::Acting { tool_name, .. } => format!("Using {}...", tool_name),
+            ReActEventKind::Observation { tool_name, .. } => {
+                format!("Got results from {}", tool_name)
+            }
+            ReActEventKind::Paused { tool_names } => {
+                format!("Waiting for approval of {}", tool_names.join(", "))
+            }
+            ReActEventKind::Reflection { approved: true, .. } => "Answer checked".to_string(),
+            ReActEventKind::Reflection {
+                approved: false, ..
+            } => "Revising the answer...".to_string(),
+            ReActEventKind::FinalAnswer { .. } => "Done".to_string(),
+            ReActEventKind::Error { message } => format!("Failed: {}", message),
+        }
+    }
+}
+
+// ============================================================================
+// LISTENERS
+// ============================================================================
+
+/// Observer of ReAct execution events
+#[async_trait]
+pub trait ReActEventListener: Send + Sync {
+    /// Called for every event, in order
+    async fn on_event(&self, event: &ReActEvent);
+}
+
+/// Forwards events into an unbounded channel
+///
+/// Events are dropped silently once the receiver is gone.
+#[async_trait]
+impl ReActEventListener for mpsc::UnboundedSender<ReActEvent> {
+    async fn on_event(&self, event: &ReActEvent) {
+        let _ = self.send(event.clone());
+    }
+}
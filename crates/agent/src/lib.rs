@@ -150,13 +150,23 @@
 // FLOW 1: SINGLE AGENT WITH RAG (rig-core based)
 // ============================================================================
 
+pub mod arguments;
 pub mod builder;
+pub mod checkpoint;
+pub mod context;
+pub mod events;
 pub mod prompts;
 pub mod rag_agent;
 pub mod react_agent;
+pub mod reflection;
+pub mod replay;
+pub mod resilience;
 pub mod rig_integration;
+pub mod routing;
 pub mod sales_agent;
+pub mod structured;
 pub mod tools;
+pub mod usage;
 
 // Single-agent exports
 pub use builder::AgentBuilder;
@@ -165,8 +175,20 @@ pub use sales_agent::SalesAgentBuilder;
 
 // ReAct agent exports (Flow 1 with reasoning loop)
 pub use react_agent::{
-    ActionRecord, ReActAgent, ReActConfig, ReActConfigBuilder, ReActError, ReActResponse,
-    ReActState, ReActStep, ThoughtAction,
+    ActionRecord, ReActAgent, ReActConfig, ReActConfigBuilder, ReActError, ReActOutcome,
+    ReActResponse, ReActState, ReActStep, RetrievedSource, ThoughtAction, ToolInvocation,
+};
+
+// Self-critique of ReAct answers
+pub use reflection::{Critique, ReflectionConfig};
+
+// Live ReAct step events
+pub use events::{ReActEvent, ReActEventKind, ReActEventListener};
+
+// Pausable/resumable ReAct runs
+pub use checkpoint::{
+    ApprovalDecision, CheckpointStore, InMemoryCheckpointStore, PendingAction, ReActCheckpoint,
+    Review,
 };
 
 // LLM integration exports
@@ -175,6 +197,27 @@ pub use rig_integration::{
     Provider, RigLlmClient, TokenUsage, ToolCall, ToolCallResponse, ToolCallingClient,
 };
 
+// Token counting and context-window budgeting
+pub use context::{ContextBudgeter, ModelFamily, TokenCounter};
+
+// Resilience layer (retries, backoff, circuit breaker)
+pub use resilience::{CircuitBreaker, CircuitState, ResilienceConfig, ResilientClient};
+
+// Provider fallback and model routing
+pub use routing::{ErrorClass, RoutingClient, RoutingConfig, RoutingRule};
+
+// Schema-constrained structured outputs
+pub use structured::{Structured, StructuredCompletion, StructuredError};
+
+// Tool argument validation and coercion
+pub use arguments::{ArgumentOptions, InvalidArguments};
+
+// Token and cost accounting
+pub use usage::{ModelUsage, UsageReport};
+
+// Deterministic LLM clients for tests (record/replay, scripted)
+pub use replay::{Cassette, CassetteError, ReplayClient, ReplayMode, ScriptedClient};
+
 // ============================================================================
 // FLOW 2: MULTI-AGENT ORCHESTRATION (CrewAI-style)
 // ============================================================================
@@ -1,43 +1,148 @@
 //! RAG-enabled agent implementation.
 
-use common::models::{AgentConfig, SearchResult};
-use common::Result;
+use common::models::{AgentConfig, ChatMessage, MessageRole, SearchResult};
+use common::{global_pricing, Error, PricingConfig, Result};
 use rag_core::embeddings::EmbeddingModel;
 use rag_core::vector_store::VectorStore;
 use rag_core::Retriever;
+use std::sync::Arc;
+
+use crate::context::ContextBudgeter;
+use crate::rig_integration::CompletionClient;
+use crate::usage::UsageReport;
+
+/// Tokens reserved for the reply when no explicit budget is configured
+const DEFAULT_REPLY_TOKENS: usize = 1024;
 
 /// A RAG-enabled agent that retrieves context before generating responses
 pub struct RagAgent<E: EmbeddingModel, V: VectorStore> {
     config: AgentConfig,
     retriever: Retriever<E, V>,
+    budgeter: ContextBudgeter,
+    llm_client: Option<Arc<dyn CompletionClient>>,
+    pricing: Arc<PricingConfig>,
 }
 
 impl<E: EmbeddingModel, V: VectorStore> RagAgent<E, V> {
     /// Create a new RAG agent
     pub fn new(config: AgentConfig, retriever: Retriever<E, V>) -> Self {
-        Self { config, retriever }
+        let budgeter = ContextBudgeter::for_model(&config.model, DEFAULT_REPLY_TOKENS);
+        Self {
+            config,
+            retriever,
+            budgeter,
+            llm_client: None,
+            pricing: global_pricing().clone(),
+        }
+    }
+
+    /// Use the given LLM client for generation
+    pub fn with_llm_client(mut self, client: Arc<dyn CompletionClient>) -> Self {
+        self.llm_client = Some(client);
+        self
+    }
+
+    /// Use a custom pricing table for cost accounting
+    pub fn with_pricing(mut self, pricing: PricingConfig) -> Self {
+        self.pricing = Arc::new(pricing);
+        self
+    }
+
+    /// Use a custom context budgeter (window size and reply reservation)
+    pub fn with_budgeter(mut self, budgeter: ContextBudgeter) -> Self {
+        self.budgeter = budgeter;
+        self
     }
 
     /// Process a chat message with RAG context
     pub async fn chat(&self, message: &str) -> Result<ChatResponse> {
+        self.chat_with_history(message, &[]).await
+    }
+
+    /// Process a chat message with RAG context and prior conversation turns
+    ///
+    /// Retrieved context and history are trimmed to fit the model's context
+    /// window; only the sources that made it into the prompt are returned.
+    pub async fn chat_with_history(
+        &self,
+        message: &str,
+        history: &[ChatMessage],
+    ) -> Result<ChatResponse> {
         // 1. Retrieve relevant documents
-        let context = self.retriever.retrieve(message).await?;
+        let mut context = self.retriever.retrieve(message).await?;
 
-        // 2. Build prompt with context
-        let augmented_prompt = self.build_augmented_prompt(message, &context);
+        // 2. Build prompt with context that fits the window
+        let (augmented_prompt, used_sources) =
+            self.build_augmented_prompt(message, &context, history);
+        context.truncate(used_sources);
 
-        // 3. Generate response (placeholder - integrate with rig here)
-        // In a full implementation, this would use rig's completion API
-        let response = self.generate_response(&augmented_prompt).await?;
+        // 3. Generate response
+        let mut usage = UsageReport::default();
+        let response = self
+            .generate_response(&augmented_prompt, &mut usage)
+            .await?;
 
         Ok(ChatResponse {
             message: response,
             sources: context,
+            usage,
         })
     }
 
-    /// Build an augmented prompt with retrieved context
-    fn build_augmented_prompt(&self, query: &str, context: &[SearchResult]) -> String {
+    /// Build an augmented prompt with retrieved context and history
+    ///
+    /// Returns the prompt and the number of context chunks included.
+    fn build_augmented_prompt(
+        &self,
+        query: &str,
+        context: &[SearchResult],
+        history: &[ChatMessage],
+    ) -> (String, usize) {
+        const CONTEXT_HEADER: &str = "Use the following context to help answer the question:\n\n";
+        const HISTORY_HEADER: &str = "Conversation so far:\n";
+
+        let question = format!("Question: {}\n\nAnswer:", query);
+        let fixed = format!(
+            "{}\n\n{}\n{}\n{}",
+            self.config.preamble, CONTEXT_HEADER, HISTORY_HEADER, question
+        );
+        let available = self.budgeter.available_for(&fixed);
+
+        // Retrieved context gets up to half of the budget (best chunks first)
+        let chunks: Vec<String> = context
+            .iter()
+            .enumerate()
+            .map(|(i, result)| {
+                format!(
+                    "[{}] (score: {:.2}): {}\n\n",
+                    i + 1,
+                    result.score,
+                    result.chunk.content
+                )
+            })
+            .collect();
+        let chunks = self.budgeter.fit_ranked(&chunks, available / 2);
+        let context_tokens: usize = chunks
+            .iter()
+            .map(|c| self.budgeter.counter().count(c))
+            .sum();
+
+        // History gets the rest, dropping the oldest turns first
+        let turns: Vec<String> = history
+            .iter()
+            .map(|m| {
+                let role = match m.role {
+                    MessageRole::User => "User",
+                    MessageRole::Assistant => "Assistant",
+                    MessageRole::System => "System",
+                };
+                format!("{}: {}\n", role, m.content)
+            })
+            .collect();
+        let turns = self
+            .budgeter
+            .fit_oldest_first(&turns, available.saturating_sub(context_tokens));
+
         let mut prompt = String::new();
 
         // Add system preamble
@@ -45,28 +150,45 @@ impl<E: EmbeddingModel, V: VectorStore> RagAgent<E, V> {
         prompt.push_str("\n\n");
 
         // Add retrieved context
-        if !context.is_empty() {
-            prompt.push_str("Use the following context to help answer the question:\n\n");
-            for (i, result) in context.iter().enumerate() {
-                prompt.push_str(&format!(
-                    "[{}] (score: {:.2}): {}\n\n",
-                    i + 1,
-                    result.score,
-                    result.chunk.content
-                ));
+        if !chunks.is_empty() {
+            prompt.push_str(CONTEXT_HEADER);
+            for chunk in &chunks {
+                prompt.push_str(chunk);
+            }
+            prompt.push('\n');
+        }
+
+        // Add conversation history
+        if !turns.is_empty() {
+            prompt.push_str(HISTORY_HEADER);
+            for turn in &turns {
+                prompt.push_str(turn);
+                if !turn.ends_with('\n') {
+                    prompt.push('\n');
+                }
             }
             prompt.push('\n');
         }
 
         // Add the user query
-        prompt.push_str(&format!("Question: {}\n\nAnswer:", query));
+        prompt.push_str(&question);
 
-        prompt
+        (prompt, chunks.len())
     }
 
-    /// Generate a response using the LLM
-    /// This is a placeholder - in production, use rig's completion API
-    async fn generate_response(&self, _prompt: &str) -> Result<String> {
+    /// Generate a response using the LLM, recording its usage
+    ///
+    /// Falls back to a placeholder when no LLM client is configured.
+    async fn generate_response(&self, prompt: &str, usage: &mut UsageReport) -> Result<String> {
+        if let Some(client) = &self.llm_client {
+            let response = client
+                .complete(prompt)
+                .await
+                .map_err(|e| Error::Llm(e.to_string()))?;
+            usage.record_response(prompt, &response, &self.pricing);
+            return Ok(response.content);
+        }
+
         // TODO: Integrate with rig's completion API
         // Example with rig:
         // let openai = openai::Client::from_env();
@@ -90,12 +212,15 @@ impl<E: EmbeddingModel, V: VectorStore> RagAgent<E, V> {
 pub struct ChatResponse {
     pub message: String,
     pub sources: Vec<SearchResult>,
+    /// Token usage and cost of the generation
+    pub usage: UsageReport,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use common::models::DocumentChunk;
+    use rag_core::vector_store::InMemoryVectorStore;
     use uuid::Uuid;
 
     fn create_test_chunk(content: &str, index: usize) -> DocumentChunk {
@@ -113,6 +238,7 @@ mod tests {
         let response = ChatResponse {
             message: "Test message".to_string(),
             sources: vec![],
+            usage: UsageReport::default(),
         };
 
         assert_eq!(response.message, "Test message");
@@ -135,6 +261,7 @@ mod tests {
         let response = ChatResponse {
             message: "Answer based on sources".to_string(),
             sources,
+            usage: UsageReport::default(),
         };
 
         assert_eq!(response.sources.len(), 2);
@@ -147,6 +274,7 @@ mod tests {
         let response = ChatResponse {
             message: "Original".to_string(),
             sources: vec![],
+            usage: UsageReport::default(),
         };
 
         let cloned = response.clone();
@@ -158,6 +286,7 @@ mod tests {
         let response = ChatResponse {
             message: "Debug test".to_string(),
             sources: vec![],
+            usage: UsageReport::default(),
         };
 
         let debug_str = format!("{:?}", response);
@@ -196,6 +325,7 @@ mod tests {
         let response = ChatResponse {
             message: "Multiple sources".to_string(),
             sources,
+            usage: UsageReport::default(),
         };
 
         assert_eq!(response.sources.len(), 5);
@@ -203,6 +333,82 @@ mod tests {
         assert_eq!(response.sources[4].score, 0.6);
     }
 
+    struct ZeroEmbedding;
+
+    #[async_trait::async_trait]
+    impl EmbeddingModel for ZeroEmbedding {
+        async fn embed(&self, _text: &str) -> Result<Vec<f32>> {
+            Ok(vec![0.0; 4])
+        }
+
+        async fn embed_batch(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+            Ok(texts.iter().map(|_| vec![0.0; 4]).collect())
+        }
+
+        fn dimension(&self) -> usize {
+            4
+        }
+    }
+
+    fn create_test_agent(context_window: usize) -> RagAgent<ZeroEmbedding, InMemoryVectorStore> {
+        let retriever = Retriever::new(ZeroEmbedding, InMemoryVectorStore::new());
+        let budgeter = ContextBudgeter::for_model("gpt-4", 256).with_context_window(context_window);
+        RagAgent::new(AgentConfig::default(), retriever).with_budgeter(budgeter)
+    }
+
+    #[test]
+    fn test_augmented_prompt_fits_context_window() {
+        let agent = create_test_agent(1024);
+        let context: Vec<SearchResult> = (0..30)
+            .map(|i| SearchResult {
+                chunk: create_test_chunk(&format!("Chunk {} {}", i, "fact ".repeat(80)), i),
+                score: 1.0 - i as f32 * 0.01,
+            })
+            .collect();
+        let history: Vec<ChatMessage> = (0..30)
+            .map(|i| ChatMessage {
+                role: MessageRole::User,
+                content: format!("Turn {} {}", i, "chat ".repeat(40)),
+            })
+            .collect();
+
+        let (prompt, used) = agent.build_augmented_prompt("What is new?", &context, &history);
+
+        assert!(agent.budgeter.counter().count(&prompt) <= 1024 - 256);
+        assert!(used > 0 && used < context.len());
+        assert!(prompt.contains("Chunk 0"));
+        assert!(prompt.contains("Turn 29"));
+        assert!(prompt.ends_with("Question: What is new?\n\nAnswer:"));
+    }
+
+    #[test]
+    fn test_augmented_prompt_keeps_everything_when_small() {
+        let agent = create_test_agent(8192);
+        let context = vec![SearchResult {
+            chunk: create_test_chunk("Rust is a systems language", 0),
+            score: 0.9,
+        }];
+
+        let (prompt, used) = agent.build_augmented_prompt("What is Rust?", &context, &[]);
+        assert_eq!(used, 1);
+        assert!(prompt.contains("Rust is a systems language"));
+        assert!(!prompt.contains("Conversation so far"));
+    }
+
+    #[tokio::test]
+    async fn test_chat_records_usage_and_cost() {
+        use crate::rig_integration::{LlmConfig, RigLlmClient};
+
+        let agent = create_test_agent(8192)
+            .with_llm_client(Arc::new(RigLlmClient::new(LlmConfig::openai("gpt-4"))))
+            .with_pricing(PricingConfig::default());
+
+        let response = agent.chat("What is Rust?").await.unwrap();
+        assert_eq!(response.usage.llm_calls, 1);
+        assert!(response.usage.cost > common::Micros::ZERO);
+        assert!(response.usage.by_model.contains_key("gpt-4"));
+    }
+
     #[test]
     fn test_document_chunk_content() {
         let chunk = create_test_chunk("Test content", 0);
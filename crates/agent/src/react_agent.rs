@@ -49,14 +49,28 @@
 //! ```
 
 use chrono::{DateTime, Utc};
+use common::models::SearchResult;
+use common::{global_pricing, PricingConfig};
+use futures::stream::{self, StreamExt};
+use rag_core::DocumentRetriever;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
 use thiserror::Error;
+use tokio::sync::mpsc;
 use tracing::{debug, info, warn};
 use uuid::Uuid;
 
+use crate::arguments::{prepare_arguments, ArgumentOptions};
+use crate::checkpoint::{ApprovalDecision, CheckpointStore, PendingAction, ReActCheckpoint};
+use crate::context::ContextBudgeter;
+use crate::events::{ReActEvent, ReActEventKind, ReActEventListener};
+use crate::reflection::{critique_prompt, Critique, ReflectionConfig};
+use crate::rig_integration::{CompletionClient, LlmResponse, ToolCallResponse, ToolCallingClient};
+use crate::structured::{StructuredCompletion, DEFAULT_MAX_REPAIRS};
+use crate::tools::policy::{min_timeout, run_bounded, CancellationToken, Interrupted, ToolPolicy};
 use crate::tools::{Tool, ToolDefinition, ToolResult};
+use crate::usage::UsageReport;
 
 // ============================================================================
 // ERRORS
@@ -74,17 +88,35 @@ pub enum ReActError {
     #[error("Tool execution failed: {0}")]
     ToolExecutionFailed(String),
 
+    #[error("{0}")]
+    InvalidToolArguments(String),
+
     #[error("LLM call failed: {0}")]
     LlmError(String),
 
     #[error("Failed to parse LLM response: {0}")]
     ParseError(String),
 
+    #[error("Tool '{0}' timed out after {1:?}")]
+    ToolTimeout(String, std::time::Duration),
+
+    #[error("Execution cancelled")]
+    Cancelled,
+
     #[error("Invalid configuration: {0}")]
     ConfigError(String),
 
     #[error("Context retrieval failed: {0}")]
     RetrievalError(String),
+
+    #[error("Execution paused: tool call(s) {0} require approval")]
+    ApprovalRequired(String),
+
+    #[error("Tool call rejected: {0}")]
+    ToolRejected(String),
+
+    #[error("Checkpoint storage failed: {0}")]
+    CheckpointError(String),
 }
 
 // ============================================================================
@@ -106,6 +138,12 @@ pub enum ReActState {
     /// Agent is processing the observation from a tool
     Observing,
 
+    /// Agent is waiting for approval before executing a tool
+    Paused { tool_name: String },
+
+    /// Agent is having a draft answer reviewed by the critic
+    Reflecting,
+
     /// Agent has reached a final answer
     Finished,
 
@@ -120,6 +158,8 @@ impl std::fmt::Display for ReActState {
             ReActState::Thinking => write!(f, "Thinking"),
             ReActState::Acting { tool_name } => write!(f, "Acting({})", tool_name),
             ReActState::Observing => write!(f, "Observing"),
+            ReActState::Paused { tool_name } => write!(f, "Paused({})", tool_name),
+            ReActState::Reflecting => write!(f, "Reflecting"),
             ReActState::Finished => write!(f, "Finished"),
             ReActState::Error { message } => write!(f, "Error: {}", message),
         }
@@ -143,6 +183,12 @@ pub enum ThoughtAction {
         thought: Option<String>,
     },
 
+    /// Agent wants to use several tools at once (executed concurrently)
+    Actions {
+        calls: Vec<ToolInvocation>,
+        thought: Option<String>,
+    },
+
     /// Agent has reached a final answer
     FinalAnswer {
         answer: String,
@@ -157,6 +203,7 @@ impl ThoughtAction {
     /// - Thought: <thinking>
     /// - Action: <tool_name>
     ///   Action Input: <json>
+    ///   (repeated Action/Action Input pairs request parallel tool calls)
     /// - Final Answer: <answer>
     pub fn parse(text: &str) -> Result<Self, ReActError> {
         let text = text.trim();
@@ -167,12 +214,20 @@ impl ThoughtAction {
             return Ok(ThoughtAction::FinalAnswer { answer, thought });
         }
 
-        // Check for Action
-        if let Some((tool_name, tool_input, thought)) = Self::extract_action(text) {
+        // Check for Action(s)
+        let mut calls = Self::extract_actions(text);
+        if calls.len() == 1 {
+            let call = calls.remove(0);
             return Ok(ThoughtAction::Action {
-                tool_name,
-                tool_input,
-                thought,
+                tool_name: call.tool_name,
+                tool_input: call.tool_input,
+                thought: Self::extract_thought(text),
+            });
+        }
+        if !calls.is_empty() {
+            return Ok(ThoughtAction::Actions {
+                calls,
+                thought: Self::extract_thought(text),
             });
         }
 
@@ -202,6 +257,58 @@ impl ThoughtAction {
         None
     }
 
+    /// Build from a native tool-calling response, falling back to parsing
+    /// its text content when the model didn't call any tool
+    pub fn from_tool_calls(response: &ToolCallResponse) -> Result<Self, ReActError> {
+        let thought = response
+            .content
+            .as_deref()
+            .map(str::trim)
+            .filter(|c| !c.is_empty())
+            .map(str::to_string);
+
+        let mut calls: Vec<ToolInvocation> = response
+            .tool_calls
+            .iter()
+            .map(|call| ToolInvocation {
+                tool_name: call.name.clone(),
+                tool_input: call.arguments.clone(),
+            })
+            .collect();
+
+        match calls.len() {
+            0 => Self::parse(thought.as_deref().unwrap_or_default()),
+            1 => {
+                let call = calls.remove(0);
+                Ok(ThoughtAction::Action {
+                    tool_name: call.tool_name,
+                    tool_input: call.tool_input,
+                    thought,
+                })
+            }
+            _ => Ok(ThoughtAction::Actions { calls, thought }),
+        }
+    }
+
+    /// Extract every "Action:" / "Action Input:" pair, in order
+    fn extract_actions(text: &str) -> Vec<ToolInvocation> {
+        let lower = text.to_lowercase();
+        let starts: Vec<usize> = lower.match_indices("action:").map(|(i, _)| i).collect();
+
+        starts
+            .iter()
+            .enumerate()
+            .filter_map(|(n, &start)| {
+                let end = starts.get(n + 1).copied().unwrap_or(text.len());
+                Self::extract_action(&text[start..end])
+            })
+            .map(|(tool_name, tool_input, _)| ToolInvocation {
+                tool_name,
+                tool_input,
+            })
+            .collect()
+    }
+
     fn extract_action(text: &str) -> Option<(String, serde_json::Value, Option<String>)> {
         let thought = Self::extract_thought(text);
 
@@ -275,6 +382,13 @@ impl ThoughtAction {
     }
 }
 
+/// A single tool call requested by the model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolInvocation {
+    pub tool_name: String,
+    pub tool_input: serde_json::Value,
+}
+
 // ============================================================================
 // CONFIGURATION
 // ============================================================================
@@ -303,8 +417,44 @@ pub struct ReActConfig {
     /// Whether to return the full reasoning trace
     pub return_trace: bool,
 
-    /// Timeout for each iteration in seconds
+    /// Timeout for each iteration in seconds; also caps every tool call
     pub iteration_timeout_secs: Option<u64>,
+
+    /// Tokens reserved for the model's reply when budgeting the prompt
+    #[serde(default = "default_max_tokens")]
+    pub max_tokens: u32,
+
+    /// Override the model's context window size (in tokens)
+    #[serde(default)]
+    pub context_window: Option<usize>,
+
+    /// Maximum number of tool calls of one step executed concurrently
+    #[serde(default = "default_max_parallel_tools")]
+    pub max_parallel_tools: usize,
+
+    /// Tools that need approval: the run pauses before calling them
+    #[serde(default)]
+    pub pause_before_tools: Vec<String>,
+
+    /// Schema validation, coercion and defaults for tool arguments
+    #[serde(default)]
+    pub tool_arguments: ArgumentOptions,
+
+    /// Timeouts and output limits for tool calls
+    #[serde(default)]
+    pub tool_policy: ToolPolicy,
+
+    /// Review final answers against the evidence before returning them
+    #[serde(default)]
+    pub reflection: Option<ReflectionConfig>,
+}
+
+fn default_max_tokens() -> u32 {
+    2048
+}
+
+fn default_max_parallel_tools() -> usize {
+    4
 }
 
 impl Default for ReActConfig {
@@ -318,6 +468,13 @@ impl Default for ReActConfig {
             top_k_documents: 5,
             return_trace: true,
             iteration_timeout_secs: Some(30),
+            max_tokens: default_max_tokens(),
+            context_window: None,
+            max_parallel_tools: default_max_parallel_tools(),
+            pause_before_tools: Vec::new(),
+            tool_arguments: ArgumentOptions::default(),
+            tool_policy: ToolPolicy::default(),
+            reflection: None,
         }
     }
 }
@@ -376,6 +533,41 @@ impl ReActConfigBuilder {
         self
     }
 
+    pub fn max_tokens(mut self, tokens: u32) -> Self {
+        self.config.max_tokens = tokens;
+        self
+    }
+
+    pub fn context_window(mut self, tokens: usize) -> Self {
+        self.config.context_window = Some(tokens);
+        self
+    }
+
+    pub fn max_parallel_tools(mut self, max: usize) -> Self {
+        self.config.max_parallel_tools = max.max(1);
+        self
+    }
+
+    pub fn pause_before_tool(mut self, tool_name: impl Into<String>) -> Self {
+        self.config.pause_before_tools.push(tool_name.into());
+        self
+    }
+
+    pub fn tool_arguments(mut self, options: ArgumentOptions) -> Self {
+        self.config.tool_arguments = options;
+        self
+    }
+
+    pub fn tool_policy(mut self, policy: ToolPolicy) -> Self {
+        self.config.tool_policy = policy;
+        self
+    }
+
+    pub fn reflection(mut self, reflection: ReflectionConfig) -> Self {
+        self.config.reflection = Some(reflection);
+        self
+    }
+
     pub fn build(self) -> ReActConfig {
         self.config
     }
@@ -408,6 +600,10 @@ pub struct ReActStep {
 
     /// Duration of this step in milliseconds
     pub duration_ms: u64,
+
+    /// Provider and model that produced this step (e.g. "openai/gpt-4")
+    #[serde(default)]
+    pub model: Option<String>,
 }
 
 /// Record of an action taken
@@ -417,6 +613,14 @@ pub struct ActionRecord {
     pub tool_input: serde_json::Value,
     pub tool_output: Option<String>,
     pub success: bool,
+
+    /// The call was stopped by its timeout
+    #[serde(default)]
+    pub timed_out: bool,
+
+    /// The output was cut to the policy's limit
+    #[serde(default)]
+    pub truncated: bool,
 }
 
 // ============================================================================
@@ -441,14 +645,56 @@ pub struct ReActResponse {
     /// The reasoning trace (if return_trace is true)
     pub trace: Option<Vec<ReActStep>>,
 
-    /// Sources from RAG (if used)
-    pub sources: Vec<String>,
+    /// Retrieved chunks included in the prompt (empty when the caller
+    /// passed the context in)
+    pub sources: Vec<RetrievedSource>,
 
     /// Final state
     pub state: ReActState,
 
     /// Token usage statistics
     pub token_usage: Option<TokenUsage>,
+
+    /// Token usage and cost per model
+    #[serde(default)]
+    pub usage: UsageReport,
+}
+
+/// A retrieved document chunk the agent was given as context
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RetrievedSource {
+    /// Document the chunk belongs to
+    pub document_id: Uuid,
+
+    /// The chunk
+    pub chunk_id: Uuid,
+
+    /// Similarity score from the vector search
+    pub score: f32,
+
+    /// Text of the chunk
+    pub content: String,
+}
+
+impl From<SearchResult> for RetrievedSource {
+    fn from(result: SearchResult) -> Self {
+        Self {
+            document_id: result.chunk.document_id,
+            chunk_id: result.chunk.id,
+            score: result.score,
+            content: result.chunk.content,
+        }
+    }
+}
+
+/// Result of a run that may pause before flagged tools
+#[derive(Debug, Clone)]
+pub enum ReActOutcome {
+    /// The run reached a final answer
+    Completed(ReActResponse),
+
+    /// The run is waiting for approval of the checkpoint's pending calls
+    Paused(ReActCheckpoint),
 }
 
 /// Token usage statistics
@@ -459,6 +705,16 @@ pub struct TokenUsage {
     pub total_tokens: u32,
 }
 
+impl From<&UsageReport> for TokenUsage {
+    fn from(usage: &UsageReport) -> Self {
+        Self {
+            prompt_tokens: usage.prompt_tokens as u32,
+            completion_tokens: usage.completion_tokens as u32,
+            total_tokens: usage.total_tokens as u32,
+        }
+    }
+}
+
 // ============================================================================
 // REACT AGENT
 // ============================================================================
@@ -471,18 +727,107 @@ pub struct ReActAgent {
     config: ReActConfig,
     tools: HashMap<String, Arc<dyn Tool>>,
     state: ReActState,
+    llm_client: Option<Arc<dyn CompletionClient>>,
+    tool_calling_client: Option<Arc<dyn ToolCallingClient>>,
+    critic_client: Option<Arc<dyn CompletionClient>>,
+    retriever: Option<Arc<dyn DocumentRetriever>>,
+    budgeter: ContextBudgeter,
+    pricing: Arc<PricingConfig>,
+    checkpoint_store: Option<Arc<dyn CheckpointStore>>,
+    listeners: Vec<Arc<dyn ReActEventListener>>,
+    cancellation: CancellationToken,
 }
 
 impl ReActAgent {
     /// Create a new ReAct agent with the given configuration
     pub fn new(config: ReActConfig) -> Self {
+        let mut budgeter = ContextBudgeter::for_model(&config.model, config.max_tokens as usize);
+        if let Some(window) = config.context_window {
+            budgeter = budgeter.with_context_window(window);
+        }
+
         Self {
             config,
             tools: HashMap::new(),
             state: ReActState::Ready,
+            llm_client: None,
+            tool_calling_client: None,
+            critic_client: None,
+            retriever: None,
+            budgeter,
+            pricing: global_pricing().clone(),
+            checkpoint_store: None,
+            listeners: Vec::new(),
+            cancellation: CancellationToken::new(),
         }
     }
 
+    /// Use the given LLM client (e.g. a resilient or routing client)
+    pub fn with_llm_client(mut self, client: Arc<dyn CompletionClient>) -> Self {
+        self.llm_client = Some(client);
+        self
+    }
+
+    /// Use a client with native tool calling; its tool calls take precedence
+    /// over the text Action format
+    pub fn with_tool_calling_client(mut self, client: Arc<dyn ToolCallingClient>) -> Self {
+        self.tool_calling_client = Some(client);
+        self
+    }
+
+    /// Use a separate model to review draft answers (defaults to the LLM
+    /// client); only used when `ReActConfig::reflection` is set
+    pub fn with_critic_client(mut self, client: Arc<dyn CompletionClient>) -> Self {
+        self.critic_client = Some(client);
+        self
+    }
+
+    /// Retrieve `top_k_documents` chunks as context before the first step
+    /// when `use_rag` is on and the caller passed no context
+    pub fn with_retriever(mut self, retriever: Arc<dyn DocumentRetriever>) -> Self {
+        self.retriever = Some(retriever);
+        self
+    }
+
+    /// Use a custom pricing table for cost accounting
+    pub fn with_pricing(mut self, pricing: PricingConfig) -> Self {
+        self.pricing = Arc::new(pricing);
+        self
+    }
+
+    /// Attach a store that receives a checkpoint after every iteration
+    pub fn with_checkpoint_store(mut self, store: Arc<dyn CheckpointStore>) -> Self {
+        self.checkpoint_store = Some(store);
+        self
+    }
+
+    /// Add a listener receiving live step events
+    pub fn with_listener(mut self, listener: Arc<dyn ReActEventListener>) -> Self {
+        self.listeners.push(listener);
+        self
+    }
+
+    /// Receive live step events through a channel
+    pub fn subscribe(&mut self) -> mpsc::UnboundedReceiver<ReActEvent> {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        self.listeners.push(Arc::new(sender));
+        receiver
+    }
+
+    /// Use a cancellation token shared with the caller
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation = token;
+        self
+    }
+
+    /// Token aborting the agent's runs when cancelled
+    ///
+    /// Running tool calls are dropped and the run fails with
+    /// [`ReActError::Cancelled`]. Once cancelled, the token stays cancelled.
+    pub fn cancellation_token(&self) -> CancellationToken {
+        self.cancellation.clone()
+    }
+
     /// Add a tool to the agent
     pub fn with_tool<T: Tool + 'static>(mut self, tool: T) -> Self {
         let name = tool.definition().name.clone();
@@ -520,37 +865,160 @@ impl ReActAgent {
     }
 
     /// Execute the ReAct loop with optional RAG context
+    ///
+    /// Without `context`, the agent's retriever (if any) supplies it.
+    ///
+    /// Fails with [`ReActError::ApprovalRequired`] if the run pauses before a
+    /// flagged tool; use [`start`](Self::start) to get the checkpoint instead.
     pub async fn run_with_context(
         &mut self,
         query: &str,
         context: Option<Vec<String>>,
     ) -> Result<ReActResponse, ReActError> {
-        let start_time = std::time::Instant::now();
-        let execution_id = Uuid::new_v4();
+        match self.start(query, context).await? {
+            ReActOutcome::Completed(response) => Ok(response),
+            ReActOutcome::Paused(checkpoint) => Err(ReActError::ApprovalRequired(
+                checkpoint.pending_tool_names().join(", "),
+            )),
+        }
+    }
+
+    /// Start a run that may pause before flagged tools
+    pub async fn start(
+        &mut self,
+        query: &str,
+        context: Option<Vec<String>>,
+    ) -> Result<ReActOutcome, ReActError> {
+        self.run_checkpoint(ReActCheckpoint::new(query).with_context(context))
+            .await
+    }
 
+    /// Run (or continue) the loop from a checkpoint without pending calls
+    pub async fn run_checkpoint(
+        &mut self,
+        mut checkpoint: ReActCheckpoint,
+    ) -> Result<ReActOutcome, ReActError> {
         info!(
-            execution_id = %execution_id,
-            query = %query,
+            execution_id = %checkpoint.execution_id,
+            query = %checkpoint.query,
+            iteration = checkpoint.iteration,
             "Starting ReAct execution"
         );
+        self.emit_started(&checkpoint).await;
+
+        if checkpoint.iteration == 0 && checkpoint.context.is_none() {
+            self.retrieve(&mut checkpoint).await?;
+        }
+        self.drive(checkpoint, std::time::Instant::now()).await
+    }
+
+    /// Resume a run from a checkpoint
+    ///
+    /// If the run paused before flagged tools, `decision` approves or rejects
+    /// the pending calls; otherwise it is ignored.
+    pub async fn resume(
+        &mut self,
+        mut checkpoint: ReActCheckpoint,
+        decision: ApprovalDecision,
+    ) -> Result<ReActOutcome, ReActError> {
+        let started = std::time::Instant::now();
+        info!(
+            execution_id = %checkpoint.execution_id,
+            iteration = checkpoint.iteration,
+            decision = ?decision,
+            "Resuming ReAct execution"
+        );
+        self.emit_started(&checkpoint).await;
+
+        if let Some(pending) = checkpoint.pending.take() {
+            match decision {
+                ApprovalDecision::Approve => self.act(&mut checkpoint, pending, started).await,
+                ApprovalDecision::Reject { reason } => {
+                    let reason = reason.unwrap_or_else(|| "rejected by reviewer".to_string());
+                    let mut results = Vec::with_capacity(pending.calls.len());
+                    for call in &pending.calls {
+                        let result =
+                            ToolOutcome::from(Err(ReActError::ToolRejected(reason.clone())));
+                        let (output, success) = result.observation();
+                        self.emit(
+                            checkpoint.execution_id,
+                            checkpoint.iteration,
+                            ReActEventKind::Observation {
+                                tool_name: call.tool_name.clone(),
+                                output,
+                                success,
+                                duration_ms: 0,
+                            },
+                        )
+                        .await;
+                        results.push(result);
+                    }
+                    self.observe(&mut checkpoint, pending, results, started);
+                }
+            }
+        }
+
+        self.drive(checkpoint, started).await
+    }
+
+    /// Run the loop, reporting failures as error events
+    async fn drive(
+        &mut self,
+        mut checkpoint: ReActCheckpoint,
+        started: std::time::Instant,
+    ) -> Result<ReActOutcome, ReActError> {
+        match self.drive_steps(&mut checkpoint, started).await {
+            Ok(Some(response)) => Ok(ReActOutcome::Completed(response)),
+            Ok(None) => Ok(ReActOutcome::Paused(checkpoint)),
+            Err(e) => {
+                self.emit(
+                    checkpoint.execution_id,
+                    checkpoint.iteration,
+                    ReActEventKind::Error {
+                        message: e.to_string(),
+                    },
+                )
+                .await;
+                Err(e)
+            }
+        }
+    }
+
+    /// The reasoning loop: think, act, observe until a final answer or pause
+    ///
+    /// Returns `None` when the run paused; `checkpoint` then holds the
+    /// pending calls.
+    async fn drive_steps(
+        &mut self,
+        checkpoint: &mut ReActCheckpoint,
+        started: std::time::Instant,
+    ) -> Result<Option<ReActResponse>, ReActError> {
+        let base_elapsed_ms = checkpoint.elapsed_ms;
+        let elapsed_ms = move || base_elapsed_ms + started.elapsed().as_millis() as u64;
 
         self.state = ReActState::Ready;
-        let mut trace = Vec::new();
-        let mut scratchpad = String::new();
-        let mut iteration = 0;
-        let total_tokens = TokenUsage::default();
 
         // Build initial prompt with tools
         let tool_descriptions = self.format_tool_descriptions();
-        let context_str = context
-            .as_ref()
-            .map(|c| format!("\n\nContext:\n{}", c.join("\n\n")))
-            .unwrap_or_default();
+        let (context_str, kept) = self.fit_context(
+            &checkpoint.query,
+            &tool_descriptions,
+            checkpoint.context.as_deref(),
+        );
+        checkpoint.sources.truncate(kept);
 
         loop {
-            iteration += 1;
+            checkpoint.iteration += 1;
+            let iteration = checkpoint.iteration;
             let step_start = std::time::Instant::now();
 
+            if self.cancellation.is_cancelled() {
+                self.state = ReActState::Error {
+                    message: "Execution cancelled".to_string(),
+                };
+                return Err(ReActError::Cancelled);
+            }
+
             if iteration > self.config.max_iterations {
                 self.state = ReActState::Error {
                     message: format!("Max iterations ({}) exceeded", self.config.max_iterations),
@@ -561,91 +1029,96 @@ impl ReActAgent {
             // THINK: Generate next thought/action
             self.state = ReActState::Thinking;
             debug!(iteration = iteration, "Thinking...");
-
-            let prompt = self.build_prompt(query, &tool_descriptions, &context_str, &scratchpad);
-            let llm_response = self.call_llm(&prompt).await?;
-
-            // Parse the response
-            let thought_action = ThoughtAction::parse(&llm_response)?;
-
-            match thought_action {
+            self.emit(checkpoint.execution_id, iteration, ReActEventKind::Thinking)
+                .await;
+
+            let fitted_scratchpad = self.fit_scratchpad(
+                &checkpoint.query,
+                &tool_descriptions,
+                &context_str,
+                &checkpoint.scratchpad,
+            );
+            let prompt = self.build_prompt(
+                &checkpoint.query,
+                &tool_descriptions,
+                &context_str,
+                &fitted_scratchpad,
+            );
+            let (thought_action, model) = tokio::select! {
+                thought = self.think(&prompt, &mut checkpoint.usage) => thought?,
+                _ = self.cancellation.cancelled() => return Err(ReActError::Cancelled),
+            };
+            let model = Some(model);
+
+            let pending = match thought_action {
                 ThoughtAction::Thought { content } => {
                     // Just thinking, add to scratchpad and continue
-                    scratchpad.push_str(&format!("\nThought: {}", content));
+                    checkpoint
+                        .scratchpad
+                        .push(format!("\nThought: {}", content));
+
+                    let duration_ms = step_start.elapsed().as_millis() as u64;
+                    self.emit(
+                        checkpoint.execution_id,
+                        iteration,
+                        ReActEventKind::Thought {
+                            content: content.clone(),
+                            duration_ms,
+                        },
+                    )
+                    .await;
 
-                    trace.push(ReActStep {
+                    checkpoint.trace.push(ReActStep {
                         step: iteration,
                         state: ReActState::Thinking,
                         thought: Some(content),
                         action: None,
                         observation: None,
                         timestamp: Utc::now(),
-                        duration_ms: step_start.elapsed().as_millis() as u64,
+                        duration_ms,
+                        model,
                     });
+                    None
                 }
 
                 ThoughtAction::Action {
                     tool_name,
                     tool_input,
                     thought,
-                } => {
-                    // ACT: Execute the tool
-                    self.state = ReActState::Acting {
-                        tool_name: tool_name.clone(),
-                    };
-                    debug!(iteration = iteration, tool = %tool_name, "Acting...");
+                } => Some(PendingAction {
+                    thought,
+                    calls: vec![ToolInvocation {
+                        tool_name,
+                        tool_input,
+                    }],
+                    model,
+                }),
+
+                ThoughtAction::Actions { calls, thought } => Some(PendingAction {
+                    thought,
+                    calls,
+                    model,
+                }),
 
-                    if let Some(t) = &thought {
-                        scratchpad.push_str(&format!("\nThought: {}", t));
+                ThoughtAction::FinalAnswer { answer, thought } => {
+                    // REFLECT: Send the draft back if the critic rejects it
+                    if let Some(critique) = self.reflect(checkpoint, &answer).await? {
+                        self.revise(checkpoint, thought, answer, critique, model, step_start);
+                        checkpoint.elapsed_ms = elapsed_ms();
+                        checkpoint.updated_at = Utc::now();
+                        self.save_checkpoint(checkpoint).await?;
+                        continue;
                     }
-                    scratchpad.push_str(&format!("\nAction: {}", tool_name));
-                    scratchpad.push_str(&format!("\nAction Input: {}", tool_input));
-
-                    // Execute tool
-                    let tool_result = self.execute_tool(&tool_name, tool_input.clone()).await;
-
-                    // OBSERVE: Process the result
-                    self.state = ReActState::Observing;
-                    let (observation, success) = match &tool_result {
-                        Ok(result) => (result.output.clone(), result.success),
-                        Err(e) => (format!("Error: {}", e), false),
-                    };
-
-                    scratchpad.push_str(&format!("\nObservation: {}", observation));
-                    debug!(iteration = iteration, "Observation: {}", observation);
-
-                    trace.push(ReActStep {
-                        step: iteration,
-                        state: ReActState::Acting {
-                            tool_name: tool_name.clone(),
-                        },
-                        thought,
-                        action: Some(ActionRecord {
-                            tool_name,
-                            tool_input,
-                            tool_output: Some(observation.clone()),
-                            success,
-                        }),
-                        observation: Some(observation),
-                        timestamp: Utc::now(),
-                        duration_ms: step_start.elapsed().as_millis() as u64,
-                    });
-                }
 
-                ThoughtAction::FinalAnswer { answer, thought } => {
                     // FINISH: We have a final answer
                     self.state = ReActState::Finished;
                     info!(
-                        execution_id = %execution_id,
+                        execution_id = %checkpoint.execution_id,
                         iterations = iteration,
                         "ReAct execution completed"
                     );
 
-                    if let Some(t) = &thought {
-                        scratchpad.push_str(&format!("\nThought: {}", t));
-                    }
-
-                    trace.push(ReActStep {
+                    checkpoint.trace.push(ReActStep {
                         step: iteration,
                         state: ReActState::Finished,
                         thought,
@@ -653,123 +1126,668 @@ impl ReActAgent {
                         observation: None,
                         timestamp: Utc::now(),
                         duration_ms: step_start.elapsed().as_millis() as u64,
+                        model,
                     });
 
-                    return Ok(ReActResponse {
-                        id: execution_id,
+                    if let Some(store) = &self.checkpoint_store {
+                        store.delete(&checkpoint.execution_id).await?;
+                    }
+
+                    let total_duration_ms = elapsed_ms();
+                    self.emit(
+                        checkpoint.execution_id,
+                        iteration,
+                        ReActEventKind::FinalAnswer {
+                            answer: answer.clone(),
+                            total_duration_ms,
+                        },
+                    )
+                    .await;
+
+                    let usage = std::mem::take(&mut checkpoint.usage);
+                    return Ok(Some(ReActResponse {
+                        id: checkpoint.execution_id,
                         final_answer: answer,
                         iterations: iteration,
-                        total_duration_ms: start_time.elapsed().as_millis() as u64,
+                        total_duration_ms,
                         trace: if self.config.return_trace {
-                            Some(trace)
+                            Some(std::mem::take(&mut checkpoint.trace))
                         } else {
                             None
                         },
-                        sources: context.unwrap_or_default(),
+                        sources: std::mem::take(&mut checkpoint.sources),
                         state: ReActState::Finished,
-                        token_usage: Some(total_tokens),
-                    });
+                        token_usage: Some(TokenUsage::from(&usage)),
+                        usage,
+                    }));
                 }
+            };
+
+            if let Some(pending) = pending {
+                if self.requires_approval(&pending) {
+                    // PAUSE: Hand the pending calls to a reviewer
+                    let tool_names: Vec<&str> =
+                        pending.calls.iter().map(|c| c.tool_name.as_str()).collect();
+                    self.state = ReActState::Paused {
+                        tool_name: tool_names.join(", "),
+                    };
+                    info!(
+                        execution_id = %checkpoint.execution_id,
+                        iteration = iteration,
+                        tools = ?tool_names,
+                        "ReAct execution paused for approval"
+                    );
+
+                    let tool_names = tool_names.iter().map(|n| n.to_string()).collect();
+                    self.emit(
+                        checkpoint.execution_id,
+                        iteration,
+                        ReActEventKind::Paused { tool_names },
+                    )
+                    .await;
+
+                    checkpoint.pending = Some(pending);
+                    checkpoint.elapsed_ms = elapsed_ms();
+                    checkpoint.updated_at = Utc::now();
+                    self.save_checkpoint(checkpoint).await?;
+                    return Ok(None);
+                }
+
+                self.act(checkpoint, pending, step_start).await;
             }
+
+            checkpoint.elapsed_ms = elapsed_ms();
+            checkpoint.updated_at = Utc::now();
+            self.save_checkpoint(checkpoint).await?;
         }
     }
 
-    /// Build the prompt for the LLM
-    fn build_prompt(
-        &self,
-        query: &str,
-        tool_descriptions: &str,
-        context: &str,
-        scratchpad: &str,
-    ) -> String {
-        format!(
-            r#"{preamble}
-
-{tool_descriptions}
-{context}
+    /// REFLECT: Have the critic review a draft answer
+    ///
+    /// Returns the critique when the answer needs revision; `None` when it is
+    /// approved, reflection is off, or the revision budget is spent.
+    async fn reflect(
+        &mut self,
+        checkpoint: &mut ReActCheckpoint,
+        answer: &str,
+    ) -> Result<Option<Critique>, ReActError> {
+        let Some(reflection) = &self.config.reflection else {
+            return Ok(None);
+        };
+        if checkpoint.revisions >= reflection.max_revisions {
+            return Ok(None);
+        }
+        let Some(client) = self.critic_client.as_ref().or(self.llm_client.as_ref()) else {
+            return Ok(None);
+        };
 
-Use the following format:
+        self.state = ReActState::Reflecting;
+        debug!(iteration = checkpoint.iteration, "Reflecting...");
 
-Question: the input question you must answer
-Thought: you should always think about what to do
-Action: the action to take, should be one of [{tool_names}]
-Action Input: the input to the action (as JSON)
-Observation: the result of the action
-... (this Thought/Action/Action Input/Observation can repeat N times)
-Thought: I now know the final answer
-Final Answer: the final answer to the original input question
+        let prompt = self.fit_critique(
+            client.as_ref(),
+            &reflection.preamble,
+            &checkpoint.query,
+            checkpoint.context.as_deref(),
+            &checkpoint.scratchpad,
+            answer,
+        );
+        let critique = match client
+            .complete_structured_with::<Critique>(
+                &reflection.preamble,
+                &prompt,
+                DEFAULT_MAX_REPAIRS,
+            )
+            .await
+        {
+            Ok(critique) => critique,
+            Err(e) => {
+                // A failed or unusable critique should not block the answer
+                warn!(error = %e, "Critic returned no usable critique, accepting answer");
+                return Ok(None);
+            }
+        };
 
-Begin!
+        let (prompt_tokens, completion_tokens) = critique
+            .usage
+            .as_ref()
+            .map(|u| (u.prompt_tokens as u64, u.completion_tokens as u64))
+            .unwrap_or_default();
+        checkpoint.usage.record_calls(
+            &critique.model,
+            critique.attempts,
+            prompt_tokens,
+            completion_tokens,
+            &self.pricing,
+        );
 
-Question: {query}
-{scratchpad}"#,
-            preamble = self.config.preamble,
-            tool_descriptions = tool_descriptions,
-            context = context,
-            tool_names = self.tools.keys().cloned().collect::<Vec<_>>().join(", "),
-            query = query,
-            scratchpad = scratchpad,
+        let critique = critique.value;
+        self.emit(
+            checkpoint.execution_id,
+            checkpoint.iteration,
+            ReActEventKind::Reflection {
+                approved: critique.approved,
+                feedback: critique.summary(),
+            },
         )
-    }
+        .await;
 
-    /// Format tool descriptions for the prompt
-    fn format_tool_descriptions(&self) -> String {
-        if self.tools.is_empty() {
-            return "You have no tools available. Answer based on your knowledge.".to_string();
+        if critique.approved {
+            return Ok(None);
         }
+        checkpoint.revisions += 1;
+        Ok(Some(critique))
+    }
 
-        let mut desc = String::from("You have access to the following tools:\n\n");
-        for tool in self.tools.values() {
-            let def = tool.definition();
-            desc.push_str(&format!(
-                "- {}: {}\n  Parameters: {}\n\n",
-                def.name,
-                def.description,
-                serde_json::to_string_pretty(&def.parameters).unwrap_or_default()
-            ));
+    /// Record a rejected draft and the critique so the next step revises it
+    fn revise(
+        &mut self,
+        checkpoint: &mut ReActCheckpoint,
+        thought: Option<String>,
+        answer: String,
+        critique: Critique,
+        model: Option<String>,
+        step_start: std::time::Instant,
+    ) {
+        let feedback = critique.summary();
+        info!(
+            execution_id = %checkpoint.execution_id,
+            revision = checkpoint.revisions,
+            "Draft answer sent back for revision"
+        );
+
+        let mut step_text = String::new();
+        if let Some(t) = &thought {
+            step_text.push_str(&format!("\nThought: {}", t));
         }
-        desc
+        step_text.push_str(&format!("\nDraft Answer: {}", answer));
+        step_text.push_str(&format!(
+            "\nCritique: {}\nRevise the answer to address the critique, using tools if more information is needed.",
+            feedback
+        ));
+        checkpoint.scratchpad.push(step_text);
+
+        checkpoint.trace.push(ReActStep {
+            step: checkpoint.iteration,
+            state: ReActState::Reflecting,
+            thought,
+            action: None,
+            observation: Some(feedback),
+            timestamp: Utc::now(),
+            duration_ms: step_start.elapsed().as_millis() as u64,
+            model,
+        });
     }
 
-    /// Call the LLM with the given prompt
-    async fn call_llm(&self, _prompt: &str) -> Result<String, ReActError> {
-        // TODO: Integrate with rig's completion API
-        // Example implementation:
-        //
-        // use rig::providers::openai;
-        // let client = openai::Client::from_env();
-        // let completion = client
-        //     .completion(&self.config.model)
-        //     .temperature(self.config.temperature)
-        //     .build();
-        //
-        // completion
-        //     .prompt(prompt)
-        //     .await
-        //     .map_err(|e| ReActError::LlmError(e.to_string()))
-
-        // Placeholder: Return a mock response for testing
-        warn!("LLM integration not implemented - returning mock response");
-        Ok(format!(
-            "Thought: This is a placeholder response. LLM integration pending.\n\
-             Final Answer: [Mock] The ReAct agent received your query but LLM is not yet integrated."
-        ))
-    }
-
-    /// Execute a tool by name
-    async fn execute_tool(
+    /// Whether any of the calls targets a tool flagged for approval
+    fn requires_approval(&self, pending: &PendingAction) -> bool {
+        pending
+            .calls
+            .iter()
+            .any(|c| self.config.pause_before_tools.contains(&c.tool_name))
+    }
+
+    /// ACT: Execute the calls of a step concurrently and observe the results
+    async fn act(
+        &mut self,
+        checkpoint: &mut ReActCheckpoint,
+        pending: PendingAction,
+        step_start: std::time::Instant,
+    ) {
+        let names: Vec<&str> = pending.calls.iter().map(|c| c.tool_name.as_str()).collect();
+        self.state = ReActState::Acting {
+            tool_name: names.join(", "),
+        };
+        debug!(iteration = checkpoint.iteration, tools = ?names, "Acting...");
+
+        let results = self
+            .execute_tools(
+                checkpoint.execution_id,
+                checkpoint.iteration,
+                &pending.calls,
+            )
+            .await;
+        self.observe(checkpoint, pending, results, step_start);
+    }
+
+    /// OBSERVE: Record one observation (and trace entry) per call
+    fn observe(
+        &mut self,
+        checkpoint: &mut ReActCheckpoint,
+        pending: PendingAction,
+        results: Vec<ToolOutcome>,
+        step_start: std::time::Instant,
+    ) {
+        self.state = ReActState::Observing;
+        let iteration = checkpoint.iteration;
+
+        let mut step_text = String::new();
+        if let Some(t) = &pending.thought {
+            step_text.push_str(&format!("\nThought: {}", t));
+        }
+
+        let duration_ms = step_start.elapsed().as_millis() as u64;
+        let mut thought = pending.thought;
+        for (call, outcome) in pending.calls.into_iter().zip(results) {
+            let (observation, success) = outcome.observation();
+
+            step_text.push_str(&format!("\nAction: {}", call.tool_name));
+            step_text.push_str(&format!("\nAction Input: {}", call.tool_input));
+            step_text.push_str(&format!("\nObservation: {}", observation));
+            debug!(
+                iteration = iteration,
+                tool = %call.tool_name,
+                "Observation: {}",
+                observation
+            );
+
+            checkpoint.trace.push(ReActStep {
+                step: iteration,
+                state: ReActState::Acting {
+                    tool_name: call.tool_name.clone(),
+                },
+                thought: thought.take(),
+                action: Some(ActionRecord {
+                    tool_name: call.tool_name,
+                    tool_input: call.tool_input,
+                    tool_output: Some(observation.clone()),
+                    success,
+                    timed_out: outcome.timed_out(),
+                    truncated: outcome.truncated,
+                }),
+                observation: Some(observation),
+                timestamp: Utc::now(),
+                duration_ms,
+                model: pending.model.clone(),
+            });
+        }
+        checkpoint.scratchpad.push(step_text);
+    }
+
+    /// Send an event to all listeners
+    async fn emit(&self, execution_id: Uuid, iteration: usize, kind: ReActEventKind) {
+        if self.listeners.is_empty() {
+            return;
+        }
+        let event = ReActEvent::new(execution_id, iteration, kind);
+        for listener in &self.listeners {
+            listener.on_event(&event).await;
+        }
+    }
+
+    async fn emit_started(&self, checkpoint: &ReActCheckpoint) {
+        self.emit(
+            checkpoint.execution_id,
+            checkpoint.iteration,
+            ReActEventKind::Started {
+                query: checkpoint.query.clone(),
+            },
+        )
+        .await;
+    }
+
+    /// Save a checkpoint to the attached store, if any
+    async fn save_checkpoint(&self, checkpoint: &ReActCheckpoint) -> Result<(), ReActError> {
+        match &self.checkpoint_store {
+            Some(store) => store.save(checkpoint).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Retrieve context for the query with the attached retriever
+    async fn retrieve(&self, checkpoint: &mut ReActCheckpoint) -> Result<(), ReActError> {
+        let Some(retriever) = self.retriever.as_ref().filter(|_| self.config.use_rag) else {
+            return Ok(());
+        };
+
+        let results = retriever
+            .retrieve_top_k(&checkpoint.query, self.config.top_k_documents)
+            .await
+            .map_err(|e| ReActError::RetrievalError(e.to_string()))?;
+        debug!(retrieved = results.len(), "Retrieved context");
+
+        let sources: Vec<RetrievedSource> = results.into_iter().map(Into::into).collect();
+        checkpoint.context = Some(sources.iter().map(|s| s.content.clone()).collect());
+        checkpoint.sources = sources;
+        Ok(())
+    }
+
+    /// Fit retrieved context into at most half of the prompt budget
+    ///
+    /// Returns the context section and the number of documents included.
+    fn fit_context(
+        &self,
+        query: &str,
+        tool_descriptions: &str,
+        context: Option<&[String]>,
+    ) -> (String, usize) {
+        let Some(context) = context else {
+            return (String::new(), 0);
+        };
+
+        let fixed = self.build_prompt(query, tool_descriptions, "", "");
+        let available = self.budgeter.available_for(&fixed);
+        let kept = self.budgeter.fit_ranked(context, available / 2);
+        if kept.len() < context.len() {
+            debug!(
+                kept = kept.len(),
+                retrieved = context.len(),
+                "Trimmed retrieved context to fit context window"
+            );
+        }
+
+        if kept.is_empty() {
+            (String::new(), 0)
+        } else {
+            (format!("\n\nContext:\n{}", kept.join("\n\n")), kept.len())
+        }
+    }
+
+    /// Fit scratchpad steps into what's left, summarizing the oldest ones
+    fn fit_scratchpad(
         &self,
-        tool_name: &str,
-        input: serde_json::Value,
-    ) -> Result<ToolResult, ReActError> {
-        let tool = self
-            .tools
-            .get(tool_name)
-            .ok_or_else(|| ReActError::ToolNotFound(tool_name.to_string()))?;
-
-        tool.execute(input)
+        query: &str,
+        tool_descriptions: &str,
+        context: &str,
+        steps: &[String],
+    ) -> String {
+        let fixed = self.build_prompt(query, tool_descriptions, context, "");
+        let available = self.budgeter.available_for(&fixed);
+        self.budgeter.fit_oldest_first(steps, available).concat()
+    }
+
+    /// Build the critique prompt, trimming context and scratchpad to the
+    /// critic's context window
+    fn fit_critique(
+        &self,
+        client: &dyn CompletionClient,
+        preamble: &str,
+        query: &str,
+        context: Option<&[String]>,
+        scratchpad: &[String],
+        answer: &str,
+    ) -> String {
+        let budgeter = if self.critic_client.is_some() {
+            ContextBudgeter::for_model(client.model(), self.config.max_tokens as usize)
+        } else {
+            self.budgeter.clone()
+        };
+
+        let fixed = format!(
+            "{}\n{}",
+            preamble,
+            critique_prompt(query, None, &[], answer)
+        );
+        let available = budgeter.available_for(&fixed);
+        let context = context.map(|docs| budgeter.fit_ranked(docs, available / 2));
+        let used = context
+            .as_ref()
+            .map(|docs| docs.iter().map(|d| budgeter.counter().count(d)).sum())
+            .unwrap_or(0);
+        let steps = budgeter.fit_oldest_first(scratchpad, available.saturating_sub(used));
+        if steps.len() < scratchpad.len() {
+            debug!(
+                kept = steps.len(),
+                steps = scratchpad.len(),
+                "Trimmed scratchpad to fit the critic's context window"
+            );
+        }
+
+        critique_prompt(query, context.as_deref(), &steps, answer)
+    }
+
+    /// Build the prompt for the LLM
+    fn build_prompt(
+        &self,
+        query: &str,
+        tool_descriptions: &str,
+        context: &str,
+        scratchpad: &str,
+    ) -> String {
+        format!(
+            r#"{preamble}
+
+{tool_descriptions}
+{context}
+
+Use the following format:
+
+Question: the input question you must answer
+Thought: you should always think about what to do
+Action: the action to take, should be one of [{tool_names}]
+Action Input: the input to the action (as JSON)
+Observation: the result of the action
+... (this Thought/Action/Action Input/Observation can repeat N times)
+(to use several tools at once, write multiple Action/Action Input pairs after one Thought)
+Thought: I now know the final answer
+Final Answer: the final answer to the original input question
+
+Begin!
+
+Question: {query}
+{scratchpad}"#,
+            preamble = self.config.preamble,
+            tool_descriptions = tool_descriptions,
+            context = context,
+            tool_names = self.tools.keys().cloned().collect::<Vec<_>>().join(", "),
+            query = query,
+            scratchpad = scratchpad,
+        )
+    }
+
+    /// Format tool descriptions for the prompt
+    fn format_tool_descriptions(&self) -> String {
+        if self.tools.is_empty() {
+            return "You have no tools available. Answer based on your knowledge.".to_string();
+        }
+
+        let mut desc = String::from("You have access to the following tools:\n\n");
+        for tool in self.tools.values() {
+            let def = tool.definition();
+            desc.push_str(&format!(
+                "- {}: {}\n  Parameters: {}\n\n",
+                def.name,
+                def.description,
+                serde_json::to_string_pretty(&def.parameters).unwrap_or_default()
+            ));
+        }
+        desc
+    }
+
+    /// Ask the LLM for the next thought/action, returning the model used
+    ///
+    /// Uses native tool calling when a tool-calling client is configured,
+    /// otherwise parses the text format.
+    async fn think(
+        &self,
+        prompt: &str,
+        usage: &mut UsageReport,
+    ) -> Result<(ThoughtAction, String), ReActError> {
+        let Some(client) = &self.tool_calling_client else {
+            let response = self.call_llm(prompt, usage).await?;
+            return Ok((ThoughtAction::parse(&response.content)?, response.model));
+        };
+
+        let response = client
+            .complete_with_tools(prompt, &self.tool_definitions())
+            .await
+            .map_err(|e| ReActError::LlmError(e.to_string()))?;
+        debug!(
+            model = %client.model(),
+            tool_calls = response.tool_calls.len(),
+            "LLM tool-calling completed"
+        );
+
+        let model = client.model().to_string();
+        let recorded = LlmResponse {
+            content: response.content.clone().unwrap_or_default(),
+            model: model.clone(),
+            usage: response.usage.clone(),
+            finish_reason: response.finish_reason.clone(),
+        };
+        usage.record_response(prompt, &recorded, &self.pricing);
+
+        Ok((ThoughtAction::from_tool_calls(&response)?, model))
+    }
+
+    /// Call the LLM with the given prompt, recording its usage
+    async fn call_llm(
+        &self,
+        prompt: &str,
+        usage: &mut UsageReport,
+    ) -> Result<LlmResponse, ReActError> {
+        if let Some(client) = &self.llm_client {
+            let response = client
+                .complete(prompt)
+                .await
+                .map_err(|e| ReActError::LlmError(e.to_string()))?;
+            debug!(model = %response.model, "LLM call completed");
+            usage.record_response(prompt, &response, &self.pricing);
+            return Ok(response);
+        }
+
+        // Placeholder: Return a mock response when no client is configured
+        warn!("No LLM client configured - returning mock response");
+        Ok(LlmResponse {
+            content: "Thought: This is a placeholder response. LLM integration pending.\n\
+                      Final Answer: [Mock] The ReAct agent received your query but LLM is not yet integrated."
+                .to_string(),
+            model: self.config.model.clone(),
+            usage: None,
+            finish_reason: None,
+        })
+    }
+
+    /// Execute several tool calls concurrently, bounded by
+    /// `max_parallel_tools`; results are returned in call order
+    async fn execute_tools(
+        &self,
+        execution_id: Uuid,
+        iteration: usize,
+        calls: &[ToolInvocation],
+    ) -> Vec<ToolOutcome> {
+        // Collected first: a mapping closure in the stream makes the
+        // future non-`Send` for callers that spawn it
+        let executions: Vec<_> = calls
+            .iter()
+            .map(|call| async move {
+                let started = std::time::Instant::now();
+                self.emit(
+                    execution_id,
+                    iteration,
+                    ReActEventKind::Acting {
+                        tool_name: call.tool_name.clone(),
+                        tool_input: call.tool_input.clone(),
+                    },
+                )
+                .await;
+
+                let outcome = self
+                    .execute_tool(&call.tool_name, call.tool_input.clone())
+                    .await;
+
+                let (output, success) = outcome.observation();
+                self.emit(
+                    execution_id,
+                    iteration,
+                    ReActEventKind::Observation {
+                        tool_name: call.tool_name.clone(),
+                        output,
+                        success,
+                        duration_ms: started.elapsed().as_millis() as u64,
+                    },
+                )
+                .await;
+                outcome
+            })
+            .collect();
+
+        stream::iter(executions)
+            .buffered(self.config.max_parallel_tools.max(1))
+            .collect()
             .await
-            .map_err(|e| ReActError::ToolExecutionFailed(e.to_string()))
+    }
+
+    /// Execute a tool by name, after checking the arguments against its
+    /// schema, within the tool policy and the iteration timeout
+    async fn execute_tool(&self, tool_name: &str, input: serde_json::Value) -> ToolOutcome {
+        let tool = match self.tools.get(tool_name) {
+            Some(tool) => tool,
+            None => return Err(ReActError::ToolNotFound(tool_name.to_string())).into(),
+        };
+
+        let input = match prepare_arguments(
+            tool_name,
+            &tool.definition().parameters,
+            input,
+            &self.config.tool_arguments,
+        ) {
+            Ok(input) => input,
+            Err(e) => return Err(ReActError::InvalidToolArguments(e.to_string())).into(),
+        };
+
+        let timeout = min_timeout(
+            self.config.tool_policy.timeout_for(tool_name),
+            self.config
+                .iteration_timeout_secs
+                .map(std::time::Duration::from_secs),
+        );
+
+        let result = match run_bounded(tool.execute(input), timeout, &self.cancellation).await {
+            Ok(result) => result.map_err(|e| match e {
+                common::Error::Timeout(limit) => {
+                    ReActError::ToolTimeout(tool_name.to_string(), limit)
+                }
+                e => ReActError::ToolExecutionFailed(e.to_string()),
+            }),
+            Err(Interrupted::TimedOut(limit)) => {
+                Err(ReActError::ToolTimeout(tool_name.to_string(), limit))
+            }
+            Err(Interrupted::Cancelled) => Err(ReActError::Cancelled),
+        };
+
+        match result {
+            Ok(mut result) => {
+                let (output, truncated) = self.config.tool_policy.limit_output(result.output);
+                result.output = output;
+                ToolOutcome {
+                    result: Ok(result),
+                    truncated,
+                }
+            }
+            Err(e) => Err(e).into(),
+        }
+    }
+}
+
+/// Result of one tool call
+struct ToolOutcome {
+    result: Result<ToolResult, ReActError>,
+
+    /// The output was cut to the policy's limit
+    truncated: bool,
+}
+
+impl ToolOutcome {
+    /// Observation text and success flag
+    fn observation(&self) -> (String, bool) {
+        match &self.result {
+            Ok(result) => (result.output.clone(), result.success),
+            Err(e) => (format!("Error: {}", e), false),
+        }
+    }
+
+    fn timed_out(&self) -> bool {
+        matches!(self.result, Err(ReActError::ToolTimeout(..)))
+    }
+}
+
+impl From<Result<ToolResult, ReActError>> for ToolOutcome {
+    fn from(result: Result<ToolResult, ReActError>) -> Self {
+        Self {
+            result,
+            truncated: false,
+        }
     }
 }
 
@@ -833,6 +1851,72 @@ Action Input: {"query": "rust programming"}"#;
         }
     }
 
+    #[test]
+    fn test_thought_action_parse_multiple_actions() {
+        let text = r#"Thought: I need both products and the brochure
+Action: product_search
+Action Input: {"query": "pump"}
+Action: product_search
+Action Input: {"query": "valve"}
+Action: get_brochure
+Action Input: {"product": "pump"}"#;
+
+        match ThoughtAction::parse(text).unwrap() {
+            ThoughtAction::Actions { calls, thought } => {
+                assert_eq!(
+                    thought,
+                    Some("I need both products and the brochure".to_string())
+                );
+                assert_eq!(calls.len(), 3);
+                assert_eq!(calls[1].tool_name, "product_search");
+                assert_eq!(calls[1].tool_input["query"], "valve");
+                assert_eq!(calls[2].tool_name, "get_brochure");
+            }
+            other => panic!("Expected Actions, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_thought_action_from_tool_calls() {
+        use crate::rig_integration::ToolCall;
+
+        let response = ToolCallResponse {
+            content: Some("Looking both up".to_string()),
+            tool_calls: vec![
+                ToolCall {
+                    id: "1".to_string(),
+                    name: "search".to_string(),
+                    arguments: serde_json::json!({"query": "a"}),
+                },
+                ToolCall {
+                    id: "2".to_string(),
+                    name: "search".to_string(),
+                    arguments: serde_json::json!({"query": "b"}),
+                },
+            ],
+            finish_reason: None,
+            usage: None,
+        };
+
+        match ThoughtAction::from_tool_calls(&response).unwrap() {
+            ThoughtAction::Actions { calls, thought } => {
+                assert_eq!(calls.len(), 2);
+                assert_eq!(thought.as_deref(), Some("Looking both up"));
+            }
+            other => panic!("Expected Actions, got {:?}", other),
+        }
+
+        let answer = ToolCallResponse {
+            content: Some("Final Answer: done".to_string()),
+            tool_calls: Vec::new(),
+            ..response
+        };
+        assert!(matches!(
+            ThoughtAction::from_tool_calls(&answer).unwrap(),
+            ThoughtAction::FinalAnswer { .. }
+        ));
+    }
+
     #[test]
     fn test_thought_action_parse_thought_only() {
         let text = "Thought: I need to think about this more carefully.";
@@ -898,6 +1982,818 @@ Action Input: {"query": "rust programming"}"#;
         assert!(agent.tools.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_react_agent_records_routed_model() {
+        use crate::rig_integration::{LlmConfig, RigLlmClient};
+        use crate::routing::RoutingClient;
+
+        let client = RoutingClient::new(vec![Arc::new(RigLlmClient::new(LlmConfig::anthropic(
+            "claude-3-haiku",
+        )))]);
+        let mut agent = ReActAgent::new(ReActConfig::default()).with_llm_client(Arc::new(client));
+
+        let response = agent.run("Hello").await.unwrap();
+        let trace = response.trace.unwrap();
+        assert_eq!(trace[0].model.as_deref(), Some("anthropic/claude-3-haiku"));
+    }
+
+    #[tokio::test]
+    async fn test_react_agent_scripted_loop() {
+        use crate::replay::ScriptedClient;
+        use crate::tools::SearchTool;
+
+        let client = Arc::new(ScriptedClient::new("gpt-4").with_responses([
+            "Thought: I should search\nAction: search\nAction Input: {\"query\": \"rust\"}",
+            "Thought: I know the answer\nFinal Answer: Rust is a systems language",
+        ]));
+        let mut agent = ReActAgent::new(ReActConfig::default())
+            .with_tool(SearchTool::new())
+            .with_llm_client(client.clone());
+
+        let response = agent.run("What is Rust?").await.unwrap();
+        assert_eq!(response.final_answer, "Rust is a systems language");
+        assert_eq!(response.iterations, 2);
+
+        let trace = response.trace.unwrap();
+        let action = trace[0].action.as_ref().unwrap();
+        assert_eq!(action.tool_name, "search");
+        assert!(action.success);
+
+        // The observation is fed back into the next prompt
+        let requests = client.requests();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[1]
+            .prompt()
+            .contains("Observation: Searched for 'rust' with limit 5"));
+    }
+
+    #[tokio::test]
+    async fn test_react_agent_feeds_argument_errors_back() {
+        use crate::replay::ScriptedClient;
+        use crate::tools::SearchTool;
+
+        let client = Arc::new(ScriptedClient::new("gpt-4").with_responses([
+            "Thought: I should search\nAction: search\nAction Input: {\"limit\": \"3\"}",
+            "Thought: I forgot the query\nAction: search\nAction Input: {\"query\": \"rust\", \"limit\": \"3\"}",
+            "Thought: I know the answer\nFinal Answer: Rust is a systems language",
+        ]));
+        let mut agent = ReActAgent::new(ReActConfig::default())
+            .with_tool(SearchTool::new())
+            .with_llm_client(client.clone());
+
+        let response = agent.run("What is Rust?").await.unwrap();
+        assert_eq!(response.iterations, 3);
+
+        let trace = response.trace.unwrap();
+        assert!(!trace[0].action.as_ref().unwrap().success);
+        assert!(trace[1].action.as_ref().unwrap().success);
+
+        let requests = client.requests();
+        assert!(requests[1].prompt().contains(
+            "Observation: Error: Invalid arguments for tool 'search': $: missing required property 'query'"
+        ));
+        // The string limit was coerced to an integer
+        assert!(requests[2]
+            .prompt()
+            .contains("Observation: Searched for 'rust' with limit 3"));
+    }
+
+    #[tokio::test]
+    async fn test_react_agent_reflection_revises_unsupported_answer() {
+        use crate::replay::ScriptedClient;
+        use crate::tools::SearchTool;
+
+        let client = Arc::new(ScriptedClient::new("gpt-4").with_responses([
+            "Final Answer: Pump X costs 1,200 THB",
+            "Thought: I should check the price\nAction: search\nAction Input: {\"query\": \"pump x price\"}",
+            "Final Answer: Let me confirm the price with sales",
+        ]));
+        let critic = Arc::new(ScriptedClient::new("gpt-4o-mini").with_responses([
+            r#"{"approved": false, "unsupported_claims": ["Pump X costs 1,200 THB"], "feedback": "No tool returned a price."}"#,
+            r#"{"approved": true}"#,
+        ]));
+        let config = ReActConfig::builder()
+            .reflection(ReflectionConfig::default())
+            .build();
+        let mut agent = ReActAgent::new(config)
+            .with_tool(SearchTool::new())
+            .with_llm_client(client.clone())
+            .with_critic_client(critic.clone());
+        let mut events = agent.subscribe();
+
+        let response = agent.run("How much is Pump X?").await.unwrap();
+        assert_eq!(response.final_answer, "Let me confirm the price with sales");
+        assert_eq!(response.iterations, 3);
+        assert_eq!(critic.requests().len(), 2);
+        assert_eq!(response.usage.by_model["gpt-4o-mini"].llm_calls, 2);
+
+        let trace = response.trace.unwrap();
+        assert_eq!(trace[0].state, ReActState::Reflecting);
+        assert!(trace[0]
+            .observation
+            .as_deref()
+            .unwrap()
+            .contains("- Pump X costs 1,200 THB"));
+
+        // The critique is fed back before the next step
+        let requests = client.requests();
+        assert!(requests[1]
+            .prompt()
+            .contains("Draft Answer: Pump X costs 1,200 THB\nCritique: No tool returned a price."));
+
+        let mut verdicts = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            if let ReActEventKind::Reflection { approved, .. } = event.kind {
+                verdicts.push(approved);
+            }
+        }
+        assert_eq!(verdicts, [false, true]);
+    }
+
+    #[tokio::test]
+    async fn test_react_agent_reflection_stops_after_max_revisions() {
+        use crate::replay::ScriptedClient;
+
+        let client = Arc::new(
+            ScriptedClient::new("gpt-4")
+                .with_responses(["Final Answer: first draft", "Final Answer: second draft"]),
+        );
+        let critic = Arc::new(
+            ScriptedClient::new("gpt-4")
+                .with_responses([r#"{"approved": false, "feedback": "Still unsupported"}"#]),
+        );
+        let config = ReActConfig::builder()
+            .reflection(ReflectionConfig::default().with_max_revisions(1))
+            .build();
+        let mut agent = ReActAgent::new(config)
+            .with_llm_client(client)
+            .with_critic_client(critic.clone());
+
+        let response = agent.run("Question").await.unwrap();
+        assert_eq!(response.final_answer, "second draft");
+        assert_eq!(critic.requests().len(), 1);
+    }
+
+    /// Retriever returning fixed chunks and counting calls
+    struct FixedRetriever {
+        results: Vec<SearchResult>,
+        calls: std::sync::atomic::AtomicUsize,
+    }
+
+    impl FixedRetriever {
+        fn new(contents: &[&str]) -> Self {
+            let results = contents
+                .iter()
+                .enumerate()
+                .map(|(i, content)| SearchResult {
+                    chunk: common::models::DocumentChunk {
+                        id: Uuid::new_v4(),
+                        document_id: Uuid::new_v4(),
+                        content: content.to_string(),
+                        chunk_index: i,
+                        metadata: serde_json::json!({}),
+                    },
+                    score: 0.9 - i as f32 * 0.1,
+                })
+                .collect();
+            Self {
+                results,
+                calls: std::sync::atomic::AtomicUsize::new(0),
+            }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl DocumentRetriever for FixedRetriever {
+        async fn retrieve_top_k(
+            &self,
+            _query: &str,
+            top_k: usize,
+        ) -> common::Result<Vec<SearchResult>> {
+            self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(self.results.iter().take(top_k).cloned().collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_react_agent_retrieves_context_and_returns_sources() {
+        use crate::replay::ScriptedClient;
+
+        let retriever = Arc::new(FixedRetriever::new(&[
+            "Pump X costs 990 THB",
+            "Pump X has a 2-year warranty",
+            "Pump Y costs 1,500 THB",
+        ]));
+        let client = Arc::new(
+            ScriptedClient::new("gpt-4").with_responses(["Final Answer: Pump X costs 990 THB"]),
+        );
+        let config = ReActConfig::builder().top_k_documents(2).build();
+        let mut agent = ReActAgent::new(config)
+            .with_retriever(retriever.clone())
+            .with_llm_client(client.clone());
+
+        let response = agent.run("How much is Pump X?").await.unwrap();
+
+        let expected: Vec<RetrievedSource> = retriever.results[..2]
+            .iter()
+            .cloned()
+            .map(Into::into)
+            .collect();
+        assert_eq!(response.sources, expected);
+        assert_eq!(response.sources[0].score, 0.9);
+
+        let prompt = client.requests()[0].prompt().to_string();
+        assert!(prompt.contains("Context:\nPump X costs 990 THB\n\nPump X has a 2-year warranty"));
+        assert!(!prompt.contains("Pump Y"));
+    }
+
+    #[tokio::test]
+    async fn test_react_agent_skips_retrieval_with_caller_context_or_rag_off() {
+        use crate::replay::ScriptedClient;
+
+        let retriever = Arc::new(FixedRetriever::new(&["Retrieved chunk"]));
+        let client = Arc::new(
+            ScriptedClient::new("gpt-4").with_responses(["Final Answer: one", "Final Answer: two"]),
+        );
+        let mut agent = ReActAgent::new(ReActConfig::default())
+            .with_retriever(retriever.clone())
+            .with_llm_client(client.clone());
+        let response = agent
+            .run_with_context("Question", Some(vec!["Caller context".to_string()]))
+            .await
+            .unwrap();
+        assert!(response.sources.is_empty());
+
+        let config = ReActConfig::builder().use_rag(false).build();
+        let mut agent = ReActAgent::new(config)
+            .with_retriever(retriever.clone())
+            .with_llm_client(client.clone());
+        agent.run("Question").await.unwrap();
+
+        assert_eq!(retriever.calls.load(std::sync::atomic::Ordering::SeqCst), 0);
+        assert!(client.requests()[0].prompt().contains("Caller context"));
+    }
+
+    /// Tool that sleeps and tracks how many calls run at the same time
+    struct SlowTool {
+        in_flight: Arc<std::sync::atomic::AtomicUsize>,
+        peak: Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    #[async_trait::async_trait]
+    impl Tool for SlowTool {
+        fn definition(&self) -> ToolDefinition {
+            ToolDefinition {
+                name: "lookup".to_string(),
+                description: "Slow lookup".to_string(),
+                parameters: serde_json::json!({"type": "object"}),
+            }
+        }
+
+        async fn execute(&self, args: serde_json::Value) -> common::Result<ToolResult> {
+            use std::sync::atomic::Ordering;
+
+            let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(now, Ordering::SeqCst);
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+
+            Ok(ToolResult {
+                tool_name: "lookup".to_string(),
+                output: format!("found {}", args["id"]),
+                success: true,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_react_agent_parallel_tool_calls() {
+        use crate::replay::ScriptedClient;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let peak = Arc::new(AtomicUsize::new(0));
+        let tool = SlowTool {
+            in_flight: Arc::new(AtomicUsize::new(0)),
+            peak: peak.clone(),
+        };
+
+        let client = Arc::new(ScriptedClient::new("gpt-4").with_responses([
+            "Thought: look up all three\n\
+             Action: lookup\nAction Input: {\"id\": 1}\n\
+             Action: lookup\nAction Input: {\"id\": 2}\n\
+             Action: lookup\nAction Input: {\"id\": 3}",
+            "Final Answer: all found",
+        ]));
+        let config = ReActConfig::builder().max_parallel_tools(2).build();
+        let mut agent = ReActAgent::new(config)
+            .with_tool(tool)
+            .with_llm_client(client.clone());
+
+        let response = agent.run("Find 1, 2 and 3").await.unwrap();
+        assert_eq!(response.iterations, 2);
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+
+        let trace = response.trace.unwrap();
+        let outputs: Vec<_> = trace
+            .iter()
+            .filter_map(|s| s.action.as_ref())
+            .map(|a| a.tool_output.clone().unwrap())
+            .collect();
+        assert_eq!(outputs, ["found 1", "found 2", "found 3"]);
+        assert!(trace[..3].iter().all(|s| s.step == 1));
+
+        let followup = client.requests()[1].prompt().to_string();
+        assert!(followup.contains("Observation: found 3"));
+    }
+
+    /// Tool that never finishes in time
+    struct HangingTool;
+
+    #[async_trait::async_trait]
+    impl Tool for HangingTool {
+        fn definition(&self) -> ToolDefinition {
+            ToolDefinition {
+                name: "hang".to_string(),
+                description: "Hangs".to_string(),
+                parameters: serde_json::json!({"type": "object"}),
+            }
+        }
+
+        async fn execute(&self, _args: serde_json::Value) -> common::Result<ToolResult> {
+            tokio::time::sleep(std::time::Duration::from_secs(30)).await;
+            Ok(ToolResult {
+                tool_name: "hang".to_string(),
+                output: "finally".to_string(),
+                success: true,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_react_agent_tool_timeout_and_truncation() {
+        use crate::replay::ScriptedClient;
+        use crate::tools::SearchTool;
+
+        let client = Arc::new(ScriptedClient::new("gpt-4").with_responses([
+            "Action: hang\nAction Input: {}\n\
+             Action: search\nAction Input: {\"query\": \"rust\"}",
+            "Final Answer: partial results",
+        ]));
+        let policy = ToolPolicy::new()
+            .with_tool_timeout_ms("hang", 20)
+            .with_max_output_chars(8);
+        let config = ReActConfig::builder().tool_policy(policy).build();
+        let mut agent = ReActAgent::new(config)
+            .with_tool(HangingTool)
+            .with_tool(SearchTool::new())
+            .with_llm_client(client.clone());
+
+        let response = agent.run("Search while hanging").await.unwrap();
+        let trace = response.trace.unwrap();
+
+        let hang = trace[0].action.as_ref().unwrap();
+        assert!(hang.timed_out);
+        assert!(!hang.success);
+        assert_eq!(
+            hang.tool_output.as_deref(),
+            Some("Error: Tool 'hang' timed out after 20ms")
+        );
+
+        let search = trace[1].action.as_ref().unwrap();
+        assert!(search.truncated);
+        assert!(!search.timed_out);
+        assert!(search
+            .tool_output
+            .as_deref()
+            .unwrap()
+            .starts_with("Searched\n[... truncated "));
+    }
+
+    #[tokio::test]
+    async fn test_react_agent_reports_crew_tool_timeouts() {
+        use crate::crew::tools::DynamicTool;
+        use crate::replay::ScriptedClient;
+
+        let slow = DynamicTool::new("slow", "Never finishes", |_| async {
+            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+            Ok("done".to_string())
+        })
+        .with_policy(ToolPolicy::new().with_timeout_ms(10));
+        let client = Arc::new(ScriptedClient::new("gpt-4").with_responses([
+            "Action: slow\nAction Input: {}",
+            "Final Answer: gave up",
+        ]));
+        let mut agent = ReActAgent::new(ReActConfig::default())
+            .with_tool(slow)
+            .with_llm_client(client);
+
+        let response = agent.run("Be slow").await.unwrap();
+        let action = response.trace.unwrap()[0].action.clone().unwrap();
+        assert!(action.timed_out);
+        assert_eq!(
+            action.tool_output.as_deref(),
+            Some("Error: Tool 'slow' timed out after 10ms")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_react_agent_cancellation_stops_running_tools() {
+        use crate::replay::ScriptedClient;
+
+        let client = Arc::new(
+            ScriptedClient::new("gpt-4")
+                .with_responses(["Action: hang\nAction Input: {}", "Final Answer: never"]),
+        );
+        let mut agent = ReActAgent::new(ReActConfig::default())
+            .with_tool(HangingTool)
+            .with_llm_client(client.clone());
+
+        let cancel = agent.cancellation_token();
+        tokio::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            cancel.cancel();
+        });
+
+        let started = std::time::Instant::now();
+        let err = agent.run("Hang please").await.unwrap_err();
+        assert!(matches!(err, ReActError::Cancelled));
+        assert!(started.elapsed() < std::time::Duration::from_secs(5));
+        assert_eq!(client.requests().len(), 1);
+    }
+
+    fn approval_agent(client: Arc<crate::replay::ScriptedClient>) -> ReActAgent {
+        use crate::tools::SearchTool;
+
+        let config = ReActConfig::builder().pause_before_tool("search").build();
+        ReActAgent::new(config)
+            .with_tool(SearchTool::new())
+            .with_llm_client(client)
+    }
+
+    #[tokio::test]
+    async fn test_react_agent_pauses_and_resumes_from_checkpoint() {
+        use crate::checkpoint::{ApprovalDecision, ReActCheckpoint};
+        use crate::replay::ScriptedClient;
+
+        let client = Arc::new(ScriptedClient::new("gpt-4").with_responses([
+            "Thought: search first\nAction: search\nAction Input: {\"query\": \"pumps\"}",
+            "Final Answer: We sell pumps",
+        ]));
+
+        let mut agent = approval_agent(client.clone());
+        let mut checkpoint = match agent.start("What do you sell?", None).await.unwrap() {
+            ReActOutcome::Paused(checkpoint) => checkpoint,
+            ReActOutcome::Completed(_) => panic!("Expected the run to pause"),
+        };
+        assert_eq!(checkpoint.pending_tool_names(), ["search"]);
+        assert!(matches!(agent.state(), ReActState::Paused { .. }));
+
+        checkpoint.record_review("sales", &ApprovalDecision::Approve);
+        assert_eq!(checkpoint.reviews[0].reviewer, "sales");
+        assert_eq!(checkpoint.reviews[0].tool_names, ["search"]);
+
+        // Survives serialization, e.g. storage in Redis between processes
+        let json = serde_json::to_string(&checkpoint).unwrap();
+        let restored: ReActCheckpoint = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.reviews, checkpoint.reviews);
+
+        let mut agent = approval_agent(client);
+        let response = match agent
+            .resume(restored, ApprovalDecision::Approve)
+            .await
+            .unwrap()
+        {
+            ReActOutcome::Completed(response) => response,
+            ReActOutcome::Paused(_) => panic!("Expected the run to complete"),
+        };
+
+        assert_eq!(response.id, checkpoint.execution_id);
+        assert_eq!(response.final_answer, "We sell pumps");
+        assert_eq!(response.iterations, 2);
+        assert_eq!(response.usage.llm_calls, 2);
+        let trace = response.trace.unwrap();
+        let action = trace[0].action.as_ref().unwrap();
+        assert!(action.success);
+        assert_eq!(trace[0].thought.as_deref(), Some("search first"));
+    }
+
+    #[tokio::test]
+    async fn test_react_agent_rejected_tool_is_observed() {
+        use crate::checkpoint::ApprovalDecision;
+        use crate::replay::ScriptedClient;
+
+        let client = Arc::new(ScriptedClient::new("gpt-4").with_responses([
+            "Action: search\nAction Input: {\"query\": \"pumps\"}",
+            "Final Answer: I could not search",
+        ]));
+
+        let mut agent = approval_agent(client.clone());
+        let ReActOutcome::Paused(checkpoint) =
+            agent.start("What do you sell?", None).await.unwrap()
+        else {
+            panic!("Expected the run to pause");
+        };
+
+        let decision = ApprovalDecision::Reject {
+            reason: Some("not allowed".to_string()),
+        };
+        let ReActOutcome::Completed(response) = agent.resume(checkpoint, decision).await.unwrap()
+        else {
+            panic!("Expected the run to complete");
+        };
+
+        let action = response.trace.unwrap()[0].action.clone().unwrap();
+        assert!(!action.success);
+        assert!(client.requests()[1]
+            .prompt()
+            .contains("Observation: Error: Tool call rejected: not allowed"));
+    }
+
+    #[tokio::test]
+    async fn test_react_agent_saves_checkpoint_when_paused() {
+        use crate::checkpoint::{CheckpointStore, InMemoryCheckpointStore};
+        use crate::replay::ScriptedClient;
+
+        let store = Arc::new(InMemoryCheckpointStore::new());
+        let client = Arc::new(ScriptedClient::new("gpt-4").with_responses([
+            "Thought: hmm",
+            "Action: search\nAction Input: {\"query\": \"pumps\"}",
+        ]));
+
+        let mut agent = approval_agent(client).with_checkpoint_store(store.clone());
+        let ReActOutcome::Paused(checkpoint) =
+            agent.start("What do you sell?", None).await.unwrap()
+        else {
+            panic!("Expected the run to pause");
+        };
+
+        let stored = store.load(&checkpoint.execution_id).await.unwrap().unwrap();
+        assert_eq!(stored.iteration, 2);
+        assert_eq!(stored.scratchpad, ["\nThought: hmm"]);
+        assert!(stored.is_paused());
+
+        // Without approval configured, run_with_context reports the pause
+        let client = Arc::new(
+            ScriptedClient::new("gpt-4")
+                .with_response("Action: search\nAction Input: {\"query\": \"pumps\"}"),
+        );
+        let err = approval_agent(client)
+            .run("What do you sell?")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ReActError::ApprovalRequired(tools) if tools == "search"));
+    }
+
+    #[tokio::test]
+    async fn test_react_agent_continues_from_iteration_checkpoint() {
+        use crate::checkpoint::{CheckpointStore, InMemoryCheckpointStore};
+        use crate::replay::ScriptedClient;
+        use crate::tools::SearchTool;
+
+        // The run stops after two iterations, as if its worker had died
+        let store = Arc::new(InMemoryCheckpointStore::new());
+        let client = Arc::new(ScriptedClient::new("gpt-4").with_responses([
+            "Thought: hmm",
+            "Action: search\nAction Input: {\"query\": \"pumps\"}",
+        ]));
+        let checkpoint = ReActCheckpoint::new("What do you sell?");
+        let execution_id = checkpoint.execution_id;
+        let mut agent = ReActAgent::new(ReActConfig::builder().max_iterations(2).build())
+            .with_tool(SearchTool::new())
+            .with_llm_client(client)
+            .with_checkpoint_store(store.clone());
+        assert!(agent.run_checkpoint(checkpoint).await.is_err());
+
+        let stored = store.load(&execution_id).await.unwrap().unwrap();
+        assert_eq!(stored.iteration, 2);
+        assert_eq!(stored.scratchpad.len(), 2);
+        assert!(stored.scratchpad[1].contains("Observation:"));
+
+        let client = Arc::new(ScriptedClient::new("gpt-4").with_response("Final Answer: pumps"));
+        let mut agent = ReActAgent::new(ReActConfig::default())
+            .with_tool(SearchTool::new())
+            .with_llm_client(client)
+            .with_checkpoint_store(store.clone());
+        let ReActOutcome::Completed(response) = agent.run_checkpoint(stored).await.unwrap() else {
+            panic!("Expected the run to complete");
+        };
+        assert_eq!(response.final_answer, "pumps");
+        assert_eq!(response.iterations, 3);
+        assert!(store.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_react_agent_deletes_checkpoint_when_finished() {
+        use crate::checkpoint::InMemoryCheckpointStore;
+        use crate::replay::ScriptedClient;
+
+        let store = Arc::new(InMemoryCheckpointStore::new());
+        let client = Arc::new(
+            ScriptedClient::new("gpt-4").with_responses(["Thought: hmm", "Final Answer: done"]),
+        );
+        let mut agent = ReActAgent::new(ReActConfig::default())
+            .with_llm_client(client)
+            .with_checkpoint_store(store.clone());
+
+        agent.run("Hello").await.unwrap();
+        assert!(store.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_react_agent_emits_step_events() {
+        use crate::events::ReActEventKind;
+        use crate::replay::ScriptedClient;
+        use crate::tools::SearchTool;
+
+        let client = Arc::new(ScriptedClient::new("gpt-4").with_responses([
+            "Thought: let me think",
+            "Action: search\nAction Input: {\"query\": \"pumps\"}",
+            "Final Answer: pumps",
+        ]));
+        let mut agent = ReActAgent::new(ReActConfig::default())
+            .with_tool(SearchTool::new())
+            .with_llm_client(client);
+        let mut events = agent.subscribe();
+
+        let response = agent.run("What do you sell?").await.unwrap();
+
+        let mut kinds = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            assert_eq!(event.execution_id, response.id);
+            kinds.push(event.kind);
+        }
+        let names: Vec<&str> = kinds.iter().map(ReActEventKind::name).collect();
+        for kind in &kinds {
+            assert_eq!(serde_json::to_value(kind).unwrap()["type"], kind.name());
+        }
+        assert_eq!(
+            names,
+            [
+                "started",
+                "thinking",
+                "thought",
+                "thinking",
+                "acting",
+                "observation",
+                "thinking",
+                "final_answer"
+            ]
+        );
+        assert!(matches!(
+            &kinds[5],
+            ReActEventKind::Observation { tool_name, success: true, .. } if tool_name == "search"
+        ));
+        assert!(kinds[5].duration_ms().is_some());
+        assert_eq!(kinds[4].duration_ms(), None);
+    }
+
+    #[tokio::test]
+    async fn test_react_agent_emits_error_event() {
+        use crate::events::ReActEventKind;
+        use crate::replay::ScriptedClient;
+
+        let client = Arc::new(ScriptedClient::new("gpt-4"));
+        let mut agent = ReActAgent::new(ReActConfig::default()).with_llm_client(client);
+        let mut events = agent.subscribe();
+
+        assert!(agent.run("Hello").await.is_err());
+
+        let mut last = None;
+        while let Ok(event) = events.try_recv() {
+            last = Some(event);
+        }
+        let last = last.unwrap();
+        assert_eq!(last.iteration, 1);
+        assert!(matches!(last.kind, ReActEventKind::Error { .. }));
+        assert!(last.progress_message().starts_with("Failed:"));
+    }
+
+    #[tokio::test]
+    async fn test_react_agent_accounts_usage_and_cost() {
+        use crate::rig_integration::{LlmConfig, RigLlmClient};
+
+        let client = RigLlmClient::new(LlmConfig::openai("gpt-4"));
+        let mut agent = ReActAgent::new(ReActConfig::default())
+            .with_llm_client(Arc::new(client))
+            .with_pricing(PricingConfig::default());
+
+        let response = agent.run("Hello").await.unwrap();
+        let usage = response.usage;
+        assert_eq!(usage.llm_calls, 1);
+        assert_eq!(usage.completion_tokens, 50);
+        assert!(usage.cost > common::Micros::ZERO);
+        assert_eq!(
+            response.token_usage.unwrap().total_tokens as u64,
+            usage.total_tokens
+        );
+    }
+
+    #[tokio::test]
+    async fn test_react_agent_without_client_records_no_usage() {
+        let mut agent = ReActAgent::new(ReActConfig::default());
+        let response = agent.run("Hello").await.unwrap();
+        assert!(response.usage.is_empty());
+    }
+
+    #[test]
+    fn test_scratchpad_trimmed_to_context_window() {
+        let config = ReActConfig::builder()
+            .context_window(1500)
+            .max_tokens(256)
+            .build();
+        let agent = ReActAgent::new(config);
+        let tools = agent.format_tool_descriptions();
+
+        let steps: Vec<String> = (0..50)
+            .map(|i| {
+                format!(
+                    "\nAction: search\nObservation: result {} {}",
+                    i,
+                    "data ".repeat(30)
+                )
+            })
+            .collect();
+        let scratchpad = agent.fit_scratchpad("query", &tools, "", &steps);
+        let prompt = agent.build_prompt("query", &tools, "", &scratchpad);
+
+        assert!(agent.budgeter.counter().count(&prompt) <= 1500 - 256);
+        assert!(scratchpad.contains("earlier step(s) omitted"));
+        assert!(scratchpad.contains("result 49"));
+    }
+
+    #[test]
+    fn test_context_trimmed_to_context_window() {
+        let config = ReActConfig::builder()
+            .context_window(1500)
+            .max_tokens(256)
+            .build();
+        let agent = ReActAgent::new(config);
+        let tools = agent.format_tool_descriptions();
+
+        let context: Vec<String> = (0..20)
+            .map(|i| format!("Document {}: {}", i, "text ".repeat(100)))
+            .collect();
+        let (context_str, kept) = agent.fit_context("query", &tools, Some(&context));
+
+        assert!(context_str.contains("Document 0"));
+        assert!(!context_str.contains("Document 19"));
+        assert!(kept > 0 && kept < context.len());
+    }
+
+    #[test]
+    fn test_critique_prompt_trimmed_to_context_window() {
+        use crate::replay::ScriptedClient;
+
+        let config = ReActConfig::builder()
+            .context_window(1500)
+            .max_tokens(256)
+            .build();
+        let client = ScriptedClient::new("gpt-4");
+        let agent = ReActAgent::new(config);
+
+        let context: Vec<String> = (0..20)
+            .map(|i| format!("Document {}: {}", i, "text ".repeat(100)))
+            .collect();
+        let steps: Vec<String> = (0..50)
+            .map(|i| format!("\nObservation: result {} {}", i, "data ".repeat(30)))
+            .collect();
+        let prompt = agent.fit_critique(
+            &client,
+            "Review the answer.",
+            "query",
+            Some(&context),
+            &steps,
+            "draft",
+        );
+
+        assert!(agent.budgeter.counter().count(&prompt) <= 1500 - 256);
+        assert!(prompt.contains("Document 0"));
+        assert!(prompt.contains("result 49"));
+        assert!(prompt.contains("Draft answer:\ndraft"));
+    }
+
+    #[tokio::test]
+    async fn test_react_agent_accepts_answer_when_critic_fails() {
+        use crate::replay::ScriptedClient;
+
+        let client =
+            Arc::new(ScriptedClient::new("gpt-4").with_responses(["Final Answer: first draft"]));
+        let critic = Arc::new(
+            ScriptedClient::new("gpt-4o-mini")
+                .with_error(crate::LlmError::ProviderError("critic down".to_string())),
+        );
+        let config = ReActConfig::builder()
+            .reflection(ReflectionConfig::default())
+            .build();
+        let mut agent = ReActAgent::new(config)
+            .with_llm_client(client)
+            .with_critic_client(critic.clone());
+
+        let response = agent.run("Question").await.unwrap();
+        assert_eq!(response.final_answer, "first draft");
+        assert_eq!(critic.requests().len(), 1);
+    }
+
     #[test]
     fn test_format_tool_descriptions_empty() {
         let agent = ReActAgent::new(ReActConfig::default());
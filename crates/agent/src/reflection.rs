@@ -0,0 +1,175 @@
+//! Self-critique of ReAct answers before they are returned
+//!
+//! With a [`ReflectionConfig`] set on `ReActConfig`, a final answer is first
+//! treated as a draft: a critic (the agent's own model, or a separate client
+//! set with `ReActAgent::with_critic_client`) checks it against the tool
+//! observations and retrieved context. If the critic finds unsupported
+//! claims, such as a price or policy no tool returned, its feedback goes
+//! back into the loop and the agent revises the answer, up to
+//! `max_revisions` times.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use agent::reflection::ReflectionConfig;
+//!
+//! let config = ReActConfig::builder()
+//!     .reflection(ReflectionConfig::default().with_max_revisions(1))
+//!     .build();
+//!
+//! let mut agent = ReActAgent::new(config)
+//!     .with_llm_client(client)
+//!     .with_critic_client(critic);
+//! ```
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// CONFIGURATION
+// ============================================================================
+
+/// Configuration of the reflection stage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReflectionConfig {
+    /// Maximum number of times a draft answer is sent back for revision
+    pub max_revisions: usize,
+
+    /// System prompt of the critic
+    pub preamble: String,
+}
+
+impl Default for ReflectionConfig {
+    fn default() -> Self {
+        Self {
+            max_revisions: 2,
+            preamble: DEFAULT_CRITIC_PREAMBLE.to_string(),
+        }
+    }
+}
+
+impl ReflectionConfig {
+    pub fn with_max_revisions(mut self, max: usize) -> Self {
+        self.max_revisions = max;
+        self
+    }
+
+    pub fn with_preamble(mut self, preamble: impl Into<String>) -> Self {
+        self.preamble = preamble.into();
+        self
+    }
+}
+
+// ============================================================================
+// CRITIQUE
+// ============================================================================
+
+/// The critic's verdict on a draft answer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Critique {
+    /// Whether the answer is fully supported and can be returned
+    pub approved: bool,
+
+    /// Claims not supported by the observations or context
+    #[serde(default)]
+    pub unsupported_claims: Vec<String>,
+
+    /// What to change in the answer
+    #[serde(default)]
+    pub feedback: String,
+}
+
+impl Critique {
+    /// Feedback text shown to the agent
+    pub fn summary(&self) -> String {
+        let mut summary = self.feedback.trim().to_string();
+        if !self.unsupported_claims.is_empty() {
+            if !summary.is_empty() {
+                summary.push('\n');
+            }
+            summary.push_str("Unsupported claims:");
+            for claim in &self.unsupported_claims {
+                summary.push_str(&format!("\n- {}", claim));
+            }
+        }
+        summary
+    }
+}
+
+// ============================================================================
+// PROMPTS
+// ============================================================================
+
+/// Default system prompt of the critic
+pub const DEFAULT_CRITIC_PREAMBLE: &str = r#"You are a careful reviewer checking an assistant's answer before it is sent to a customer.
+
+Approve the answer only if every factual claim in it (prices, stock, specifications, policies, dates, contact details) is supported by the tool observations or the retrieved context. General phrasing and politeness need no support.
+
+If a claim is unsupported or contradicts the evidence, do not approve. List each such claim and explain how to fix the answer, e.g. by looking the fact up with a tool or by removing it."#;
+
+/// Prompt asking the critic to review `answer`
+pub fn critique_prompt(
+    query: &str,
+    context: Option<&[String]>,
+    observations: &[String],
+    answer: &str,
+) -> String {
+    let context = match context {
+        Some(docs) if !docs.is_empty() => docs
+            .iter()
+            .enumerate()
+            .map(|(i, doc)| format!("[{}] {}", i + 1, doc))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => "(none)".to_string(),
+    };
+    let observations = if observations.is_empty() {
+        "(none)".to_string()
+    } else {
+        observations.concat().trim().to_string()
+    };
+
+    format!(
+        "Question:\n{}\n\nRetrieved context:\n{}\n\nReasoning and tool observations:\n{}\n\n\
+         Draft answer:\n{}\n\nReview the draft answer.",
+        query, context, observations, answer
+    )
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_critique_summary() {
+        let critique = Critique {
+            approved: false,
+            unsupported_claims: vec!["Price is 1,200 THB".to_string()],
+            feedback: "Look up the price first.".to_string(),
+        };
+
+        assert_eq!(
+            critique.summary(),
+            "Look up the price first.\nUnsupported claims:\n- Price is 1,200 THB"
+        );
+    }
+
+    #[test]
+    fn test_critique_prompt_includes_evidence() {
+        let prompt = critique_prompt(
+            "How much is the pump?",
+            Some(&["Pump X costs 990 THB".to_string()]),
+            &["\nAction: product_search\nObservation: Pump X".to_string()],
+            "It costs 1,200 THB",
+        );
+
+        assert!(prompt.contains("[1] Pump X costs 990 THB"));
+        assert!(prompt.contains("Observation: Pump X"));
+        assert!(prompt.contains("Draft answer:\nIt costs 1,200 THB"));
+    }
+}
@@ -0,0 +1,646 @@
+//! Record/replay and scripted LLM clients for deterministic tests
+//!
+//! - [`ReplayClient`] records real request/response pairs to a cassette file
+//!   and replays them later. Requests are matched by a hash of the
+//!   normalized prompt, so whitespace-only changes don't invalidate cassettes.
+//! - [`ScriptedClient`] returns a queued sequence of responses, regardless of
+//!   the prompt, and keeps the requests it received for assertions.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use agent::replay::{ReplayClient, ReplayMode, ScriptedClient};
+//! use agent::rig_integration::openai_client;
+//!
+//! // Record once against the real provider (LLM_CASSETTE_MODE=record),
+//! // replay offline afterwards
+//! let client = ReplayClient::open(
+//!     "tests/cassettes/capital.json",
+//!     ReplayMode::from_env(),
+//!     Some(openai_client("gpt-4")),
+//! )?;
+//!
+//! // Drive a ReAct loop step by step
+//! let client = ScriptedClient::new("gpt-4")
+//!     .with_response("Thought: I should search\nAction: search\nAction Input: {\"query\": \"rust\"}")
+//!     .with_response("Final Answer: Rust is a systems language");
+//! let mut agent = ReActAgent::new(config).with_llm_client(Arc::new(client));
+//! ```
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+use tracing::{debug, info};
+
+use crate::rig_integration::{
+    ChatMessage, CompletionClient, FinishReason, LlmError, LlmResponse, Provider,
+};
+
+/// Environment variable read by [`ReplayMode::from_env`]
+pub const CASSETTE_MODE_ENV: &str = "LLM_CASSETTE_MODE";
+
+// ============================================================================
+// ERRORS
+// ============================================================================
+
+/// Errors reading or writing cassette files
+#[derive(Error, Debug)]
+pub enum CassetteError {
+    #[error("Cassette I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid cassette: {0}")]
+    Parse(#[from] serde_json::Error),
+
+    #[error("Recording requires an inner client")]
+    MissingClient,
+}
+
+// ============================================================================
+// REQUESTS
+// ============================================================================
+
+/// A completion request as seen by a test client
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RecordedRequest {
+    Complete {
+        prompt: String,
+    },
+    CompleteWithSystem {
+        system: String,
+        prompt: String,
+    },
+    Chat {
+        messages: Vec<ChatMessage>,
+    },
+    CompleteJson {
+        system: String,
+        prompt: String,
+        schema: serde_json::Value,
+    },
+}
+
+impl RecordedRequest {
+    /// The user prompt (last message for chats)
+    pub fn prompt(&self) -> &str {
+        match self {
+            RecordedRequest::Complete { prompt }
+            | RecordedRequest::CompleteWithSystem { prompt, .. }
+            | RecordedRequest::CompleteJson { prompt, .. } => prompt,
+            RecordedRequest::Chat { messages } => messages
+                .last()
+                .map(|m| m.content.as_str())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Hash of the normalized request, used to match cassette entries
+    pub fn key(&self) -> String {
+        let mut hasher = Sha256::new();
+        let mut feed = |part: &str| {
+            hasher.update(normalize_prompt(part).as_bytes());
+            hasher.update([0u8]);
+        };
+
+        match self {
+            RecordedRequest::Complete { prompt } => {
+                feed("complete");
+                feed(prompt);
+            }
+            RecordedRequest::CompleteWithSystem { system, prompt } => {
+                feed("complete_with_system");
+                feed(system);
+                feed(prompt);
+            }
+            RecordedRequest::Chat { messages } => {
+                feed("chat");
+                for message in messages {
+                    feed(&serde_json::to_string(&message.role).unwrap_or_default());
+                    feed(&message.content);
+                }
+            }
+            RecordedRequest::CompleteJson {
+                system,
+                prompt,
+                schema,
+            } => {
+                feed("complete_json");
+                feed(system);
+                feed(prompt);
+                feed(&schema.to_string());
+            }
+        }
+
+        format!("{:x}", hasher.finalize())
+    }
+}
+
+/// Normalize a prompt for matching: trim and collapse runs of whitespace
+pub fn normalize_prompt(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// ============================================================================
+// CASSETTES
+// ============================================================================
+
+/// One recorded request/response pair
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Interaction {
+    /// Hash of the normalized request
+    pub key: String,
+    pub request: RecordedRequest,
+    pub response: LlmResponse,
+}
+
+/// Recorded interactions, stored as pretty-printed JSON
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Cassette {
+    /// Model of the client that was recorded
+    pub model: String,
+
+    /// Provider of the client that was recorded
+    pub provider: Provider,
+
+    pub interactions: Vec<Interaction>,
+}
+
+impl Cassette {
+    pub fn new(model: impl Into<String>, provider: Provider) -> Self {
+        Self {
+            model: model.into(),
+            provider,
+            interactions: Vec::new(),
+        }
+    }
+
+    /// Load a cassette from a JSON file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, CassetteError> {
+        let content = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Write the cassette to a JSON file, creating parent directories
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), CassetteError> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// The `nth` response recorded for a request key
+    fn find(&self, key: &str, nth: usize) -> Option<&LlmResponse> {
+        let mut matches = self.interactions.iter().filter(|i| i.key == key);
+        let count = matches.clone().count();
+        // Repeat the last recording once a key's entries are used up
+        matches
+            .nth(nth.min(count.checked_sub(1)?))
+            .map(|i| &i.response)
+    }
+}
+
+// ============================================================================
+// REPLAY CLIENT
+// ============================================================================
+
+/// How a [`ReplayClient`] handles requests
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplayMode {
+    /// Call the inner client and overwrite the cassette
+    Record,
+    /// Only answer from the cassette; unknown requests fail
+    Replay,
+    /// Answer from the cassette, recording requests it doesn't contain
+    Auto,
+}
+
+impl ReplayMode {
+    /// Read the mode from `LLM_CASSETTE_MODE` (`record`, `replay`, `auto`),
+    /// defaulting to `Replay` so tests never hit the network by accident
+    pub fn from_env() -> Self {
+        match std::env::var(CASSETTE_MODE_ENV)
+            .unwrap_or_default()
+            .to_lowercase()
+            .as_str()
+        {
+            "record" => ReplayMode::Record,
+            "auto" => ReplayMode::Auto,
+            _ => ReplayMode::Replay,
+        }
+    }
+}
+
+/// [`CompletionClient`] that records to and replays from a cassette file
+pub struct ReplayClient {
+    mode: ReplayMode,
+    path: PathBuf,
+    inner: Option<Arc<dyn CompletionClient>>,
+    model: String,
+    provider: Provider,
+    cassette: Mutex<Cassette>,
+    /// How many times each key has been replayed
+    cursors: Mutex<HashMap<String, usize>>,
+}
+
+impl ReplayClient {
+    /// Open a cassette in the given mode
+    ///
+    /// `Record` and `Auto` require `inner`. `Record` starts an empty
+    /// cassette; the others load the file (`Auto` tolerates it missing).
+    pub fn open<P: Into<PathBuf>>(
+        path: P,
+        mode: ReplayMode,
+        inner: Option<Arc<dyn CompletionClient>>,
+    ) -> Result<Self, CassetteError> {
+        let path = path.into();
+
+        let cassette = match (mode, &inner) {
+            (ReplayMode::Record, Some(client)) => Cassette::new(client.model(), client.provider()),
+            (ReplayMode::Auto, Some(client)) if !path.exists() => {
+                Cassette::new(client.model(), client.provider())
+            }
+            (ReplayMode::Record | ReplayMode::Auto, None) => {
+                return Err(CassetteError::MissingClient)
+            }
+            _ => Cassette::load(&path)?,
+        };
+
+        info!(
+            path = %path.display(),
+            mode = ?mode,
+            interactions = cassette.interactions.len(),
+            "Opened LLM cassette"
+        );
+
+        Ok(Self {
+            mode,
+            path,
+            inner,
+            model: cassette.model.clone(),
+            provider: cassette.provider,
+            cassette: Mutex::new(cassette),
+            cursors: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Record calls to `inner` into a new cassette at `path`
+    pub fn record<P: Into<PathBuf>>(
+        path: P,
+        inner: Arc<dyn CompletionClient>,
+    ) -> Result<Self, CassetteError> {
+        Self::open(path, ReplayMode::Record, Some(inner))
+    }
+
+    /// Replay the cassette at `path` without any network access
+    pub fn replay<P: Into<PathBuf>>(path: P) -> Result<Self, CassetteError> {
+        Self::open(path, ReplayMode::Replay, None)
+    }
+
+    /// Get the mode
+    pub fn mode(&self) -> ReplayMode {
+        self.mode
+    }
+
+    /// Snapshot of the current cassette
+    pub fn cassette(&self) -> Cassette {
+        self.cassette.lock().unwrap().clone()
+    }
+
+    /// Answer a request from the cassette, or record it from the inner client
+    async fn dispatch<F, Fut>(
+        &self,
+        request: RecordedRequest,
+        call: F,
+    ) -> Result<LlmResponse, LlmError>
+    where
+        F: FnOnce(Arc<dyn CompletionClient>) -> Fut + Send,
+        Fut: std::future::Future<Output = Result<LlmResponse, LlmError>> + Send,
+    {
+        let key = request.key();
+
+        if self.mode != ReplayMode::Record {
+            let nth = {
+                let mut cursors = self.cursors.lock().unwrap();
+                let cursor = cursors.entry(key.clone()).or_default();
+                *cursor += 1;
+                *cursor - 1
+            };
+            if let Some(response) = self.cassette.lock().unwrap().find(&key, nth) {
+                debug!(key = %key, "Replaying recorded LLM response");
+                return Ok(response.clone());
+            }
+        }
+
+        let Some(inner) = self
+            .inner
+            .clone()
+            .filter(|_| self.mode != ReplayMode::Replay)
+        else {
+            return Err(LlmError::ProviderError(format!(
+                "No recorded response in {} for request {} (prompt: {:.80})",
+                self.path.display(),
+                key,
+                normalize_prompt(request.prompt())
+            )));
+        };
+
+        let response = call(inner).await?;
+
+        let mut cassette = self.cassette.lock().unwrap();
+        cassette.interactions.push(Interaction {
+            key: key.clone(),
+            request,
+            response: response.clone(),
+        });
+        cassette
+            .save(&self.path)
+            .map_err(|e| LlmError::ProviderError(e.to_string()))?;
+        debug!(key = %key, "Recorded LLM response");
+
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl CompletionClient for ReplayClient {
+    async fn complete(&self, prompt: &str) -> Result<LlmResponse, LlmError> {
+        let request = RecordedRequest::Complete {
+            prompt: prompt.to_string(),
+        };
+        self.dispatch(request, |c| async move { c.complete(prompt).await })
+            .await
+    }
+
+    async fn complete_with_system(
+        &self,
+        system: &str,
+        prompt: &str,
+    ) -> Result<LlmResponse, LlmError> {
+        let request = RecordedRequest::CompleteWithSystem {
+            system: system.to_string(),
+            prompt: prompt.to_string(),
+        };
+        self.dispatch(request, |c| async move {
+            c.complete_with_system(system, prompt).await
+        })
+        .await
+    }
+
+    async fn chat(&self, messages: Vec<ChatMessage>) -> Result<LlmResponse, LlmError> {
+        let request = RecordedRequest::Chat {
+            messages: messages.clone(),
+        };
+        self.dispatch(request, |c| async move { c.chat(messages).await })
+            .await
+    }
+
+    async fn complete_json(
+        &self,
+        system: &str,
+        prompt: &str,
+        schema: &serde_json::Value,
+    ) -> Result<LlmResponse, LlmError> {
+        let request = RecordedRequest::CompleteJson {
+            system: system.to_string(),
+            prompt: prompt.to_string(),
+            schema: schema.clone(),
+        };
+        self.dispatch(request, |c| async move {
+            c.complete_json(system, prompt, schema).await
+        })
+        .await
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn provider(&self) -> Provider {
+        self.provider
+    }
+}
+
+// ============================================================================
+// SCRIPTED CLIENT
+// ============================================================================
+
+/// [`CompletionClient`] returning a queued sequence of responses
+///
+/// Each call pops the next response, whatever the prompt; once the queue is
+/// empty calls fail with [`LlmError::ProviderError`]. Responses carry no
+/// token usage, so accounting falls back to local token counting.
+pub struct ScriptedClient {
+    model: String,
+    responses: Mutex<VecDeque<Result<LlmResponse, LlmError>>>,
+    requests: Mutex<Vec<RecordedRequest>>,
+}
+
+impl ScriptedClient {
+    pub fn new(model: impl Into<String>) -> Self {
+        Self {
+            model: model.into(),
+            responses: Mutex::new(VecDeque::new()),
+            requests: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Queue a response with the given content
+    pub fn with_response(self, content: impl Into<String>) -> Self {
+        self.push_response(content);
+        self
+    }
+
+    /// Queue several responses
+    pub fn with_responses<I, S>(self, contents: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        for content in contents {
+            self.push_response(content);
+        }
+        self
+    }
+
+    /// Queue an error
+    pub fn with_error(self, error: LlmError) -> Self {
+        self.responses.lock().unwrap().push_back(Err(error));
+        self
+    }
+
+    /// Queue a response with the given content on a shared client
+    pub fn push_response(&self, content: impl Into<String>) {
+        let response = LlmResponse {
+            content: content.into(),
+            model: self.model.clone(),
+            usage: None,
+            finish_reason: Some(FinishReason::Stop),
+        };
+        self.responses.lock().unwrap().push_back(Ok(response));
+    }
+
+    /// Number of queued responses not yet consumed
+    pub fn remaining(&self) -> usize {
+        self.responses.lock().unwrap().len()
+    }
+
+    /// Requests received so far, in order
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    fn next(&self, request: RecordedRequest) -> Result<LlmResponse, LlmError> {
+        self.requests.lock().unwrap().push(request);
+        self.responses
+            .lock()
+            .unwrap()
+            .pop_front()
+            .unwrap_or_else(|| {
+                Err(LlmError::ProviderError(
+                    "Scripted client has no responses left".to_string(),
+                ))
+            })
+    }
+}
+
+#[async_trait]
+impl CompletionClient for ScriptedClient {
+    async fn complete(&self, prompt: &str) -> Result<LlmResponse, LlmError> {
+        self.next(RecordedRequest::Complete {
+            prompt: prompt.to_string(),
+        })
+    }
+
+    async fn complete_with_system(
+        &self,
+        system: &str,
+        prompt: &str,
+    ) -> Result<LlmResponse, LlmError> {
+        self.next(RecordedRequest::CompleteWithSystem {
+            system: system.to_string(),
+            prompt: prompt.to_string(),
+        })
+    }
+
+    async fn chat(&self, messages: Vec<ChatMessage>) -> Result<LlmResponse, LlmError> {
+        self.next(RecordedRequest::Chat { messages })
+    }
+
+    async fn complete_json(
+        &self,
+        system: &str,
+        prompt: &str,
+        schema: &serde_json::Value,
+    ) -> Result<LlmResponse, LlmError> {
+        self.next(RecordedRequest::CompleteJson {
+            system: system.to_string(),
+            prompt: prompt.to_string(),
+            schema: schema.clone(),
+        })
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn provider(&self) -> Provider {
+        Provider::Custom
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cassette_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("cassette-{}-{}.json", name, uuid::Uuid::new_v4()))
+    }
+
+    #[test]
+    fn test_key_ignores_whitespace() {
+        let a = RecordedRequest::Complete {
+            prompt: "What is\n  Rust? ".to_string(),
+        };
+        let b = RecordedRequest::Complete {
+            prompt: "What is Rust?".to_string(),
+        };
+        let c = RecordedRequest::CompleteWithSystem {
+            system: String::new(),
+            prompt: "What is Rust?".to_string(),
+        };
+        assert_eq!(a.key(), b.key());
+        assert_ne!(b.key(), c.key());
+    }
+
+    #[tokio::test]
+    async fn test_record_then_replay() {
+        let path = cassette_path("roundtrip");
+        let inner = Arc::new(ScriptedClient::new("gpt-4").with_responses(["Paris", "Berlin"]));
+
+        let recorder = ReplayClient::record(&path, inner.clone()).unwrap();
+        recorder.complete("Capital of France?").await.unwrap();
+        recorder
+            .complete_with_system("Be brief", "Capital of Germany?")
+            .await
+            .unwrap();
+        assert_eq!(inner.remaining(), 0);
+
+        let player = ReplayClient::replay(&path).unwrap();
+        assert_eq!(player.model(), "gpt-4");
+        assert_eq!(player.provider(), Provider::Custom);
+        let germany = player
+            .complete_with_system("Be brief", "Capital of  Germany?\n")
+            .await
+            .unwrap();
+        assert_eq!(germany.content, "Berlin");
+        assert_eq!(
+            player.complete("Capital of France?").await.unwrap().content,
+            "Paris"
+        );
+        assert!(player.complete("Capital of Italy?").await.is_err());
+
+        std::fs::remove_file(path).ok();
+    }
+
+    #[tokio::test]
+    async fn test_auto_records_misses() {
+        let path = cassette_path("auto");
+        let inner = Arc::new(ScriptedClient::new("gpt-4").with_response("first"));
+
+        let client = ReplayClient::open(&path, ReplayMode::Auto, Some(inner.clone())).unwrap();
+        assert_eq!(client.complete("hi").await.unwrap().content, "first");
+        // Replayed from the cassette, the scripted queue is not consulted again
+        assert_eq!(client.complete("hi").await.unwrap().content, "first");
+        assert_eq!(client.cassette().interactions.len(), 1);
+
+        std::fs::remove_file(path).ok();
+    }
+
+    #[tokio::test]
+    async fn test_scripted_client_sequence() {
+        let client = ScriptedClient::new("gpt-4")
+            .with_response("one")
+            .with_error(LlmError::Timeout(std::time::Duration::from_secs(5)));
+
+        assert_eq!(client.complete("a").await.unwrap().content, "one");
+        assert!(matches!(
+            client.complete("b").await,
+            Err(LlmError::Timeout(limit)) if limit.as_secs() == 5
+        ));
+        assert!(client.complete("c").await.is_err());
+        assert_eq!(client.requests().len(), 3);
+        assert_eq!(client.requests()[1].prompt(), "b");
+    }
+}
@@ -0,0 +1,678 @@
+//! Resilience layer for LLM calls
+//!
+//! Wraps any [`CompletionClient`] with retries, backoff, rate-limit handling
+//! and a circuit breaker so transient provider failures don't surface as
+//! failed chats.
+//!
+//! ```text
+//! request ─▶ circuit breaker ─▶ provider semaphore ─▶ inner client
+//!                 ▲                                        │
+//!                 │        transient error (429/timeout)   │
+//!                 └──── backoff (jittered / Retry-After) ◀─┘
+//! ```
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use agent::resilience::{ResilienceConfig, ResilientClient};
+//! use agent::rig_integration::{LlmConfig, RigLlmClient};
+//!
+//! let config = LlmConfig::openai("gpt-4").with_resilience(ResilienceConfig {
+//!     max_retries: 5,
+//!     ..Default::default()
+//! });
+//!
+//! let inner = Arc::new(RigLlmClient::new(config.clone()));
+//! let client = ResilientClient::new(inner, config.resilience);
+//! let response = client.complete("What is Rust?").await?;
+//! ```
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+use tracing::{debug, warn};
+
+use crate::rig_integration::{ChatMessage, CompletionClient, LlmError, LlmResponse, Provider};
+
+// ============================================================================
+// CONFIGURATION
+// ============================================================================
+
+/// Retry, backoff, concurrency and circuit breaker settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ResilienceConfig {
+    /// Maximum number of retries after the first attempt (0 disables retries)
+    pub max_retries: u32,
+
+    /// Delay before the first retry in milliseconds
+    pub initial_backoff_ms: u64,
+
+    /// Upper bound for the backoff, including Retry-After waits, in milliseconds
+    pub max_backoff_ms: u64,
+
+    /// Multiplier applied to the backoff after each attempt
+    pub backoff_multiplier: f64,
+
+    /// Randomize backoff to avoid thundering herds
+    pub jitter: bool,
+
+    /// Wait for the provider's Retry-After hint when present
+    pub respect_retry_after: bool,
+
+    /// Maximum in-flight requests per provider (None = unlimited)
+    pub max_concurrent_requests: Option<usize>,
+
+    /// Consecutive transient failures before the circuit opens (0 disables)
+    pub circuit_breaker_threshold: u32,
+
+    /// How long the circuit stays open before a trial request is allowed
+    pub circuit_breaker_cooldown_secs: u64,
+}
+
+impl Default for ResilienceConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff_ms: 500,
+            max_backoff_ms: 30_000,
+            backoff_multiplier: 2.0,
+            jitter: true,
+            respect_retry_after: true,
+            max_concurrent_requests: None,
+            circuit_breaker_threshold: 5,
+            circuit_breaker_cooldown_secs: 30,
+        }
+    }
+}
+
+impl ResilienceConfig {
+    /// Configuration that never retries and never trips the breaker
+    pub fn disabled() -> Self {
+        Self {
+            max_retries: 0,
+            max_concurrent_requests: None,
+            circuit_breaker_threshold: 0,
+            ..Default::default()
+        }
+    }
+
+    /// Compute the delay before retry number `attempt` (0-based)
+    pub fn backoff_delay(&self, attempt: u32, error: &LlmError) -> Duration {
+        if self.respect_retry_after {
+            if let Some(retry_after) = error.retry_after() {
+                return retry_after.min(Duration::from_millis(self.max_backoff_ms));
+            }
+        }
+
+        let base = self.initial_backoff_ms as f64 * self.backoff_multiplier.powi(attempt as i32);
+        let capped = base.min(self.max_backoff_ms as f64);
+        let millis = if self.jitter {
+            // "Equal jitter": keep at least half of the computed delay
+            capped * (0.5 + 0.5 * jitter_fraction())
+        } else {
+            capped
+        };
+
+        Duration::from_millis(millis as u64)
+    }
+}
+
+/// Pseudo-random value in [0, 1) without pulling in a RNG dependency
+fn jitter_fraction() -> f64 {
+    use std::hash::{BuildHasher, Hasher};
+
+    let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+    hasher.write_u128(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or_default(),
+    );
+    hasher.finish() as f64 / u64::MAX as f64
+}
+
+// ============================================================================
+// PROVIDER CONCURRENCY LIMITS
+// ============================================================================
+
+/// A provider's semaphore and the limit it was created with
+type ProviderLimit = (usize, Arc<Semaphore>);
+
+/// Shared semaphores, one per provider
+static PROVIDER_LIMITS: OnceLock<Mutex<HashMap<Provider, ProviderLimit>>> = OnceLock::new();
+
+/// Get (or create) the concurrency limiter for a provider
+///
+/// The limit is fixed by the first client that registers the provider, so all
+/// clients talking to the same provider share one budget. A client asking
+/// for a different limit gets the existing one, with a warning.
+pub fn provider_semaphore(provider: Provider, limit: usize) -> Arc<Semaphore> {
+    let limit = limit.max(1);
+    let limits = PROVIDER_LIMITS.get_or_init(|| Mutex::new(HashMap::new()));
+    let mut limits = limits.lock().unwrap_or_else(|e| e.into_inner());
+    let (existing, semaphore) = limits
+        .entry(provider)
+        .or_insert_with(|| (limit, Arc::new(Semaphore::new(limit))));
+    if *existing != limit {
+        warn!(
+            provider = %provider,
+            requested = limit,
+            limit = *existing,
+            "Provider concurrency limit already set; keeping the first one"
+        );
+    }
+    semaphore.clone()
+}
+
+// ============================================================================
+// CIRCUIT BREAKER
+// ============================================================================
+
+/// Observable circuit breaker state
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    /// Requests flow normally
+    Closed,
+    /// Requests are rejected without reaching the provider
+    Open,
+    /// Cooldown elapsed; a single trial request is let through
+    HalfOpen,
+}
+
+#[derive(Debug, Default)]
+struct BreakerState {
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+    /// While half-open, other requests are rejected until the trial finishes
+    /// or this deadline passes (in case the trial is dropped)
+    trial_until: Option<Instant>,
+}
+
+/// Consecutive-failure circuit breaker
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    threshold: u32,
+    cooldown: Duration,
+    state: Mutex<BreakerState>,
+}
+
+impl CircuitBreaker {
+    /// Create a breaker that opens after `threshold` consecutive failures
+    pub fn new(threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            threshold,
+            cooldown,
+            state: Mutex::new(BreakerState::default()),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BreakerState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Current state of the breaker
+    pub fn state(&self) -> CircuitState {
+        match self.lock().open_until {
+            None => CircuitState::Closed,
+            Some(until) if Instant::now() < until => CircuitState::Open,
+            Some(_) => CircuitState::HalfOpen,
+        }
+    }
+
+    /// Whether a request may be attempted right now
+    ///
+    /// Once half-open, only the first caller is admitted as the trial request.
+    pub fn allow_request(&self) -> bool {
+        if self.threshold == 0 {
+            return true;
+        }
+        let mut state = self.lock();
+        let now = Instant::now();
+        match state.open_until {
+            None => true,
+            Some(until) if now < until => false,
+            Some(_) => {
+                if state.trial_until.is_some_and(|trial| now < trial) {
+                    return false;
+                }
+                state.trial_until = Some(now + self.cooldown);
+                true
+            }
+        }
+    }
+
+    /// Record a successful call, closing the circuit
+    pub fn record_success(&self) {
+        let mut state = self.lock();
+        state.consecutive_failures = 0;
+        state.open_until = None;
+        state.trial_until = None;
+    }
+
+    /// Record a failed call, opening the circuit once the threshold is hit
+    pub fn record_failure(&self) {
+        if self.threshold == 0 {
+            return;
+        }
+        let mut state = self.lock();
+        state.consecutive_failures += 1;
+        if state.consecutive_failures >= self.threshold {
+            state.open_until = Some(Instant::now() + self.cooldown);
+            state.trial_until = None;
+        }
+    }
+}
+
+// ============================================================================
+// RESILIENT CLIENT
+// ============================================================================
+
+/// [`CompletionClient`] wrapper adding retries, rate limiting and a circuit breaker
+pub struct ResilientClient {
+    inner: Arc<dyn CompletionClient>,
+    config: ResilienceConfig,
+    limiter: Option<Arc<Semaphore>>,
+    breaker: CircuitBreaker,
+    timeout: Option<Duration>,
+}
+
+impl ResilientClient {
+    /// Wrap a client with the given resilience configuration
+    pub fn new(inner: Arc<dyn CompletionClient>, config: ResilienceConfig) -> Self {
+        let limiter = config
+            .max_concurrent_requests
+            .map(|limit| provider_semaphore(inner.provider(), limit));
+        let breaker = CircuitBreaker::new(
+            config.circuit_breaker_threshold,
+            Duration::from_secs(config.circuit_breaker_cooldown_secs),
+        );
+
+        Self {
+            inner,
+            config,
+            limiter,
+            breaker,
+            timeout: None,
+        }
+    }
+
+    /// Fail each attempt with [`LlmError::Timeout`] after `secs` seconds
+    pub fn with_timeout_secs(self, secs: u64) -> Self {
+        self.with_timeout(Duration::from_secs(secs))
+    }
+
+    /// Fail each attempt with [`LlmError::Timeout`] after `timeout`
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = (!timeout.is_zero()).then_some(timeout);
+        self
+    }
+
+    /// Current circuit breaker state
+    pub fn circuit_state(&self) -> CircuitState {
+        self.breaker.state()
+    }
+
+    /// Resilience configuration in use
+    pub fn config(&self) -> &ResilienceConfig {
+        &self.config
+    }
+
+    /// Run `op` with retries, concurrency limiting and circuit breaking
+    async fn execute<F, Fut>(&self, op: F) -> Result<LlmResponse, LlmError>
+    where
+        F: Fn() -> Fut + Send + Sync,
+        Fut: Future<Output = Result<LlmResponse, LlmError>> + Send,
+    {
+        let mut attempt: u32 = 0;
+
+        loop {
+            if !self.breaker.allow_request() {
+                return Err(LlmError::CircuitOpen(self.inner.provider().to_string()));
+            }
+
+            let result = {
+                let _permit = match &self.limiter {
+                    Some(semaphore) => Some(semaphore.acquire().await.map_err(|e| {
+                        LlmError::ProviderError(format!("Concurrency limiter closed: {}", e))
+                    })?),
+                    None => None,
+                };
+
+                match self.timeout {
+                    Some(timeout) => tokio::time::timeout(timeout, op())
+                        .await
+                        .unwrap_or(Err(LlmError::Timeout(timeout))),
+                    None => op().await,
+                }
+            };
+
+            let error = match result {
+                Ok(response) => {
+                    self.breaker.record_success();
+                    return Ok(response);
+                }
+                Err(e) => e,
+            };
+
+            if !error.is_transient() {
+                return Err(error);
+            }
+
+            self.breaker.record_failure();
+
+            if attempt >= self.config.max_retries {
+                warn!(
+                    provider = %self.inner.provider(),
+                    model = %self.inner.model(),
+                    attempts = attempt + 1,
+                    error = %error,
+                    "LLM call failed after retries"
+                );
+                return Err(error);
+            }
+
+            let delay = self.config.backoff_delay(attempt, &error);
+            debug!(
+                provider = %self.inner.provider(),
+                attempt = attempt + 1,
+                delay_ms = delay.as_millis() as u64,
+                error = %error,
+                "Retrying LLM call"
+            );
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+}
+
+#[async_trait]
+impl CompletionClient for ResilientClient {
+    async fn complete(&self, prompt: &str) -> Result<LlmResponse, LlmError> {
+        self.execute(|| self.inner.complete(prompt)).await
+    }
+
+    async fn complete_with_system(
+        &self,
+        system: &str,
+        prompt: &str,
+    ) -> Result<LlmResponse, LlmError> {
+        self.execute(|| self.inner.complete_with_system(system, prompt))
+            .await
+    }
+
+    async fn chat(&self, messages: Vec<ChatMessage>) -> Result<LlmResponse, LlmError> {
+        self.execute(|| self.inner.chat(messages.clone())).await
+    }
+
+    async fn complete_json(
+        &self,
+        system: &str,
+        prompt: &str,
+        schema: &serde_json::Value,
+    ) -> Result<LlmResponse, LlmError> {
+        self.execute(|| self.inner.complete_json(system, prompt, schema))
+            .await
+    }
+
+    fn model(&self) -> &str {
+        self.inner.model()
+    }
+
+    fn provider(&self) -> Provider {
+        self.inner.provider()
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Client that fails with a fixed error for the first `failures` calls
+    struct FlakyClient {
+        failures: usize,
+        error: fn() -> LlmError,
+        calls: AtomicUsize,
+    }
+
+    impl FlakyClient {
+        fn new(failures: usize, error: fn() -> LlmError) -> Self {
+            Self {
+                failures,
+                error,
+                calls: AtomicUsize::new(0),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl CompletionClient for FlakyClient {
+        async fn complete(&self, _prompt: &str) -> Result<LlmResponse, LlmError> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst);
+            if call < self.failures {
+                return Err((self.error)());
+            }
+            Ok(LlmResponse {
+                content: "ok".to_string(),
+                model: "flaky".to_string(),
+                usage: None,
+                finish_reason: None,
+            })
+        }
+
+        async fn complete_with_system(
+            &self,
+            _system: &str,
+            prompt: &str,
+        ) -> Result<LlmResponse, LlmError> {
+            self.complete(prompt).await
+        }
+
+        async fn chat(&self, _messages: Vec<ChatMessage>) -> Result<LlmResponse, LlmError> {
+            self.complete("").await
+        }
+
+        fn model(&self) -> &str {
+            "flaky"
+        }
+
+        fn provider(&self) -> Provider {
+            Provider::Custom
+        }
+    }
+
+    fn fast_config() -> ResilienceConfig {
+        ResilienceConfig {
+            initial_backoff_ms: 1,
+            max_backoff_ms: 5,
+            jitter: false,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retries_transient_errors() {
+        let inner = Arc::new(FlakyClient::new(2, || LlmError::RateLimited("429".into())));
+        let client = ResilientClient::new(inner.clone(), fast_config());
+
+        let response = client.complete("hello").await.unwrap();
+        assert_eq!(response.content, "ok");
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 3);
+        assert_eq!(client.circuit_state(), CircuitState::Closed);
+    }
+
+    #[tokio::test]
+    async fn test_does_not_retry_permanent_errors() {
+        let inner = Arc::new(FlakyClient::new(1, || {
+            LlmError::InvalidApiKey("openai".into())
+        }));
+        let client = ResilientClient::new(inner.clone(), fast_config());
+
+        let err = client.complete("hello").await.unwrap_err();
+        assert!(matches!(err, LlmError::InvalidApiKey(_)));
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_retries() {
+        let inner = Arc::new(FlakyClient::new(10, || {
+            LlmError::Timeout(Duration::from_secs(1))
+        }));
+        let config = ResilienceConfig {
+            max_retries: 2,
+            circuit_breaker_threshold: 0,
+            ..fast_config()
+        };
+        let client = ResilientClient::new(inner.clone(), config);
+
+        let err = client.complete("hello").await.unwrap_err();
+        assert!(matches!(err, LlmError::Timeout(_)));
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_timeout_keeps_sub_second_precision() {
+        let inner = Arc::new(FlakyClient::new(0, || LlmError::Timeout(Duration::ZERO)));
+        let config = ResilienceConfig {
+            max_retries: 0,
+            ..fast_config()
+        };
+        let client = ResilientClient::new(inner, config).with_timeout(Duration::from_millis(20));
+
+        let err = client
+            .execute(|| async {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                Err(LlmError::NetworkError("too late".into()))
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(err, LlmError::Timeout(limit) if limit == Duration::from_millis(20)));
+        assert_eq!(err.to_string(), "Timeout after 20ms");
+    }
+
+    #[tokio::test]
+    async fn test_circuit_opens_after_threshold() {
+        let inner = Arc::new(FlakyClient::new(10, || {
+            LlmError::NetworkError("reset".into())
+        }));
+        let config = ResilienceConfig {
+            max_retries: 0,
+            circuit_breaker_threshold: 2,
+            circuit_breaker_cooldown_secs: 60,
+            ..fast_config()
+        };
+        let client = ResilientClient::new(inner.clone(), config);
+
+        assert!(client.complete("a").await.is_err());
+        assert!(client.complete("b").await.is_err());
+        assert_eq!(client.circuit_state(), CircuitState::Open);
+
+        let err = client.complete("c").await.unwrap_err();
+        assert!(matches!(err, LlmError::CircuitOpen(_)));
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_circuit_half_open_after_cooldown() {
+        let breaker = CircuitBreaker::new(1, Duration::ZERO);
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        assert!(breaker.allow_request());
+
+        breaker.record_success();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_circuit_half_open_allows_single_trial() {
+        let breaker = CircuitBreaker::new(1, Duration::from_millis(20));
+        breaker.record_failure();
+        assert!(!breaker.allow_request());
+
+        std::thread::sleep(Duration::from_millis(25));
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        assert!(breaker.allow_request());
+        assert!(!breaker.allow_request());
+
+        // A failed trial reopens the circuit
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(!breaker.allow_request());
+    }
+
+    #[test]
+    fn test_backoff_exponential_and_capped() {
+        let config = ResilienceConfig {
+            initial_backoff_ms: 100,
+            max_backoff_ms: 350,
+            backoff_multiplier: 2.0,
+            jitter: false,
+            ..Default::default()
+        };
+        let err = LlmError::Timeout(Duration::from_secs(1));
+
+        assert_eq!(config.backoff_delay(0, &err), Duration::from_millis(100));
+        assert_eq!(config.backoff_delay(1, &err), Duration::from_millis(200));
+        assert_eq!(config.backoff_delay(2, &err), Duration::from_millis(350));
+    }
+
+    #[test]
+    fn test_backoff_jitter_within_bounds() {
+        let config = ResilienceConfig {
+            initial_backoff_ms: 1000,
+            jitter: true,
+            ..Default::default()
+        };
+        let delay = config.backoff_delay(0, &LlmError::Timeout(Duration::from_secs(1)));
+        assert!(delay >= Duration::from_millis(500));
+        assert!(delay <= Duration::from_millis(1000));
+    }
+
+    #[test]
+    fn test_backoff_honors_retry_after() {
+        let config = ResilienceConfig {
+            jitter: false,
+            ..Default::default()
+        };
+        let err = LlmError::RateLimitedRetryAfter {
+            message: "slow down".into(),
+            retry_after_secs: 12,
+        };
+        assert_eq!(config.backoff_delay(0, &err), Duration::from_secs(12));
+
+        // Retry-After never exceeds the backoff cap
+        assert_eq!(fast_config().backoff_delay(0, &err), Duration::from_millis(5));
+
+        let ignoring = ResilienceConfig {
+            respect_retry_after: false,
+            ..fast_config()
+        };
+        assert_eq!(ignoring.backoff_delay(0, &err), Duration::from_millis(1));
+    }
+
+    #[test]
+    fn test_provider_semaphore_shared() {
+        let a = provider_semaphore(Provider::Cohere, 3);
+        let b = provider_semaphore(Provider::Cohere, 10);
+        assert!(Arc::ptr_eq(&a, &b));
+        assert_eq!(b.available_permits(), 3);
+    }
+
+    #[test]
+    fn test_resilience_config_serde_defaults() {
+        let config: ResilienceConfig = serde_json::from_str(r#"{"max_retries": 1}"#).unwrap();
+        assert_eq!(config.max_retries, 1);
+        assert_eq!(config.circuit_breaker_threshold, 5);
+        assert!(config.respect_retry_after);
+    }
+}
@@ -30,6 +30,8 @@ use std::sync::Arc;
 use thiserror::Error;
 use tracing::debug;
 
+use crate::resilience::{ResilienceConfig, ResilientClient};
+use crate::structured::schema_instructions;
 use crate::tools::ToolDefinition;
 
 // ============================================================================
@@ -48,6 +50,12 @@ pub enum LlmError {
     #[error("Rate limited: {0}")]
     RateLimited(String),
 
+    #[error("Rate limited: {message} (retry after {retry_after_secs} seconds)")]
+    RateLimitedRetryAfter {
+        message: String,
+        retry_after_secs: u64,
+    },
+
     #[error("Model not found: {0}")]
     ModelNotFound(String),
 
@@ -60,8 +68,34 @@ pub enum LlmError {
     #[error("Parse error: {0}")]
     ParseError(String),
 
-    #[error("Timeout after {0} seconds")]
-    Timeout(u64),
+    #[error("Timeout after {0:?}")]
+    Timeout(std::time::Duration),
+
+    #[error("Circuit breaker open for provider: {0}")]
+    CircuitOpen(String),
+}
+
+impl LlmError {
+    /// Whether the error is transient and the request may succeed if retried
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            LlmError::RateLimited(_)
+                | LlmError::RateLimitedRetryAfter { .. }
+                | LlmError::NetworkError(_)
+                | LlmError::Timeout(_)
+        )
+    }
+
+    /// Server-provided Retry-After hint, if any
+    pub fn retry_after(&self) -> Option<std::time::Duration> {
+        match self {
+            LlmError::RateLimitedRetryAfter {
+                retry_after_secs, ..
+            } => Some(std::time::Duration::from_secs(*retry_after_secs)),
+            _ => None,
+        }
+    }
 }
 
 // ============================================================================
@@ -69,7 +103,7 @@ pub enum LlmError {
 // ============================================================================
 
 /// Supported LLM providers
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Provider {
     OpenAI,
@@ -86,6 +120,35 @@ impl Default for Provider {
     }
 }
 
+impl Provider {
+    /// Whether the provider can enforce JSON output natively
+    pub fn supports_json_mode(&self) -> bool {
+        matches!(self, Provider::OpenAI | Provider::Gemini | Provider::Ollama)
+    }
+
+    /// Request parameters enforcing `schema` through the provider's JSON mode
+    ///
+    /// Returns `None` for providers without a native JSON mode.
+    pub fn json_mode_params(&self, schema: &serde_json::Value) -> Option<serde_json::Value> {
+        match self {
+            Provider::OpenAI => Some(serde_json::json!({
+                "response_format": {
+                    "type": "json_schema",
+                    "json_schema": { "name": "output", "schema": schema }
+                }
+            })),
+            Provider::Gemini => Some(serde_json::json!({
+                "generationConfig": {
+                    "responseMimeType": "application/json",
+                    "responseSchema": schema
+                }
+            })),
+            Provider::Ollama => Some(serde_json::json!({ "format": schema })),
+            _ => None,
+        }
+    }
+}
+
 impl std::fmt::Display for Provider {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -128,6 +191,10 @@ pub struct LlmConfig {
 
     /// Custom base URL (for Ollama or custom endpoints)
     pub base_url: Option<String>,
+
+    /// Retry, rate-limit and circuit breaker behavior
+    #[serde(default)]
+    pub resilience: ResilienceConfig,
 }
 
 impl Default for LlmConfig {
@@ -142,6 +209,7 @@ impl Default for LlmConfig {
             stop_sequences: vec![],
             timeout_secs: 60,
             base_url: None,
+            resilience: ResilienceConfig::default(),
         }
     }
 }
@@ -192,6 +260,12 @@ impl LlmConfig {
         self.api_key = Some(key.into());
         self
     }
+
+    /// Set resilience (retry/backoff/circuit breaker) configuration
+    pub fn with_resilience(mut self, resilience: ResilienceConfig) -> Self {
+        self.resilience = resilience;
+        self
+    }
 }
 
 // ============================================================================
@@ -253,6 +327,22 @@ pub trait CompletionClient: Send + Sync {
     /// Generate a chat completion with message history
     async fn chat(&self, messages: Vec<ChatMessage>) -> Result<LlmResponse, LlmError>;
 
+    /// Generate a completion constrained to a JSON schema
+    ///
+    /// The default appends schema instructions to the system message. Clients
+    /// for providers with a native JSON mode should override this to enforce it.
+    /// Use [`StructuredCompletion`](crate::structured::StructuredCompletion) for
+    /// validated, typed results.
+    async fn complete_json(
+        &self,
+        system: &str,
+        prompt: &str,
+        schema: &serde_json::Value,
+    ) -> Result<LlmResponse, LlmError> {
+        let system = format!("{}\n\n{}", system, schema_instructions(schema));
+        self.complete_with_system(&system, prompt).await
+    }
+
     /// Get the model name
     fn model(&self) -> &str;
 
@@ -381,9 +471,13 @@ Question: {query}
     }
 }
 
-#[async_trait]
-impl CompletionClient for RigLlmClient {
-    async fn complete(&self, prompt: &str) -> Result<LlmResponse, LlmError> {
+impl RigLlmClient {
+    /// Send a prompt, with extra provider request parameters if any
+    async fn send(
+        &self,
+        prompt: &str,
+        params: Option<&serde_json::Value>,
+    ) -> Result<LlmResponse, LlmError> {
         // TODO: Implement actual rig integration
         //
         // Example with rig-core:
@@ -391,10 +485,14 @@ impl CompletionClient for RigLlmClient {
         // use rig::providers::openai;
         //
         // let client = openai::Client::from_env();
-        // let response = client
+        // let mut request = client
         //     .completion(&self.config.model)
         //     .temperature(self.config.temperature)
-        //     .max_tokens(self.config.max_tokens.unwrap_or(2048) as i32)
+        //     .max_tokens(self.config.max_tokens.unwrap_or(2048) as i32);
+        // if let Some(params) = params {
+        //     request = request.additional_params(params.clone());
+        // }
+        // let response = request
         //     .prompt(prompt)
         //     .await
         //     .map_err(|e| LlmError::ProviderError(e.to_string()))?;
@@ -404,6 +502,7 @@ impl CompletionClient for RigLlmClient {
             provider = %self.config.provider,
             model = %self.config.model,
             prompt_len = prompt.len(),
+            json_mode = params.is_some(),
             "Calling LLM"
         );
 
@@ -425,6 +524,13 @@ impl CompletionClient for RigLlmClient {
             finish_reason: Some(FinishReason::Stop),
         })
     }
+}
+
+#[async_trait]
+impl CompletionClient for RigLlmClient {
+    async fn complete(&self, prompt: &str) -> Result<LlmResponse, LlmError> {
+        self.send(prompt, None).await
+    }
 
     async fn complete_with_system(
         &self,
@@ -446,6 +552,26 @@ impl CompletionClient for RigLlmClient {
         self.complete(&prompt).await
     }
 
+    async fn complete_json(
+        &self,
+        system: &str,
+        prompt: &str,
+        schema: &serde_json::Value,
+    ) -> Result<LlmResponse, LlmError> {
+        // Providers with a native JSON mode enforce the schema themselves;
+        // the others are asked for it in the system message
+        match self.config.provider.json_mode_params(schema) {
+            Some(params) => {
+                let full_prompt = format!("{}\n\n{}", system, prompt);
+                self.send(&full_prompt, Some(&params)).await
+            }
+            None => {
+                let system = format!("{}\n\n{}", system, schema_instructions(schema));
+                self.complete_with_system(&system, prompt).await
+            }
+        }
+    }
+
     fn model(&self) -> &str {
         &self.config.model
     }
@@ -535,8 +661,13 @@ impl ToolCallingClient for RigLlmClient {
 // ============================================================================
 
 /// Create an LLM client for the specified provider
+///
+/// The client is wrapped in a [`ResilientClient`] using `config.resilience`.
 pub fn create_client(config: LlmConfig) -> Arc<dyn CompletionClient> {
-    Arc::new(RigLlmClient::new(config))
+    let resilience = config.resilience.clone();
+    let timeout_secs = config.timeout_secs;
+    let inner: Arc<dyn CompletionClient> = Arc::new(RigLlmClient::new(config));
+    Arc::new(ResilientClient::new(inner, resilience).with_timeout_secs(timeout_secs))
 }
 
 /// Create an OpenAI client
@@ -584,6 +715,17 @@ mod tests {
         assert_eq!(config.model, "claude-3-opus");
     }
 
+    #[test]
+    fn test_json_mode_params() {
+        let schema = serde_json::json!({"type": "object"});
+
+        let openai = Provider::OpenAI.json_mode_params(&schema).unwrap();
+        assert_eq!(openai["response_format"]["json_schema"]["schema"], schema);
+        let ollama = Provider::Ollama.json_mode_params(&schema).unwrap();
+        assert_eq!(ollama["format"], schema);
+        assert!(Provider::Anthropic.json_mode_params(&schema).is_none());
+    }
+
     #[test]
     fn test_chat_message() {
         let system = ChatMessage::system("You are helpful");
@@ -604,6 +746,22 @@ mod tests {
         assert_eq!(response.model, "gpt-4");
     }
 
+    #[test]
+    fn test_llm_error_transient() {
+        assert!(LlmError::RateLimited("429".into()).is_transient());
+        assert!(LlmError::Timeout(std::time::Duration::from_secs(30)).is_transient());
+        assert!(LlmError::NetworkError("reset".into()).is_transient());
+        assert!(!LlmError::InvalidApiKey("openai".into()).is_transient());
+        assert!(!LlmError::ContextLengthExceeded("too long".into()).is_transient());
+
+        let err = LlmError::RateLimitedRetryAfter {
+            message: "429".into(),
+            retry_after_secs: 7,
+        };
+        assert!(err.is_transient());
+        assert_eq!(err.retry_after(), Some(std::time::Duration::from_secs(7)));
+    }
+
     #[test]
     fn test_provider_display() {
         assert_eq!(Provider::OpenAI.to_string(), "openai");
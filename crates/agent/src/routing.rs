@@ -0,0 +1,481 @@
+//! Provider fallback chain and model routing
+//!
+//! [`RoutingClient`] sits in front of several [`CompletionClient`]s:
+//!
+//! - **Fallback**: clients are tried in order; when one fails with an error
+//!   class listed in [`RoutingConfig::fallback_on`], the next one is tried.
+//! - **Routing rules**: short or simple prompts can be sent to a cheaper
+//!   model first, with the regular chain as fallback.
+//!
+//! The client that actually answered is recorded in [`LlmResponse::model`]
+//! as `"<provider>/<model>"`, e.g. `"anthropic/claude-3-haiku"`.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use agent::routing::{RoutingClient, RoutingRule};
+//! use agent::rig_integration::{anthropic_client, openai_client};
+//!
+//! let client = RoutingClient::new(vec![
+//!     openai_client("gpt-4"),
+//!     anthropic_client("claude-3-opus"),
+//! ])
+//! .with_rule(RoutingRule::short_prompts(500, openai_client("gpt-4o-mini")));
+//!
+//! let response = client.complete("Hi!").await?;
+//! assert_eq!(response.model, "openai/gpt-4o-mini");
+//! ```
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{debug, warn};
+
+use crate::rig_integration::{ChatMessage, CompletionClient, LlmError, LlmResponse, Provider};
+
+// ============================================================================
+// ERROR CLASSES
+// ============================================================================
+
+/// Coarse classification of [`LlmError`] used to decide on fallback
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorClass {
+    RateLimited,
+    Timeout,
+    Network,
+    Provider,
+    InvalidApiKey,
+    ModelNotFound,
+    ContextLength,
+    CircuitOpen,
+    Parse,
+}
+
+impl ErrorClass {
+    /// Classify an error
+    pub fn of(error: &LlmError) -> Self {
+        match error {
+            LlmError::RateLimited(_) | LlmError::RateLimitedRetryAfter { .. } => {
+                ErrorClass::RateLimited
+            }
+            LlmError::Timeout(_) => ErrorClass::Timeout,
+            LlmError::NetworkError(_) => ErrorClass::Network,
+            LlmError::ProviderError(_) => ErrorClass::Provider,
+            LlmError::InvalidApiKey(_) => ErrorClass::InvalidApiKey,
+            LlmError::ModelNotFound(_) => ErrorClass::ModelNotFound,
+            LlmError::ContextLengthExceeded(_) => ErrorClass::ContextLength,
+            LlmError::CircuitOpen(_) => ErrorClass::CircuitOpen,
+            LlmError::ParseError(_) => ErrorClass::Parse,
+        }
+    }
+}
+
+// ============================================================================
+// CONFIGURATION
+// ============================================================================
+
+/// Routing behavior
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RoutingConfig {
+    /// Error classes that cause the next client in the chain to be tried
+    pub fallback_on: Vec<ErrorClass>,
+}
+
+impl Default for RoutingConfig {
+    fn default() -> Self {
+        Self {
+            fallback_on: vec![
+                ErrorClass::RateLimited,
+                ErrorClass::Timeout,
+                ErrorClass::Network,
+                ErrorClass::Provider,
+                ErrorClass::ModelNotFound,
+                ErrorClass::CircuitOpen,
+            ],
+        }
+    }
+}
+
+impl RoutingConfig {
+    /// Whether an error should fall through to the next client
+    pub fn should_fallback(&self, error: &LlmError) -> bool {
+        self.fallback_on.contains(&ErrorClass::of(error))
+    }
+}
+
+/// Rule sending matching prompts to a (usually cheaper) client first
+#[derive(Clone)]
+pub struct RoutingRule {
+    /// Rule name, used in logs
+    pub name: String,
+
+    /// Prompts longer than this (in characters) never match
+    pub max_prompt_chars: usize,
+
+    /// Prompts containing any of these markers (case-insensitive) never match
+    pub excluded_markers: Vec<String>,
+
+    /// Client to use when the rule matches
+    pub client: Arc<dyn CompletionClient>,
+}
+
+impl RoutingRule {
+    /// Route prompts up to `max_chars` characters to `client`
+    pub fn short_prompts(max_chars: usize, client: Arc<dyn CompletionClient>) -> Self {
+        Self {
+            name: "short_prompts".to_string(),
+            max_prompt_chars: max_chars,
+            excluded_markers: Vec::new(),
+            client,
+        }
+    }
+
+    /// Route short prompts without any "complex task" markers to `client`
+    ///
+    /// Code fences and tool-use/reasoning instructions are treated as signs
+    /// that the prompt needs the stronger model.
+    pub fn simple_prompts(max_chars: usize, client: Arc<dyn CompletionClient>) -> Self {
+        Self {
+            name: "simple_prompts".to_string(),
+            max_prompt_chars: max_chars,
+            excluded_markers: ["```", "action input:", "step by step", "analyze", "วิเคราะห์"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            client,
+        }
+    }
+
+    /// Add a marker that disqualifies a prompt from this rule
+    pub fn exclude(mut self, marker: impl Into<String>) -> Self {
+        self.excluded_markers.push(marker.into());
+        self
+    }
+
+    /// Whether the prompt matches this rule
+    pub fn matches(&self, prompt: &str) -> bool {
+        if prompt.chars().count() > self.max_prompt_chars {
+            return false;
+        }
+        let lower = prompt.to_lowercase();
+        !self
+            .excluded_markers
+            .iter()
+            .any(|m| lower.contains(&m.to_lowercase()))
+    }
+}
+
+/// Format the provider and model recorded in routed responses
+pub fn qualified_model(provider: Provider, model: &str) -> String {
+    format!("{}/{}", provider, model)
+}
+
+// ============================================================================
+// ROUTING CLIENT
+// ============================================================================
+
+/// [`CompletionClient`] that routes and falls back across several clients
+pub struct RoutingClient {
+    chain: Vec<Arc<dyn CompletionClient>>,
+    rules: Vec<RoutingRule>,
+    config: RoutingConfig,
+}
+
+impl RoutingClient {
+    /// Create a router over an ordered fallback chain (first = primary)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chain` is empty.
+    pub fn new(chain: Vec<Arc<dyn CompletionClient>>) -> Self {
+        assert!(!chain.is_empty(), "RoutingClient needs at least one client");
+        Self {
+            chain,
+            rules: Vec::new(),
+            config: RoutingConfig::default(),
+        }
+    }
+
+    /// Set the routing configuration
+    pub fn with_config(mut self, config: RoutingConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Add a routing rule (rules are checked in insertion order)
+    pub fn with_rule(mut self, rule: RoutingRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Clients to try for a prompt, in order
+    fn plan(&self, prompt: &str) -> Vec<&Arc<dyn CompletionClient>> {
+        let mut plan: Vec<&Arc<dyn CompletionClient>> = Vec::with_capacity(self.chain.len() + 1);
+        if let Some(rule) = self.rules.iter().find(|r| r.matches(prompt)) {
+            debug!(rule = %rule.name, model = %rule.client.model(), "Routing rule matched");
+            plan.push(&rule.client);
+        }
+        plan.extend(self.chain.iter());
+        plan
+    }
+
+    /// Try each client of the plan until one succeeds or fails permanently
+    async fn route<'a, F, Fut>(&'a self, prompt: &str, call: F) -> Result<LlmResponse, LlmError>
+    where
+        F: Fn(&'a Arc<dyn CompletionClient>) -> Fut + Send + Sync,
+        Fut: std::future::Future<Output = Result<LlmResponse, LlmError>> + Send,
+    {
+        let plan = self.plan(prompt);
+        let last = plan.len() - 1;
+
+        for (i, client) in plan.into_iter().enumerate() {
+            match call(client).await {
+                Ok(mut response) => {
+                    response.model = qualified_model(client.provider(), client.model());
+                    debug!(model = %response.model, attempt = i + 1, "Routed LLM call");
+                    return Ok(response);
+                }
+                Err(e) if i < last && self.config.should_fallback(&e) => {
+                    warn!(
+                        provider = %client.provider(),
+                        model = %client.model(),
+                        error = %e,
+                        "LLM call failed, falling back to next client"
+                    );
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        unreachable!("routing plan always contains at least one client")
+    }
+}
+
+#[async_trait]
+impl CompletionClient for RoutingClient {
+    async fn complete(&self, prompt: &str) -> Result<LlmResponse, LlmError> {
+        self.route(prompt, |c| c.complete(prompt)).await
+    }
+
+    async fn complete_with_system(
+        &self,
+        system: &str,
+        prompt: &str,
+    ) -> Result<LlmResponse, LlmError> {
+        self.route(prompt, |c| c.complete_with_system(system, prompt))
+            .await
+    }
+
+    async fn chat(&self, messages: Vec<ChatMessage>) -> Result<LlmResponse, LlmError> {
+        let prompt: String = messages
+            .iter()
+            .map(|m| m.content.as_str())
+            .collect::<Vec<_>>()
+            .join("\n");
+        self.route(&prompt, |c| c.chat(messages.clone())).await
+    }
+
+    async fn complete_json(
+        &self,
+        system: &str,
+        prompt: &str,
+        schema: &serde_json::Value,
+    ) -> Result<LlmResponse, LlmError> {
+        self.route(prompt, |c| c.complete_json(system, prompt, schema))
+            .await
+    }
+
+    fn model(&self) -> &str {
+        self.chain[0].model()
+    }
+
+    fn provider(&self) -> Provider {
+        self.chain[0].provider()
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Client that always succeeds or always fails with a fixed error
+    struct StubClient {
+        provider: Provider,
+        model: String,
+        error: Option<fn() -> LlmError>,
+        calls: AtomicUsize,
+    }
+
+    impl StubClient {
+        fn ok(provider: Provider, model: &str) -> Arc<Self> {
+            Arc::new(Self {
+                provider,
+                model: model.to_string(),
+                error: None,
+                calls: AtomicUsize::new(0),
+            })
+        }
+
+        fn failing(provider: Provider, model: &str, error: fn() -> LlmError) -> Arc<Self> {
+            Arc::new(Self {
+                provider,
+                model: model.to_string(),
+                error: Some(error),
+                calls: AtomicUsize::new(0),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl CompletionClient for StubClient {
+        async fn complete(&self, _prompt: &str) -> Result<LlmResponse, LlmError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            match self.error {
+                Some(error) => Err(error()),
+                None => Ok(LlmResponse {
+                    content: format!("from {}", self.model),
+                    model: self.model.clone(),
+                    usage: None,
+                    finish_reason: None,
+                }),
+            }
+        }
+
+        async fn complete_with_system(
+            &self,
+            _system: &str,
+            prompt: &str,
+        ) -> Result<LlmResponse, LlmError> {
+            self.complete(prompt).await
+        }
+
+        async fn chat(&self, _messages: Vec<ChatMessage>) -> Result<LlmResponse, LlmError> {
+            self.complete("").await
+        }
+
+        fn model(&self) -> &str {
+            &self.model
+        }
+
+        fn provider(&self) -> Provider {
+            self.provider
+        }
+    }
+
+    #[tokio::test]
+    async fn test_primary_used_when_healthy() {
+        let primary = StubClient::ok(Provider::OpenAI, "gpt-4");
+        let backup = StubClient::ok(Provider::Anthropic, "claude-3-opus");
+        let router = RoutingClient::new(vec![primary.clone(), backup.clone()]);
+
+        let response = router.complete("hello").await.unwrap();
+        assert_eq!(response.model, "openai/gpt-4");
+        assert_eq!(backup.calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_on_configured_errors() {
+        let primary = StubClient::failing(Provider::OpenAI, "gpt-4", || {
+            LlmError::RateLimited("quota".into())
+        });
+        let backup = StubClient::ok(Provider::Anthropic, "claude-3-opus");
+        let router = RoutingClient::new(vec![primary.clone(), backup]);
+
+        let response = router.complete("hello").await.unwrap();
+        assert_eq!(response.model, "anthropic/claude-3-opus");
+        assert_eq!(primary.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_does_not_fall_back_on_other_errors() {
+        let primary = StubClient::failing(Provider::OpenAI, "gpt-4", || {
+            LlmError::ContextLengthExceeded("too long".into())
+        });
+        let backup = StubClient::ok(Provider::Anthropic, "claude-3-opus");
+        let router = RoutingClient::new(vec![primary, backup.clone()]);
+
+        let err = router.complete("hello").await.unwrap_err();
+        assert!(matches!(err, LlmError::ContextLengthExceeded(_)));
+        assert_eq!(backup.calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_last_error_returned_when_chain_exhausted() {
+        let primary = StubClient::failing(Provider::OpenAI, "gpt-4", || {
+            LlmError::Timeout(std::time::Duration::from_secs(60))
+        });
+        let backup = StubClient::failing(Provider::Anthropic, "claude-3-opus", || {
+            LlmError::NetworkError("down".into())
+        });
+        let router = RoutingClient::new(vec![primary, backup]);
+
+        let err = router.complete("hello").await.unwrap_err();
+        assert!(matches!(err, LlmError::NetworkError(_)));
+    }
+
+    #[tokio::test]
+    async fn test_short_prompt_routed_to_cheap_model() {
+        let primary = StubClient::ok(Provider::OpenAI, "gpt-4");
+        let cheap = StubClient::ok(Provider::OpenAI, "gpt-4o-mini");
+        let router = RoutingClient::new(vec![primary])
+            .with_rule(RoutingRule::short_prompts(20, cheap.clone()));
+
+        let short = router.complete("Hi!").await.unwrap();
+        assert_eq!(short.model, "openai/gpt-4o-mini");
+
+        let long = router
+            .complete("Please compare these three products in detail")
+            .await
+            .unwrap();
+        assert_eq!(long.model, "openai/gpt-4");
+        assert_eq!(cheap.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_cheap_model_falls_back_to_chain() {
+        let primary = StubClient::ok(Provider::OpenAI, "gpt-4");
+        let cheap = StubClient::failing(Provider::OpenAI, "gpt-4o-mini", || {
+            LlmError::ModelNotFound("gpt-4o-mini".into())
+        });
+        let router =
+            RoutingClient::new(vec![primary]).with_rule(RoutingRule::short_prompts(100, cheap));
+
+        let response = router.complete("Hi!").await.unwrap();
+        assert_eq!(response.model, "openai/gpt-4");
+    }
+
+    #[test]
+    fn test_simple_prompt_rule_excludes_markers() {
+        let rule = RoutingRule::simple_prompts(200, StubClient::ok(Provider::OpenAI, "mini"));
+        assert!(rule.matches("What are your opening hours?"));
+        assert!(!rule.matches("Analyze this:\n```rust\nfn main() {}\n```"));
+        assert!(!rule.matches(&"x".repeat(201)));
+    }
+
+    #[test]
+    fn test_error_class_of() {
+        assert_eq!(
+            ErrorClass::of(&LlmError::RateLimitedRetryAfter {
+                message: "429".into(),
+                retry_after_secs: 1
+            }),
+            ErrorClass::RateLimited
+        );
+        assert_eq!(
+            ErrorClass::of(&LlmError::CircuitOpen("openai".into())),
+            ErrorClass::CircuitOpen
+        );
+
+        let config: RoutingConfig =
+            serde_json::from_str(r#"{"fallback_on": ["timeout"]}"#).unwrap();
+        assert!(config.should_fallback(&LlmError::Timeout(std::time::Duration::from_secs(1))));
+        assert!(!config.should_fallback(&LlmError::RateLimited("429".into())));
+    }
+}
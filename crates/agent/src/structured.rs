@@ -0,0 +1,635 @@
+//! Schema-constrained structured outputs
+//!
+//! Asks the model for JSON matching a schema, validates the reply and
+//! re-prompts with the validation errors until it conforms or the repair
+//! budget is spent.
+//!
+//! ```text
+//! prompt + schema ──▶ LLM ──▶ extract JSON ──▶ validate ──▶ deserialize ──▶ T
+//!                      ▲                          │
+//!                      └──── repair prompt ◀──────┘ (errors, up to N times)
+//! ```
+//!
+//! The schema is sent through [`CompletionClient::complete_json`], which
+//! uses the provider's native JSON mode where available and falls back to
+//! schema instructions in the system prompt otherwise.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use agent::structured::StructuredCompletion;
+//! use schemars::JsonSchema;
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize, JsonSchema)]
+//! struct Quote {
+//!     product: String,
+//!     quantity: u32,
+//! }
+//!
+//! let quote = client
+//!     .complete_structured::<Quote>("Extract the order from: 3 boxes of A4 paper")
+//!     .await?;
+//! println!("{} x {}", quote.value.quantity, quote.value.product);
+//! ```
+
+use async_trait::async_trait;
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use thiserror::Error;
+use tracing::{debug, warn};
+
+use crate::rig_integration::{CompletionClient, LlmError, TokenUsage};
+
+/// Default number of repair re-prompts after the first attempt
+pub const DEFAULT_MAX_REPAIRS: usize = 2;
+
+/// Default system prompt for structured completions
+const DEFAULT_SYSTEM: &str =
+    "You are a precise assistant that extracts and produces structured data.";
+
+// ============================================================================
+// ERRORS
+// ============================================================================
+
+/// Errors from structured completions
+#[derive(Error, Debug)]
+pub enum StructuredError {
+    #[error("LLM error: {0}")]
+    Llm(#[from] LlmError),
+
+    #[error("Invalid structured output after {attempts} attempt(s): {}", errors.join("; "))]
+    Invalid {
+        attempts: usize,
+        errors: Vec<String>,
+        raw: String,
+    },
+}
+
+// ============================================================================
+// STRUCTURED RESPONSE
+// ============================================================================
+
+/// A validated structured completion
+#[derive(Debug, Clone)]
+pub struct Structured<T> {
+    /// The parsed value
+    pub value: T,
+
+    /// Raw content of the accepted reply
+    pub raw: String,
+
+    /// Model that produced the accepted reply
+    pub model: String,
+
+    /// Number of LLM calls made (1 + repairs)
+    pub attempts: usize,
+
+    /// Token usage summed over all attempts
+    pub usage: Option<TokenUsage>,
+}
+
+// ============================================================================
+// STRUCTURED COMPLETION
+// ============================================================================
+
+/// Structured completions for any [`CompletionClient`]
+#[async_trait]
+pub trait StructuredCompletion {
+    /// Complete into a typed value using the schema derived from `T`
+    async fn complete_structured<T>(&self, prompt: &str) -> Result<Structured<T>, StructuredError>
+    where
+        T: DeserializeOwned + JsonSchema + Send;
+
+    /// Complete into a typed value with a custom system prompt and repair budget
+    async fn complete_structured_with<T>(
+        &self,
+        system: &str,
+        prompt: &str,
+        max_repairs: usize,
+    ) -> Result<Structured<T>, StructuredError>
+    where
+        T: DeserializeOwned + JsonSchema + Send;
+
+    /// Complete into a JSON value validated against `schema`
+    async fn complete_json_value(
+        &self,
+        system: &str,
+        prompt: &str,
+        schema: &Value,
+        max_repairs: usize,
+    ) -> Result<Structured<Value>, StructuredError>;
+}
+
+#[async_trait]
+impl<C: CompletionClient + ?Sized> StructuredCompletion for C {
+    async fn complete_structured<T>(&self, prompt: &str) -> Result<Structured<T>, StructuredError>
+    where
+        T: DeserializeOwned + JsonSchema + Send,
+    {
+        self.complete_structured_with(DEFAULT_SYSTEM, prompt, DEFAULT_MAX_REPAIRS)
+            .await
+    }
+
+    async fn complete_structured_with<T>(
+        &self,
+        system: &str,
+        prompt: &str,
+        max_repairs: usize,
+    ) -> Result<Structured<T>, StructuredError>
+    where
+        T: DeserializeOwned + JsonSchema + Send,
+    {
+        let schema = schema_for::<T>();
+        complete_validated(self, system, prompt, &schema, max_repairs, |value| {
+            serde_json::from_value(value).map_err(|e| vec![format!("$: {}", e)])
+        })
+        .await
+    }
+
+    async fn complete_json_value(
+        &self,
+        system: &str,
+        prompt: &str,
+        schema: &Value,
+        max_repairs: usize,
+    ) -> Result<Structured<Value>, StructuredError> {
+        complete_validated(self, system, prompt, schema, max_repairs, Ok).await
+    }
+}
+
+/// Request, validate and repair until the reply converts or the budget is spent
+async fn complete_validated<C, T, F>(
+    client: &C,
+    system: &str,
+    prompt: &str,
+    schema: &Value,
+    max_repairs: usize,
+    convert: F,
+) -> Result<Structured<T>, StructuredError>
+where
+    C: CompletionClient + ?Sized,
+    F: Fn(Value) -> Result<T, Vec<String>> + Send,
+{
+    let mut usage: Option<TokenUsage> = None;
+    let mut current_prompt = prompt.to_string();
+    let mut errors = Vec::new();
+    let mut raw = String::new();
+
+    for attempt in 1..=max_repairs + 1 {
+        let response = client
+            .complete_json(system, &current_prompt, schema)
+            .await?;
+        usage = add_usage(usage, response.usage.as_ref());
+
+        let result = parse_json(&response.content)
+            .map_err(|e| vec![e])
+            .and_then(|value| {
+                let violations = validate(schema, &value);
+                if violations.is_empty() {
+                    convert(value)
+                } else {
+                    Err(violations)
+                }
+            });
+
+        match result {
+            Ok(value) => {
+                debug!(attempt, "Structured output accepted");
+                return Ok(Structured {
+                    value,
+                    raw: response.content,
+                    model: response.model,
+                    attempts: attempt,
+                    usage,
+                });
+            }
+            Err(violations) => {
+                warn!(attempt, errors = ?violations, "Structured output failed validation");
+                current_prompt = repair_prompt(prompt, &response.content, &violations);
+                errors = violations;
+                raw = response.content;
+            }
+        }
+    }
+
+    Err(StructuredError::Invalid {
+        attempts: max_repairs + 1,
+        errors,
+        raw,
+    })
+}
+
+fn add_usage(total: Option<TokenUsage>, usage: Option<&TokenUsage>) -> Option<TokenUsage> {
+    match (total, usage) {
+        (Some(t), Some(u)) => Some(TokenUsage {
+            prompt_tokens: t.prompt_tokens + u.prompt_tokens,
+            completion_tokens: t.completion_tokens + u.completion_tokens,
+            total_tokens: t.total_tokens + u.total_tokens,
+        }),
+        (t, u) => t.or_else(|| u.cloned()),
+    }
+}
+
+// ============================================================================
+// PROMPTS
+// ============================================================================
+
+/// JSON schema for a Rust type
+pub fn schema_for<T: JsonSchema>() -> Value {
+    serde_json::to_value(schemars::schema_for!(T)).unwrap_or(Value::Bool(true))
+}
+
+/// Instructions asking the model to answer with JSON matching `schema`
+pub fn schema_instructions(schema: &Value) -> String {
+    let schema = serde_json::to_string_pretty(schema).unwrap_or_default();
+    format!(
+        "Respond with a single JSON value that conforms to the following JSON schema. \
+         Output only the JSON, without prose or code fences.\n\nJSON schema:\n{}",
+        schema
+    )
+}
+
+/// Re-prompt carrying the rejected reply and its validation errors
+fn repair_prompt(prompt: &str, previous: &str, errors: &[String]) -> String {
+    let errors: String = errors.iter().map(|e| format!("- {}\n", e)).collect();
+    format!(
+        "{}\n\nYour previous response was rejected:\n{}\n\nValidation errors:\n{}\n\
+         Respond again with only the corrected JSON.",
+        prompt, previous, errors
+    )
+}
+
+// ============================================================================
+// PARSING
+// ============================================================================
+
+/// Parse the JSON value in an LLM reply, tolerating code fences and prose
+pub fn parse_json(content: &str) -> Result<Value, String> {
+    let trimmed = strip_code_fence(content.trim());
+
+    if let Ok(value) = serde_json::from_str(trimmed) {
+        return Ok(value);
+    }
+
+    // Fall back to the outermost object or array embedded in prose
+    let start = trimmed.find(['{', '[']);
+    let end = trimmed.rfind(['}', ']']);
+    match (start, end) {
+        (Some(start), Some(end)) if start < end => serde_json::from_str(&trimmed[start..=end])
+            .map_err(|e| format!("$: response is not valid JSON ({})", e)),
+        _ => Err("$: response does not contain a JSON value".to_string()),
+    }
+}
+
+fn strip_code_fence(content: &str) -> &str {
+    let Some(rest) = content.strip_prefix("```") else {
+        return content;
+    };
+    // Drop the language tag line, e.g. ```json
+    let rest = rest.split_once('\n').map(|(_, body)| body).unwrap_or(rest);
+    rest.trim_end().strip_suffix("```").unwrap_or(rest).trim()
+}
+
+// ============================================================================
+// VALIDATION
+// ============================================================================
+
+/// Validate `value` against a JSON schema, returning one message per violation
+///
+/// Supports the subset emitted by `schemars` and used in tool schemas:
+/// `type`, `enum`, `const`, `properties`, `required`, `additionalProperties`,
+/// `items`, length/size/range bounds, `allOf`/`anyOf`/`oneOf` and local `$ref`s.
+pub fn validate(schema: &Value, value: &Value) -> Vec<String> {
+    let mut errors = Vec::new();
+    validate_at(schema, schema, value, "$", &mut errors);
+    errors
+}
+
+/// Validate `value` against `schema`, a sub-schema of `root` that may hold
+/// `$ref`s into it
+pub(crate) fn validate_in(root: &Value, schema: &Value, value: &Value) -> Vec<String> {
+    let mut errors = Vec::new();
+    validate_at(root, schema, value, "$", &mut errors);
+    errors
+}
+
+fn validate_at(root: &Value, schema: &Value, value: &Value, path: &str, errors: &mut Vec<String>) {
+    let Some(schema) = schema.as_object() else {
+        if schema == &Value::Bool(false) {
+            errors.push(format!("{}: no value is allowed here", path));
+        }
+        return;
+    };
+
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        match resolve_ref(root, reference) {
+            Some(target) => validate_at(root, target, value, path, errors),
+            None => errors.push(format!(
+                "{}: unresolvable schema reference {}",
+                path, reference
+            )),
+        }
+    }
+
+    if let Some(expected) = schema.get("type") {
+        let types: Vec<&str> = match expected {
+            Value::String(t) => vec![t.as_str()],
+            Value::Array(ts) => ts.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !types.is_empty() && !types.iter().any(|t| matches_type(t, value)) {
+            errors.push(format!(
+                "{}: expected {}, got {}",
+                path,
+                types.join(" or "),
+                type_name(value)
+            ));
+            return;
+        }
+    }
+
+    if let Some(allowed) = schema.get("enum").and_then(Value::as_array) {
+        if !allowed.contains(value) {
+            errors.push(format!(
+                "{}: must be one of {}",
+                path,
+                Value::Array(allowed.clone())
+            ));
+        }
+    }
+
+    if let Some(constant) = schema.get("const") {
+        if constant != value {
+            errors.push(format!("{}: must equal {}", path, constant));
+        }
+    }
+
+    match value {
+        Value::Object(map) => {
+            let properties = schema.get("properties").and_then(Value::as_object);
+
+            if let Some(required) = schema.get("required").and_then(Value::as_array) {
+                for key in required.iter().filter_map(Value::as_str) {
+                    if !map.contains_key(key) {
+                        errors.push(format!("{}: missing required property '{}'", path, key));
+                    }
+                }
+            }
+
+            for (key, item) in map {
+                let item_path = format!("{}.{}", path, key);
+                match properties.and_then(|p| p.get(key)) {
+                    Some(item_schema) => validate_at(root, item_schema, item, &item_path, errors),
+                    None => match schema.get("additionalProperties") {
+                        Some(Value::Bool(false)) => {
+                            errors.push(format!("{}: unknown property", item_path))
+                        }
+                        Some(extra) => validate_at(root, extra, item, &item_path, errors),
+                        None => {}
+                    },
+                }
+            }
+        }
+        Value::Array(items) => {
+            if let Some(item_schema) = schema.get("items") {
+                for (i, item) in items.iter().enumerate() {
+                    validate_at(root, item_schema, item, &format!("{}[{}]", path, i), errors);
+                }
+            }
+            check_bound(
+                schema,
+                "minItems",
+                items.len() as f64,
+                path,
+                "items",
+                errors,
+            );
+            check_bound(
+                schema,
+                "maxItems",
+                items.len() as f64,
+                path,
+                "items",
+                errors,
+            );
+        }
+        Value::String(s) => {
+            let len = s.chars().count() as f64;
+            check_bound(schema, "minLength", len, path, "characters", errors);
+            check_bound(schema, "maxLength", len, path, "characters", errors);
+        }
+        Value::Number(n) => {
+            let n = n.as_f64().unwrap_or_default();
+            check_bound(schema, "minimum", n, path, "", errors);
+            check_bound(schema, "maximum", n, path, "", errors);
+        }
+        _ => {}
+    }
+
+    if let Some(all) = schema.get("allOf").and_then(Value::as_array) {
+        for sub in all {
+            validate_at(root, sub, value, path, errors);
+        }
+    }
+
+    for keyword in ["anyOf", "oneOf"] {
+        if let Some(options) = schema.get(keyword).and_then(Value::as_array) {
+            let matching = options
+                .iter()
+                .filter(|sub| {
+                    let mut sub_errors = Vec::new();
+                    validate_at(root, sub, value, path, &mut sub_errors);
+                    sub_errors.is_empty()
+                })
+                .count();
+            let ok = if keyword == "oneOf" {
+                matching == 1
+            } else {
+                matching > 0
+            };
+            if !ok {
+                errors.push(format!("{}: does not match {} alternatives", path, keyword));
+            }
+        }
+    }
+}
+
+pub(crate) fn resolve_ref<'a>(root: &'a Value, reference: &str) -> Option<&'a Value> {
+    let pointer = reference.strip_prefix('#')?;
+    root.pointer(pointer)
+}
+
+fn matches_type(expected: &str, value: &Value) -> bool {
+    match expected {
+        "null" => value.is_null(),
+        "boolean" => value.is_boolean(),
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => {
+            value.is_i64() || value.is_u64() || value.as_f64().is_some_and(|n| n.fract() == 0.0)
+        }
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        _ => true,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn check_bound(
+    schema: &serde_json::Map<String, Value>,
+    keyword: &str,
+    actual: f64,
+    path: &str,
+    unit: &str,
+    errors: &mut Vec<String>,
+) {
+    let Some(bound) = schema.get(keyword).and_then(Value::as_f64) else {
+        return;
+    };
+    let violated = if keyword.starts_with("min") {
+        actual < bound
+    } else {
+        actual > bound
+    };
+    if violated {
+        let relation = if keyword.starts_with("min") {
+            "at least"
+        } else {
+            "at most"
+        };
+        let unit = if unit.is_empty() {
+            String::new()
+        } else {
+            format!(" {}", unit)
+        };
+        errors.push(format!("{}: must be {} {}{}", path, relation, bound, unit));
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::replay::{RecordedRequest, ScriptedClient};
+    use serde::Deserialize;
+    use serde_json::json;
+
+    #[derive(Debug, Deserialize, JsonSchema)]
+    struct Order {
+        product: String,
+        quantity: u32,
+        notes: Option<String>,
+    }
+
+    #[test]
+    fn test_parse_json_tolerates_fences_and_prose() {
+        let fenced = "```json\n{\"a\": 1}\n```";
+        assert_eq!(parse_json(fenced).unwrap(), json!({"a": 1}));
+
+        let prose = "Sure! Here it is: {\"a\": [1, 2]} Hope that helps.";
+        assert_eq!(parse_json(prose).unwrap(), json!({"a": [1, 2]}));
+
+        assert!(parse_json("no json here").is_err());
+    }
+
+    #[test]
+    fn test_validate_schema_for_type() {
+        let schema = schema_for::<Order>();
+
+        let valid = json!({"product": "paper", "quantity": 3, "notes": null});
+        assert!(validate(&schema, &valid).is_empty());
+
+        let invalid = json!({"product": 1, "quantity": -2});
+        let errors = validate(&schema, &invalid);
+        assert!(errors
+            .iter()
+            .any(|e| e.starts_with("$.product: expected string")));
+        assert!(errors.iter().any(|e| e.starts_with("$.quantity")));
+    }
+
+    #[test]
+    fn test_validate_required_enum_and_additional() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "status": {"type": "string", "enum": ["open", "closed"]},
+                "tags": {"type": "array", "items": {"type": "string"}, "maxItems": 2}
+            },
+            "required": ["status"],
+            "additionalProperties": false
+        });
+
+        let errors = validate(&schema, &json!({"tags": ["a", "b", "c"], "extra": true}));
+        assert!(errors.contains(&"$: missing required property 'status'".to_string()));
+        assert!(errors.contains(&"$.tags: must be at most 2 items".to_string()));
+        assert!(errors.contains(&"$.extra: unknown property".to_string()));
+
+        let errors = validate(&schema, &json!({"status": "pending"}));
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("$.status: must be one of"));
+    }
+
+    #[tokio::test]
+    async fn test_complete_structured_repairs_invalid_reply() {
+        let client = ScriptedClient::new("scripted").with_responses([
+            "I think they want paper.",
+            r#"{"product": "paper", "quantity": "three"}"#,
+            r#"```json
+{"product": "paper", "quantity": 3}
+```"#,
+        ]);
+
+        let order = client
+            .complete_structured::<Order>("3 boxes of paper")
+            .await
+            .unwrap();
+
+        assert_eq!(order.value.product, "paper");
+        assert_eq!(order.value.quantity, 3);
+        assert!(order.value.notes.is_none());
+        assert_eq!(order.attempts, 3);
+
+        // The repair prompt carries the validation errors
+        match &client.requests()[2] {
+            RecordedRequest::CompleteJson { prompt, .. } => {
+                assert!(prompt.contains("$.quantity: expected integer, got string"))
+            }
+            other => panic!("expected a JSON completion, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_complete_structured_gives_up_after_budget() {
+        let client = ScriptedClient::new("scripted").with_responses(["nope", "still nope"]);
+
+        let result = client
+            .complete_structured_with::<Order>("system", "3 boxes of paper", 1)
+            .await;
+
+        match result {
+            Err(StructuredError::Invalid { attempts, raw, .. }) => {
+                assert_eq!(attempts, 2);
+                assert_eq!(raw, "still nope");
+            }
+            other => panic!(
+                "expected invalid output error, got {:?}",
+                other.map(|s| s.raw)
+            ),
+        }
+    }
+}
@@ -0,0 +1,549 @@
+//! Appointment booking tool for sales agent.
+//!
+//! Books demos and visits into the slots the sales team opens. Booking
+//! takes two steps: `hold` reserves a seat for a short time while the agent
+//! confirms the details with the customer, and `confirm` books it. Holds
+//! that are not confirmed in time free their seat again.
+//!
+//! Dates given by the customer and times shown to them use the business
+//! time zone (UTC+7 by default).
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use agent::tools::appointment::AppointmentTool;
+//! use db::AppointmentRepository;
+//!
+//! let tool = AppointmentTool::new(Arc::new(AppointmentRepository::new(db_pool)))
+//!     .with_conversation_id(conversation_id)
+//!     .with_hold_ttl(10 * 60);
+//! ```
+
+use super::lead::{non_blank, normalize_email, normalize_thai_phone};
+use super::{Tool, ToolDefinition, ToolResult};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, TimeZone, Utc};
+use common::{Error, Result};
+use db::models::{Appointment, AppointmentSlot};
+use db::{AppointmentRepository, HoldInput, SlotAvailability};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+const DEFAULT_HOLD_TTL_SECS: u32 = 15 * 60;
+const DEFAULT_SEARCH_DAYS: i64 = 14;
+const MAX_SLOTS: usize = 10;
+
+// ============================================================================
+// BOOK
+// ============================================================================
+
+/// Slots and appointments for [`AppointmentTool`]
+#[async_trait]
+pub trait AppointmentBook: Send + Sync {
+    /// Slots with free seats starting between `from` and `to`
+    async fn available_slots(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<SlotAvailability>>;
+
+    /// Hold a seat in a slot
+    async fn hold(&self, hold: HoldInput) -> Result<Appointment>;
+
+    /// Confirm an appointment held in `conversation_id`; `None` when the
+    /// hold is gone or belongs to another conversation
+    async fn confirm(
+        &self,
+        appointment_id: Uuid,
+        conversation_id: Option<Uuid>,
+    ) -> Result<Option<Appointment>>;
+}
+
+#[async_trait]
+impl AppointmentBook for AppointmentRepository {
+    async fn available_slots(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<SlotAvailability>> {
+        let repo = self.clone();
+        tokio::task::spawn_blocking(move || repo.slots(from, to, true, limit))
+            .await
+            .map_err(|e| Error::Internal(e.to_string()))?
+    }
+
+    async fn hold(&self, hold: HoldInput) -> Result<Appointment> {
+        let repo = self.clone();
+        tokio::task::spawn_blocking(move || repo.hold(&hold))
+            .await
+            .map_err(|e| Error::Internal(e.to_string()))?
+    }
+
+    async fn confirm(
+        &self,
+        appointment_id: Uuid,
+        conversation_id: Option<Uuid>,
+    ) -> Result<Option<Appointment>> {
+        let repo = self.clone();
+        tokio::task::spawn_blocking(move || repo.confirm(&appointment_id, conversation_id))
+            .await
+            .map_err(|e| Error::Internal(e.to_string()))?
+    }
+}
+
+// ============================================================================
+// TOOL
+// ============================================================================
+
+/// Step of the booking flow
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BookingAction {
+    CheckAvailability,
+    Hold,
+    Confirm,
+}
+
+/// Appointment tool parameters
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppointmentParams {
+    pub action: BookingAction,
+    /// First day to search, `YYYY-MM-DD` in the business time zone
+    pub date_from: Option<String>,
+    /// Last day to search, inclusive
+    pub date_to: Option<String>,
+    pub slot_id: Option<String>,
+    pub name: Option<String>,
+    pub phone: Option<String>,
+    pub email: Option<String>,
+    pub notes: Option<String>,
+    pub appointment_id: Option<String>,
+}
+
+/// Books demo appointments into open slots
+pub struct AppointmentTool {
+    book: Arc<dyn AppointmentBook>,
+    conversation_id: Option<Uuid>,
+    hold_ttl_secs: u32,
+    utc_offset: FixedOffset,
+}
+
+impl AppointmentTool {
+    pub fn new(book: Arc<dyn AppointmentBook>) -> Self {
+        Self {
+            book,
+            conversation_id: None,
+            hold_ttl_secs: DEFAULT_HOLD_TTL_SECS,
+            utc_offset: FixedOffset::east_opt(7 * 3600).expect("valid offset"),
+        }
+    }
+
+    /// Link booked appointments to a conversation
+    pub fn with_conversation_id(mut self, conversation_id: Uuid) -> Self {
+        self.conversation_id = Some(conversation_id);
+        self
+    }
+
+    /// Set how long a held seat is kept unconfirmed
+    pub fn with_hold_ttl(mut self, secs: u32) -> Self {
+        self.hold_ttl_secs = secs;
+        self
+    }
+
+    /// Set the business time zone
+    pub fn with_utc_offset(mut self, offset: FixedOffset) -> Self {
+        self.utc_offset = offset;
+        self
+    }
+
+    /// Search window from the requested dates, never starting in the past
+    fn search_window(
+        &self,
+        params: &AppointmentParams,
+        now: DateTime<Utc>,
+    ) -> Result<(DateTime<Utc>, DateTime<Utc>)> {
+        let from = match &params.date_from {
+            Some(date) => self.start_of_day(parse_date(date)?).max(now),
+            None => now,
+        };
+        let to = match &params.date_to {
+            Some(date) => self.start_of_day(parse_date(date)? + Duration::days(1)),
+            None => from + Duration::days(DEFAULT_SEARCH_DAYS),
+        };
+        if to <= from {
+            return Err(Error::Validation(
+                "date_to must not be before date_from or in the past".to_string(),
+            ));
+        }
+        Ok((from, to))
+    }
+
+    fn start_of_day(&self, date: NaiveDate) -> DateTime<Utc> {
+        let midnight = date.and_hms_opt(0, 0, 0).expect("valid time");
+        (midnight - self.utc_offset).and_utc()
+    }
+
+    fn local(&self, time: DateTime<Utc>) -> String {
+        self.utc_offset
+            .from_utc_datetime(&time.naive_utc())
+            .to_rfc3339()
+    }
+
+    fn slot_json(&self, slot: &AppointmentSlot) -> serde_json::Value {
+        serde_json::json!({
+            "slot_id": slot.id,
+            "kind": slot.kind,
+            "starts_at": self.local(slot.starts_at),
+            "ends_at": self.local(slot.ends_at),
+            "location": slot.location,
+            "host": slot.host,
+        })
+    }
+
+    async fn check_availability(&self, params: &AppointmentParams) -> Result<serde_json::Value> {
+        let (from, to) = self.search_window(params, Utc::now())?;
+        let slots = self.book.available_slots(from, to, MAX_SLOTS).await?;
+
+        let results: Vec<serde_json::Value> = slots
+            .iter()
+            .map(|availability| {
+                let mut slot = self.slot_json(&availability.slot);
+                slot["remaining"] = availability.remaining.into();
+                slot
+            })
+            .collect();
+        let mut output = serde_json::json!({
+            "from": self.local(from),
+            "to": self.local(to),
+            "slots": results,
+            "total": results.len(),
+        });
+        if results.is_empty() {
+            output["message"] = "No open slots in this period".into();
+        }
+        Ok(output)
+    }
+
+    async fn hold(&self, params: AppointmentParams) -> Result<serde_json::Value> {
+        let slot_id = parse_id("slot_id", params.slot_id.as_deref())?;
+        let name = non_blank(params.name)
+            .ok_or_else(|| Error::Validation("Holding a slot needs the customer's name".into()))?;
+        let phone = non_blank(params.phone)
+            .map(|p| normalize_thai_phone(&p))
+            .transpose()?;
+        let email = non_blank(params.email)
+            .map(|e| normalize_email(&e))
+            .transpose()?;
+        if phone.is_none() && email.is_none() {
+            return Err(Error::Validation(
+                "Holding a slot needs a phone number or an email address".to_string(),
+            ));
+        }
+
+        let hold_expires_at = Utc::now() + Duration::seconds(i64::from(self.hold_ttl_secs));
+        let appointment = self
+            .book
+            .hold(HoldInput {
+                slot_id,
+                conversation_id: self.conversation_id,
+                customer_name: name,
+                customer_phone: phone,
+                customer_email: email,
+                notes: non_blank(params.notes),
+                hold_expires_at,
+            })
+            .await?;
+
+        Ok(serde_json::json!({
+            "appointment_id": appointment.id,
+            "slot_id": appointment.slot_id,
+            "status": appointment.status,
+            "hold_expires_at": self.local(hold_expires_at),
+            "message": "The slot is held; confirm the details with the customer, then call this tool with action confirm",
+        }))
+    }
+
+    async fn confirm(&self, params: AppointmentParams) -> Result<serde_json::Value> {
+        let appointment_id = parse_id("appointment_id", params.appointment_id.as_deref())?;
+        let appointment = self
+            .book
+            .confirm(appointment_id, self.conversation_id)
+            .await?
+            .ok_or_else(|| {
+                Error::Validation(
+                    "The hold has expired or does not exist; check availability and hold a slot again"
+                        .to_string(),
+                )
+            })?;
+
+        Ok(serde_json::json!({
+            "appointment_id": appointment.id,
+            "slot_id": appointment.slot_id,
+            "status": appointment.status,
+            "customer_name": appointment.customer_name,
+            "confirmed_at": appointment.confirmed_at.map(|at| self.local(at)),
+        }))
+    }
+}
+
+fn parse_date(value: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+        .map_err(|_| Error::Validation(format!("Invalid date (expected YYYY-MM-DD): {}", value)))
+}
+
+fn parse_id(field: &str, value: Option<&str>) -> Result<Uuid> {
+    let value = value.ok_or_else(|| Error::Validation(format!("{} is required", field)))?;
+    Uuid::parse_str(value.trim())
+        .map_err(|_| Error::Validation(format!("Invalid {}: {}", field, value)))
+}
+
+#[async_trait]
+impl Tool for AppointmentTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            name: "book_appointment".to_string(),
+            description: "นัดหมายสาธิตสินค้า: ตรวจสอบช่วงเวลาว่าง (check_availability), จองช่วงเวลาไว้ชั่วคราว (hold) และยืนยันการนัดหมายหลังลูกค้าตกลง (confirm)".to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "action": {
+                        "type": "string",
+                        "enum": ["check_availability", "hold", "confirm"],
+                        "description": "ขั้นตอนการนัดหมาย"
+                    },
+                    "date_from": {
+                        "type": "string",
+                        "description": "วันแรกที่ต้องการ (YYYY-MM-DD) สำหรับ check_availability"
+                    },
+                    "date_to": {
+                        "type": "string",
+                        "description": "วันสุดท้ายที่ต้องการ (YYYY-MM-DD) สำหรับ check_availability"
+                    },
+                    "slot_id": {
+                        "type": "string",
+                        "description": "รหัสช่วงเวลาจาก check_availability สำหรับ hold"
+                    },
+                    "name": {
+                        "type": "string",
+                        "description": "ชื่อลูกค้า สำหรับ hold"
+                    },
+                    "phone": {
+                        "type": "string",
+                        "description": "เบอร์โทรศัพท์ลูกค้า สำหรับ hold"
+                    },
+                    "email": {
+                        "type": "string",
+                        "description": "อีเมลลูกค้า สำหรับ hold"
+                    },
+                    "notes": {
+                        "type": "string",
+                        "description": "หมายเหตุ เช่น สินค้าที่ต้องการดู (optional)"
+                    },
+                    "appointment_id": {
+                        "type": "string",
+                        "description": "รหัสนัดหมายจาก hold สำหรับ confirm"
+                    }
+                },
+                "required": ["action"]
+            }),
+        }
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> Result<ToolResult> {
+        let params: AppointmentParams = serde_json::from_value(args)?;
+
+        let output = match params.action {
+            BookingAction::CheckAvailability => self.check_availability(&params).await?,
+            BookingAction::Hold => self.hold(params).await?,
+            BookingAction::Confirm => self.confirm(params).await?,
+        };
+
+        Ok(ToolResult {
+            tool_name: "book_appointment".to_string(),
+            output: serde_json::to_string(&output)?,
+            success: true,
+        })
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct StubBook {
+        slots: Vec<SlotAvailability>,
+        windows: Mutex<Vec<(DateTime<Utc>, DateTime<Utc>)>>,
+        holds: Mutex<Vec<HoldInput>>,
+        confirms: Mutex<Vec<(Uuid, Option<Uuid>)>>,
+    }
+
+    fn slot(starts_at: DateTime<Utc>) -> AppointmentSlot {
+        AppointmentSlot {
+            id: Uuid::new_v4(),
+            kind: "demo".to_string(),
+            starts_at,
+            ends_at: starts_at + Duration::hours(1),
+            capacity: 2,
+            location: Some("Bangkok showroom".to_string()),
+            host: None,
+            is_active: true,
+            metadata: serde_json::json!({}),
+            created_at: starts_at,
+            updated_at: starts_at,
+        }
+    }
+
+    fn appointment(hold: &HoldInput, status: &str) -> Appointment {
+        Appointment {
+            id: Uuid::nil(),
+            slot_id: hold.slot_id,
+            conversation_id: hold.conversation_id,
+            customer_name: hold.customer_name.clone(),
+            customer_phone: hold.customer_phone.clone(),
+            customer_email: hold.customer_email.clone(),
+            notes: hold.notes.clone(),
+            status: status.to_string(),
+            hold_expires_at: Some(hold.hold_expires_at),
+            confirmed_at: None,
+            metadata: serde_json::json!({}),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[async_trait]
+    impl AppointmentBook for StubBook {
+        async fn available_slots(
+            &self,
+            from: DateTime<Utc>,
+            to: DateTime<Utc>,
+            _limit: usize,
+        ) -> Result<Vec<SlotAvailability>> {
+            self.windows.lock().unwrap().push((from, to));
+            Ok(self.slots.clone())
+        }
+
+        async fn hold(&self, hold: HoldInput) -> Result<Appointment> {
+            let held = appointment(&hold, "held");
+            self.holds.lock().unwrap().push(hold);
+            Ok(held)
+        }
+
+        async fn confirm(
+            &self,
+            appointment_id: Uuid,
+            conversation_id: Option<Uuid>,
+        ) -> Result<Option<Appointment>> {
+            self.confirms
+                .lock()
+                .unwrap()
+                .push((appointment_id, conversation_id));
+            Ok(None)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_check_availability_uses_local_days() {
+        let starts_at = Utc.with_ymd_and_hms(2099, 3, 2, 3, 0, 0).unwrap();
+        let book = Arc::new(StubBook {
+            slots: vec![SlotAvailability {
+                slot: slot(starts_at),
+                remaining: 1,
+            }],
+            ..Default::default()
+        });
+        let tool = AppointmentTool::new(book.clone());
+
+        let result = tool
+            .execute(serde_json::json!({
+                "action": "check_availability",
+                "date_from": "2099-03-02",
+                "date_to": "2099-03-03"
+            }))
+            .await
+            .unwrap();
+
+        let output: serde_json::Value = serde_json::from_str(&result.output).unwrap();
+        assert_eq!(output["total"], 1);
+        assert_eq!(output["slots"][0]["starts_at"], "2099-03-02T10:00:00+07:00");
+        assert_eq!(output["slots"][0]["remaining"], 1);
+
+        let windows = book.windows.lock().unwrap();
+        assert_eq!(
+            windows[0],
+            (
+                Utc.with_ymd_and_hms(2099, 3, 1, 17, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2099, 3, 3, 17, 0, 0).unwrap()
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_hold_validates_contact_and_links_conversation() {
+        let book = Arc::new(StubBook::default());
+        let conversation_id = Uuid::new_v4();
+        let tool = AppointmentTool::new(book.clone())
+            .with_conversation_id(conversation_id)
+            .with_hold_ttl(600);
+        let slot_id = Uuid::new_v4();
+
+        let invalid = tool
+            .execute(serde_json::json!({
+                "action": "hold",
+                "slot_id": slot_id.to_string(),
+                "name": "Somchai",
+                "phone": "12345"
+            }))
+            .await;
+        assert!(matches!(invalid, Err(Error::Validation(_))));
+
+        let result = tool
+            .execute(serde_json::json!({
+                "action": "hold",
+                "slot_id": slot_id.to_string(),
+                "name": "Somchai",
+                "phone": "081-234-5678"
+            }))
+            .await
+            .unwrap();
+        let output: serde_json::Value = serde_json::from_str(&result.output).unwrap();
+        assert_eq!(output["status"], "held");
+
+        let holds = book.holds.lock().unwrap();
+        assert_eq!(holds.len(), 1);
+        assert_eq!(holds[0].slot_id, slot_id);
+        assert_eq!(holds[0].conversation_id, Some(conversation_id));
+        assert_eq!(holds[0].customer_phone.as_deref(), Some("+66812345678"));
+        assert!(holds[0].hold_expires_at <= Utc::now() + Duration::seconds(600));
+    }
+
+    #[tokio::test]
+    async fn test_confirm_reports_expired_hold() {
+        let book = Arc::new(StubBook::default());
+        let conversation_id = Uuid::new_v4();
+        let tool = AppointmentTool::new(book.clone()).with_conversation_id(conversation_id);
+        let appointment_id = Uuid::new_v4();
+
+        let result = tool
+            .execute(serde_json::json!({
+                "action": "confirm",
+                "appointment_id": appointment_id.to_string()
+            }))
+            .await;
+
+        assert!(matches!(result, Err(Error::Validation(_))));
+        assert_eq!(
+            *book.confirms.lock().unwrap(),
+            [(appointment_id, Some(conversation_id))]
+        );
+    }
+}
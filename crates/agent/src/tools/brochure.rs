@@ -1,9 +1,40 @@
 //! Brochure/document download tool for sales agent.
+//!
+//! Brochures come from a [`BrochureCatalog`], normally the `brochures` table
+//! via [`BrochureRepository`]. Only public brochures are offered, each with a
+//! time-limited download link from [`DownloadLinks`] that saves the file
+//! under its original name. Every link handed out counts as a download.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use agent::tools::BrochureTool;
+//! use db::BrochureRepository;
+//!
+//! let tool = BrochureTool::new()
+//!     .with_catalog(Arc::new(BrochureRepository::new(db_pool)))
+//!     .with_download_links(Arc::new(storage_client))
+//!     .with_link_ttl(15 * 60);
+//! ```
 
 use super::{Tool, ToolDefinition, ToolResult};
 use async_trait::async_trait;
-use common::Result;
+use common::{Error, Result};
+use db::models::Brochure;
+use db::{BrochureFilter, BrochureRepository};
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use storage::StorageClient;
+use uuid::Uuid;
+
+/// Number of brochures returned when the call sets no limit
+const DEFAULT_LIMIT: usize = 5;
+
+/// Upper bound on the number of brochures returned
+const MAX_LIMIT: usize = 20;
+
+/// Default lifetime of download links, in seconds
+const DEFAULT_LINK_TTL_SECS: u32 = 3600;
 
 /// Brochure search parameters
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,16 +53,153 @@ pub struct BrochureSearchParams {
     pub limit: Option<usize>,
 }
 
+impl BrochureSearchParams {
+    /// Catalog filter described by these parameters, public brochures only
+    pub fn filter(&self) -> Result<BrochureFilter> {
+        let product_id = self
+            .product_id
+            .as_deref()
+            .map(|id| {
+                Uuid::parse_str(id)
+                    .map_err(|_| Error::Validation(format!("Invalid product_id: {}", id)))
+            })
+            .transpose()?;
+
+        Ok(BrochureFilter {
+            query: self.query.clone().filter(|q| !q.trim().is_empty()),
+            product_id,
+            category: self.category.clone(),
+            file_type: self.file_type.clone(),
+            language: self.language.clone(),
+            is_public: Some(true),
+        })
+    }
+
+    /// Requested number of results, capped at `MAX_LIMIT`
+    pub fn limit(&self) -> usize {
+        self.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT)
+    }
+}
+
+/// A brochure offered for download
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BrochureLink {
+    pub id: Uuid,
+    pub title: String,
+    pub description: String,
+    pub file_name: String,
+    pub file_type: String,
+    pub file_size_bytes: i64,
+    pub category: String,
+    pub language: String,
+    pub download_url: String,
+    pub expires_in_seconds: u32,
+}
+
+// ============================================================================
+// CATALOG AND LINKS
+// ============================================================================
+
+/// Source of brochures for [`BrochureTool`]
+#[async_trait]
+pub trait BrochureCatalog: Send + Sync {
+    /// Brochures matching `filter`
+    async fn search_brochures(&self, filter: BrochureFilter, limit: usize)
+        -> Result<Vec<Brochure>>;
+
+    /// Count one download of a brochure
+    async fn record_download(&self, id: Uuid) -> Result<()>;
+}
+
+#[async_trait]
+impl BrochureCatalog for BrochureRepository {
+    async fn search_brochures(
+        &self,
+        filter: BrochureFilter,
+        limit: usize,
+    ) -> Result<Vec<Brochure>> {
+        let repo = self.clone();
+        tokio::task::spawn_blocking(move || repo.search(&filter, limit as i64))
+            .await
+            .map_err(|e| Error::Internal(e.to_string()))?
+    }
+
+    async fn record_download(&self, id: Uuid) -> Result<()> {
+        let repo = self.clone();
+        tokio::task::spawn_blocking(move || repo.increment_download_count(&id))
+            .await
+            .map_err(|e| Error::Internal(e.to_string()))?
+    }
+}
+
+/// Issuer of time-limited download links
+#[async_trait]
+pub trait DownloadLinks: Send + Sync {
+    /// URL downloading the file at `file_url` as `file_name`
+    async fn download_link(
+        &self,
+        file_url: &str,
+        file_name: &str,
+        expires_in: u32,
+    ) -> Result<String>;
+}
+
+#[async_trait]
+impl DownloadLinks for StorageClient {
+    async fn download_link(
+        &self,
+        file_url: &str,
+        file_name: &str,
+        expires_in: u32,
+    ) -> Result<String> {
+        let (bucket, key) = self
+            .object_location(file_url)
+            .ok_or_else(|| Error::NotFound(format!("Storage object for {}", file_url)))?;
+
+        let presigned = self
+            .download_url(&bucket, &key, Some(file_name), expires_in)
+            .await
+            .map_err(|e| Error::Internal(e.to_string()))?;
+        Ok(presigned.url)
+    }
+}
+
+// ============================================================================
+// TOOL
+// ============================================================================
+
 /// Brochure search and download tool
 pub struct BrochureTool {
-    // In production:
-    // - Brochure repository
-    // - File storage service
+    catalog: Option<Arc<dyn BrochureCatalog>>,
+    links: Option<Arc<dyn DownloadLinks>>,
+    link_ttl_secs: u32,
 }
 
 impl BrochureTool {
     pub fn new() -> Self {
-        Self {}
+        Self {
+            catalog: None,
+            links: None,
+            link_ttl_secs: DEFAULT_LINK_TTL_SECS,
+        }
+    }
+
+    /// Search brochures in `catalog`
+    pub fn with_catalog(mut self, catalog: Arc<dyn BrochureCatalog>) -> Self {
+        self.catalog = Some(catalog);
+        self
+    }
+
+    /// Issue download links with `links`
+    pub fn with_download_links(mut self, links: Arc<dyn DownloadLinks>) -> Self {
+        self.links = Some(links);
+        self
+    }
+
+    /// Set how long download links stay valid, in seconds
+    pub fn with_link_ttl(mut self, secs: u32) -> Self {
+        self.link_ttl_secs = secs;
+        self
     }
 }
 
@@ -82,18 +250,54 @@ impl Tool for BrochureTool {
     }
 
     async fn execute(&self, args: serde_json::Value) -> Result<ToolResult> {
-        let _params: BrochureSearchParams = serde_json::from_value(args)?;
+        let params: BrochureSearchParams = serde_json::from_value(args)?;
+        let (Some(catalog), Some(links)) = (&self.catalog, &self.links) else {
+            return Err(Error::Config(
+                "Brochure downloads are not configured".to_string(),
+            ));
+        };
 
-        // TODO: Implement actual brochure search
-        // 1. Query brochure database
-        // 2. Apply filters
-        // 3. Generate download URLs (presigned if needed)
-        // 4. Return brochure list with download links
+        let brochures = catalog
+            .search_brochures(params.filter()?, params.limit())
+            .await?;
+
+        let mut offered = Vec::with_capacity(brochures.len());
+        for brochure in brochures {
+            let download_url = match links
+                .download_link(&brochure.file_url, &brochure.file_name, self.link_ttl_secs)
+                .await
+            {
+                Ok(url) => url,
+                Err(e) => {
+                    tracing::warn!(brochure_id = %brochure.id, error = %e, "download link failed");
+                    continue;
+                }
+            };
+            if let Err(e) = catalog.record_download(brochure.id).await {
+                tracing::warn!(brochure_id = %brochure.id, error = %e, "download count not updated");
+            }
 
-        let output = serde_json::json!({
-            "brochures": [],
-            "message": "Brochure search will return download links"
+            offered.push(BrochureLink {
+                id: brochure.id,
+                title: brochure.title,
+                description: brochure.description,
+                file_name: brochure.file_name,
+                file_type: brochure.file_type,
+                file_size_bytes: brochure.file_size_bytes,
+                category: brochure.category,
+                language: brochure.language,
+                download_url,
+                expires_in_seconds: self.link_ttl_secs,
+            });
+        }
+
+        let mut output = serde_json::json!({
+            "total": offered.len(),
+            "brochures": offered,
         });
+        if offered.is_empty() {
+            output["message"] = "No brochures matched the search".into();
+        }
 
         Ok(ToolResult {
             tool_name: "get_brochure".to_string(),
@@ -102,3 +306,131 @@ impl Tool for BrochureTool {
         })
     }
 }
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// Catalog returning fixed brochures and recording filters and downloads
+    #[derive(Default)]
+    struct StubCatalog {
+        brochures: Vec<Brochure>,
+        filters: Mutex<Vec<BrochureFilter>>,
+        downloads: Mutex<Vec<Uuid>>,
+    }
+
+    #[async_trait]
+    impl BrochureCatalog for StubCatalog {
+        async fn search_brochures(
+            &self,
+            filter: BrochureFilter,
+            limit: usize,
+        ) -> Result<Vec<Brochure>> {
+            self.filters.lock().unwrap().push(filter);
+            Ok(self.brochures.iter().take(limit).cloned().collect())
+        }
+
+        async fn record_download(&self, id: Uuid) -> Result<()> {
+            self.downloads.lock().unwrap().push(id);
+            Ok(())
+        }
+    }
+
+    /// Links signing every file except those under `missing/`
+    struct StubLinks;
+
+    #[async_trait]
+    impl DownloadLinks for StubLinks {
+        async fn download_link(
+            &self,
+            file_url: &str,
+            file_name: &str,
+            expires_in: u32,
+        ) -> Result<String> {
+            if file_url.starts_with("missing/") {
+                return Err(Error::NotFound(file_url.to_string()));
+            }
+            Ok(format!(
+                "https://s3.example.com/{}?filename={}&expires={}",
+                file_url, file_name, expires_in
+            ))
+        }
+    }
+
+    fn brochure(title: &str, file_url: &str) -> Brochure {
+        let now = chrono::Utc::now();
+        Brochure {
+            id: Uuid::new_v4(),
+            title: title.to_string(),
+            description: String::new(),
+            file_name: format!("{}.pdf", title),
+            file_url: file_url.to_string(),
+            file_type: "pdf".to_string(),
+            file_size_bytes: 1024,
+            product_ids: serde_json::json!([]),
+            category: "brochure".to_string(),
+            language: "th".to_string(),
+            is_public: true,
+            download_count: 0,
+            metadata: serde_json::json!({}),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_returns_links_and_counts_downloads() {
+        let available = brochure("pump-x", "brochures/pump-x.pdf");
+        let catalog = Arc::new(StubCatalog {
+            brochures: vec![available.clone(), brochure("gone", "missing/gone.pdf")],
+            ..Default::default()
+        });
+        let tool = BrochureTool::new()
+            .with_catalog(catalog.clone())
+            .with_download_links(Arc::new(StubLinks))
+            .with_link_ttl(600);
+
+        let product_id = Uuid::new_v4();
+        let result = tool
+            .execute(serde_json::json!({
+                "query": "pump",
+                "product_id": product_id.to_string(),
+                "language": "th"
+            }))
+            .await
+            .unwrap();
+
+        let output: serde_json::Value = serde_json::from_str(&result.output).unwrap();
+        assert_eq!(output["total"], 1);
+        assert_eq!(output["brochures"][0]["file_name"], "pump-x.pdf");
+        assert_eq!(
+            output["brochures"][0]["download_url"],
+            "https://s3.example.com/brochures/pump-x.pdf?filename=pump-x.pdf&expires=600"
+        );
+        assert_eq!(output["brochures"][0]["expires_in_seconds"], 600);
+
+        let filters = catalog.filters.lock().unwrap();
+        assert_eq!(filters[0].product_id, Some(product_id));
+        assert_eq!(filters[0].language.as_deref(), Some("th"));
+        assert_eq!(filters[0].is_public, Some(true));
+        assert_eq!(*catalog.downloads.lock().unwrap(), vec![available.id]);
+    }
+
+    #[tokio::test]
+    async fn test_execute_rejects_invalid_product_id() {
+        let tool = BrochureTool::new()
+            .with_catalog(Arc::new(StubCatalog::default()))
+            .with_download_links(Arc::new(StubLinks));
+
+        let result = tool
+            .execute(serde_json::json!({"product_id": "pump-x"}))
+            .await;
+
+        assert!(matches!(result, Err(Error::Validation(_))));
+    }
+}
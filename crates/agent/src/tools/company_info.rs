@@ -1,9 +1,39 @@
 //! Company information tool for sales agent.
+//!
+//! Answers come from [`CompanyKnowledge`], normally the `company_info` and
+//! `faqs` tables via [`DbCompanyKnowledge`]. Lookups use the requested
+//! language (Thai by default) and fall back to English when nothing is
+//! found. FAQs are matched semantically when an embedding model is set, and
+//! every FAQ returned counts as a view.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use agent::tools::company_info::{CompanyInfoTool, DbCompanyKnowledge};
+//!
+//! let tool = CompanyInfoTool::new()
+//!     .with_knowledge(Arc::new(DbCompanyKnowledge::new(db_pool)))
+//!     .with_embedding_model(embedding_model);
+//! ```
 
 use super::{Tool, ToolDefinition, ToolResult};
 use async_trait::async_trait;
-use common::Result;
+use common::{Error, Result};
+use db::models::CompanyInfo;
+use db::{CompanyInfoRepository, DbPool, FaqRepository, ScoredFaq};
+use rag_core::embeddings::EmbeddingModel;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Language used when the call sets none
+const DEFAULT_LANGUAGE: &str = "th";
+
+/// Language tried when the requested one has no data
+const FALLBACK_LANGUAGE: &str = "en";
+
+/// Number of FAQs returned per lookup
+const FAQ_LIMIT: usize = 3;
 
 /// Company info query parameters
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,6 +42,35 @@ pub struct CompanyInfoParams {
     pub info_type: CompanyInfoType,
     /// Specific topic or question
     pub topic: Option<String>,
+    /// Preferred language (th, en)
+    pub language: Option<String>,
+}
+
+impl CompanyInfoParams {
+    /// Languages to try, in order
+    pub fn languages(&self) -> Vec<String> {
+        let requested = self
+            .language
+            .as_deref()
+            .map(str::trim)
+            .filter(|lang| !lang.is_empty())
+            .unwrap_or(DEFAULT_LANGUAGE)
+            .to_lowercase();
+
+        let mut languages = vec![requested];
+        if languages[0] != FALLBACK_LANGUAGE {
+            languages.push(FALLBACK_LANGUAGE.to_string());
+        }
+        languages
+    }
+
+    fn topic_terms(&self) -> Vec<String> {
+        self.topic
+            .iter()
+            .flat_map(|topic| topic.split_whitespace())
+            .map(str::to_lowercase)
+            .collect()
+    }
 }
 
 /// Types of company information
@@ -36,25 +95,256 @@ pub enum CompanyInfoType {
     General,
 }
 
+impl CompanyInfoType {
+    /// `company_info` category holding this type; `None` searches all
+    pub fn category(&self) -> Option<&'static str> {
+        match self {
+            Self::About => Some("about"),
+            Self::Contact => Some("contact"),
+            Self::Policy => Some("policy"),
+            Self::Service => Some("service"),
+            Self::Location => Some("location"),
+            Self::Hours => Some("hours"),
+            Self::Faq | Self::General => None,
+        }
+    }
+}
+
+/// A company info entry returned by the tool
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InfoEntry {
+    pub key: String,
+    pub value: String,
+    pub category: String,
+}
+
+impl From<CompanyInfo> for InfoEntry {
+    fn from(info: CompanyInfo) -> Self {
+        Self {
+            key: info.key,
+            value: info.value,
+            category: info.category,
+        }
+    }
+}
+
+/// An FAQ returned by the tool, ranked by `score`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FaqMatch {
+    pub id: Uuid,
+    pub question: String,
+    pub answer: String,
+    pub category: String,
+    pub score: f32,
+}
+
+impl From<ScoredFaq> for FaqMatch {
+    fn from(scored: ScoredFaq) -> Self {
+        Self {
+            id: scored.faq.id,
+            question: scored.faq.question,
+            answer: scored.faq.answer,
+            category: scored.faq.category,
+            score: scored.score,
+        }
+    }
+}
+
+// ============================================================================
+// KNOWLEDGE
+// ============================================================================
+
+/// Source of company information for [`CompanyInfoTool`]
+#[async_trait]
+pub trait CompanyKnowledge: Send + Sync {
+    /// Entries in `language`, optionally limited to one category
+    async fn entries(&self, category: Option<String>, language: String)
+        -> Result<Vec<CompanyInfo>>;
+
+    /// Active FAQs in `language` matching `query`, best match first
+    async fn search_faqs(
+        &self,
+        query: String,
+        embedding: Option<Vec<f32>>,
+        language: String,
+        limit: usize,
+    ) -> Result<Vec<ScoredFaq>>;
+
+    /// Count one view of each FAQ
+    async fn record_faq_views(&self, ids: Vec<Uuid>) -> Result<()>;
+}
+
+/// Company knowledge stored in the `company_info` and `faqs` tables
+#[derive(Clone)]
+pub struct DbCompanyKnowledge {
+    info: CompanyInfoRepository,
+    faqs: FaqRepository,
+}
+
+impl DbCompanyKnowledge {
+    pub fn new(pool: DbPool) -> Self {
+        Self {
+            info: CompanyInfoRepository::new(pool.clone()),
+            faqs: FaqRepository::new(pool),
+        }
+    }
+}
+
+#[async_trait]
+impl CompanyKnowledge for DbCompanyKnowledge {
+    async fn entries(
+        &self,
+        category: Option<String>,
+        language: String,
+    ) -> Result<Vec<CompanyInfo>> {
+        let info = self.info.clone();
+        tokio::task::spawn_blocking(move || info.list(category.as_deref(), &language))
+            .await
+            .map_err(|e| Error::Internal(e.to_string()))?
+    }
+
+    async fn search_faqs(
+        &self,
+        query: String,
+        embedding: Option<Vec<f32>>,
+        language: String,
+        limit: usize,
+    ) -> Result<Vec<ScoredFaq>> {
+        let faqs = self.faqs.clone();
+        tokio::task::spawn_blocking(move || {
+            faqs.search(&query, embedding.as_deref(), &language, limit)
+        })
+        .await
+        .map_err(|e| Error::Internal(e.to_string()))?
+    }
+
+    async fn record_faq_views(&self, ids: Vec<Uuid>) -> Result<()> {
+        let faqs = self.faqs.clone();
+        tokio::task::spawn_blocking(move || faqs.increment_view_counts(&ids))
+            .await
+            .map_err(|e| Error::Internal(e.to_string()))?
+    }
+}
+
+// ============================================================================
+// TOOL
+// ============================================================================
+
 /// Company information lookup tool
+#[derive(Default)]
 pub struct CompanyInfoTool {
-    // In production:
-    // - Company info repository
-    // - FAQ vector store
+    knowledge: Option<Arc<dyn CompanyKnowledge>>,
+    embedding_model: Option<Arc<dyn EmbeddingModel>>,
 }
 
 impl CompanyInfoTool {
     pub fn new() -> Self {
-        Self {}
+        Self::default()
+    }
+
+    /// Look information up in `knowledge`
+    pub fn with_knowledge(mut self, knowledge: Arc<dyn CompanyKnowledge>) -> Self {
+        self.knowledge = Some(knowledge);
+        self
+    }
+
+    /// Match FAQs semantically using question embeddings from `model`
+    pub fn with_embedding_model(mut self, model: Arc<dyn EmbeddingModel>) -> Self {
+        self.embedding_model = Some(model);
+        self
+    }
+
+    async fn embed_topic(&self, topic: &str) -> Option<Vec<f32>> {
+        let model = self.embedding_model.as_ref()?;
+        if topic.trim().is_empty() {
+            return None;
+        }
+
+        match model.embed(topic).await {
+            Ok(embedding) => Some(embedding),
+            Err(e) => {
+                tracing::warn!(error = %e, "topic embedding failed, using keyword search");
+                None
+            }
+        }
+    }
+
+    /// FAQs in the first language that has any, with that language
+    async fn lookup_faqs(
+        &self,
+        knowledge: &dyn CompanyKnowledge,
+        params: &CompanyInfoParams,
+    ) -> Result<Option<(String, Vec<FaqMatch>)>> {
+        let topic = params.topic.clone().unwrap_or_default();
+        let embedding = self.embed_topic(&topic).await;
+
+        for language in params.languages() {
+            let faqs = knowledge
+                .search_faqs(
+                    topic.clone(),
+                    embedding.clone(),
+                    language.clone(),
+                    FAQ_LIMIT,
+                )
+                .await?;
+            if faqs.is_empty() {
+                continue;
+            }
+
+            let ids = faqs.iter().map(|scored| scored.faq.id).collect();
+            if let Err(e) = knowledge.record_faq_views(ids).await {
+                tracing::warn!(error = %e, "FAQ view counts not updated");
+            }
+            return Ok(Some((language, faqs.into_iter().map(Into::into).collect())));
+        }
+        Ok(None)
     }
-}
 
-impl Default for CompanyInfoTool {
-    fn default() -> Self {
-        Self::new()
+    /// Entries in the first language that has any, with that language
+    ///
+    /// With a topic, entries mentioning it are preferred. A general lookup
+    /// returns only those; a typed lookup falls back to its whole category.
+    async fn lookup_entries(
+        &self,
+        knowledge: &dyn CompanyKnowledge,
+        params: &CompanyInfoParams,
+    ) -> Result<Option<(String, Vec<InfoEntry>)>> {
+        let category = params.info_type.category();
+        let terms = params.topic_terms();
+
+        for language in params.languages() {
+            let entries = knowledge
+                .entries(category.map(str::to_string), language.clone())
+                .await?;
+
+            let (matching, other): (Vec<_>, Vec<_>) = entries
+                .into_iter()
+                .partition(|entry| mentions(entry, &terms));
+            let found = if !matching.is_empty() {
+                matching
+            } else if terms.is_empty() || category.is_some() {
+                other
+            } else {
+                Vec::new()
+            };
+
+            if !found.is_empty() {
+                return Ok(Some((
+                    language,
+                    found.into_iter().map(Into::into).collect(),
+                )));
+            }
+        }
+        Ok(None)
     }
 }
 
+/// Whether an entry mentions any of the topic terms
+fn mentions(entry: &CompanyInfo, terms: &[String]) -> bool {
+    let text = format!("{} {}", entry.key, entry.value).to_lowercase();
+    terms.iter().any(|term| text.contains(term.as_str()))
+}
+
 #[async_trait]
 impl Tool for CompanyInfoTool {
     fn definition(&self) -> ToolDefinition {
@@ -72,6 +362,11 @@ impl Tool for CompanyInfoTool {
                     "topic": {
                         "type": "string",
                         "description": "หัวข้อหรือคำถามเฉพาะ (optional)"
+                    },
+                    "language": {
+                        "type": "string",
+                        "description": "ภาษา เช่น th, en",
+                        "default": "th"
                     }
                 },
                 "required": ["info_type"]
@@ -81,19 +376,37 @@ impl Tool for CompanyInfoTool {
 
     async fn execute(&self, args: serde_json::Value) -> Result<ToolResult> {
         let params: CompanyInfoParams = serde_json::from_value(args)?;
+        let knowledge = self
+            .knowledge
+            .as_deref()
+            .ok_or_else(|| Error::Config("Company knowledge is not configured".to_string()))?;
 
-        // TODO: Implement company info lookup
-        // 1. Query company info database
-        // 2. If FAQ, search vector store
-        // 3. Return relevant information
-
-        let output = serde_json::json!({
-            "info_type": format!("{:?}", params.info_type),
+        let mut output = serde_json::json!({
+            "info_type": params.info_type,
             "topic": params.topic,
-            "data": {},
-            "message": "Company info will be retrieved from knowledge base"
         });
 
+        let found = match params.info_type {
+            CompanyInfoType::Faq => self
+                .lookup_faqs(knowledge, &params)
+                .await?
+                .map(|(language, faqs)| (language, "faqs", serde_json::to_value(faqs))),
+            _ => self
+                .lookup_entries(knowledge, &params)
+                .await?
+                .map(|(language, entries)| (language, "entries", serde_json::to_value(entries))),
+        };
+
+        match found {
+            Some((language, field, data)) => {
+                output["language"] = language.into();
+                output[field] = data?;
+            }
+            None => {
+                output["message"] = "No company information found".into();
+            }
+        }
+
         Ok(ToolResult {
             tool_name: "company_info".to_string(),
             output: serde_json::to_string(&output)?,
@@ -101,3 +414,183 @@ impl Tool for CompanyInfoTool {
         })
     }
 }
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use db::models::Faq;
+    use std::sync::Mutex;
+
+    /// In-memory knowledge recording FAQ views
+    #[derive(Default)]
+    struct StubKnowledge {
+        entries: Vec<CompanyInfo>,
+        faqs: Vec<Faq>,
+        views: Mutex<Vec<Uuid>>,
+    }
+
+    #[async_trait]
+    impl CompanyKnowledge for StubKnowledge {
+        async fn entries(
+            &self,
+            category: Option<String>,
+            language: String,
+        ) -> Result<Vec<CompanyInfo>> {
+            Ok(self
+                .entries
+                .iter()
+                .filter(|e| e.language == language)
+                .filter(|e| category.as_ref().map_or(true, |c| &e.category == c))
+                .cloned()
+                .collect())
+        }
+
+        async fn search_faqs(
+            &self,
+            query: String,
+            _embedding: Option<Vec<f32>>,
+            language: String,
+            limit: usize,
+        ) -> Result<Vec<ScoredFaq>> {
+            let query = query.to_lowercase();
+            Ok(self
+                .faqs
+                .iter()
+                .filter(|f| f.language == language && f.question.to_lowercase().contains(&query))
+                .take(limit)
+                .map(|f| ScoredFaq {
+                    faq: f.clone(),
+                    score: 1.0,
+                })
+                .collect())
+        }
+
+        async fn record_faq_views(&self, ids: Vec<Uuid>) -> Result<()> {
+            self.views.lock().unwrap().extend(ids);
+            Ok(())
+        }
+    }
+
+    fn entry(key: &str, value: &str, category: &str, language: &str) -> CompanyInfo {
+        let now = chrono::Utc::now();
+        CompanyInfo {
+            id: Uuid::new_v4(),
+            key: key.to_string(),
+            value: value.to_string(),
+            category: category.to_string(),
+            language: language.to_string(),
+            metadata: serde_json::json!({}),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn faq(question: &str, answer: &str, language: &str) -> Faq {
+        let now = chrono::Utc::now();
+        Faq {
+            id: Uuid::new_v4(),
+            question: question.to_string(),
+            answer: answer.to_string(),
+            category: "general".to_string(),
+            language: language.to_string(),
+            is_active: true,
+            view_count: 0,
+            metadata: serde_json::json!({}),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    async fn run(tool: &CompanyInfoTool, args: serde_json::Value) -> serde_json::Value {
+        let result = tool.execute(args).await.unwrap();
+        serde_json::from_str(&result.output).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_entries_fall_back_to_english() {
+        let knowledge = StubKnowledge {
+            entries: vec![
+                entry("phone", "02-123-4567", "contact", "en"),
+                entry("about", "Founded in 1999", "about", "th"),
+            ],
+            ..Default::default()
+        };
+        let tool = CompanyInfoTool::new().with_knowledge(Arc::new(knowledge));
+
+        let output = run(&tool, serde_json::json!({"info_type": "contact"})).await;
+
+        assert_eq!(output["info_type"], "contact");
+        assert_eq!(output["language"], "en");
+        assert_eq!(output["entries"][0]["value"], "02-123-4567");
+    }
+
+    #[tokio::test]
+    async fn test_entries_prefer_topic_matches() {
+        let knowledge = StubKnowledge {
+            entries: vec![
+                entry("return_policy", "คืนสินค้าได้ภายใน 7 วัน", "policy", "th"),
+                entry("warranty_policy", "รับประกัน 1 ปี", "policy", "th"),
+            ],
+            ..Default::default()
+        };
+        let tool = CompanyInfoTool::new().with_knowledge(Arc::new(knowledge));
+
+        let output = run(
+            &tool,
+            serde_json::json!({"info_type": "policy", "topic": "warranty"}),
+        )
+        .await;
+        assert_eq!(output["entries"].as_array().unwrap().len(), 1);
+        assert_eq!(output["entries"][0]["key"], "warranty_policy");
+
+        let output = run(
+            &tool,
+            serde_json::json!({"info_type": "policy", "topic": "shipping"}),
+        )
+        .await;
+        assert_eq!(output["entries"].as_array().unwrap().len(), 2);
+
+        let output = run(
+            &tool,
+            serde_json::json!({"info_type": "general", "topic": "shipping"}),
+        )
+        .await;
+        assert!(output["message"].is_string());
+    }
+
+    #[tokio::test]
+    async fn test_faqs_fall_back_and_count_views() {
+        let shipping = faq("How long is shipping?", "2-3 days", "en");
+        let knowledge = Arc::new(StubKnowledge {
+            faqs: vec![faq("ส่งกี่วัน", "2-3 วัน", "th"), shipping.clone()],
+            ..Default::default()
+        });
+        let tool = CompanyInfoTool::new().with_knowledge(knowledge.clone());
+
+        let output = run(
+            &tool,
+            serde_json::json!({"info_type": "faq", "topic": "shipping", "language": "th"}),
+        )
+        .await;
+
+        assert_eq!(output["language"], "en");
+        assert_eq!(output["faqs"][0]["answer"], "2-3 days");
+        assert_eq!(*knowledge.views.lock().unwrap(), vec![shipping.id]);
+    }
+
+    #[test]
+    fn test_languages() {
+        let params = |language: Option<&str>| CompanyInfoParams {
+            info_type: CompanyInfoType::General,
+            topic: None,
+            language: language.map(str::to_string),
+        };
+
+        assert_eq!(params(None).languages(), vec!["th", "en"]);
+        assert_eq!(params(Some("EN")).languages(), vec!["en"]);
+    }
+}
@@ -0,0 +1,185 @@
+//! Human handoff tool for sales agent.
+//!
+//! Escalates the conversation to a live agent when the bot cannot help or
+//! the customer asks for a person. The conversation moves to
+//! `waiting_human` and stays there until an operator claims it; the bot
+//! does not answer it again until the operator hands it back.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use agent::tools::handoff::HandoffTool;
+//! use worker::RedisHandoffQueue;
+//!
+//! let tool = HandoffTool::new(Arc::new(RedisHandoffQueue::new(redis_pool, db_pool)))
+//!     .with_conversation_id(conversation_id);
+//! ```
+
+use super::{Tool, ToolDefinition, ToolResult};
+use async_trait::async_trait;
+use common::models::{ConversationState, HandoffRequest};
+use common::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Queue of conversations waiting for a live agent
+#[async_trait]
+pub trait HandoffQueue: Send + Sync {
+    /// Hand a conversation answered by the bot to the operators
+    ///
+    /// Returns the state of the conversation afterwards. A conversation
+    /// already waiting for or handled by an operator is not queued again.
+    async fn request_handoff(&self, request: HandoffRequest) -> Result<ConversationState>;
+}
+
+/// Handoff tool parameters
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HandoffParams {
+    /// Why the conversation needs a person
+    pub reason: String,
+    /// What the customer needs, for the operator
+    pub summary: Option<String>,
+}
+
+/// Escalates the conversation to a live agent
+pub struct HandoffTool {
+    queue: Arc<dyn HandoffQueue>,
+    conversation_id: Option<Uuid>,
+}
+
+impl HandoffTool {
+    pub fn new(queue: Arc<dyn HandoffQueue>) -> Self {
+        Self {
+            queue,
+            conversation_id: None,
+        }
+    }
+
+    /// Conversation to hand off
+    pub fn with_conversation_id(mut self, conversation_id: Uuid) -> Self {
+        self.conversation_id = Some(conversation_id);
+        self
+    }
+}
+
+#[async_trait]
+impl Tool for HandoffTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            name: "handoff".to_string(),
+            description:
+                "ส่งต่อการสนทนาให้เจ้าหน้าที่ (คน) ดูแลต่อ ใช้เมื่อลูกค้าขอคุยกับเจ้าหน้าที่ หรือเมื่อไม่สามารถช่วยลูกค้าได้"
+                    .to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "reason": {
+                        "type": "string",
+                        "description": "เหตุผลที่ต้องส่งต่อ เช่น ลูกค้าขอคุยกับเจ้าหน้าที่, ต้องการต่อรองราคา"
+                    },
+                    "summary": {
+                        "type": "string",
+                        "description": "สรุปสิ่งที่ลูกค้าต้องการ เพื่อให้เจ้าหน้าที่ไม่ต้องถามซ้ำ (optional)"
+                    }
+                },
+                "required": ["reason"]
+            }),
+        }
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> Result<ToolResult> {
+        let params: HandoffParams = serde_json::from_value(args)?;
+        let conversation_id = self.conversation_id.ok_or_else(|| {
+            Error::Validation("Handoff needs a conversation to hand off".to_string())
+        })?;
+        let reason = params.reason.trim();
+        if reason.is_empty() {
+            return Err(Error::Validation("A handoff needs a reason".to_string()));
+        }
+
+        let mut request = HandoffRequest::new(conversation_id, reason);
+        if let Some(summary) = params.summary.filter(|s| !s.trim().is_empty()) {
+            request = request.with_summary(summary.trim());
+        }
+        let state = self.queue.request_handoff(request).await?;
+
+        let message = match state {
+            ConversationState::Human => "An operator is already handling this conversation",
+            _ => "A live agent will reply in this conversation shortly",
+        };
+        let output = serde_json::json!({
+            "conversation_id": conversation_id,
+            "state": state,
+            "message": message,
+        });
+
+        Ok(ToolResult {
+            tool_name: "handoff".to_string(),
+            output: serde_json::to_string(&output)?,
+            success: true,
+        })
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct StubQueue {
+        requests: Mutex<Vec<HandoffRequest>>,
+    }
+
+    #[async_trait]
+    impl HandoffQueue for StubQueue {
+        async fn request_handoff(&self, request: HandoffRequest) -> Result<ConversationState> {
+            self.requests.lock().unwrap().push(request);
+            Ok(ConversationState::WaitingHuman)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_queues_conversation() {
+        let queue = Arc::new(StubQueue::default());
+        let conversation_id = Uuid::new_v4();
+        let tool = HandoffTool::new(queue.clone()).with_conversation_id(conversation_id);
+
+        let result = tool
+            .execute(serde_json::json!({
+                "reason": " Customer asks for a person ",
+                "summary": "Wants a price for 200 pumps"
+            }))
+            .await
+            .unwrap();
+
+        let output: serde_json::Value = serde_json::from_str(&result.output).unwrap();
+        assert_eq!(output["state"], "waiting_human");
+
+        let requests = queue.requests.lock().unwrap();
+        assert_eq!(requests[0].conversation_id, conversation_id);
+        assert_eq!(requests[0].reason, "Customer asks for a person");
+        assert_eq!(
+            requests[0].summary.as_deref(),
+            Some("Wants a price for 200 pumps")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_execute_requires_conversation() {
+        let queue = Arc::new(StubQueue::default());
+        let tool = HandoffTool::new(queue.clone());
+
+        let result = tool
+            .execute(serde_json::json!({"reason": "Customer asks for a person"}))
+            .await;
+
+        assert!(matches!(result, Err(Error::Validation(_))));
+        assert!(queue.requests.lock().unwrap().is_empty());
+    }
+}
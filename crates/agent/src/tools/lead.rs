@@ -0,0 +1,390 @@
+//! Lead capture tool for sales agent.
+//!
+//! Stores the contact details of a customer who wants to hear from the sales
+//! team. A lead is only stored once the customer has consented to be
+//! contacted; the time of consent is recorded with it. Phone numbers are
+//! validated as Thai numbers and stored in E.164 form (`+66812345678`).
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use agent::tools::lead::LeadCaptureTool;
+//! use db::LeadRepository;
+//!
+//! let tool = LeadCaptureTool::new(Arc::new(LeadRepository::new(db_pool)))
+//!     .with_conversation_id(conversation_id);
+//! ```
+
+use super::{Tool, ToolDefinition, ToolResult};
+use async_trait::async_trait;
+use common::{Error, Result};
+use db::{LeadInput, LeadRepository};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+// ============================================================================
+// VALIDATION
+// ============================================================================
+
+/// Validate a Thai phone number, returning it in E.164 form
+///
+/// Accepts national (`081-234-5678`, `02 123 4567`) and international
+/// (`+66 81 234 5678`) formats. Mobile numbers have 10 digits starting with
+/// 06, 08 or 09; landlines have 9 digits starting with 02-05 or 07.
+pub fn normalize_thai_phone(input: &str) -> Result<String> {
+    let invalid = || Error::Validation(format!("Invalid Thai phone number: {}", input));
+
+    let compact: String = input
+        .chars()
+        .filter(|c| !matches!(c, ' ' | '-' | '(' | ')' | '.'))
+        .collect();
+    let digits = compact.strip_prefix('+').unwrap_or(&compact);
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return Err(invalid());
+    }
+
+    let national = match digits.strip_prefix("66") {
+        Some(rest) if !rest.starts_with('0') => format!("0{}", rest),
+        Some(_) => return Err(invalid()),
+        None if compact.starts_with('+') => return Err(invalid()),
+        None => digits.to_string(),
+    };
+
+    let valid = match national.as_bytes() {
+        [b'0', b'6' | b'8' | b'9', ..] => national.len() == 10,
+        [b'0', b'2'..=b'5' | b'7', ..] => national.len() == 9,
+        _ => false,
+    };
+    if !valid {
+        return Err(invalid());
+    }
+    Ok(format!("+66{}", &national[1..]))
+}
+
+/// Validate an email address, lowercasing its domain
+pub fn normalize_email(input: &str) -> Result<String> {
+    let email = input.trim();
+    let invalid = || Error::Validation(format!("Invalid email address: {}", input));
+
+    let (local, domain) = email.split_once('@').ok_or_else(invalid)?;
+    let local_valid = !local.is_empty()
+        && local.len() <= 64
+        && !local.starts_with('.')
+        && !local.ends_with('.')
+        && local
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "!#$%&'*+-/=?^_`{|}~.".contains(c));
+    let labels: Vec<&str> = domain.split('.').collect();
+    let domain_valid = labels.len() >= 2
+        && labels.iter().all(|label| {
+            !label.is_empty()
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        });
+    if !local_valid || !domain_valid || email.len() > 255 {
+        return Err(invalid());
+    }
+    Ok(format!("{}@{}", local, domain.to_ascii_lowercase()))
+}
+
+/// Trimmed value, or `None` when blank
+pub(super) fn non_blank(value: Option<String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+// ============================================================================
+// STORE
+// ============================================================================
+
+/// Lead storage for [`LeadCaptureTool`]
+#[async_trait]
+pub trait LeadStore: Send + Sync {
+    /// Persist a validated lead, returning its ID
+    async fn save_lead(&self, lead: LeadInput) -> Result<Uuid>;
+}
+
+#[async_trait]
+impl LeadStore for LeadRepository {
+    async fn save_lead(&self, lead: LeadInput) -> Result<Uuid> {
+        let repo = self.clone();
+        let lead = tokio::task::spawn_blocking(move || repo.create(&lead))
+            .await
+            .map_err(|e| Error::Internal(e.to_string()))??;
+        Ok(lead.id)
+    }
+}
+
+// ============================================================================
+// TOOL
+// ============================================================================
+
+/// Lead capture tool parameters
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeadParams {
+    pub name: String,
+    pub company: Option<String>,
+    pub phone: Option<String>,
+    pub email: Option<String>,
+    /// Names or IDs of the products the customer asked about
+    #[serde(default)]
+    pub interested_products: Vec<String>,
+    pub notes: Option<String>,
+    /// Whether the customer agreed to be contacted by the sales team
+    #[serde(default)]
+    pub consent: bool,
+}
+
+impl LeadParams {
+    /// Validate the parameters into a lead of `conversation_id`
+    pub fn into_input(self, conversation_id: Option<Uuid>) -> Result<LeadInput> {
+        if !self.consent {
+            return Err(Error::Validation(
+                "The customer has not consented to be contacted; ask for consent before capturing the lead".to_string(),
+            ));
+        }
+        let name = non_blank(Some(self.name))
+            .ok_or_else(|| Error::Validation("A lead needs a name".to_string()))?;
+        let phone = non_blank(self.phone)
+            .map(|p| normalize_thai_phone(&p))
+            .transpose()?;
+        let email = non_blank(self.email)
+            .map(|e| normalize_email(&e))
+            .transpose()?;
+        if phone.is_none() && email.is_none() {
+            return Err(Error::Validation(
+                "A lead needs a phone number or an email address".to_string(),
+            ));
+        }
+
+        Ok(LeadInput {
+            conversation_id,
+            name,
+            company: non_blank(self.company),
+            phone,
+            email,
+            interested_products: self
+                .interested_products
+                .into_iter()
+                .filter_map(|p| non_blank(Some(p)))
+                .collect(),
+            notes: non_blank(self.notes),
+            consent: true,
+        })
+    }
+}
+
+/// Stores customer contact details for the sales team
+pub struct LeadCaptureTool {
+    store: Arc<dyn LeadStore>,
+    conversation_id: Option<Uuid>,
+}
+
+impl LeadCaptureTool {
+    pub fn new(store: Arc<dyn LeadStore>) -> Self {
+        Self {
+            store,
+            conversation_id: None,
+        }
+    }
+
+    /// Link captured leads to a conversation
+    pub fn with_conversation_id(mut self, conversation_id: Uuid) -> Self {
+        self.conversation_id = Some(conversation_id);
+        self
+    }
+}
+
+#[async_trait]
+impl Tool for LeadCaptureTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            name: "capture_lead".to_string(),
+            description: "บันทึกข้อมูลติดต่อของลูกค้าที่ต้องการให้ทีมขายติดต่อกลับ ต้องได้รับความยินยอมจากลูกค้าก่อนเสมอ และต้องมีเบอร์โทรศัพท์หรืออีเมลอย่างน้อยหนึ่งอย่าง".to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "name": {
+                        "type": "string",
+                        "description": "ชื่อลูกค้า"
+                    },
+                    "company": {
+                        "type": "string",
+                        "description": "ชื่อบริษัท (optional)"
+                    },
+                    "phone": {
+                        "type": "string",
+                        "description": "เบอร์โทรศัพท์ เช่น 081-234-5678 หรือ 02-123-4567"
+                    },
+                    "email": {
+                        "type": "string",
+                        "description": "อีเมล"
+                    },
+                    "interested_products": {
+                        "type": "array",
+                        "items": {"type": "string"},
+                        "description": "ชื่อหรือรหัสสินค้าที่ลูกค้าสนใจ"
+                    },
+                    "notes": {
+                        "type": "string",
+                        "description": "รายละเอียดเพิ่มเติม เช่น ช่วงเวลาที่สะดวกให้ติดต่อ (optional)"
+                    },
+                    "consent": {
+                        "type": "boolean",
+                        "description": "ลูกค้ายินยอมให้ทีมขายติดต่อกลับหรือไม่"
+                    }
+                },
+                "required": ["name", "consent"]
+            }),
+        }
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> Result<ToolResult> {
+        let params: LeadParams = serde_json::from_value(args)?;
+        let lead = params.into_input(self.conversation_id)?;
+
+        let mut output = serde_json::json!({
+            "name": lead.name,
+            "company": lead.company,
+            "phone": lead.phone,
+            "email": lead.email,
+            "interested_products": lead.interested_products,
+            "consent": lead.consent,
+        });
+        let lead_id = self.store.save_lead(lead).await?;
+        output["lead_id"] = serde_json::json!(lead_id);
+        output["status"] = "new".into();
+
+        Ok(ToolResult {
+            tool_name: "capture_lead".to_string(),
+            output: serde_json::to_string(&output)?,
+            success: true,
+        })
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct StubStore {
+        leads: Mutex<Vec<LeadInput>>,
+    }
+
+    #[async_trait]
+    impl LeadStore for StubStore {
+        async fn save_lead(&self, lead: LeadInput) -> Result<Uuid> {
+            self.leads.lock().unwrap().push(lead);
+            Ok(Uuid::nil())
+        }
+    }
+
+    #[test]
+    fn test_normalize_thai_phone() {
+        assert_eq!(
+            normalize_thai_phone("081-234-5678").unwrap(),
+            "+66812345678"
+        );
+        assert_eq!(
+            normalize_thai_phone("+66 81 234 5678").unwrap(),
+            "+66812345678"
+        );
+        assert_eq!(normalize_thai_phone("66812345678").unwrap(), "+66812345678");
+        assert_eq!(
+            normalize_thai_phone("(02) 123 4567").unwrap(),
+            "+6621234567"
+        );
+
+        for invalid in [
+            "12345",
+            "081-234-567",
+            "02-123-45678",
+            "011-234-5678",
+            "+1 415 555 0100",
+            "+66 081 234 5678",
+            "08x-234-5678",
+        ] {
+            assert!(
+                normalize_thai_phone(invalid).is_err(),
+                "{} should be rejected",
+                invalid
+            );
+        }
+    }
+
+    #[test]
+    fn test_normalize_email() {
+        assert_eq!(
+            normalize_email(" Somchai.K@Example.CO.TH ").unwrap(),
+            "Somchai.K@example.co.th"
+        );
+        for invalid in [
+            "somchai",
+            "@example.com",
+            "somchai@example",
+            "a b@example.com",
+            "a@b@c.com",
+        ] {
+            assert!(
+                normalize_email(invalid).is_err(),
+                "{} should be rejected",
+                invalid
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_stores_lead_with_conversation() {
+        let store = Arc::new(StubStore::default());
+        let conversation_id = Uuid::new_v4();
+        let tool = LeadCaptureTool::new(store.clone()).with_conversation_id(conversation_id);
+
+        let result = tool
+            .execute(serde_json::json!({
+                "name": " Somchai ",
+                "company": "",
+                "phone": "089 123 4567",
+                "interested_products": ["Pump X"],
+                "consent": true
+            }))
+            .await
+            .unwrap();
+
+        let output: serde_json::Value = serde_json::from_str(&result.output).unwrap();
+        assert_eq!(output["lead_id"], Uuid::nil().to_string());
+        assert_eq!(output["phone"], "+66891234567");
+
+        let leads = store.leads.lock().unwrap();
+        assert_eq!(leads[0].conversation_id, Some(conversation_id));
+        assert_eq!(leads[0].name, "Somchai");
+        assert_eq!(leads[0].company, None);
+        assert!(leads[0].consent);
+    }
+
+    #[tokio::test]
+    async fn test_execute_requires_consent_and_contact() {
+        let store = Arc::new(StubStore::default());
+        let tool = LeadCaptureTool::new(store.clone());
+
+        let without_consent = tool
+            .execute(serde_json::json!({"name": "Somchai", "email": "somchai@example.com"}))
+            .await;
+        assert!(matches!(without_consent, Err(Error::Validation(_))));
+
+        let without_contact = tool
+            .execute(serde_json::json!({"name": "Somchai", "consent": true}))
+            .await;
+        assert!(matches!(without_contact, Err(Error::Validation(_))));
+
+        assert!(store.leads.lock().unwrap().is_empty());
+    }
+}
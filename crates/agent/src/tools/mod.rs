@@ -4,16 +4,30 @@
 //! - `product_search`: ค้นหาและแนะนำสินค้า
 //! - `get_brochure`: ค้นหาและให้ลิงก์ดาวน์โหลดเอกสาร
 //! - `company_info`: ค้นหาข้อมูลบริษัท, FAQ, นโยบาย
+//! - `quote`: คำนวณราคาและสร้างร่างใบเสนอราคา
+//! - `capture_lead`: บันทึกข้อมูลติดต่อลูกค้าพร้อมความยินยอม
+//! - `handoff`: ส่งต่อการสนทนาให้เจ้าหน้าที่
+//! - `book_appointment`: ตรวจสอบเวลาว่างและนัดหมายสาธิตสินค้า
 //! - `search`: ค้นหาทั่วไปใน knowledge base
 
+pub mod appointment;
 pub mod brochure;
 pub mod company_info;
+pub mod handoff;
+pub mod lead;
+pub mod policy;
 pub mod product_search;
+pub mod quote;
 pub mod search;
 
-pub use brochure::BrochureTool;
-pub use company_info::CompanyInfoTool;
-pub use product_search::ProductSearchTool;
+pub use appointment::{AppointmentBook, AppointmentTool};
+pub use brochure::{BrochureCatalog, BrochureTool, DownloadLinks};
+pub use company_info::{CompanyInfoTool, CompanyKnowledge, DbCompanyKnowledge};
+pub use handoff::{HandoffQueue, HandoffTool};
+pub use lead::{LeadCaptureTool, LeadStore};
+pub use policy::ToolPolicy;
+pub use product_search::{ProductCatalog, ProductSearchTool};
+pub use quote::{DbQuoteStore, QuoteConfig, QuoteTool};
 pub use search::SearchTool;
 
 // Tool trait for defining custom tools
@@ -21,7 +35,12 @@ pub use search::SearchTool;
 
 use async_trait::async_trait;
 use common::Result;
+use db::{AppointmentRepository, BrochureRepository, DbPool, LeadRepository, ProductRepository};
+use rag_core::{DocumentRetriever, EmbeddingModel};
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use storage::StorageClient;
+use uuid::Uuid;
 
 /// Tool definition
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -58,3 +77,49 @@ pub fn create_sales_agent_tools() -> Vec<Box<dyn Tool>> {
         Box::new(SearchTool::new()),
     ]
 }
+
+/// Create the sales agent tools backed by the database and object storage
+///
+/// Leads and appointments captured by the tools are linked to `conversation_id`.
+/// With `embedding_model`, product and FAQ search also rank by semantic
+/// similarity. Knowledge base search is offered only with a `retriever`.
+pub fn create_db_sales_agent_tools(
+    pool: DbPool,
+    storage: StorageClient,
+    embedding_model: Option<Arc<dyn EmbeddingModel>>,
+    retriever: Option<Arc<dyn DocumentRetriever>>,
+    conversation_id: Option<Uuid>,
+) -> Vec<Box<dyn Tool>> {
+    let mut lead_capture = LeadCaptureTool::new(Arc::new(LeadRepository::new(pool.clone())));
+    let mut booking = AppointmentTool::new(Arc::new(AppointmentRepository::new(pool.clone())));
+    if let Some(conversation_id) = conversation_id {
+        lead_capture = lead_capture.with_conversation_id(conversation_id);
+        booking = booking.with_conversation_id(conversation_id);
+    }
+
+    let mut product_search =
+        ProductSearchTool::new().with_catalog(Arc::new(ProductRepository::new(pool.clone())));
+    let mut company_info =
+        CompanyInfoTool::new().with_knowledge(Arc::new(DbCompanyKnowledge::new(pool.clone())));
+    if let Some(model) = &embedding_model {
+        product_search = product_search.with_embedding_model(model.clone());
+        company_info = company_info.with_embedding_model(model.clone());
+    }
+
+    let mut tools: Vec<Box<dyn Tool>> = vec![
+        Box::new(product_search),
+        Box::new(
+            BrochureTool::new()
+                .with_catalog(Arc::new(BrochureRepository::new(pool.clone())))
+                .with_download_links(Arc::new(storage)),
+        ),
+        Box::new(company_info),
+        Box::new(QuoteTool::new(Arc::new(DbQuoteStore::new(pool.clone())))),
+        Box::new(lead_capture),
+        Box::new(booking),
+    ];
+    if let Some(retriever) = retriever {
+        tools.push(Box::new(SearchTool::new().with_retriever(retriever)));
+    }
+    tools
+}
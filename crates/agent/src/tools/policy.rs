@@ -0,0 +1,214 @@
+//! Execution policy for tool calls: timeouts, cancellation, output limits.
+//!
+//! A [`ToolPolicy`] bounds how long a tool may run (globally or per tool) and
+//! how much output it may return. Outputs over the limit are cut and end with
+//! a truncation marker so the model knows it saw only part of the result.
+//!
+//! Running calls are dropped when their [`CancellationToken`] is cancelled,
+//! e.g. when a ReAct run is aborted.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use agent::tools::policy::ToolPolicy;
+//!
+//! let policy = ToolPolicy::new()
+//!     .with_timeout_ms(10_000)
+//!     .with_tool_timeout_ms("web_search", 30_000)
+//!     .with_max_output_chars(4_000);
+//!
+//! let config = ReActConfig::builder().tool_policy(policy).build();
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::time::Duration;
+
+pub use tokio_util::sync::CancellationToken;
+
+// ============================================================================
+// POLICY
+// ============================================================================
+
+/// Limits applied to every tool call
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ToolPolicy {
+    /// Timeout for tools without an override, in milliseconds
+    pub timeout_ms: Option<u64>,
+
+    /// Per-tool timeouts in milliseconds, keyed by tool name
+    pub tool_timeouts_ms: HashMap<String, u64>,
+
+    /// Maximum characters of tool output passed on
+    pub max_output_chars: Option<usize>,
+}
+
+impl ToolPolicy {
+    /// Policy without limits
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the default timeout
+    pub fn with_timeout_ms(mut self, ms: u64) -> Self {
+        self.timeout_ms = Some(ms);
+        self
+    }
+
+    /// Set the timeout of one tool
+    pub fn with_tool_timeout_ms(mut self, tool_name: impl Into<String>, ms: u64) -> Self {
+        self.tool_timeouts_ms.insert(tool_name.into(), ms);
+        self
+    }
+
+    /// Set the output limit
+    pub fn with_max_output_chars(mut self, chars: usize) -> Self {
+        self.max_output_chars = Some(chars);
+        self
+    }
+
+    /// Timeout of a tool: its override, else the default
+    pub fn timeout_for(&self, tool_name: &str) -> Option<Duration> {
+        self.tool_timeouts_ms
+            .get(tool_name)
+            .copied()
+            .or(self.timeout_ms)
+            .map(Duration::from_millis)
+    }
+
+    /// Apply the output limit, returning the output and whether it was cut
+    pub fn limit_output(&self, output: String) -> (String, bool) {
+        match self.max_output_chars {
+            Some(max) => truncate_output(output, max),
+            None => (output, false),
+        }
+    }
+}
+
+/// Cut `output` to `max_chars` characters, appending a truncation marker
+pub fn truncate_output(output: String, max_chars: usize) -> (String, bool) {
+    let Some((cut, _)) = output.char_indices().nth(max_chars) else {
+        return (output, false);
+    };
+
+    let omitted = output[cut..].chars().count();
+    let mut truncated = output;
+    truncated.truncate(cut);
+    truncated.push_str(&format!("\n[... truncated {} characters]", omitted));
+    (truncated, true)
+}
+
+// ============================================================================
+// BOUNDED EXECUTION
+// ============================================================================
+
+/// Why a tool call did not finish
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interrupted {
+    /// The timeout elapsed
+    TimedOut(Duration),
+
+    /// The call was cancelled
+    Cancelled,
+}
+
+/// Run a tool call until it finishes, times out or is cancelled
+///
+/// The call future is dropped on timeout or cancellation.
+pub async fn run_bounded<F: Future>(
+    call: F,
+    timeout: Option<Duration>,
+    cancel: &CancellationToken,
+) -> Result<F::Output, Interrupted> {
+    let bounded = async {
+        match timeout {
+            Some(limit) => tokio::time::timeout(limit, call)
+                .await
+                .map_err(|_| Interrupted::TimedOut(limit)),
+            None => Ok(call.await),
+        }
+    };
+
+    tokio::select! {
+        result = bounded => result,
+        _ = cancel.cancelled() => Err(Interrupted::Cancelled),
+    }
+}
+
+/// The shorter of two optional timeouts
+pub fn min_timeout(a: Option<Duration>, b: Option<Duration>) -> Option<Duration> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timeout_for_prefers_override() {
+        let policy = ToolPolicy::new()
+            .with_timeout_ms(1_000)
+            .with_tool_timeout_ms("slow", 5_000);
+
+        assert_eq!(policy.timeout_for("slow"), Some(Duration::from_secs(5)));
+        assert_eq!(policy.timeout_for("fast"), Some(Duration::from_secs(1)));
+        assert_eq!(ToolPolicy::new().timeout_for("fast"), None);
+    }
+
+    #[test]
+    fn test_truncate_output_on_char_boundary() {
+        let (output, truncated) = truncate_output("สวัสดีครับ".to_string(), 6);
+        assert!(truncated);
+        assert_eq!(output, "สวัสดี\n[... truncated 4 characters]");
+
+        let (output, truncated) = truncate_output("short".to_string(), 10);
+        assert!(!truncated);
+        assert_eq!(output, "short");
+    }
+
+    #[tokio::test]
+    async fn test_run_bounded_times_out() {
+        let result = run_bounded(
+            tokio::time::sleep(Duration::from_secs(5)),
+            Some(Duration::from_millis(10)),
+            &CancellationToken::new(),
+        )
+        .await;
+
+        assert_eq!(
+            result,
+            Err(Interrupted::TimedOut(Duration::from_millis(10)))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_bounded_cancels() {
+        let cancel = CancellationToken::new();
+        let trigger = cancel.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            trigger.cancel();
+        });
+
+        let result = run_bounded(tokio::time::sleep(Duration::from_secs(5)), None, &cancel).await;
+        assert_eq!(result, Err(Interrupted::Cancelled));
+    }
+
+    #[test]
+    fn test_min_timeout() {
+        let a = Some(Duration::from_secs(1));
+        let b = Some(Duration::from_secs(2));
+        assert_eq!(min_timeout(a, b), a);
+        assert_eq!(min_timeout(None, b), b);
+        assert_eq!(min_timeout(None, None), None);
+    }
+}
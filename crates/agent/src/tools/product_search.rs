@@ -1,9 +1,35 @@
 //! Product search and recommendation tool for sales agent.
+//!
+//! Products come from a [`ProductCatalog`], normally the `products` table via
+//! [`ProductRepository`]. With an embedding model set, the query is embedded
+//! and products are ranked by similarity to their stored embeddings; without
+//! one, or if embedding fails, ranking falls back to keyword matches.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use agent::tools::ProductSearchTool;
+//! use db::ProductRepository;
+//!
+//! let tool = ProductSearchTool::new()
+//!     .with_catalog(Arc::new(ProductRepository::new(db_pool)))
+//!     .with_embedding_model(embedding_model);
+//! ```
 
 use super::{Tool, ToolDefinition, ToolResult};
 use async_trait::async_trait;
-use common::Result;
+use common::{Error, Result};
+use db::{ProductFilter, ProductRepository, ScoredProduct};
+use rag_core::embeddings::EmbeddingModel;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Number of products returned when the call sets no limit
+const DEFAULT_LIMIT: usize = 5;
+
+/// Upper bound on the number of products returned
+const MAX_LIMIT: usize = 20;
 
 /// Product search parameters
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -15,26 +41,139 @@ pub struct ProductSearchParams {
     /// Filter by price range
     pub min_price: Option<f64>,
     pub max_price: Option<f64>,
+    /// Filter by availability; only active products when unset
+    pub is_active: Option<bool>,
     /// Maximum number of results
     pub limit: Option<usize>,
 }
 
+impl ProductSearchParams {
+    /// Catalog filter described by these parameters
+    pub fn filter(&self) -> ProductFilter {
+        ProductFilter {
+            category: self.category.clone(),
+            min_price: self.min_price,
+            max_price: self.max_price,
+            is_active: Some(self.is_active.unwrap_or(true)),
+        }
+    }
+
+    /// Requested number of results, capped at `MAX_LIMIT`
+    pub fn limit(&self) -> usize {
+        self.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT)
+    }
+}
+
+/// A product returned by the search, ranked by `score`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProductMatch {
+    pub id: Uuid,
+    pub name: String,
+    pub description: String,
+    pub category: String,
+    pub price: Option<f64>,
+    pub currency: Option<String>,
+    pub features: Vec<String>,
+    pub image_urls: Vec<String>,
+    pub score: f32,
+}
+
+impl From<ScoredProduct> for ProductMatch {
+    fn from(scored: ScoredProduct) -> Self {
+        let product = scored.product;
+        Self {
+            price: product.price_f64(),
+            features: product.feature_list(),
+            image_urls: product.image_url_list(),
+            id: product.id,
+            name: product.name,
+            description: product.description,
+            category: product.category,
+            currency: product.currency,
+            score: scored.score,
+        }
+    }
+}
+
+// ============================================================================
+// CATALOG
+// ============================================================================
+
+/// Source of products for [`ProductSearchTool`]
+#[async_trait]
+pub trait ProductCatalog: Send + Sync {
+    /// Products matching `filter`, best match first
+    async fn search_products(
+        &self,
+        query: &str,
+        embedding: Option<Vec<f32>>,
+        filter: ProductFilter,
+        limit: usize,
+    ) -> Result<Vec<ProductMatch>>;
+}
+
+#[async_trait]
+impl ProductCatalog for ProductRepository {
+    async fn search_products(
+        &self,
+        query: &str,
+        embedding: Option<Vec<f32>>,
+        filter: ProductFilter,
+        limit: usize,
+    ) -> Result<Vec<ProductMatch>> {
+        let repo = self.clone();
+        let query = query.to_string();
+        let products = tokio::task::spawn_blocking(move || {
+            repo.search(&query, embedding.as_deref(), &filter, limit)
+        })
+        .await
+        .map_err(|e| Error::Internal(e.to_string()))??;
+
+        Ok(products.into_iter().map(Into::into).collect())
+    }
+}
+
+// ============================================================================
+// TOOL
+// ============================================================================
+
 /// Product search tool for finding and recommending products
+#[derive(Default)]
 pub struct ProductSearchTool {
-    // In production, this would hold reference to:
-    // - Product repository
-    // - Vector store for semantic search
+    catalog: Option<Arc<dyn ProductCatalog>>,
+    embedding_model: Option<Arc<dyn EmbeddingModel>>,
 }
 
 impl ProductSearchTool {
     pub fn new() -> Self {
-        Self {}
+        Self::default()
     }
-}
 
-impl Default for ProductSearchTool {
-    fn default() -> Self {
-        Self::new()
+    /// Search products in `catalog`
+    pub fn with_catalog(mut self, catalog: Arc<dyn ProductCatalog>) -> Self {
+        self.catalog = Some(catalog);
+        self
+    }
+
+    /// Rank products semantically using query embeddings from `model`
+    pub fn with_embedding_model(mut self, model: Arc<dyn EmbeddingModel>) -> Self {
+        self.embedding_model = Some(model);
+        self
+    }
+
+    async fn embed_query(&self, query: &str) -> Option<Vec<f32>> {
+        let model = self.embedding_model.as_ref()?;
+        if query.trim().is_empty() {
+            return None;
+        }
+
+        match model.embed(query).await {
+            Ok(embedding) => Some(embedding),
+            Err(e) => {
+                tracing::warn!(error = %e, "query embedding failed, using keyword search");
+                None
+            }
+        }
     }
 }
 
@@ -65,6 +204,11 @@ impl Tool for ProductSearchTool {
                         "type": "number",
                         "description": "ราคาสูงสุด (optional)"
                     },
+                    "is_active": {
+                        "type": "boolean",
+                        "description": "ค้นหาเฉพาะสินค้าที่ยังจำหน่ายอยู่",
+                        "default": true
+                    },
                     "limit": {
                         "type": "integer",
                         "description": "จำนวนผลลัพธ์สูงสุด",
@@ -78,18 +222,24 @@ impl Tool for ProductSearchTool {
 
     async fn execute(&self, args: serde_json::Value) -> Result<ToolResult> {
         let params: ProductSearchParams = serde_json::from_value(args)?;
+        let catalog = self
+            .catalog
+            .as_ref()
+            .ok_or_else(|| Error::Config("Product catalog is not configured".to_string()))?;
 
-        // TODO: Implement actual product search
-        // 1. Generate embedding for query
-        // 2. Search vector store for similar products
-        // 3. Apply filters (category, price range)
-        // 4. Return ranked results
+        let embedding = self.embed_query(&params.query).await;
+        let products = catalog
+            .search_products(&params.query, embedding, params.filter(), params.limit())
+            .await?;
 
-        let output = serde_json::json!({
+        let mut output = serde_json::json!({
             "query": params.query,
-            "products": [],
-            "message": "Product search will be implemented with vector store"
+            "total": products.len(),
+            "products": products,
         });
+        if products.is_empty() {
+            output["message"] = "No products matched the search".into();
+        }
 
         Ok(ToolResult {
             tool_name: "product_search".to_string(),
@@ -98,3 +248,139 @@ impl Tool for ProductSearchTool {
         })
     }
 }
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// Query, embedding, filter and limit of one catalog call
+    type CatalogCall = (String, Option<Vec<f32>>, ProductFilter, usize);
+
+    /// Catalog returning fixed products and recording each call
+    #[derive(Default)]
+    struct StubCatalog {
+        products: Vec<ProductMatch>,
+        calls: Mutex<Vec<CatalogCall>>,
+    }
+
+    #[async_trait]
+    impl ProductCatalog for StubCatalog {
+        async fn search_products(
+            &self,
+            query: &str,
+            embedding: Option<Vec<f32>>,
+            filter: ProductFilter,
+            limit: usize,
+        ) -> Result<Vec<ProductMatch>> {
+            self.calls
+                .lock()
+                .unwrap()
+                .push((query.to_string(), embedding, filter, limit));
+            Ok(self.products.iter().take(limit).cloned().collect())
+        }
+    }
+
+    struct FixedEmbedding(Vec<f32>);
+
+    #[async_trait]
+    impl EmbeddingModel for FixedEmbedding {
+        async fn embed(&self, _text: &str) -> Result<Vec<f32>> {
+            Ok(self.0.clone())
+        }
+
+        async fn embed_batch(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+            Ok(texts.iter().map(|_| self.0.clone()).collect())
+        }
+
+        fn dimension(&self) -> usize {
+            self.0.len()
+        }
+    }
+
+    fn pump() -> ProductMatch {
+        ProductMatch {
+            id: Uuid::new_v4(),
+            name: "Pump X".to_string(),
+            description: "Stainless water pump".to_string(),
+            category: "pumps".to_string(),
+            price: Some(990.0),
+            currency: Some("THB".to_string()),
+            features: vec!["1 HP".to_string()],
+            image_urls: vec!["https://cdn.example.com/pump-x.png".to_string()],
+            score: 0.9,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_returns_ranked_products() {
+        let catalog = Arc::new(StubCatalog {
+            products: vec![pump()],
+            ..Default::default()
+        });
+        let tool = ProductSearchTool::new().with_catalog(catalog.clone());
+
+        let result = tool
+            .execute(serde_json::json!({
+                "query": "water pump",
+                "category": "pumps",
+                "max_price": 1000.0
+            }))
+            .await
+            .unwrap();
+
+        let output: serde_json::Value = serde_json::from_str(&result.output).unwrap();
+        assert_eq!(output["total"], 1);
+        assert_eq!(output["products"][0]["name"], "Pump X");
+        assert_eq!(output["products"][0]["currency"], "THB");
+        assert_eq!(
+            output["products"][0]["image_urls"][0],
+            "https://cdn.example.com/pump-x.png"
+        );
+
+        let calls = catalog.calls.lock().unwrap();
+        let (query, embedding, filter, limit) = &calls[0];
+        assert_eq!(query, "water pump");
+        assert_eq!(*embedding, None);
+        assert_eq!(filter.category.as_deref(), Some("pumps"));
+        assert_eq!(filter.max_price, Some(1000.0));
+        assert_eq!(filter.is_active, Some(true));
+        assert_eq!(*limit, DEFAULT_LIMIT);
+    }
+
+    #[tokio::test]
+    async fn test_execute_embeds_query() {
+        let catalog = Arc::new(StubCatalog::default());
+        let tool = ProductSearchTool::new()
+            .with_catalog(catalog.clone())
+            .with_embedding_model(Arc::new(FixedEmbedding(vec![1.0, 0.0])));
+
+        let result = tool
+            .execute(serde_json::json!({"query": "pump", "is_active": false, "limit": 100}))
+            .await
+            .unwrap();
+
+        let output: serde_json::Value = serde_json::from_str(&result.output).unwrap();
+        assert_eq!(output["total"], 0);
+        assert!(output["message"].is_string());
+
+        let calls = catalog.calls.lock().unwrap();
+        let (_, embedding, filter, limit) = &calls[0];
+        assert_eq!(*embedding, Some(vec![1.0, 0.0]));
+        assert_eq!(filter.is_active, Some(false));
+        assert_eq!(*limit, MAX_LIMIT);
+    }
+
+    #[tokio::test]
+    async fn test_execute_without_catalog_fails() {
+        let result = ProductSearchTool::new()
+            .execute(serde_json::json!({"query": "pump"}))
+            .await;
+
+        assert!(matches!(result, Err(Error::Config(_))));
+    }
+}
@@ -0,0 +1,626 @@
+//! Quote calculator tool for sales agent.
+//!
+//! Prices the requested products from the catalog, applies volume discounts
+//! and VAT, and stores the result as a draft quote, so the agent quotes
+//! totals computed here instead of doing arithmetic itself. Catalog prices
+//! are taken as VAT-exclusive. Amounts are computed in minor units (satang,
+//! cents) and rounded per line.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use agent::tools::quote::{DbQuoteStore, QuoteConfig, QuoteTool, VolumeDiscount};
+//!
+//! let config = QuoteConfig::default()
+//!     .with_vat_rate(7.0)
+//!     .with_volume_discount(VolumeDiscount::new(10, 5.0))
+//!     .with_volume_discount(VolumeDiscount::new(50, 10.0));
+//!
+//! let tool = QuoteTool::new(Arc::new(DbQuoteStore::new(db_pool))).with_config(config);
+//! ```
+
+use super::{Tool, ToolDefinition, ToolResult};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use common::{Error, Result};
+use db::models::Product;
+use db::{DbPool, ProductRepository, QuoteDraft, QuoteRepository};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+// ============================================================================
+// CONFIGURATION
+// ============================================================================
+
+/// Discount applied to a line from a minimum quantity
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VolumeDiscount {
+    pub min_quantity: u32,
+    /// Discount in percent of the line subtotal
+    pub percent: f64,
+}
+
+impl VolumeDiscount {
+    pub fn new(min_quantity: u32, percent: f64) -> Self {
+        Self {
+            min_quantity,
+            percent,
+        }
+    }
+}
+
+/// Pricing rules of the quote tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuoteConfig {
+    /// VAT rate in percent
+    pub vat_rate: f64,
+
+    /// Volume discount tiers; a line gets the highest tier it reaches
+    pub volume_discounts: Vec<VolumeDiscount>,
+
+    /// Currency of products without one
+    pub default_currency: String,
+
+    /// Days a quote stays valid
+    pub valid_days: u32,
+}
+
+impl Default for QuoteConfig {
+    fn default() -> Self {
+        Self {
+            vat_rate: 7.0,
+            volume_discounts: Vec::new(),
+            default_currency: "THB".to_string(),
+            valid_days: 30,
+        }
+    }
+}
+
+impl QuoteConfig {
+    pub fn with_vat_rate(mut self, percent: f64) -> Self {
+        self.vat_rate = percent;
+        self
+    }
+
+    pub fn with_volume_discount(mut self, discount: VolumeDiscount) -> Self {
+        self.volume_discounts.push(discount);
+        self
+    }
+
+    pub fn with_default_currency(mut self, currency: impl Into<String>) -> Self {
+        self.default_currency = currency.into();
+        self
+    }
+
+    pub fn with_valid_days(mut self, days: u32) -> Self {
+        self.valid_days = days;
+        self
+    }
+
+    /// Discount percent for a line of `quantity` units
+    pub fn discount_percent(&self, quantity: u32) -> f64 {
+        self.volume_discounts
+            .iter()
+            .filter(|tier| quantity >= tier.min_quantity)
+            .max_by_key(|tier| tier.min_quantity)
+            .map_or(0.0, |tier| tier.percent)
+    }
+}
+
+// ============================================================================
+// CALCULATION
+// ============================================================================
+
+/// The pricing facts of a product needed to quote it
+#[derive(Debug, Clone, PartialEq)]
+pub struct PricedProduct {
+    pub id: Uuid,
+    pub name: String,
+    pub price: Option<f64>,
+    pub currency: Option<String>,
+    pub is_active: bool,
+}
+
+impl From<Product> for PricedProduct {
+    fn from(product: Product) -> Self {
+        Self {
+            price: product.price_f64(),
+            id: product.id,
+            name: product.name,
+            currency: product.currency,
+            is_active: product.is_active,
+        }
+    }
+}
+
+/// One priced line of a quote, amounts in minor units
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuoteLine {
+    pub product_id: Uuid,
+    pub name: String,
+    pub quantity: u32,
+    pub unit_price: i64,
+    pub subtotal: i64,
+    pub discount_percent: f64,
+    pub discount: i64,
+    pub total: i64,
+}
+
+/// Totals of a quote, amounts in minor units
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuoteCalculation {
+    pub currency: String,
+    pub lines: Vec<QuoteLine>,
+    pub subtotal: i64,
+    pub discount_total: i64,
+    pub vat_rate: f64,
+    pub vat_amount: i64,
+    pub total: i64,
+}
+
+impl QuoteCalculation {
+    /// Price `items` of (product, quantity) under `config`
+    pub fn compute(items: &[(PricedProduct, u32)], config: &QuoteConfig) -> Result<Self> {
+        if items.is_empty() {
+            return Err(Error::Validation(
+                "A quote needs at least one item".to_string(),
+            ));
+        }
+
+        let mut currency: Option<String> = None;
+        let mut lines = Vec::with_capacity(items.len());
+        for (product, quantity) in items {
+            if *quantity == 0 {
+                return Err(Error::Validation(format!(
+                    "Quantity of '{}' must be at least 1",
+                    product.name
+                )));
+            }
+            if !product.is_active {
+                return Err(Error::Validation(format!(
+                    "Product '{}' is no longer available",
+                    product.name
+                )));
+            }
+            let price = product.price.ok_or_else(|| {
+                Error::Validation(format!(
+                    "Product '{}' has no list price; a sales representative must quote it",
+                    product.name
+                ))
+            })?;
+
+            let product_currency = product
+                .currency
+                .clone()
+                .unwrap_or_else(|| config.default_currency.clone());
+            match &currency {
+                Some(c) if *c != product_currency => {
+                    return Err(Error::Validation(format!(
+                        "Cannot quote '{}' priced in {} together with items priced in {}",
+                        product.name, product_currency, c
+                    )));
+                }
+                Some(_) => {}
+                None => currency = Some(product_currency),
+            }
+
+            let unit_price = to_minor_units(price);
+            let subtotal = unit_price * i64::from(*quantity);
+            let discount_percent = config.discount_percent(*quantity);
+            let discount = percent_of(subtotal, discount_percent);
+            lines.push(QuoteLine {
+                product_id: product.id,
+                name: product.name.clone(),
+                quantity: *quantity,
+                unit_price,
+                subtotal,
+                discount_percent,
+                discount,
+                total: subtotal - discount,
+            });
+        }
+
+        let subtotal = lines.iter().map(|line| line.subtotal).sum();
+        let discount_total = lines.iter().map(|line| line.discount).sum();
+        let taxable = lines.iter().map(|line| line.total).sum();
+        let vat_amount = percent_of(taxable, config.vat_rate);
+
+        Ok(Self {
+            currency: currency.unwrap_or_else(|| config.default_currency.clone()),
+            lines,
+            subtotal,
+            discount_total,
+            vat_rate: config.vat_rate,
+            vat_amount,
+            total: taxable + vat_amount,
+        })
+    }
+
+    /// Draft to persist, with line items as JSON
+    pub fn to_draft(&self, notes: Option<String>, valid_until: DateTime<Utc>) -> QuoteDraft {
+        QuoteDraft {
+            currency: self.currency.clone(),
+            items: self.lines_json(),
+            subtotal: self.subtotal,
+            discount_total: self.discount_total,
+            vat_rate: self.vat_rate,
+            vat_amount: self.vat_amount,
+            total: self.total,
+            notes,
+            valid_until,
+        }
+    }
+
+    /// One-line description of the quote
+    pub fn summary(&self) -> String {
+        let units: u32 = self.lines.iter().map(|line| line.quantity).sum();
+        let mut summary = format!(
+            "{} item(s), {} unit(s): subtotal {} {}",
+            self.lines.len(),
+            units,
+            format_amount(self.subtotal),
+            self.currency
+        );
+        if self.discount_total > 0 {
+            summary.push_str(&format!(
+                ", discount -{}",
+                format_amount(self.discount_total)
+            ));
+        }
+        summary.push_str(&format!(
+            ", VAT {}% {}, total {} {}",
+            self.vat_rate,
+            format_amount(self.vat_amount),
+            format_amount(self.total),
+            self.currency
+        ));
+        summary
+    }
+
+    fn lines_json(&self) -> serde_json::Value {
+        self.lines
+            .iter()
+            .map(|line| {
+                serde_json::json!({
+                    "product_id": line.product_id,
+                    "name": line.name,
+                    "quantity": line.quantity,
+                    "unit_price": to_major_units(line.unit_price),
+                    "subtotal": to_major_units(line.subtotal),
+                    "discount_percent": line.discount_percent,
+                    "discount": to_major_units(line.discount),
+                    "total": to_major_units(line.total),
+                })
+            })
+            .collect()
+    }
+}
+
+fn to_minor_units(amount: f64) -> i64 {
+    (amount * 100.0).round() as i64
+}
+
+fn to_major_units(amount: i64) -> f64 {
+    amount as f64 / 100.0
+}
+
+/// `percent` of a minor-unit amount, rounded half away from zero
+fn percent_of(amount: i64, percent: f64) -> i64 {
+    (amount as f64 * percent / 100.0).round() as i64
+}
+
+fn format_amount(amount: i64) -> String {
+    format!("{:.2}", to_major_units(amount))
+}
+
+// ============================================================================
+// STORE
+// ============================================================================
+
+/// Products and quote storage for [`QuoteTool`]
+#[async_trait]
+pub trait QuoteStore: Send + Sync {
+    /// Products with the given IDs; unknown IDs are left out
+    async fn products(&self, ids: Vec<Uuid>) -> Result<Vec<PricedProduct>>;
+
+    /// Persist a draft quote, returning its ID
+    async fn save_draft(&self, draft: QuoteDraft) -> Result<Uuid>;
+}
+
+/// Quote store backed by the `products` and `quotes` tables
+#[derive(Clone)]
+pub struct DbQuoteStore {
+    products: ProductRepository,
+    quotes: QuoteRepository,
+}
+
+impl DbQuoteStore {
+    pub fn new(pool: DbPool) -> Self {
+        Self {
+            products: ProductRepository::new(pool.clone()),
+            quotes: QuoteRepository::new(pool),
+        }
+    }
+}
+
+#[async_trait]
+impl QuoteStore for DbQuoteStore {
+    async fn products(&self, ids: Vec<Uuid>) -> Result<Vec<PricedProduct>> {
+        let products = self.products.clone();
+        let found = tokio::task::spawn_blocking(move || products.get_many(&ids))
+            .await
+            .map_err(|e| Error::Internal(e.to_string()))??;
+        Ok(found.into_iter().map(Into::into).collect())
+    }
+
+    async fn save_draft(&self, draft: QuoteDraft) -> Result<Uuid> {
+        let quotes = self.quotes.clone();
+        let quote = tokio::task::spawn_blocking(move || quotes.create_draft(&draft))
+            .await
+            .map_err(|e| Error::Internal(e.to_string()))??;
+        Ok(quote.id)
+    }
+}
+
+// ============================================================================
+// TOOL
+// ============================================================================
+
+/// A requested quote line
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuoteItemParams {
+    pub product_id: String,
+    pub quantity: u32,
+}
+
+/// Quote tool parameters
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuoteParams {
+    pub items: Vec<QuoteItemParams>,
+    /// Notes stored with the quote, e.g. delivery requirements
+    pub notes: Option<String>,
+}
+
+/// Price calculator creating draft quotes
+pub struct QuoteTool {
+    store: Arc<dyn QuoteStore>,
+    config: QuoteConfig,
+}
+
+impl QuoteTool {
+    pub fn new(store: Arc<dyn QuoteStore>) -> Self {
+        Self {
+            store,
+            config: QuoteConfig::default(),
+        }
+    }
+
+    pub fn with_config(mut self, config: QuoteConfig) -> Self {
+        self.config = config;
+        self
+    }
+}
+
+#[async_trait]
+impl Tool for QuoteTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            name: "quote".to_string(),
+            description: "คำนวณใบเสนอราคาจากรหัสสินค้าและจำนวน รวมส่วนลดและ VAT แล้วบันทึกเป็นร่างใบเสนอราคา ใช้ตัวเลขจากเครื่องมือนี้เท่านั้นเมื่อแจ้งราคารวม".to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "items": {
+                        "type": "array",
+                        "description": "รายการสินค้า (รวมค่าติดตั้งหากเป็นสินค้าในระบบ)",
+                        "minItems": 1,
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "product_id": {
+                                    "type": "string",
+                                    "description": "รหัสสินค้าจาก product_search"
+                                },
+                                "quantity": {
+                                    "type": "integer",
+                                    "minimum": 1,
+                                    "description": "จำนวน"
+                                }
+                            },
+                            "required": ["product_id", "quantity"]
+                        }
+                    },
+                    "notes": {
+                        "type": "string",
+                        "description": "หมายเหตุ เช่น เงื่อนไขการจัดส่ง (optional)"
+                    }
+                },
+                "required": ["items"]
+            }),
+        }
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> Result<ToolResult> {
+        let params: QuoteParams = serde_json::from_value(args)?;
+
+        let mut requested = Vec::with_capacity(params.items.len());
+        for item in &params.items {
+            let id = Uuid::parse_str(&item.product_id).map_err(|_| {
+                Error::Validation(format!("Invalid product_id: {}", item.product_id))
+            })?;
+            requested.push((id, item.quantity));
+        }
+
+        let ids = requested.iter().map(|(id, _)| *id).collect();
+        let products: HashMap<Uuid, PricedProduct> = self
+            .store
+            .products(ids)
+            .await?
+            .into_iter()
+            .map(|product| (product.id, product))
+            .collect();
+
+        let items = requested
+            .into_iter()
+            .map(|(id, quantity)| {
+                products
+                    .get(&id)
+                    .cloned()
+                    .map(|product| (product, quantity))
+                    .ok_or_else(|| Error::NotFound(format!("Product {}", id)))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let quote = QuoteCalculation::compute(&items, &self.config)?;
+        let valid_until = Utc::now() + Duration::days(i64::from(self.config.valid_days));
+        let quote_id = self
+            .store
+            .save_draft(quote.to_draft(params.notes, valid_until))
+            .await?;
+
+        let output = serde_json::json!({
+            "quote_id": quote_id,
+            "status": "draft",
+            "currency": quote.currency,
+            "items": quote.lines_json(),
+            "subtotal": to_major_units(quote.subtotal),
+            "discount_total": to_major_units(quote.discount_total),
+            "vat_rate": quote.vat_rate,
+            "vat_amount": to_major_units(quote.vat_amount),
+            "total": to_major_units(quote.total),
+            "valid_until": valid_until,
+            "summary": quote.summary(),
+        });
+
+        Ok(ToolResult {
+            tool_name: "quote".to_string(),
+            output: serde_json::to_string(&output)?,
+            success: true,
+        })
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct StubStore {
+        products: Vec<PricedProduct>,
+        drafts: Mutex<Vec<QuoteDraft>>,
+    }
+
+    #[async_trait]
+    impl QuoteStore for StubStore {
+        async fn products(&self, ids: Vec<Uuid>) -> Result<Vec<PricedProduct>> {
+            Ok(self
+                .products
+                .iter()
+                .filter(|p| ids.contains(&p.id))
+                .cloned()
+                .collect())
+        }
+
+        async fn save_draft(&self, draft: QuoteDraft) -> Result<Uuid> {
+            self.drafts.lock().unwrap().push(draft);
+            Ok(Uuid::nil())
+        }
+    }
+
+    fn product(name: &str, price: f64, currency: &str) -> PricedProduct {
+        PricedProduct {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            price: Some(price),
+            currency: Some(currency.to_string()),
+            is_active: true,
+        }
+    }
+
+    #[test]
+    fn test_compute_applies_discount_tiers_and_vat() {
+        let config = QuoteConfig::default()
+            .with_volume_discount(VolumeDiscount::new(10, 5.0))
+            .with_volume_discount(VolumeDiscount::new(50, 10.0));
+        let items = vec![
+            (product("Pump X", 990.0, "THB"), 20),
+            (product("Installation", 1500.0, "THB"), 1),
+        ];
+
+        let quote = QuoteCalculation::compute(&items, &config).unwrap();
+
+        assert_eq!(quote.lines[0].subtotal, 1_980_000);
+        assert_eq!(quote.lines[0].discount_percent, 5.0);
+        assert_eq!(quote.lines[0].discount, 99_000);
+        assert_eq!(quote.lines[1].discount, 0);
+        assert_eq!(quote.subtotal, 2_130_000);
+        assert_eq!(quote.discount_total, 99_000);
+        assert_eq!(quote.vat_amount, 142_170);
+        assert_eq!(quote.total, 2_173_170);
+    }
+
+    #[test]
+    fn test_compute_rejects_unpriced_and_mixed_currency() {
+        let config = QuoteConfig::default();
+
+        let mut unpriced = product("Custom build", 0.0, "THB");
+        unpriced.price = None;
+        let result = QuoteCalculation::compute(&[(unpriced, 1)], &config);
+        assert!(matches!(result, Err(Error::Validation(_))));
+
+        let items = vec![
+            (product("Pump X", 990.0, "THB"), 1),
+            (product("Pump Y", 30.0, "USD"), 1),
+        ];
+        let result = QuoteCalculation::compute(&items, &config);
+        assert!(matches!(result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_execute_saves_draft() {
+        let pump = product("Pump X", 990.0, "THB");
+        let store = Arc::new(StubStore {
+            products: vec![pump.clone()],
+            ..Default::default()
+        });
+        let tool = QuoteTool::new(store.clone());
+
+        let result = tool
+            .execute(serde_json::json!({
+                "items": [{"product_id": pump.id.to_string(), "quantity": 2}],
+                "notes": "Deliver to Bangkok"
+            }))
+            .await
+            .unwrap();
+
+        let output: serde_json::Value = serde_json::from_str(&result.output).unwrap();
+        assert_eq!(output["quote_id"], Uuid::nil().to_string());
+        assert_eq!(output["total"], 2118.6);
+        assert_eq!(output["items"][0]["unit_price"], 990.0);
+
+        let drafts = store.drafts.lock().unwrap();
+        assert_eq!(drafts[0].total, 211_860);
+        assert_eq!(drafts[0].notes.as_deref(), Some("Deliver to Bangkok"));
+    }
+
+    #[tokio::test]
+    async fn test_execute_rejects_unknown_product() {
+        let tool = QuoteTool::new(Arc::new(StubStore::default()));
+
+        let result = tool
+            .execute(serde_json::json!({
+                "items": [{"product_id": Uuid::new_v4().to_string(), "quantity": 1}]
+            }))
+            .await;
+
+        assert!(matches!(result, Err(Error::NotFound(_))));
+    }
+}
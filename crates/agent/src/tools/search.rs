@@ -1,9 +1,24 @@
 //! Search tool for agents.
+//!
+//! Queries the knowledge base through a [`DocumentRetriever`], usually a
+//! [`rag_core::Retriever`] over the vector store the documents are indexed in.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use agent::tools::search::SearchTool;
+//! use rag_core::Retriever;
+//!
+//! let tool = SearchTool::new().with_retriever(Arc::new(Retriever::new(model, store)));
+//! ```
 
 use super::{Tool, ToolDefinition, ToolResult};
 use async_trait::async_trait;
+use common::models::SearchResult;
 use common::Result;
+use rag_core::DocumentRetriever;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
 /// Search tool parameters
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -13,19 +28,20 @@ pub struct SearchParams {
 }
 
 /// Search tool for querying the knowledge base
+#[derive(Default)]
 pub struct SearchTool {
-    // In production, this would hold a reference to the retriever
+    retriever: Option<Arc<dyn DocumentRetriever>>,
 }
 
 impl SearchTool {
     pub fn new() -> Self {
-        Self {}
+        Self::default()
     }
-}
 
-impl Default for SearchTool {
-    fn default() -> Self {
-        Self::new()
+    /// Answer searches from `retriever`
+    pub fn with_retriever(mut self, retriever: Arc<dyn DocumentRetriever>) -> Self {
+        self.retriever = Some(retriever);
+        self
     }
 }
 
@@ -55,13 +71,15 @@ impl Tool for SearchTool {
 
     async fn execute(&self, args: serde_json::Value) -> Result<ToolResult> {
         let params: SearchParams = serde_json::from_value(args)?;
+        let limit = params.limit.unwrap_or(5);
 
-        // In production, this would query the vector store
-        let output = format!(
-            "Searched for '{}' with limit {}",
-            params.query,
-            params.limit.unwrap_or(5)
-        );
+        let output = match &self.retriever {
+            Some(retriever) => {
+                let results = retriever.retrieve_top_k(&params.query, limit).await?;
+                format_results(&params.query, &results)
+            }
+            None => format!("Searched for '{}' with limit {}", params.query, limit),
+        };
 
         Ok(ToolResult {
             tool_name: "search".to_string(),
@@ -70,3 +88,79 @@ impl Tool for SearchTool {
         })
     }
 }
+
+fn format_results(query: &str, results: &[SearchResult]) -> String {
+    if results.is_empty() {
+        return format!("No documents found for '{}'", query);
+    }
+
+    results
+        .iter()
+        .enumerate()
+        .map(|(i, result)| {
+            format!(
+                "[{}] (score {:.2}) {}",
+                i + 1,
+                result.score,
+                result.chunk.content.trim()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::models::DocumentChunk;
+    use uuid::Uuid;
+
+    struct FixedRetriever(Vec<&'static str>);
+
+    #[async_trait]
+    impl DocumentRetriever for FixedRetriever {
+        async fn retrieve_top_k(&self, _query: &str, top_k: usize) -> Result<Vec<SearchResult>> {
+            Ok(self
+                .0
+                .iter()
+                .take(top_k)
+                .enumerate()
+                .map(|(i, content)| SearchResult {
+                    chunk: DocumentChunk {
+                        id: Uuid::new_v4(),
+                        document_id: Uuid::new_v4(),
+                        content: content.to_string(),
+                        chunk_index: i,
+                        metadata: serde_json::json!({}),
+                    },
+                    score: 1.0 - i as f32 / 4.0,
+                })
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_search_uses_retriever() {
+        let tool = SearchTool::new().with_retriever(Arc::new(FixedRetriever(vec![
+            "Pumps ship in 3 days ",
+            "Warranty is 2 years",
+            "Unused",
+        ])));
+
+        let result = tool
+            .execute(serde_json::json!({"query": "pump", "limit": 2}))
+            .await
+            .unwrap();
+        assert_eq!(
+            result.output,
+            "[1] (score 1.00) Pumps ship in 3 days\n\n[2] (score 0.75) Warranty is 2 years"
+        );
+
+        let empty = SearchTool::new().with_retriever(Arc::new(FixedRetriever(vec![])));
+        let result = empty
+            .execute(serde_json::json!({"query": "valve"}))
+            .await
+            .unwrap();
+        assert_eq!(result.output, "No documents found for 'valve'");
+    }
+}
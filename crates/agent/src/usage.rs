@@ -0,0 +1,210 @@
+//! Token and cost accounting
+//!
+//! Every LLM call made by an agent is recorded into a [`UsageReport`], which
+//! sums prompt/completion tokens and prices them with the per-model table
+//! from [`PricingConfig`]. Reports merge upwards: ReAct and RAG responses,
+//! crew results and flow results each carry the total for their run.
+//! Costs are in the pricing configuration's currency and are summed as
+//! integer [`Micros`].
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use agent::usage::UsageReport;
+//! use common::global_pricing;
+//!
+//! let mut usage = UsageReport::default();
+//! usage.record_response(&prompt, &response, global_pricing());
+//! println!("{} tokens, {} {}", usage.total_tokens, usage.cost, global_pricing().currency);
+//! ```
+
+use common::{Micros, PricingConfig};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tracing::debug;
+
+use crate::context::TokenCounter;
+use crate::rig_integration::LlmResponse;
+
+/// Usage of a single model within a report
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ModelUsage {
+    pub llm_calls: usize,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    /// Cost in the currency of the pricing configuration
+    pub cost: Micros,
+}
+
+/// Aggregated token usage and cost of one or more LLM calls
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UsageReport {
+    /// Number of LLM calls made
+    pub llm_calls: usize,
+
+    /// Prompt (input) tokens across all calls
+    pub prompt_tokens: u64,
+
+    /// Completion (output) tokens across all calls
+    pub completion_tokens: u64,
+
+    /// Prompt + completion tokens
+    pub total_tokens: u64,
+
+    /// Total cost in the currency of the pricing configuration; calls to
+    /// models without a configured price count as zero
+    pub cost: Micros,
+
+    /// Breakdown per model
+    #[serde(default)]
+    pub by_model: BTreeMap<String, ModelUsage>,
+}
+
+impl UsageReport {
+    /// Whether no LLM call has been recorded
+    pub fn is_empty(&self) -> bool {
+        self.llm_calls == 0
+    }
+
+    /// Record one LLM call with known token counts
+    pub fn record(
+        &mut self,
+        model: &str,
+        prompt_tokens: u64,
+        completion_tokens: u64,
+        pricing: &PricingConfig,
+    ) {
+        self.record_calls(model, 1, prompt_tokens, completion_tokens, pricing);
+    }
+
+    /// Record `calls` LLM calls to the same model with summed token counts
+    pub fn record_calls(
+        &mut self,
+        model: &str,
+        calls: usize,
+        prompt_tokens: u64,
+        completion_tokens: u64,
+        pricing: &PricingConfig,
+    ) {
+        let cost = pricing
+            .cost(model, prompt_tokens, completion_tokens)
+            .unwrap_or_else(|| {
+                debug!(model = %model, "No pricing configured for model");
+                Micros::ZERO
+            });
+
+        self.llm_calls += calls;
+        self.prompt_tokens += prompt_tokens;
+        self.completion_tokens += completion_tokens;
+        self.total_tokens += prompt_tokens + completion_tokens;
+        self.cost += cost;
+
+        let entry = self.by_model.entry(model.to_string()).or_default();
+        entry.llm_calls += calls;
+        entry.prompt_tokens += prompt_tokens;
+        entry.completion_tokens += completion_tokens;
+        entry.cost += cost;
+    }
+
+    /// Record an LLM response, counting tokens locally if the provider
+    /// did not report usage
+    pub fn record_response(
+        &mut self,
+        prompt: &str,
+        response: &LlmResponse,
+        pricing: &PricingConfig,
+    ) {
+        let (prompt_tokens, completion_tokens) = match &response.usage {
+            Some(usage) => (usage.prompt_tokens as u64, usage.completion_tokens as u64),
+            None => {
+                let counter = TokenCounter::for_model(&response.model);
+                (
+                    counter.count(prompt) as u64,
+                    counter.count(&response.content) as u64,
+                )
+            }
+        };
+        self.record(&response.model, prompt_tokens, completion_tokens, pricing);
+    }
+
+    /// Add another report into this one
+    pub fn merge(&mut self, other: &UsageReport) {
+        self.llm_calls += other.llm_calls;
+        self.prompt_tokens += other.prompt_tokens;
+        self.completion_tokens += other.completion_tokens;
+        self.total_tokens += other.total_tokens;
+        self.cost += other.cost;
+
+        for (model, usage) in &other.by_model {
+            let entry = self.by_model.entry(model.clone()).or_default();
+            entry.llm_calls += usage.llm_calls;
+            entry.prompt_tokens += usage.prompt_tokens;
+            entry.completion_tokens += usage.completion_tokens;
+            entry.cost += usage.cost;
+        }
+    }
+}
+
+impl<'a> std::iter::Sum<&'a UsageReport> for UsageReport {
+    fn sum<I: Iterator<Item = &'a UsageReport>>(iter: I) -> Self {
+        iter.fold(UsageReport::default(), |mut total, report| {
+            total.merge(report);
+            total
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rig_integration::TokenUsage;
+
+    #[test]
+    fn test_record_and_merge() {
+        let pricing = PricingConfig::default();
+
+        let mut a = UsageReport::default();
+        a.record("gpt-4", 1_000, 500, &pricing);
+        a.record("openai/gpt-4o-mini", 2_000, 1_000, &pricing);
+
+        let mut b = UsageReport::default();
+        b.record("llama3", 100, 100, &pricing);
+
+        let total: UsageReport = [a.clone(), b].iter().sum();
+        assert_eq!(total.llm_calls, 3);
+        assert_eq!(total.prompt_tokens, 3_100);
+        assert_eq!(total.completion_tokens, 1_600);
+        assert_eq!(total.total_tokens, 4_700);
+        assert_eq!(total.cost, Micros(60_900));
+        assert_eq!(total.by_model["llama3"].cost, Micros::ZERO);
+        assert_eq!(total.by_model["gpt-4"], a.by_model["gpt-4"]);
+    }
+
+    #[test]
+    fn test_record_response_estimates_missing_usage() {
+        let pricing = PricingConfig::default();
+        let mut usage = UsageReport::default();
+
+        let reported = LlmResponse {
+            content: "Paris".to_string(),
+            model: "gpt-4".to_string(),
+            usage: Some(TokenUsage {
+                prompt_tokens: 12,
+                completion_tokens: 3,
+                total_tokens: 15,
+            }),
+            finish_reason: None,
+        };
+        usage.record_response("What is the capital of France?", &reported, &pricing);
+        assert_eq!(usage.total_tokens, 15);
+
+        let unreported = LlmResponse {
+            usage: None,
+            ..reported
+        };
+        usage.record_response("What is the capital of France?", &unreported, &pricing);
+        assert_eq!(usage.llm_calls, 2);
+        assert!(usage.prompt_tokens > 12);
+        assert!(usage.completion_tokens > 3);
+    }
+}
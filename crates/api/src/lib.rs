@@ -12,6 +12,6 @@ pub mod queue;
 pub mod routes;
 pub mod state;
 
-pub use queue::JobProducer;
+pub use queue::{HandoffInbox, JobProducer, ResumeOutcome};
 pub use routes::create_router;
 pub use state::AppState;
@@ -1,3 +1,4 @@
+use api::middleware::auth::ApiKeys;
 use api::{create_router, queue, AppState};
 use db::DbPool;
 use std::net::SocketAddr;
@@ -41,7 +42,11 @@ async fn main() -> anyhow::Result<()> {
         }
     }
 
-    let state = AppState::new(db_pool, redis_pool, storage_client);
+    let api_keys = ApiKeys::from_env();
+    if api_keys.is_empty() {
+        tracing::warn!("API_KEYS is not set; staff endpoints will reject every request");
+    }
+    let state = AppState::new(db_pool, redis_pool, storage_client).with_api_keys(api_keys);
     let app = create_router(state);
 
     let host = std::env::var("SERVER_HOST").unwrap_or_else(|_| "0.0.0.0".into());
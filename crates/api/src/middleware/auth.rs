@@ -1,20 +1,76 @@
 //! Authentication middleware.
+//!
+//! Staff endpoints require an `X-API-Key` header holding one of the keys in
+//! `API_KEYS`, a comma-separated list of `name:key` pairs. The key's name is
+//! passed on to handlers as the caller's [`ApiPrincipal`]. With no keys
+//! configured, every request to those endpoints is rejected.
 
-use axum::{extract::Request, http::StatusCode, middleware::Next, response::Response};
+use crate::state::AppState;
+use axum::{
+    extract::{Request, State},
+    http::StatusCode,
+    middleware::Next,
+    response::Response,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
 
-/// Simple API key authentication middleware
-pub async fn api_key_auth(request: Request, next: Next) -> Result<Response, StatusCode> {
-    // Get API key from header
-    let api_key = request
-        .headers()
-        .get("X-API-Key")
-        .and_then(|v| v.to_str().ok());
+/// Caller identity established by [`api_key_auth`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiPrincipal(pub String);
+
+/// API keys accepted by the service, each naming its holder
+#[derive(Debug, Clone, Default)]
+pub struct ApiKeys {
+    keys: Arc<HashMap<String, String>>,
+}
+
+impl ApiKeys {
+    /// Parse `name:key` pairs separated by commas, skipping malformed ones
+    pub fn parse(spec: &str) -> Self {
+        let keys = spec
+            .split(',')
+            .filter_map(|pair| {
+                let (name, key) = pair.trim().split_once(':')?;
+                let (name, key) = (name.trim(), key.trim());
+                (!name.is_empty() && !key.is_empty()).then(|| (key.to_string(), name.to_string()))
+            })
+            .collect();
+        Self {
+            keys: Arc::new(keys),
+        }
+    }
+
+    /// Keys from the `API_KEYS` environment variable
+    pub fn from_env() -> Self {
+        Self::parse(&std::env::var("API_KEYS").unwrap_or_default())
+    }
+
+    /// Name of the holder of `key`, if it is valid
+    pub fn principal(&self, key: &str) -> Option<&str> {
+        self.keys.get(key).map(String::as_str)
+    }
 
-    // TODO: Validate API key against stored keys
-    // For now, skip validation if no key is required
-    if api_key.is_some() {
-        // Validate key here
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
     }
+}
+
+/// API key authentication middleware
+pub async fn api_key_auth(
+    State(state): State<AppState>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let principal = request
+        .headers()
+        .get("X-API-Key")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|key| state.api_keys.principal(key))
+        .ok_or(StatusCode::UNAUTHORIZED)?;
 
+    request
+        .extensions_mut()
+        .insert(ApiPrincipal(principal.to_string()));
     Ok(next.run(request).await)
 }
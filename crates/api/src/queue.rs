@@ -1,7 +1,13 @@
-use common::models::{EmbedDocumentJob, IndexDocumentJob, ProcessChatJob};
-use common::queue::{keys, queues, JobResult, RESULT_TTL_SECONDS};
-use common::{Error, Result};
-use deadpool_redis::{redis::AsyncCommands, Config, Pool, Runtime};
+use common::models::{
+    EmbedDocumentJob, HandoffRequest, IndexDocumentJob, ProcessChatJob, ResumeChatJob,
+};
+use common::queue::{
+    keys, queues, JobResult, CHECKPOINT_TTL_SECONDS, COMPARE_AND_SET_STATUS_SCRIPT,
+    RESULT_TTL_SECONDS,
+};
+use common::{Error, QueueJobStatus, Result};
+use deadpool_redis::redis::{AsyncCommands, Script};
+use deadpool_redis::{Config, Pool, Runtime};
 use uuid::Uuid;
 
 pub type RedisPool = Pool;
@@ -12,6 +18,16 @@ pub fn create_pool(redis_url: &str) -> Result<RedisPool> {
         .map_err(|e| Error::Queue(e.to_string()))
 }
 
+/// What became of a request to resume a paused job
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResumeOutcome {
+    /// The job was paused; it is now resuming and the resume job is queued
+    Queued,
+    /// The job is not paused, e.g. another decision got there first
+    NotPaused,
+    NotFound,
+}
+
 #[derive(Clone)]
 pub struct JobProducer {
     pool: RedisPool,
@@ -50,6 +66,49 @@ impl JobProducer {
             .await
     }
 
+    /// Queue the decision on a paused job
+    ///
+    /// The job moves from paused to resuming and the resume job is queued in
+    /// one atomic step, so concurrent or retried decisions queue it only once.
+    pub async fn push_resume_job(&self, job: &ResumeChatJob) -> Result<ResumeOutcome> {
+        let mut conn = self.conn().await?;
+        let key = keys::job_status(&job.job_id);
+
+        let current: Option<String> = conn
+            .get(&key)
+            .await
+            .map_err(|e| Error::Queue(e.to_string()))?;
+        let Some(current) = current else {
+            return Ok(ResumeOutcome::NotFound);
+        };
+        let status: JobResult = serde_json::from_str(&current)?;
+        if !matches!(status.status, QueueJobStatus::Paused) {
+            return Ok(ResumeOutcome::NotPaused);
+        }
+
+        let resuming = serde_json::to_string(&JobResult {
+            status: QueueJobStatus::Resuming,
+            ..status
+        })?;
+        let swapped: i64 = Script::new(COMPARE_AND_SET_STATUS_SCRIPT)
+            .key(&key)
+            .key(queues::RESUME_QUEUE)
+            .arg(&current)
+            .arg(&resuming)
+            .arg(CHECKPOINT_TTL_SECONDS)
+            .arg(serde_json::to_string(job)?)
+            .invoke_async(&mut conn)
+            .await
+            .map_err(|e| Error::Queue(e.to_string()))?;
+
+        if swapped == 1 {
+            tracing::info!(job_id = %job.job_id, "resume queued");
+            Ok(ResumeOutcome::Queued)
+        } else {
+            Ok(ResumeOutcome::NotPaused)
+        }
+    }
+
     pub async fn push_embed_job(&self, job: &EmbedDocumentJob) -> Result<Uuid> {
         self.push_job(
             queues::EMBED_QUEUE,
@@ -80,3 +139,63 @@ impl JobProducer {
             .transpose()
     }
 }
+
+/// Operator side of the queue of conversations handed off by the agent
+#[derive(Clone)]
+pub struct HandoffInbox {
+    pool: RedisPool,
+}
+
+impl HandoffInbox {
+    pub fn new(pool: RedisPool) -> Self {
+        Self { pool }
+    }
+
+    async fn conn(&self) -> Result<deadpool_redis::Connection> {
+        self.pool
+            .get()
+            .await
+            .map_err(|e| Error::Queue(e.to_string()))
+    }
+
+    /// Take the oldest handoff request off the queue
+    pub async fn pop(&self) -> Result<Option<HandoffRequest>> {
+        let mut conn = self.conn().await?;
+        let json: Option<String> = conn
+            .rpop(queues::HANDOFF_QUEUE, None)
+            .await
+            .map_err(|e| Error::Queue(e.to_string()))?;
+
+        json.map(|json| serde_json::from_str(&json).map_err(Into::into))
+            .transpose()
+    }
+
+    /// Put a popped request back as the oldest on the queue
+    pub async fn requeue(&self, handoff: &HandoffRequest) -> Result<()> {
+        let mut conn = self.conn().await?;
+        conn.rpush::<_, _, ()>(queues::HANDOFF_QUEUE, serde_json::to_string(handoff)?)
+            .await
+            .map_err(|e| Error::Queue(e.to_string()))
+    }
+
+    /// Drop the queued requests of a conversation
+    pub async fn remove(&self, conversation_id: &Uuid) -> Result<()> {
+        let mut conn = self.conn().await?;
+        let queued: Vec<String> = conn
+            .lrange(queues::HANDOFF_QUEUE, 0, -1)
+            .await
+            .map_err(|e| Error::Queue(e.to_string()))?;
+
+        for json in queued {
+            let matches = serde_json::from_str::<HandoffRequest>(&json)
+                .map(|request| request.conversation_id == *conversation_id)
+                .unwrap_or(false);
+            if matches {
+                conn.lrem::<_, _, ()>(queues::HANDOFF_QUEUE, 0, &json)
+                    .await
+                    .map_err(|e| Error::Queue(e.to_string()))?;
+            }
+        }
+        Ok(())
+    }
+}
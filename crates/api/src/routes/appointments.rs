@@ -0,0 +1,322 @@
+//! Appointment slot management and booking export endpoints.
+
+use crate::state::AppState;
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Duration, Utc};
+use common::Error;
+use db::models::{Appointment, AppointmentSlot};
+use db::{AppointmentFilter, AppointmentRepository, SlotAvailability, SlotInput};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Days of slots listed when no end is given
+const DEFAULT_SLOT_DAYS: i64 = 30;
+
+#[derive(Debug, Deserialize)]
+pub struct ListSlotsQuery {
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    /// Leave out fully booked slots
+    pub available_only: Option<bool>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateSlotRequest {
+    pub kind: Option<String>,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub capacity: Option<i32>,
+    pub location: Option<String>,
+    pub host: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SlotResponse {
+    pub id: Uuid,
+    pub kind: String,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub capacity: i32,
+    pub remaining: i64,
+    pub location: Option<String>,
+    pub host: Option<String>,
+}
+
+impl From<SlotAvailability> for SlotResponse {
+    fn from(availability: SlotAvailability) -> Self {
+        let slot = availability.slot;
+        Self {
+            id: slot.id,
+            kind: slot.kind,
+            starts_at: slot.starts_at,
+            ends_at: slot.ends_at,
+            capacity: slot.capacity,
+            remaining: availability.remaining,
+            location: slot.location,
+            host: slot.host,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListAppointmentsQuery {
+    pub status: Option<String>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+impl ListAppointmentsQuery {
+    fn filter(&self, default_status: Option<&str>) -> AppointmentFilter {
+        AppointmentFilter {
+            status: self
+                .status
+                .clone()
+                .or_else(|| default_status.map(str::to_string)),
+            from: self.from,
+            to: self.to,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct AppointmentResponse {
+    pub id: Uuid,
+    pub slot_id: Uuid,
+    pub kind: String,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub location: Option<String>,
+    pub conversation_id: Option<Uuid>,
+    pub customer_name: String,
+    pub customer_phone: Option<String>,
+    pub customer_email: Option<String>,
+    pub notes: Option<String>,
+    pub status: String,
+    pub hold_expires_at: Option<DateTime<Utc>>,
+    pub confirmed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<(Appointment, AppointmentSlot)> for AppointmentResponse {
+    fn from((appointment, slot): (Appointment, AppointmentSlot)) -> Self {
+        Self {
+            id: appointment.id,
+            slot_id: slot.id,
+            kind: slot.kind,
+            starts_at: slot.starts_at,
+            ends_at: slot.ends_at,
+            location: slot.location,
+            conversation_id: appointment.conversation_id,
+            customer_name: appointment.customer_name,
+            customer_phone: appointment.customer_phone,
+            customer_email: appointment.customer_email,
+            notes: appointment.notes,
+            status: appointment.status,
+            hold_expires_at: appointment.hold_expires_at,
+            confirmed_at: appointment.confirmed_at,
+            created_at: appointment.created_at,
+        }
+    }
+}
+
+pub async fn list_slots(
+    State(state): State<AppState>,
+    Query(query): Query<ListSlotsQuery>,
+) -> Result<Json<Vec<SlotResponse>>, StatusCode> {
+    let repo = AppointmentRepository::new(state.db_pool.clone());
+    let from = query.from.unwrap_or_else(Utc::now);
+    let to = query
+        .to
+        .unwrap_or_else(|| from + Duration::days(DEFAULT_SLOT_DAYS));
+    let limit = query.limit.unwrap_or(100);
+
+    match repo.slots(from, to, query.available_only.unwrap_or(false), limit) {
+        Ok(slots) => Ok(Json(slots.into_iter().map(Into::into).collect())),
+        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+pub async fn create_slot(
+    State(state): State<AppState>,
+    Json(request): Json<CreateSlotRequest>,
+) -> Result<Json<SlotResponse>, StatusCode> {
+    let repo = AppointmentRepository::new(state.db_pool.clone());
+    let input = SlotInput {
+        kind: request.kind.unwrap_or_else(|| "demo".to_string()),
+        starts_at: request.starts_at,
+        ends_at: request.ends_at,
+        capacity: request.capacity.unwrap_or(1),
+        location: request.location,
+        host: request.host,
+    };
+
+    match repo.create_slot(&input) {
+        Ok(slot) => Ok(Json(
+            SlotAvailability {
+                remaining: i64::from(slot.capacity),
+                slot,
+            }
+            .into(),
+        )),
+        Err(Error::Validation(_)) => Err(StatusCode::BAD_REQUEST),
+        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+/// Stop offering a slot; appointments already booked into it are kept
+pub async fn delete_slot(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, StatusCode> {
+    let repo = AppointmentRepository::new(state.db_pool.clone());
+
+    match repo.deactivate_slot(&id) {
+        Ok(true) => Ok(StatusCode::NO_CONTENT),
+        Ok(false) => Err(StatusCode::NOT_FOUND),
+        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+pub async fn list_appointments(
+    State(state): State<AppState>,
+    Query(query): Query<ListAppointmentsQuery>,
+) -> Result<Json<Vec<AppointmentResponse>>, StatusCode> {
+    let repo = AppointmentRepository::new(state.db_pool.clone());
+
+    match repo.list(&query.filter(None)) {
+        Ok(appointments) => Ok(Json(appointments.into_iter().map(Into::into).collect())),
+        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+pub async fn cancel_appointment(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, StatusCode> {
+    let repo = AppointmentRepository::new(state.db_pool.clone());
+
+    match repo.cancel(&id) {
+        Ok(true) => Ok(StatusCode::NO_CONTENT),
+        Ok(false) => Err(StatusCode::NOT_FOUND),
+        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+/// Export bookings as an iCalendar feed; only confirmed ones by default
+pub async fn export_appointments(
+    State(state): State<AppState>,
+    Query(query): Query<ListAppointmentsQuery>,
+) -> Result<Response, StatusCode> {
+    let repo = AppointmentRepository::new(state.db_pool.clone());
+    let appointments = repo
+        .list(&query.filter(Some("confirmed")))
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/calendar; charset=utf-8"),
+            (
+                header::CONTENT_DISPOSITION,
+                "attachment; filename=\"appointments.ics\"",
+            ),
+        ],
+        to_icalendar(&appointments, Utc::now()),
+    )
+        .into_response())
+}
+
+// ============================================================================
+// iCalendar (RFC 5545)
+// ============================================================================
+
+fn to_icalendar(appointments: &[(Appointment, AppointmentSlot)], now: DateTime<Utc>) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//agentic-rust//appointments//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+    ];
+
+    for (appointment, slot) in appointments {
+        let mut description = vec![format!("Customer: {}", appointment.customer_name)];
+        if let Some(phone) = &appointment.customer_phone {
+            description.push(format!("Phone: {}", phone));
+        }
+        if let Some(email) = &appointment.customer_email {
+            description.push(format!("Email: {}", email));
+        }
+        if let Some(notes) = &appointment.notes {
+            description.push(format!("Notes: {}", notes));
+        }
+
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:{}@agentic-rust", appointment.id));
+        lines.push(format!("DTSTAMP:{}", ical_time(now)));
+        lines.push(format!("DTSTART:{}", ical_time(slot.starts_at)));
+        lines.push(format!("DTEND:{}", ical_time(slot.ends_at)));
+        lines.push(format!(
+            "SUMMARY:{}",
+            ical_text(&format!("{} - {}", slot.kind, appointment.customer_name))
+        ));
+        if let Some(location) = &slot.location {
+            lines.push(format!("LOCATION:{}", ical_text(location)));
+        }
+        lines.push(format!(
+            "DESCRIPTION:{}",
+            ical_text(&description.join("\n"))
+        ));
+        lines.push(format!(
+            "STATUS:{}",
+            match appointment.status.as_str() {
+                "confirmed" => "CONFIRMED",
+                "cancelled" | "expired" => "CANCELLED",
+                _ => "TENTATIVE",
+            }
+        ));
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    lines
+        .iter()
+        .map(|line| fold_line(line))
+        .collect::<Vec<_>>()
+        .join("")
+}
+
+fn ical_time(time: DateTime<Utc>) -> String {
+    time.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Escape a TEXT value
+fn ical_text(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Fold a content line at 75 octets without splitting characters
+fn fold_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + 8);
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            width = 1;
+        }
+        folded.push(c);
+        width += c.len_utf8();
+    }
+    folded.push_str("\r\n");
+    folded
+}
@@ -1,12 +1,14 @@
 //! Chat endpoints for conversational AI.
 
+use crate::middleware::auth::ApiPrincipal;
+use crate::queue::ResumeOutcome;
 use crate::state::AppState;
 use axum::{
     extract::{Path, State},
     http::StatusCode,
-    Json,
+    Extension, Json,
 };
-use common::models::ProcessChatJob;
+use common::models::{ProcessChatJob, ResumeChatJob};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -41,6 +43,13 @@ pub struct AsyncChatResponse {
     pub status: String,
 }
 
+/// Decision on a paused job's pending tool calls
+#[derive(Debug, Deserialize)]
+pub struct ResumeJobRequest {
+    pub approve: bool,
+    pub reason: Option<String>,
+}
+
 /// Job status response
 #[derive(Debug, Serialize)]
 pub struct JobStatusResponse {
@@ -123,3 +132,37 @@ pub async fn get_job_status(
         None => Err(StatusCode::NOT_FOUND),
     }
 }
+
+/// Resume a paused job
+/// Approves or rejects the tool calls the agent is waiting on and requeues it;
+/// the caller's API key is recorded as the reviewer
+pub async fn resume_job(
+    State(state): State<AppState>,
+    Extension(ApiPrincipal(reviewer)): Extension<ApiPrincipal>,
+    Path(job_id): Path<Uuid>,
+    Json(request): Json<ResumeJobRequest>,
+) -> Result<Json<AsyncChatResponse>, StatusCode> {
+    let job = if request.approve {
+        ResumeChatJob::approve(job_id, reviewer)
+    } else {
+        ResumeChatJob::reject(job_id, reviewer, request.reason)
+    };
+
+    let outcome = state
+        .job_producer
+        .push_resume_job(&job)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to push resume job to queue");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    match outcome {
+        ResumeOutcome::Queued => Ok(Json(AsyncChatResponse {
+            job_id,
+            status: "queued".to_string(),
+        })),
+        ResumeOutcome::NotPaused => Err(StatusCode::CONFLICT),
+        ResumeOutcome::NotFound => Err(StatusCode::NOT_FOUND),
+    }
+}
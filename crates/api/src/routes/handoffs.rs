@@ -0,0 +1,217 @@
+//! Operator endpoints for conversations handed off to live agents.
+
+use crate::middleware::auth::ApiPrincipal;
+use crate::queue::HandoffInbox;
+use crate::state::AppState;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use common::models::ConversationState;
+use db::models::{Conversation, Message};
+use db::{ConversationRepository, MessageRepository};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize)]
+pub struct ListHandoffsQuery {
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OperatorReplyRequest {
+    pub content: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ConversationStateResponse {
+    pub conversation_id: Uuid,
+    pub state: String,
+    pub operator_id: Option<String>,
+    pub handoff_reason: Option<String>,
+    pub handoff_requested_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<Conversation> for ConversationStateResponse {
+    fn from(conversation: Conversation) -> Self {
+        Self {
+            conversation_id: conversation.id,
+            state: conversation.state,
+            operator_id: conversation.operator_id,
+            handoff_reason: conversation.handoff_reason,
+            handoff_requested_at: conversation.handoff_requested_at,
+            updated_at: conversation.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct MessageResponse {
+    pub id: Uuid,
+    pub role: String,
+    pub content: String,
+    /// Written by a human operator rather than the agent
+    pub operator: bool,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<Message> for MessageResponse {
+    fn from(message: Message) -> Self {
+        Self {
+            operator: message.metadata["operator"].as_bool().unwrap_or(false),
+            id: message.id,
+            role: message.role,
+            content: message.content,
+            created_at: message.created_at,
+        }
+    }
+}
+
+/// Conversations waiting for an operator, longest waiting first
+pub async fn list_handoffs(
+    State(state): State<AppState>,
+    Query(query): Query<ListHandoffsQuery>,
+) -> Result<Json<Vec<ConversationStateResponse>>, StatusCode> {
+    let repo = ConversationRepository::new(state.db_pool.clone());
+    let limit = query.limit.unwrap_or(20);
+
+    match repo.list_by_state(ConversationState::WaitingHuman, limit) {
+        Ok(conversations) => Ok(Json(conversations.into_iter().map(Into::into).collect())),
+        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+/// Claim the oldest conversation on the handoff queue
+///
+/// A request popped off the queue is put back if the claim fails, so a
+/// database error never loses a waiting customer.
+pub async fn claim_next_handoff(
+    State(state): State<AppState>,
+    Extension(ApiPrincipal(operator_id)): Extension<ApiPrincipal>,
+) -> Result<Json<ConversationStateResponse>, StatusCode> {
+    let inbox = HandoffInbox::new(state.redis_pool.clone());
+    let repo = ConversationRepository::new(state.db_pool.clone());
+
+    loop {
+        let handoff = inbox
+            .pop()
+            .await
+            .map_err(|e| {
+                tracing::error!(error = %e, "Failed to read handoff queue");
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?
+            .ok_or(StatusCode::NOT_FOUND)?;
+
+        // Requests of conversations claimed directly are stale
+        match repo.claim(&handoff.conversation_id, &operator_id) {
+            Ok(Some(conversation)) => return Ok(Json(conversation.into())),
+            Ok(None) => continue,
+            Err(_) => {
+                if let Err(e) = inbox.requeue(&handoff).await {
+                    tracing::error!(
+                        error = %e,
+                        conversation_id = %handoff.conversation_id,
+                        "Failed to requeue handoff"
+                    );
+                }
+                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        }
+    }
+}
+
+/// Claim a waiting conversation
+pub async fn claim_conversation(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Extension(ApiPrincipal(operator_id)): Extension<ApiPrincipal>,
+) -> Result<Json<ConversationStateResponse>, StatusCode> {
+    let repo = ConversationRepository::new(state.db_pool.clone());
+
+    let conversation = match repo.claim(&id, &operator_id) {
+        Ok(Some(conversation)) => conversation,
+        Ok(None) => return Err(not_found_or_conflict(&repo, &id)),
+        Err(_) => return Err(StatusCode::INTERNAL_SERVER_ERROR),
+    };
+
+    if let Err(e) = HandoffInbox::new(state.redis_pool.clone())
+        .remove(&id)
+        .await
+    {
+        tracing::warn!(error = %e, conversation_id = %id, "Failed to dequeue claimed handoff");
+    }
+    Ok(Json(conversation.into()))
+}
+
+/// Hand a conversation back to the agent
+pub async fn release_conversation(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Extension(ApiPrincipal(operator_id)): Extension<ApiPrincipal>,
+) -> Result<Json<ConversationStateResponse>, StatusCode> {
+    let repo = ConversationRepository::new(state.db_pool.clone());
+
+    match repo.release(&id, &operator_id) {
+        Ok(Some(conversation)) => Ok(Json(conversation.into())),
+        Ok(None) => Err(not_found_or_conflict(&repo, &id)),
+        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+pub async fn list_messages(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<MessageResponse>>, StatusCode> {
+    let repo = MessageRepository::new(state.db_pool.clone());
+
+    match repo.get_by_conversation(&id) {
+        Ok(messages) => Ok(Json(messages.into_iter().map(Into::into).collect())),
+        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+/// Reply to the customer as the assistant, flagged as written by an operator
+///
+/// Only the operator who claimed the conversation can reply. Operators are
+/// identified by their API key.
+pub async fn operator_reply(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Extension(ApiPrincipal(operator_id)): Extension<ApiPrincipal>,
+    Json(request): Json<OperatorReplyRequest>,
+) -> Result<Json<MessageResponse>, StatusCode> {
+    let conversations = ConversationRepository::new(state.db_pool.clone());
+    let conversation = conversations
+        .get(&id)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let handled_by_operator = ConversationState::from_db(&conversation.state)
+        == ConversationState::Human
+        && conversation.operator_id.as_deref() == Some(operator_id.as_str());
+    if !handled_by_operator {
+        return Err(StatusCode::CONFLICT);
+    }
+
+    let repo = MessageRepository::new(state.db_pool.clone());
+    let metadata = serde_json::json!({
+        "operator": true,
+        "operator_id": operator_id,
+    });
+
+    match repo.create_with_metadata(id, "assistant", &request.content, metadata) {
+        Ok(message) => Ok(Json(message.into())),
+        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+/// 404 for unknown conversations, 409 for ones in the wrong state
+fn not_found_or_conflict(repo: &ConversationRepository, id: &Uuid) -> StatusCode {
+    match repo.get(id) {
+        Ok(Some(_)) => StatusCode::CONFLICT,
+        Ok(None) => StatusCode::NOT_FOUND,
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
@@ -0,0 +1,161 @@
+//! Lead endpoints for the sales team.
+
+use crate::state::AppState;
+use axum::{
+    extract::{Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use db::models::Lead;
+use db::{LeadFilter, LeadRepository};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Most leads returned by one list request
+const MAX_LIMIT: i64 = 100;
+
+/// Columns of the CSV export
+const EXPORT_HEADER: &str = "id,created_at,status,name,company,phone,email,\
+    interested_products,notes,consent,consent_at,conversation_id";
+
+#[derive(Debug, Deserialize)]
+pub struct ListLeadsQuery {
+    pub status: Option<String>,
+    pub conversation_id: Option<Uuid>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+impl ListLeadsQuery {
+    fn filter(&self) -> LeadFilter {
+        LeadFilter {
+            status: self.status.clone(),
+            conversation_id: self.conversation_id,
+            since: self.since,
+            until: self.until,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct LeadResponse {
+    pub id: Uuid,
+    pub conversation_id: Option<Uuid>,
+    pub name: String,
+    pub company: Option<String>,
+    pub phone: Option<String>,
+    pub email: Option<String>,
+    pub interested_products: Vec<String>,
+    pub notes: Option<String>,
+    pub consent: bool,
+    pub consent_at: Option<DateTime<Utc>>,
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<Lead> for LeadResponse {
+    fn from(lead: Lead) -> Self {
+        Self {
+            interested_products: lead.interested_product_list(),
+            id: lead.id,
+            conversation_id: lead.conversation_id,
+            name: lead.name,
+            company: lead.company,
+            phone: lead.phone,
+            email: lead.email,
+            notes: lead.notes,
+            consent: lead.consent,
+            consent_at: lead.consent_at,
+            status: lead.status,
+            created_at: lead.created_at,
+        }
+    }
+}
+
+pub async fn list_leads(
+    State(state): State<AppState>,
+    Query(query): Query<ListLeadsQuery>,
+) -> Result<Json<Vec<LeadResponse>>, StatusCode> {
+    let repo = LeadRepository::new(state.db_pool.clone());
+    let limit = query.limit.unwrap_or(20).clamp(1, MAX_LIMIT);
+    let offset = query.offset.unwrap_or(0).max(0);
+
+    match repo.list(&query.filter(), limit, offset) {
+        Ok(leads) => Ok(Json(leads.into_iter().map(Into::into).collect())),
+        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+/// Export the leads matching the query as CSV, ignoring `limit` and `offset`
+pub async fn export_leads(
+    State(state): State<AppState>,
+    Query(query): Query<ListLeadsQuery>,
+) -> Result<Response, StatusCode> {
+    let repo = LeadRepository::new(state.db_pool.clone());
+    let leads = repo
+        .export(&query.filter())
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    // The byte order mark makes spreadsheet apps read Thai text as UTF-8
+    let mut csv = String::from("\u{feff}");
+    csv.push_str(EXPORT_HEADER);
+    csv.push_str("\r\n");
+    for lead in leads {
+        let row = [
+            lead.id.to_string(),
+            lead.created_at.to_rfc3339(),
+            lead.status.clone(),
+            text_cell(&lead.name),
+            text_cell(lead.company.as_deref().unwrap_or_default()),
+            lead.phone.clone().unwrap_or_default(),
+            text_cell(lead.email.as_deref().unwrap_or_default()),
+            text_cell(&lead.interested_product_list().join("; ")),
+            text_cell(lead.notes.as_deref().unwrap_or_default()),
+            lead.consent.to_string(),
+            lead.consent_at
+                .map(|at| at.to_rfc3339())
+                .unwrap_or_default(),
+            lead.conversation_id
+                .map(|id| id.to_string())
+                .unwrap_or_default(),
+        ];
+        let cells: Vec<String> = row.iter().map(|cell| csv_cell(cell)).collect();
+        csv.push_str(&cells.join(","));
+        csv.push_str("\r\n");
+    }
+
+    let filename = format!("leads-{}.csv", Utc::now().format("%Y%m%d"));
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        csv,
+    )
+        .into_response())
+}
+
+/// Free text entered by customers, kept from being read as a spreadsheet formula
+fn text_cell(value: &str) -> String {
+    if value.starts_with(['=', '+', '-', '@']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    }
+}
+
+/// Quote a CSV cell when it contains separators, quotes or line breaks
+fn csv_cell(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
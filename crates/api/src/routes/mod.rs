@@ -1,14 +1,21 @@
 //! API route definitions.
 
+pub mod appointments;
 pub mod brochures;
 pub mod chat;
 pub mod documents;
 pub mod files;
+pub mod handoffs;
 pub mod health;
+pub mod leads;
 pub mod products;
 
+use crate::middleware::auth::api_key_auth;
 use crate::state::AppState;
-use axum::{routing::delete, routing::get, routing::post, routing::put, Router};
+use axum::{
+    middleware::from_fn_with_state, routing::delete, routing::get, routing::post, routing::put,
+    Router,
+};
 use tower_http::cors::CorsLayer;
 use tower_http::trace::TraceLayer;
 
@@ -19,7 +26,7 @@ pub fn create_router(state: AppState) -> Router {
         .route("/health", get(health::health_check))
         .route("/ready", get(health::readiness_check))
         // API v1
-        .nest("/api/v1", api_v1_routes())
+        .nest("/api/v1", api_v1_routes(state.clone()))
         // Middleware
         .layer(TraceLayer::new_for_http())
         .layer(CorsLayer::permissive())
@@ -27,8 +34,9 @@ pub fn create_router(state: AppState) -> Router {
 }
 
 /// API v1 routes
-fn api_v1_routes() -> Router<AppState> {
+fn api_v1_routes(state: AppState) -> Router<AppState> {
     Router::new()
+        .merge(staff_routes(state))
         // Chat endpoints (Sales Agent)
         .route("/chat", post(chat::chat_handler))
         .route("/chat/async", post(chat::chat_async_handler))
@@ -68,3 +76,43 @@ fn api_v1_routes() -> Router<AppState> {
         .route("/documents/:id/index", post(documents::index_document))
         .route("/documents/search", post(documents::search_documents))
 }
+
+/// Routes for staff, which require an API key
+fn staff_routes(state: AppState) -> Router<AppState> {
+    Router::new()
+        // Lead endpoints (Sales team)
+        .route("/leads", get(leads::list_leads))
+        .route("/leads/export", get(leads::export_leads))
+        // Approval of paused chat jobs
+        .route("/chat/jobs/:job_id/resume", post(chat::resume_job))
+        // Handoff endpoints (Live agents)
+        .route("/handoffs", get(handoffs::list_handoffs))
+        .route("/handoffs/claim", post(handoffs::claim_next_handoff))
+        .route(
+            "/conversations/:id/claim",
+            post(handoffs::claim_conversation),
+        )
+        .route(
+            "/conversations/:id/release",
+            post(handoffs::release_conversation),
+        )
+        .route("/conversations/:id/messages", get(handoffs::list_messages))
+        .route(
+            "/conversations/:id/messages",
+            post(handoffs::operator_reply),
+        )
+        // Appointment endpoints (Demo booking)
+        .route("/appointment-slots", get(appointments::list_slots))
+        .route("/appointment-slots", post(appointments::create_slot))
+        .route("/appointment-slots/:id", delete(appointments::delete_slot))
+        .route("/appointments", get(appointments::list_appointments))
+        .route(
+            "/appointments/export.ics",
+            get(appointments::export_appointments),
+        )
+        .route(
+            "/appointments/:id/cancel",
+            post(appointments::cancel_appointment),
+        )
+        .route_layer(from_fn_with_state(state, api_key_auth))
+}
@@ -1,3 +1,4 @@
+use crate::middleware::auth::ApiKeys;
 use crate::queue::{JobProducer, RedisPool};
 use db::DbPool;
 use storage::StorageClient;
@@ -8,6 +9,7 @@ pub struct AppState {
     pub redis_pool: RedisPool,
     pub job_producer: JobProducer,
     pub storage_client: StorageClient,
+    pub api_keys: ApiKeys,
 }
 
 impl AppState {
@@ -18,6 +20,13 @@ impl AppState {
             redis_pool,
             job_producer,
             storage_client,
+            api_keys: ApiKeys::default(),
         }
     }
+
+    /// Accept these keys on the staff endpoints
+    pub fn with_api_keys(mut self, api_keys: ApiKeys) -> Self {
+        self.api_keys = api_keys;
+        self
+    }
 }
@@ -35,6 +35,9 @@ pub enum Error {
     #[error("Internal error: {0}")]
     Internal(String),
 
+    #[error("Timeout after {0:?}")]
+    Timeout(std::time::Duration),
+
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
 }
@@ -5,11 +5,13 @@ pub mod constants;
 pub mod error;
 pub mod langfuse;
 pub mod models;
+pub mod pricing;
 pub mod prompt_config;
 pub mod queue;
 
 pub use config::AppConfig;
 pub use error::{Error, Result};
 pub use langfuse::LangfusePromptManager;
+pub use pricing::{global_pricing, Micros, ModelPricing, PricingConfig};
 pub use prompt_config::{global_config, PromptConfig};
 pub use queue::{JobResult, QueueJobStatus};
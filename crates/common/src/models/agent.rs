@@ -31,6 +31,38 @@ pub enum MessageRole {
     System,
 }
 
+/// Who answers a conversation
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConversationState {
+    /// The agent answers
+    #[default]
+    Bot,
+    /// Handed off, waiting for an operator to claim it
+    WaitingHuman,
+    /// An operator answers
+    Human,
+}
+
+impl ConversationState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Bot => "bot",
+            Self::WaitingHuman => "waiting_human",
+            Self::Human => "human",
+        }
+    }
+
+    /// Parse a stored state; unknown values fall back to `Bot`
+    pub fn from_db(value: &str) -> Self {
+        match value {
+            "waiting_human" => Self::WaitingHuman,
+            "human" => Self::Human,
+            _ => Self::Bot,
+        }
+    }
+}
+
 /// Conversation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Conversation {
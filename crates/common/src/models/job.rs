@@ -45,6 +45,30 @@ pub struct ProcessChatJob {
     pub agent_id: Option<String>,
 }
 
+/// Job to resume a paused chat execution
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResumeChatJob {
+    /// ID of the paused chat job (and of its checkpoint)
+    pub job_id: Uuid,
+    /// Name of the API key that decided on the calls
+    pub reviewer: String,
+    /// Whether the pending tool calls are approved
+    pub approve: bool,
+    /// Reason given to the agent when the calls are rejected
+    pub reason: Option<String>,
+}
+
+/// Request for a live agent, queued for operators when the bot hands off
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HandoffRequest {
+    pub conversation_id: Uuid,
+    /// Why the conversation needs a person
+    pub reason: String,
+    /// What the customer needs, so the operator does not have to re-ask
+    pub summary: Option<String>,
+    pub requested_at: DateTime<Utc>,
+}
+
 /// Job to index a document into the vector store
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IndexDocumentJob {
@@ -99,3 +123,39 @@ impl ProcessChatJob {
         self
     }
 }
+
+impl ResumeChatJob {
+    pub fn approve(job_id: Uuid, reviewer: impl Into<String>) -> Self {
+        Self {
+            job_id,
+            reviewer: reviewer.into(),
+            approve: true,
+            reason: None,
+        }
+    }
+
+    pub fn reject(job_id: Uuid, reviewer: impl Into<String>, reason: Option<String>) -> Self {
+        Self {
+            job_id,
+            reviewer: reviewer.into(),
+            approve: false,
+            reason,
+        }
+    }
+}
+
+impl HandoffRequest {
+    pub fn new(conversation_id: Uuid, reason: impl Into<String>) -> Self {
+        Self {
+            conversation_id,
+            reason: reason.into(),
+            summary: None,
+            requested_at: Utc::now(),
+        }
+    }
+
+    pub fn with_summary(mut self, summary: impl Into<String>) -> Self {
+        self.summary = Some(summary.into());
+        self
+    }
+}
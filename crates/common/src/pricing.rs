@@ -0,0 +1,279 @@
+//! Per-model LLM pricing configuration.
+//!
+//! Prices are loaded from `config/pricing.toml` when present, otherwise from
+//! the `pricing.toml` compiled into this crate. Used to compute the cost of
+//! agent runs.
+//!
+//! Amounts are held as integer [`Micros`] so that summing many small call
+//! costs does not drift; they become decimals only when displayed or
+//! serialized.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashMap;
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign};
+use std::path::Path;
+use std::sync::{Arc, OnceLock};
+
+/// Global pricing configuration singleton
+static GLOBAL_PRICING: OnceLock<Arc<PricingConfig>> = OnceLock::new();
+
+/// Pricing bundled at build time, used when no pricing file is found
+const DEFAULT_PRICING: &str = include_str!("../pricing.toml");
+
+/// Get or initialize the global pricing configuration
+///
+/// Every caller shares the same table; cloning the returned `Arc` is cheap.
+pub fn global_pricing() -> &'static Arc<PricingConfig> {
+    GLOBAL_PRICING.get_or_init(|| Arc::new(PricingConfig::load()))
+}
+
+/// An amount of money in millionths of the currency unit
+///
+/// Serialized as a decimal number of currency units, e.g. `0.0609`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Micros(pub u64);
+
+impl Micros {
+    pub const ZERO: Micros = Micros(0);
+
+    /// Convert an amount in currency units, rounding to the nearest micro-unit
+    pub fn from_units(units: f64) -> Self {
+        Micros((units * 1_000_000.0).round().max(0.0) as u64)
+    }
+
+    /// The amount in currency units, for display
+    pub fn as_units(self) -> f64 {
+        self.0 as f64 / 1_000_000.0
+    }
+}
+
+impl fmt::Display for Micros {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{:06}", self.0 / 1_000_000, self.0 % 1_000_000)
+    }
+}
+
+impl Add for Micros {
+    type Output = Micros;
+
+    fn add(self, other: Micros) -> Micros {
+        Micros(self.0 + other.0)
+    }
+}
+
+impl AddAssign for Micros {
+    fn add_assign(&mut self, other: Micros) {
+        self.0 += other.0;
+    }
+}
+
+impl Sum for Micros {
+    fn sum<I: Iterator<Item = Micros>>(iter: I) -> Micros {
+        iter.fold(Micros::ZERO, Add::add)
+    }
+}
+
+impl Serialize for Micros {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(self.as_units())
+    }
+}
+
+impl<'de> Deserialize<'de> for Micros {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let units = f64::deserialize(deserializer)?;
+        if units.is_finite() && units >= 0.0 {
+            Ok(Micros::from_units(units))
+        } else {
+            Err(serde::de::Error::custom(format!(
+                "invalid amount {}, expected a non-negative number",
+                units
+            )))
+        }
+    }
+}
+
+/// Price of a model per million tokens
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ModelPricing {
+    /// Price per million prompt (input) tokens
+    pub input_per_million: Micros,
+    /// Price per million completion (output) tokens
+    pub output_per_million: Micros,
+}
+
+impl ModelPricing {
+    /// Prices per million tokens, in currency units
+    pub fn new(input_per_million: f64, output_per_million: f64) -> Self {
+        Self {
+            input_per_million: Micros::from_units(input_per_million),
+            output_per_million: Micros::from_units(output_per_million),
+        }
+    }
+
+    /// Cost of a call with the given token counts, rounded to the nearest
+    /// micro-unit
+    pub fn cost(&self, prompt_tokens: u64, completion_tokens: u64) -> Micros {
+        let scaled = prompt_tokens as u128 * self.input_per_million.0 as u128
+            + completion_tokens as u128 * self.output_per_million.0 as u128;
+        Micros(((scaled + 500_000) / 1_000_000) as u64)
+    }
+}
+
+/// Root pricing configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PricingConfig {
+    /// Currency the prices are expressed in
+    #[serde(default = "default_currency")]
+    pub currency: String,
+    /// Prices keyed by model name (matched by longest prefix)
+    #[serde(default)]
+    pub models: HashMap<String, ModelPricing>,
+}
+
+fn default_currency() -> String {
+    "USD".to_string()
+}
+
+impl Default for PricingConfig {
+    fn default() -> Self {
+        Self::parse_toml(DEFAULT_PRICING).expect("bundled pricing.toml is valid")
+    }
+}
+
+impl PricingConfig {
+    /// Load pricing configuration from a TOML file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> crate::Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| crate::Error::Config(format!("Failed to read pricing config: {}", e)))?;
+        Self::parse_toml(&content)
+    }
+
+    /// Load pricing configuration from a TOML string
+    pub fn parse_toml(content: &str) -> crate::Result<Self> {
+        toml::from_str(content)
+            .map_err(|e| crate::Error::Config(format!("Failed to parse pricing config: {}", e)))
+    }
+
+    /// Load from default path (config/pricing.toml) or use defaults
+    pub fn load() -> Self {
+        let config_paths = [
+            "config/pricing.toml",
+            "./pricing.toml",
+            "/etc/agentic-rust/pricing.toml",
+        ];
+
+        for path in config_paths {
+            if Path::new(path).exists() {
+                match Self::from_file(path) {
+                    Ok(config) => {
+                        tracing::info!("Loaded pricing config from: {}", path);
+                        return config;
+                    }
+                    Err(e) => {
+                        tracing::warn!("Failed to load pricing config from {}: {}", path, e);
+                    }
+                }
+            }
+        }
+
+        tracing::info!("Using default pricing configuration");
+        Self::default()
+    }
+
+    /// Find the pricing for a model
+    ///
+    /// Accepts routed names ("openai/gpt-4o") and dated versions
+    /// ("gpt-4o-2024-08-06"); the longest matching model prefix wins.
+    pub fn lookup(&self, model: &str) -> Option<&ModelPricing> {
+        let name = model.rsplit('/').next().unwrap_or(model);
+
+        self.models.get(name).or_else(|| {
+            self.models
+                .iter()
+                .filter(|(key, _)| name.starts_with(key.as_str()))
+                .max_by_key(|(key, _)| key.len())
+                .map(|(_, pricing)| pricing)
+        })
+    }
+
+    /// Cost of a call, or `None` when the model has no configured price
+    pub fn cost(&self, model: &str, prompt_tokens: u64, completion_tokens: u64) -> Option<Micros> {
+        self.lookup(model)
+            .map(|pricing| pricing.cost(prompt_tokens, completion_tokens))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup_longest_prefix() {
+        let pricing = PricingConfig::default();
+
+        assert_eq!(
+            pricing.lookup("gpt-4").unwrap().input_per_million,
+            Micros(30_000_000)
+        );
+        assert_eq!(
+            pricing
+                .lookup("gpt-4o-mini-2024-07-18")
+                .unwrap()
+                .input_per_million,
+            Micros(150_000)
+        );
+        assert_eq!(
+            pricing.lookup("openai/gpt-4o").unwrap().output_per_million,
+            Micros(10_000_000)
+        );
+        assert!(pricing.lookup("llama3").is_none());
+    }
+
+    #[test]
+    fn test_cost() {
+        let pricing = PricingConfig::default();
+        let cost = pricing.cost("gpt-4", 1_000, 500).unwrap();
+        assert_eq!(cost, Micros(60_000));
+        assert_eq!(cost.to_string(), "0.060000");
+
+        // 7 tokens at 0.15 per million is 1.05 micro-units
+        let cost = pricing.cost("gpt-4o-mini", 7, 0).unwrap();
+        assert_eq!(cost, Micros(1));
+    }
+
+    #[test]
+    fn test_micros_sum_exactly() {
+        let total: Micros = std::iter::repeat(Micros::from_units(0.1)).take(10).sum();
+        assert_eq!(total, Micros::from_units(1.0));
+        assert_eq!(serde_json::to_string(&total).unwrap(), "1.0");
+        assert_eq!(
+            serde_json::from_str::<Micros>("0.0609").unwrap(),
+            Micros(60_900)
+        );
+        assert!(serde_json::from_str::<Micros>("-1").is_err());
+    }
+
+    #[test]
+    fn test_parse_toml() {
+        let config = PricingConfig::parse_toml(
+            r#"
+            currency = "EUR"
+
+            [models.llama3]
+            input_per_million = 0.1
+            output_per_million = 0.2
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.currency, "EUR");
+        assert_eq!(config.models.len(), 1);
+        assert_eq!(
+            config.lookup("llama3:8b").unwrap().output_per_million,
+            Micros(200_000)
+        );
+    }
+}
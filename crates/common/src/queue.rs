@@ -8,6 +8,9 @@ pub mod queues {
     pub const CHAT_QUEUE: &str = "agentic:chat";
     pub const EMBED_QUEUE: &str = "agentic:embed";
     pub const INDEX_QUEUE: &str = "agentic:index";
+    pub const RESUME_QUEUE: &str = "agentic:resume";
+    /// Conversations waiting for a live agent, consumed by operators
+    pub const HANDOFF_QUEUE: &str = "agentic:handoff";
 }
 
 /// Redis keys for job results
@@ -21,6 +24,21 @@ pub mod keys {
     pub fn job_status(job_id: &Uuid) -> String {
         format!("agentic:status:{}", job_id)
     }
+
+    pub fn checkpoint(job_id: &Uuid) -> String {
+        format!("agentic:checkpoint:{}", job_id)
+    }
+
+    /// Pub/sub channel carrying live progress events of a job
+    pub fn job_events(job_id: &Uuid) -> String {
+        format!("agentic:events:{}", job_id)
+    }
+
+    /// Sorted set of the jobs taken from `queue` and still processing,
+    /// scored by their lease deadline
+    pub fn processing(queue: &str) -> String {
+        format!("{}:processing", queue)
+    }
 }
 
 /// Job status stored in Redis
@@ -29,6 +47,10 @@ pub mod keys {
 pub enum QueueJobStatus {
     Pending,
     Processing,
+    /// Waiting for approval of a tool call
+    Paused,
+    /// Approval decided, resume queued
+    Resuming,
     Completed,
     Failed,
 }
@@ -64,6 +86,17 @@ impl JobResult {
         }
     }
 
+    /// Paused job; `result` describes what is waiting for approval
+    pub fn paused(job_id: Uuid, pending: serde_json::Value) -> Self {
+        Self {
+            job_id,
+            status: QueueJobStatus::Paused,
+            result: Some(pending),
+            error: None,
+            completed_at: None,
+        }
+    }
+
     pub fn failed(job_id: Uuid, error: impl Into<String>) -> Self {
         Self {
             job_id,
@@ -75,5 +108,28 @@ impl JobResult {
     }
 }
 
+/// Lua script replacing a job status only if it still holds the expected value
+///
+/// KEYS[1] is the status key, ARGV[1] the expected JSON, ARGV[2] the new JSON
+/// and ARGV[3] its TTL. With a second key, ARGV[4] is pushed onto that queue
+/// in the same step. Returns 1 when the status was replaced, 0 otherwise.
+pub const COMPARE_AND_SET_STATUS_SCRIPT: &str = r#"
+if redis.call('GET', KEYS[1]) ~= ARGV[1] then
+    return 0
+end
+redis.call('SET', KEYS[1], ARGV[2], 'EX', ARGV[3])
+if KEYS[2] then
+    redis.call('LPUSH', KEYS[2], ARGV[4])
+end
+return 1
+"#;
+
 /// TTL for job results in Redis (1 hour)
 pub const RESULT_TTL_SECONDS: u64 = 3600;
+
+/// TTL for execution checkpoints in Redis (7 days, to wait for approvals)
+pub const CHECKPOINT_TTL_SECONDS: u64 = 7 * 24 * 3600;
+
+/// Lease on a job taken by a worker; renewed while the job runs, and the job
+/// is requeued once it runs out
+pub const LEASE_TTL_SECONDS: u64 = 30;
@@ -0,0 +1,3 @@
+DROP FUNCTION IF EXISTS cosine_similarity(REAL[], REAL[]);
+DROP TRIGGER IF EXISTS update_product_embeddings_updated_at ON product_embeddings;
+DROP TABLE IF EXISTS product_embeddings;
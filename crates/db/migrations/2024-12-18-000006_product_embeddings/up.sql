@@ -0,0 +1,27 @@
+-- Embeddings for semantic product search
+-- One row per product, written when the product is (re-)indexed
+
+CREATE TABLE product_embeddings (
+    product_id UUID PRIMARY KEY REFERENCES products(id) ON DELETE CASCADE,
+    embedding REAL[] NOT NULL,
+    model VARCHAR(100) NOT NULL DEFAULT '',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TRIGGER update_product_embeddings_updated_at
+    BEFORE UPDATE ON product_embeddings
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- Cosine similarity of two embeddings, 0 when they can't be compared
+-- Lets search rank rows in SQL instead of loading every embedding
+CREATE OR REPLACE FUNCTION cosine_similarity(a REAL[], b REAL[])
+RETURNS REAL AS $$
+    SELECT CASE
+        WHEN a IS NULL OR b IS NULL OR cardinality(a) <> cardinality(b) THEN 0
+        ELSE COALESCE((
+            SELECT SUM(x * y) / NULLIF(SQRT(SUM(x * x)) * SQRT(SUM(y * y)), 0)
+            FROM unnest(a, b) AS v(x, y)
+        ), 0)
+    END::REAL
+$$ LANGUAGE SQL IMMUTABLE PARALLEL SAFE;
@@ -0,0 +1,22 @@
+DROP TRIGGER IF EXISTS update_faq_embeddings_updated_at ON faq_embeddings;
+DROP TABLE IF EXISTS faq_embeddings;
+
+DROP INDEX IF EXISTS idx_company_info_language;
+
+-- Keys may now repeat across languages; keep one row per key (the Thai
+-- one, else the oldest) so UNIQUE (key) can be restored. The other
+-- translations are lost.
+DELETE FROM company_info
+WHERE id IN (
+    SELECT id FROM (
+        SELECT id, ROW_NUMBER() OVER (
+            PARTITION BY key
+            ORDER BY (language = 'th') DESC, created_at, id
+        ) AS rank
+        FROM company_info
+    ) ranked
+    WHERE rank > 1
+);
+
+ALTER TABLE company_info DROP CONSTRAINT IF EXISTS company_info_key_language_key;
+ALTER TABLE company_info ADD CONSTRAINT company_info_key_key UNIQUE (key);
@@ -0,0 +1,23 @@
+-- Company info keys are unique per language, so a key can have th and en values
+
+ALTER TABLE company_info DROP CONSTRAINT IF EXISTS company_info_key_key;
+ALTER TABLE company_info ADD CONSTRAINT company_info_key_language_key UNIQUE (key, language);
+
+CREATE INDEX idx_company_info_language ON company_info(language);
+
+-- Embeddings for semantic FAQ matching
+-- content_hash is the md5 of the embedded question and answer; view counts
+-- bump faqs.updated_at, so it tells real edits apart
+
+CREATE TABLE faq_embeddings (
+    faq_id UUID PRIMARY KEY REFERENCES faqs(id) ON DELETE CASCADE,
+    embedding REAL[] NOT NULL,
+    model VARCHAR(100) NOT NULL DEFAULT '',
+    content_hash VARCHAR(32) NOT NULL DEFAULT '',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TRIGGER update_faq_embeddings_updated_at
+    BEFORE UPDATE ON faq_embeddings
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,2 @@
+DROP TRIGGER IF EXISTS update_quotes_updated_at ON quotes;
+DROP TABLE IF EXISTS quotes;
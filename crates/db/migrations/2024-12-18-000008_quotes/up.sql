@@ -0,0 +1,27 @@
+-- Price quotes computed by the quote tool
+-- Line items are stored with the prices used at quoting time
+
+CREATE TABLE quotes (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    status VARCHAR(20) NOT NULL DEFAULT 'draft'
+        CHECK (status IN ('draft', 'sent', 'accepted', 'rejected', 'expired')),
+    currency VARCHAR(10) NOT NULL,
+    items JSONB NOT NULL DEFAULT '[]',
+    subtotal DECIMAL(15, 2) NOT NULL,
+    discount_total DECIMAL(15, 2) NOT NULL DEFAULT 0,
+    vat_rate DECIMAL(5, 2) NOT NULL DEFAULT 0,
+    vat_amount DECIMAL(15, 2) NOT NULL DEFAULT 0,
+    total DECIMAL(15, 2) NOT NULL,
+    notes TEXT,
+    valid_until TIMESTAMPTZ NOT NULL,
+    metadata JSONB NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_quotes_status ON quotes(status);
+CREATE INDEX idx_quotes_created_at ON quotes(created_at DESC);
+
+CREATE TRIGGER update_quotes_updated_at
+    BEFORE UPDATE ON quotes
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,2 @@
+DROP TRIGGER IF EXISTS update_leads_updated_at ON leads;
+DROP TABLE IF EXISTS leads;
@@ -0,0 +1,30 @@
+-- Sales leads captured by the agent
+-- conversation_id is not a foreign key: chat jobs may run before the
+-- conversation row is stored
+
+CREATE TABLE leads (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    conversation_id UUID,
+    name VARCHAR(255) NOT NULL,
+    company VARCHAR(255),
+    phone VARCHAR(20),
+    email VARCHAR(255),
+    interested_products JSONB NOT NULL DEFAULT '[]',
+    notes TEXT,
+    consent BOOLEAN NOT NULL DEFAULT FALSE,
+    consent_at TIMESTAMPTZ,
+    status VARCHAR(20) NOT NULL DEFAULT 'new'
+        CHECK (status IN ('new', 'contacted', 'qualified', 'won', 'lost')),
+    metadata JSONB NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK (phone IS NOT NULL OR email IS NOT NULL)
+);
+
+CREATE INDEX idx_leads_conversation_id ON leads(conversation_id);
+CREATE INDEX idx_leads_status ON leads(status);
+CREATE INDEX idx_leads_created_at ON leads(created_at DESC);
+
+CREATE TRIGGER update_leads_updated_at
+    BEFORE UPDATE ON leads
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
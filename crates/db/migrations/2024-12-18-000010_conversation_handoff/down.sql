@@ -0,0 +1,7 @@
+DROP INDEX IF EXISTS idx_conversations_state;
+
+ALTER TABLE conversations
+    DROP COLUMN IF EXISTS handoff_requested_at,
+    DROP COLUMN IF EXISTS handoff_reason,
+    DROP COLUMN IF EXISTS operator_id,
+    DROP COLUMN IF EXISTS state;
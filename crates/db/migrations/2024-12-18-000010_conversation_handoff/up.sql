@@ -0,0 +1,11 @@
+-- Who is answering a conversation: the bot, nobody yet (waiting for a live
+-- agent after a handoff), or a human operator
+
+ALTER TABLE conversations
+    ADD COLUMN state VARCHAR(20) NOT NULL DEFAULT 'bot'
+        CHECK (state IN ('bot', 'waiting_human', 'human')),
+    ADD COLUMN operator_id VARCHAR(255),
+    ADD COLUMN handoff_reason TEXT,
+    ADD COLUMN handoff_requested_at TIMESTAMPTZ;
+
+CREATE INDEX idx_conversations_state ON conversations(state);
@@ -0,0 +1,4 @@
+DROP TRIGGER IF EXISTS update_appointments_updated_at ON appointments;
+DROP TRIGGER IF EXISTS update_appointment_slots_updated_at ON appointment_slots;
+DROP TABLE IF EXISTS appointments;
+DROP TABLE IF EXISTS appointment_slots;
@@ -0,0 +1,48 @@
+-- Bookable time slots and the appointments (demos, visits) booked into them
+-- A held appointment takes a seat until hold_expires_at; a confirmed one
+-- keeps it
+
+CREATE TABLE appointment_slots (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    kind VARCHAR(50) NOT NULL DEFAULT 'demo',
+    starts_at TIMESTAMPTZ NOT NULL,
+    ends_at TIMESTAMPTZ NOT NULL,
+    capacity INTEGER NOT NULL DEFAULT 1 CHECK (capacity > 0),
+    location VARCHAR(255),
+    host VARCHAR(255),
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    metadata JSONB NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK (ends_at > starts_at)
+);
+
+CREATE INDEX idx_appointment_slots_starts_at ON appointment_slots(starts_at);
+
+CREATE TABLE appointments (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    slot_id UUID NOT NULL REFERENCES appointment_slots(id) ON DELETE CASCADE,
+    conversation_id UUID,
+    customer_name VARCHAR(255) NOT NULL,
+    customer_phone VARCHAR(20),
+    customer_email VARCHAR(255),
+    notes TEXT,
+    status VARCHAR(20) NOT NULL DEFAULT 'held'
+        CHECK (status IN ('held', 'confirmed', 'cancelled', 'expired')),
+    hold_expires_at TIMESTAMPTZ,
+    confirmed_at TIMESTAMPTZ,
+    metadata JSONB NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_appointments_slot_id ON appointments(slot_id, status);
+CREATE INDEX idx_appointments_conversation_id ON appointments(conversation_id);
+
+CREATE TRIGGER update_appointment_slots_updated_at
+    BEFORE UPDATE ON appointment_slots
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TRIGGER update_appointments_updated_at
+    BEFORE UPDATE ON appointments
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
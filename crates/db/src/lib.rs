@@ -13,5 +13,9 @@ pub mod schema;
 
 pub use pool::{DbPool, PgConn, PgPool, MIGRATIONS};
 pub use repositories::{
-    ConversationRepository, DocumentRepository, JobRepository, MessageRepository,
+    AppointmentFilter, AppointmentRepository, BrochureFilter, BrochureRepository,
+    CompanyInfoRepository, ConversationRepository, DocumentRepository, FaqRepository, HoldInput,
+    JobRepository, LeadFilter, LeadInput, LeadRepository, MessageRepository, ProductFilter,
+    ProductRepository, QuoteDraft, QuoteRepository, ScoredChunk, ScoredFaq, ScoredProduct,
+    SlotAvailability, SlotInput,
 };
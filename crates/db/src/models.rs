@@ -45,6 +45,12 @@ pub struct Conversation {
     pub agent_id: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Who answers the conversation: `bot`, `waiting_human` or `human`
+    pub state: String,
+    /// Operator handling the conversation while it is `human`
+    pub operator_id: Option<String>,
+    pub handoff_reason: Option<String>,
+    pub handoff_requested_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Clone, Insertable)]
@@ -230,6 +236,68 @@ pub struct NewProduct<'a> {
     pub updated_at: DateTime<Utc>,
 }
 
+impl Product {
+    /// Price as a float, if set
+    pub fn price_f64(&self) -> Option<f64> {
+        use bigdecimal::ToPrimitive;
+        self.price.as_ref().and_then(|price| price.to_f64())
+    }
+
+    /// Feature list stored in the `features` JSON array
+    pub fn feature_list(&self) -> Vec<String> {
+        string_list(&self.features)
+    }
+
+    /// Image URLs stored in the `image_urls` JSON array
+    pub fn image_url_list(&self) -> Vec<String> {
+        string_list(&self.image_urls)
+    }
+
+    /// Text embedded for semantic search
+    pub fn embedding_text(&self) -> String {
+        format!(
+            "{}\n{}\nCategory: {}\nFeatures: {}",
+            self.name,
+            self.description,
+            self.category,
+            self.feature_list().join(", ")
+        )
+    }
+}
+
+fn string_list(value: &serde_json::Value) -> Vec<String> {
+    value
+        .as_array()
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| item.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[derive(Debug, Clone, Queryable, Selectable, Serialize, Deserialize)]
+#[diesel(table_name = product_embeddings)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ProductEmbedding {
+    pub product_id: Uuid,
+    pub embedding: Vec<f32>,
+    pub model: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = product_embeddings)]
+pub struct NewProductEmbedding<'a> {
+    pub product_id: Uuid,
+    pub embedding: &'a [f32],
+    pub model: &'a str,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
 // ============================================================================
 // Brochures
 // ============================================================================
@@ -310,6 +378,29 @@ pub struct NewFaq<'a> {
     pub updated_at: DateTime<Utc>,
 }
 
+#[derive(Debug, Clone, Queryable, Selectable, Serialize, Deserialize)]
+#[diesel(table_name = faq_embeddings)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct FaqEmbedding {
+    pub faq_id: Uuid,
+    pub embedding: Vec<f32>,
+    pub model: String,
+    pub content_hash: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = faq_embeddings)]
+pub struct NewFaqEmbedding<'a> {
+    pub faq_id: Uuid,
+    pub embedding: &'a [f32],
+    pub model: &'a str,
+    pub content_hash: &'a str,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
 // ============================================================================
 // Company Info
 // ============================================================================
@@ -352,3 +443,170 @@ pub struct ProductBrochure {
     pub product_id: Uuid,
     pub brochure_id: Uuid,
 }
+
+// ============================================================================
+// Quotes
+// ============================================================================
+
+#[derive(Debug, Clone, Queryable, Selectable, Serialize, Deserialize)]
+#[diesel(table_name = quotes)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Quote {
+    pub id: Uuid,
+    pub status: String,
+    pub currency: String,
+    pub items: serde_json::Value,
+    pub subtotal: bigdecimal::BigDecimal,
+    pub discount_total: bigdecimal::BigDecimal,
+    pub vat_rate: bigdecimal::BigDecimal,
+    pub vat_amount: bigdecimal::BigDecimal,
+    pub total: bigdecimal::BigDecimal,
+    pub notes: Option<String>,
+    pub valid_until: DateTime<Utc>,
+    pub metadata: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = quotes)]
+pub struct NewQuote<'a> {
+    pub id: Uuid,
+    pub status: &'a str,
+    pub currency: &'a str,
+    pub items: serde_json::Value,
+    pub subtotal: bigdecimal::BigDecimal,
+    pub discount_total: bigdecimal::BigDecimal,
+    pub vat_rate: bigdecimal::BigDecimal,
+    pub vat_amount: bigdecimal::BigDecimal,
+    pub total: bigdecimal::BigDecimal,
+    pub notes: Option<&'a str>,
+    pub valid_until: DateTime<Utc>,
+    pub metadata: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// ============================================================================
+// Leads
+// ============================================================================
+
+#[derive(Debug, Clone, Queryable, Selectable, Serialize, Deserialize)]
+#[diesel(table_name = leads)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Lead {
+    pub id: Uuid,
+    pub conversation_id: Option<Uuid>,
+    pub name: String,
+    pub company: Option<String>,
+    pub phone: Option<String>,
+    pub email: Option<String>,
+    pub interested_products: serde_json::Value,
+    pub notes: Option<String>,
+    pub consent: bool,
+    pub consent_at: Option<DateTime<Utc>>,
+    pub status: String,
+    pub metadata: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = leads)]
+pub struct NewLead<'a> {
+    pub id: Uuid,
+    pub conversation_id: Option<Uuid>,
+    pub name: &'a str,
+    pub company: Option<&'a str>,
+    pub phone: Option<&'a str>,
+    pub email: Option<&'a str>,
+    pub interested_products: serde_json::Value,
+    pub notes: Option<&'a str>,
+    pub consent: bool,
+    pub consent_at: Option<DateTime<Utc>>,
+    pub status: &'a str,
+    pub metadata: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Lead {
+    /// Interested products as a list of names or IDs
+    pub fn interested_product_list(&self) -> Vec<String> {
+        string_list(&self.interested_products)
+    }
+}
+
+// ============================================================================
+// Appointments
+// ============================================================================
+
+#[derive(Debug, Clone, Queryable, Selectable, Serialize, Deserialize)]
+#[diesel(table_name = appointment_slots)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct AppointmentSlot {
+    pub id: Uuid,
+    pub kind: String,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub capacity: i32,
+    pub location: Option<String>,
+    pub host: Option<String>,
+    pub is_active: bool,
+    pub metadata: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = appointment_slots)]
+pub struct NewAppointmentSlot<'a> {
+    pub id: Uuid,
+    pub kind: &'a str,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub capacity: i32,
+    pub location: Option<&'a str>,
+    pub host: Option<&'a str>,
+    pub is_active: bool,
+    pub metadata: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Queryable, Selectable, Serialize, Deserialize)]
+#[diesel(table_name = appointments)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Appointment {
+    pub id: Uuid,
+    pub slot_id: Uuid,
+    pub conversation_id: Option<Uuid>,
+    pub customer_name: String,
+    pub customer_phone: Option<String>,
+    pub customer_email: Option<String>,
+    pub notes: Option<String>,
+    pub status: String,
+    pub hold_expires_at: Option<DateTime<Utc>>,
+    pub confirmed_at: Option<DateTime<Utc>>,
+    pub metadata: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = appointments)]
+pub struct NewAppointment<'a> {
+    pub id: Uuid,
+    pub slot_id: Uuid,
+    pub conversation_id: Option<Uuid>,
+    pub customer_name: &'a str,
+    pub customer_phone: Option<&'a str>,
+    pub customer_email: Option<&'a str>,
+    pub notes: Option<&'a str>,
+    pub status: &'a str,
+    pub hold_expires_at: Option<DateTime<Utc>>,
+    pub confirmed_at: Option<DateTime<Utc>>,
+    pub metadata: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
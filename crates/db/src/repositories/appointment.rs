@@ -0,0 +1,329 @@
+//! Appointment repository using Diesel ORM.
+//!
+//! A slot has `capacity` seats. Confirmed appointments take a seat, and so
+//! do held ones until their hold expires.
+
+use crate::models::{Appointment, AppointmentSlot, NewAppointment, NewAppointmentSlot};
+use crate::pool::DbPool;
+use crate::schema::{appointment_slots, appointments};
+use chrono::{DateTime, Utc};
+use common::{Error, Result};
+use diesel::prelude::*;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// A slot to create
+#[derive(Debug, Clone)]
+pub struct SlotInput {
+    pub kind: String,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub capacity: i32,
+    pub location: Option<String>,
+    pub host: Option<String>,
+}
+
+/// A slot with its free seats
+#[derive(Debug, Clone)]
+pub struct SlotAvailability {
+    pub slot: AppointmentSlot,
+    pub remaining: i64,
+}
+
+/// Customer details of an appointment to hold
+#[derive(Debug, Clone)]
+pub struct HoldInput {
+    pub slot_id: Uuid,
+    pub conversation_id: Option<Uuid>,
+    pub customer_name: String,
+    pub customer_phone: Option<String>,
+    pub customer_email: Option<String>,
+    pub notes: Option<String>,
+    /// When the held seat is released unless confirmed
+    pub hold_expires_at: DateTime<Utc>,
+}
+
+/// Filters applied when listing appointments
+#[derive(Debug, Clone, Default)]
+pub struct AppointmentFilter {
+    pub status: Option<String>,
+    /// Only appointments in slots starting at or after this time
+    pub from: Option<DateTime<Utc>>,
+    /// Only appointments in slots starting before this time
+    pub to: Option<DateTime<Utc>>,
+}
+
+enum HoldOutcome {
+    Held(Box<Appointment>),
+    SlotNotFound,
+    SlotClosed,
+    SlotFull,
+}
+
+#[derive(Clone)]
+pub struct AppointmentRepository {
+    pool: DbPool,
+}
+
+impl AppointmentRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    // ========================================================================
+    // Slots
+    // ========================================================================
+
+    pub fn create_slot(&self, input: &SlotInput) -> Result<AppointmentSlot> {
+        if input.ends_at <= input.starts_at {
+            return Err(Error::Validation(
+                "A slot must end after it starts".to_string(),
+            ));
+        }
+        if input.capacity < 1 {
+            return Err(Error::Validation(
+                "A slot needs a capacity of at least 1".to_string(),
+            ));
+        }
+
+        let mut conn = self.pool.conn()?;
+        let now = Utc::now();
+        let new_slot = NewAppointmentSlot {
+            id: Uuid::new_v4(),
+            kind: &input.kind,
+            starts_at: input.starts_at,
+            ends_at: input.ends_at,
+            capacity: input.capacity,
+            location: input.location.as_deref(),
+            host: input.host.as_deref(),
+            is_active: true,
+            metadata: serde_json::json!({}),
+            created_at: now,
+            updated_at: now,
+        };
+
+        diesel::insert_into(appointment_slots::table)
+            .values(&new_slot)
+            .returning(AppointmentSlot::as_returning())
+            .get_result(&mut conn)
+            .map_err(|e| Error::Database(e.to_string()))
+    }
+
+    /// Stop offering a slot; its appointments are kept
+    pub fn deactivate_slot(&self, id: &Uuid) -> Result<bool> {
+        let mut conn = self.pool.conn()?;
+        let count = diesel::update(appointment_slots::table.find(id))
+            .set(appointment_slots::is_active.eq(false))
+            .execute(&mut conn)
+            .map_err(|e| Error::Database(e.to_string()))?;
+        Ok(count > 0)
+    }
+
+    /// Active slots starting between `from` and `to`, earliest first
+    ///
+    /// With `only_available`, fully booked slots are left out.
+    pub fn slots(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        only_available: bool,
+        limit: usize,
+    ) -> Result<Vec<SlotAvailability>> {
+        let mut conn = self.pool.conn()?;
+        let slots: Vec<AppointmentSlot> = appointment_slots::table
+            .filter(appointment_slots::is_active.eq(true))
+            .filter(appointment_slots::starts_at.ge(from))
+            .filter(appointment_slots::starts_at.lt(to))
+            .order(appointment_slots::starts_at.asc())
+            .load(&mut conn)
+            .map_err(|e| Error::Database(e.to_string()))?;
+
+        let ids: Vec<Uuid> = slots.iter().map(|slot| slot.id).collect();
+        let seated: Vec<Uuid> = taking_seat(Utc::now())
+            .filter(appointments::slot_id.eq_any(&ids))
+            .select(appointments::slot_id)
+            .load(&mut conn)
+            .map_err(|e| Error::Database(e.to_string()))?;
+        let mut taken: HashMap<Uuid, i64> = HashMap::new();
+        for slot_id in seated {
+            *taken.entry(slot_id).or_default() += 1;
+        }
+
+        Ok(slots
+            .into_iter()
+            .map(|slot| SlotAvailability {
+                remaining: i64::from(slot.capacity) - taken.get(&slot.id).copied().unwrap_or(0),
+                slot,
+            })
+            .filter(|availability| !only_available || availability.remaining > 0)
+            .take(limit)
+            .collect())
+    }
+
+    // ========================================================================
+    // Appointments
+    // ========================================================================
+
+    pub fn get(&self, id: &Uuid) -> Result<Option<(Appointment, AppointmentSlot)>> {
+        let mut conn = self.pool.conn()?;
+        appointments::table
+            .inner_join(appointment_slots::table)
+            .filter(appointments::id.eq(id))
+            .select((Appointment::as_select(), AppointmentSlot::as_select()))
+            .first(&mut conn)
+            .optional()
+            .map_err(|e| Error::Database(e.to_string()))
+    }
+
+    /// Hold a seat in a future, active slot
+    ///
+    /// The slot row is locked while its seats are counted, so concurrent
+    /// holds cannot overbook it.
+    pub fn hold(&self, input: &HoldInput) -> Result<Appointment> {
+        let mut conn = self.pool.conn()?;
+        let now = Utc::now();
+
+        let outcome = conn
+            .transaction::<_, diesel::result::Error, _>(|conn| {
+                let Some(slot) = appointment_slots::table
+                    .find(input.slot_id)
+                    .for_update()
+                    .first::<AppointmentSlot>(conn)
+                    .optional()?
+                else {
+                    return Ok(HoldOutcome::SlotNotFound);
+                };
+                if !slot.is_active || slot.starts_at <= now {
+                    return Ok(HoldOutcome::SlotClosed);
+                }
+
+                let taken: i64 = taking_seat(now)
+                    .filter(appointments::slot_id.eq(slot.id))
+                    .count()
+                    .get_result(conn)?;
+                if taken >= i64::from(slot.capacity) {
+                    return Ok(HoldOutcome::SlotFull);
+                }
+
+                let new_appointment = NewAppointment {
+                    id: Uuid::new_v4(),
+                    slot_id: slot.id,
+                    conversation_id: input.conversation_id,
+                    customer_name: &input.customer_name,
+                    customer_phone: input.customer_phone.as_deref(),
+                    customer_email: input.customer_email.as_deref(),
+                    notes: input.notes.as_deref(),
+                    status: "held",
+                    hold_expires_at: Some(input.hold_expires_at),
+                    confirmed_at: None,
+                    metadata: serde_json::json!({}),
+                    created_at: now,
+                    updated_at: now,
+                };
+                diesel::insert_into(appointments::table)
+                    .values(&new_appointment)
+                    .returning(Appointment::as_returning())
+                    .get_result(conn)
+                    .map(|appointment| HoldOutcome::Held(Box::new(appointment)))
+            })
+            .map_err(|e| Error::Database(e.to_string()))?;
+
+        match outcome {
+            HoldOutcome::Held(appointment) => Ok(*appointment),
+            HoldOutcome::SlotNotFound => Err(Error::NotFound(format!("Slot {}", input.slot_id))),
+            HoldOutcome::SlotClosed => Err(Error::Validation(
+                "The slot is no longer open for booking".to_string(),
+            )),
+            HoldOutcome::SlotFull => Err(Error::Validation("The slot is fully booked".to_string())),
+        }
+    }
+
+    /// Confirm a held appointment whose hold has not expired
+    ///
+    /// Only holds made in `conversation_id` match, so one conversation cannot
+    /// confirm another's booking; `None` matches holds made outside any
+    /// conversation. Returns `None` when there is no such hold.
+    pub fn confirm(&self, id: &Uuid, conversation_id: Option<Uuid>) -> Result<Option<Appointment>> {
+        let mut conn = self.pool.conn()?;
+        let now = Utc::now();
+        diesel::update(
+            appointments::table
+                .find(id)
+                .filter(appointments::status.eq("held"))
+                .filter(appointments::hold_expires_at.gt(now))
+                .filter(appointments::conversation_id.is_not_distinct_from(conversation_id)),
+        )
+        .set((
+            appointments::status.eq("confirmed"),
+            appointments::confirmed_at.eq(now),
+            appointments::hold_expires_at.eq(None::<DateTime<Utc>>),
+        ))
+        .returning(Appointment::as_returning())
+        .get_result(&mut conn)
+        .optional()
+        .map_err(|e| Error::Database(e.to_string()))
+    }
+
+    pub fn cancel(&self, id: &Uuid) -> Result<bool> {
+        let mut conn = self.pool.conn()?;
+        let count = diesel::update(
+            appointments::table
+                .find(id)
+                .filter(appointments::status.eq_any(["held", "confirmed"])),
+        )
+        .set(appointments::status.eq("cancelled"))
+        .execute(&mut conn)
+        .map_err(|e| Error::Database(e.to_string()))?;
+        Ok(count > 0)
+    }
+
+    /// Mark holds past their expiry as expired, returning how many were
+    pub fn expire_holds(&self) -> Result<usize> {
+        let mut conn = self.pool.conn()?;
+        diesel::update(
+            appointments::table
+                .filter(appointments::status.eq("held"))
+                .filter(appointments::hold_expires_at.le(Utc::now())),
+        )
+        .set(appointments::status.eq("expired"))
+        .execute(&mut conn)
+        .map_err(|e| Error::Database(e.to_string()))
+    }
+
+    /// Appointments matching `filter` with their slots, by slot start time
+    pub fn list(&self, filter: &AppointmentFilter) -> Result<Vec<(Appointment, AppointmentSlot)>> {
+        let mut conn = self.pool.conn()?;
+        let mut sql = appointments::table
+            .inner_join(appointment_slots::table)
+            .select((Appointment::as_select(), AppointmentSlot::as_select()))
+            .into_boxed();
+
+        if let Some(status) = &filter.status {
+            sql = sql.filter(appointments::status.eq(status.clone()));
+        }
+        if let Some(from) = filter.from {
+            sql = sql.filter(appointment_slots::starts_at.ge(from));
+        }
+        if let Some(to) = filter.to {
+            sql = sql.filter(appointment_slots::starts_at.lt(to));
+        }
+
+        sql.order((
+            appointment_slots::starts_at.asc(),
+            appointments::created_at.asc(),
+        ))
+        .load(&mut conn)
+        .map_err(|e| Error::Database(e.to_string()))
+    }
+}
+
+/// Appointments taking a seat at `now`: confirmed ones and unexpired holds
+fn taking_seat(now: DateTime<Utc>) -> appointments::BoxedQuery<'static, diesel::pg::Pg> {
+    appointments::table
+        .filter(
+            appointments::status.eq("confirmed").or(appointments::status
+                .eq("held")
+                .and(appointments::hold_expires_at.gt(now))),
+        )
+        .into_boxed()
+}
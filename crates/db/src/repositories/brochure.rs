@@ -0,0 +1,99 @@
+//! Brochure repository using Diesel ORM.
+
+use crate::models::Brochure;
+use crate::pool::DbPool;
+use crate::schema::{brochures, product_brochures};
+use common::{Error, Result};
+use diesel::dsl::exists;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+/// Filters applied to a brochure search
+#[derive(Debug, Clone, Default)]
+pub struct BrochureFilter {
+    /// Words that must each appear in the title, description or file name
+    pub query: Option<String>,
+    /// Brochures linked to this product
+    pub product_id: Option<Uuid>,
+    pub category: Option<String>,
+    pub file_type: Option<String>,
+    pub language: Option<String>,
+    /// Only public (or only private) brochures; `None` matches both
+    pub is_public: Option<bool>,
+}
+
+#[derive(Clone)]
+pub struct BrochureRepository {
+    pool: DbPool,
+}
+
+impl BrochureRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    pub fn get(&self, id: &Uuid) -> Result<Option<Brochure>> {
+        let mut conn = self.pool.conn()?;
+        brochures::table
+            .find(id)
+            .first(&mut conn)
+            .optional()
+            .map_err(|e| Error::Database(e.to_string()))
+    }
+
+    /// Brochures matching `filter`, most downloaded first
+    ///
+    /// A product matches through the `product_brochures` join table or the
+    /// brochure's own `product_ids` list.
+    pub fn search(&self, filter: &BrochureFilter, limit: i64) -> Result<Vec<Brochure>> {
+        let mut conn = self.pool.conn()?;
+        let mut sql = brochures::table.into_boxed();
+
+        for term in filter.query.iter().flat_map(|q| q.split_whitespace()) {
+            let pattern = format!("%{}%", term);
+            sql = sql.filter(
+                brochures::title
+                    .ilike(pattern.clone())
+                    .or(brochures::description.ilike(pattern.clone()))
+                    .or(brochures::file_name.ilike(pattern)),
+            );
+        }
+        if let Some(product_id) = filter.product_id {
+            let linked = product_brochures::table
+                .filter(product_brochures::brochure_id.eq(brochures::id))
+                .filter(product_brochures::product_id.eq(product_id));
+            sql = sql.filter(
+                exists(linked).or(brochures::product_ids.contains(serde_json::json!([product_id]))),
+            );
+        }
+        if let Some(category) = &filter.category {
+            sql = sql.filter(brochures::category.ilike(category.clone()));
+        }
+        if let Some(file_type) = &filter.file_type {
+            sql = sql.filter(brochures::file_type.ilike(file_type.clone()));
+        }
+        if let Some(language) = &filter.language {
+            sql = sql.filter(brochures::language.eq(language.to_lowercase()));
+        }
+        if let Some(is_public) = filter.is_public {
+            sql = sql.filter(brochures::is_public.eq(is_public));
+        }
+
+        sql.order((
+            brochures::download_count.desc(),
+            brochures::updated_at.desc(),
+        ))
+        .limit(limit)
+        .load(&mut conn)
+        .map_err(|e| Error::Database(e.to_string()))
+    }
+
+    pub fn increment_download_count(&self, id: &Uuid) -> Result<()> {
+        let mut conn = self.pool.conn()?;
+        diesel::update(brochures::table.find(id))
+            .set(brochures::download_count.eq(brochures::download_count + 1))
+            .execute(&mut conn)
+            .map_err(|e| Error::Database(e.to_string()))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+//! Company info repository using Diesel ORM.
+
+use crate::models::CompanyInfo;
+use crate::pool::DbPool;
+use crate::schema::company_info;
+use common::{Error, Result};
+use diesel::prelude::*;
+
+#[derive(Clone)]
+pub struct CompanyInfoRepository {
+    pool: DbPool,
+}
+
+impl CompanyInfoRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    pub fn get(&self, key: &str, language: &str) -> Result<Option<CompanyInfo>> {
+        let mut conn = self.pool.conn()?;
+        company_info::table
+            .filter(company_info::key.eq(key))
+            .filter(company_info::language.eq(language))
+            .first(&mut conn)
+            .optional()
+            .map_err(|e| Error::Database(e.to_string()))
+    }
+
+    /// Entries in `language`, optionally limited to one category
+    pub fn list(&self, category: Option<&str>, language: &str) -> Result<Vec<CompanyInfo>> {
+        let mut conn = self.pool.conn()?;
+        let mut sql = company_info::table
+            .filter(company_info::language.eq(language))
+            .into_boxed();
+        if let Some(category) = category {
+            sql = sql.filter(company_info::category.eq(category));
+        }
+
+        sql.order(company_info::key.asc())
+            .load(&mut conn)
+            .map_err(|e| Error::Database(e.to_string()))
+    }
+}
@@ -6,11 +6,12 @@ use crate::models::{
 use crate::pool::DbPool;
 use crate::schema::{conversations, messages};
 use chrono::Utc;
-use common::models::{ChatMessage, Conversation, MessageRole};
+use common::models::{ChatMessage, Conversation, ConversationState, MessageRole};
 use common::{Error, Result};
 use diesel::prelude::*;
 use uuid::Uuid;
 
+#[derive(Clone)]
 pub struct ConversationRepository {
     pool: DbPool,
 }
@@ -158,4 +159,114 @@ impl ConversationRepository {
             .load(&mut conn)
             .map_err(|e| Error::Database(e.to_string()))
     }
+
+    // ========================================================================
+    // Handoff to live agents
+    // ========================================================================
+
+    /// Who answers the conversation; conversations not stored yet are `Bot`
+    pub fn state(&self, id: &Uuid) -> Result<ConversationState> {
+        Ok(self.get(id)?.map_or(ConversationState::Bot, |c| {
+            ConversationState::from_db(&c.state)
+        }))
+    }
+
+    /// Conversations in `state`, longest waiting first
+    pub fn list_by_state(
+        &self,
+        state: ConversationState,
+        limit: i64,
+    ) -> Result<Vec<DbConversation>> {
+        let mut conn = self.pool.conn()?;
+        conversations::table
+            .filter(conversations::state.eq(state.as_str()))
+            .order((
+                conversations::handoff_requested_at.asc(),
+                conversations::updated_at.asc(),
+            ))
+            .limit(limit)
+            .load(&mut conn)
+            .map_err(|e| Error::Database(e.to_string()))
+    }
+
+    /// Move a conversation answered by the bot to `WaitingHuman`
+    ///
+    /// The conversation is created if it is not stored yet. Returns `None`
+    /// when it is already waiting for or handled by an operator.
+    pub fn request_handoff(&self, id: &Uuid, reason: &str) -> Result<Option<DbConversation>> {
+        let mut conn = self.pool.conn()?;
+        let now = Utc::now();
+        let new_conv = NewConversation {
+            id: *id,
+            agent_id: None,
+            created_at: now,
+            updated_at: now,
+        };
+        diesel::insert_into(conversations::table)
+            .values(&new_conv)
+            .on_conflict_do_nothing()
+            .execute(&mut conn)
+            .map_err(|e| Error::Database(e.to_string()))?;
+
+        diesel::update(
+            conversations::table
+                .find(id)
+                .filter(conversations::state.eq(ConversationState::Bot.as_str())),
+        )
+        .set((
+            conversations::state.eq(ConversationState::WaitingHuman.as_str()),
+            conversations::handoff_reason.eq(reason),
+            conversations::handoff_requested_at.eq(now),
+            conversations::updated_at.eq(now),
+        ))
+        .returning(DbConversation::as_returning())
+        .get_result(&mut conn)
+        .optional()
+        .map_err(|e| Error::Database(e.to_string()))
+    }
+
+    /// Assign a waiting conversation to `operator_id`
+    ///
+    /// Returns `None` when the conversation is not waiting for an operator.
+    pub fn claim(&self, id: &Uuid, operator_id: &str) -> Result<Option<DbConversation>> {
+        let mut conn = self.pool.conn()?;
+        diesel::update(
+            conversations::table
+                .find(id)
+                .filter(conversations::state.eq(ConversationState::WaitingHuman.as_str())),
+        )
+        .set((
+            conversations::state.eq(ConversationState::Human.as_str()),
+            conversations::operator_id.eq(operator_id),
+            conversations::updated_at.eq(Utc::now()),
+        ))
+        .returning(DbConversation::as_returning())
+        .get_result(&mut conn)
+        .optional()
+        .map_err(|e| Error::Database(e.to_string()))
+    }
+
+    /// Hand a conversation handled by `operator_id` back to the bot
+    ///
+    /// Returns `None` when the conversation is not handled by that operator.
+    pub fn release(&self, id: &Uuid, operator_id: &str) -> Result<Option<DbConversation>> {
+        let mut conn = self.pool.conn()?;
+        diesel::update(
+            conversations::table
+                .find(id)
+                .filter(conversations::state.eq(ConversationState::Human.as_str()))
+                .filter(conversations::operator_id.eq(operator_id)),
+        )
+        .set((
+            conversations::state.eq(ConversationState::Bot.as_str()),
+            conversations::operator_id.eq(None::<String>),
+            conversations::handoff_reason.eq(None::<String>),
+            conversations::handoff_requested_at.eq(None::<chrono::DateTime<Utc>>),
+            conversations::updated_at.eq(Utc::now()),
+        ))
+        .returning(DbConversation::as_returning())
+        .get_result(&mut conn)
+        .optional()
+        .map_err(|e| Error::Database(e.to_string()))
+    }
 }
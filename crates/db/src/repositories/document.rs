@@ -1,13 +1,27 @@
 //! Document repository using Diesel ORM.
 
-use crate::models::{Document, NewDocument};
+use crate::models::{Document, DocumentChunk, NewDocument};
 use crate::pool::DbPool;
-use crate::schema::documents;
+use crate::repositories::ranking::{
+    contains_pattern, in_rank_order, keyword_score_sql, query_terms, RankedId,
+};
+use crate::schema::{document_chunks, documents};
 use chrono::Utc;
 use common::{Error, Result};
+use diesel::dsl::sql_query;
+use diesel::pg::Pg;
 use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Text};
 use uuid::Uuid;
 
+/// A document chunk with its search relevance
+#[derive(Debug, Clone)]
+pub struct ScoredChunk {
+    pub chunk: DocumentChunk,
+    pub score: f32,
+}
+
+#[derive(Clone)]
 pub struct DocumentRepository {
     pool: DbPool,
 }
@@ -81,4 +95,46 @@ impl DocumentRepository {
             .map_err(|e| Error::Database(e.to_string()))?;
         Ok(count > 0)
     }
+
+    /// Document chunks ranked by the share of `query` words they contain
+    ///
+    /// Chunks matching none of the words are left out. Ranking happens in
+    /// SQL; only the top `limit` chunks are loaded.
+    pub fn search_chunks(&self, query: &str, limit: usize) -> Result<Vec<ScoredChunk>> {
+        let terms = query_terms(query);
+        if terms.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // Terms are bound first, as $1..$n
+        let keyword = keyword_score_sql("lower(c.content)", terms.len(), 1);
+        let sql = format!(
+            "SELECT c.id, ({})::REAL AS score FROM document_chunks c \
+             WHERE {} > 0 ORDER BY score DESC, c.chunk_index LIMIT ${}",
+            keyword,
+            keyword,
+            terms.len() + 1
+        );
+
+        let mut conn = self.pool.conn()?;
+        let mut ranking = sql_query(sql).into_boxed::<Pg>();
+        for term in &terms {
+            ranking = ranking.bind::<Text, _>(contains_pattern(term));
+        }
+        let ranked: Vec<RankedId> = ranking
+            .bind::<BigInt, _>(limit as i64)
+            .load(&mut conn)
+            .map_err(|e| Error::Database(e.to_string()))?;
+
+        let ids: Vec<Uuid> = ranked.iter().map(|r| r.id).collect();
+        let chunks: Vec<DocumentChunk> = document_chunks::table
+            .filter(document_chunks::id.eq_any(&ids))
+            .load(&mut conn)
+            .map_err(|e| Error::Database(e.to_string()))?;
+
+        Ok(in_rank_order(ranked, chunks, |chunk| chunk.id)
+            .into_iter()
+            .map(|(chunk, score)| ScoredChunk { chunk, score })
+            .collect())
+    }
 }
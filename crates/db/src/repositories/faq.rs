@@ -0,0 +1,193 @@
+//! FAQ repository using Diesel ORM.
+//!
+//! Questions are matched like products: by similarity to the stored FAQ
+//! embeddings when the query has one, blended with keyword matches.
+
+use crate::models::{Faq, NewFaqEmbedding};
+use crate::pool::DbPool;
+use crate::repositories::ranking::{
+    contains_pattern, hybrid_score_sql, in_rank_order, keyword_score_sql, query_terms, RankedId,
+};
+use crate::schema::{faq_embeddings, faqs};
+use chrono::Utc;
+use common::{Error, Result};
+use diesel::dsl::sql_query;
+use diesel::pg::Pg;
+use diesel::prelude::*;
+use diesel::sql_types::{Array, BigInt, Float4, Text};
+use uuid::Uuid;
+
+/// Text a query is matched against: question, answer and category
+const SEARCH_TEXT: &str = "lower(f.question || ' ' || f.answer || ' ' || f.category)";
+
+define_sql_function!(fn md5(x: Text) -> Text);
+
+/// An FAQ with its search relevance
+#[derive(Debug, Clone)]
+pub struct ScoredFaq {
+    pub faq: Faq,
+    pub score: f32,
+}
+
+#[derive(Clone)]
+pub struct FaqRepository {
+    pool: DbPool,
+}
+
+impl FaqRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Active FAQs in `language` ranked by relevance to `query`
+    ///
+    /// An empty query lists the most viewed FAQs. Ranking happens in SQL;
+    /// only the top `limit` FAQs are loaded.
+    pub fn search(
+        &self,
+        query: &str,
+        embedding: Option<&[f32]>,
+        language: &str,
+        limit: usize,
+    ) -> Result<Vec<ScoredFaq>> {
+        let mut conn = self.pool.conn()?;
+
+        let terms = query_terms(query);
+        if terms.is_empty() {
+            let rows: Vec<Faq> = faqs::table
+                .filter(faqs::is_active.eq(true))
+                .filter(faqs::language.eq(language))
+                .order(faqs::view_count.desc())
+                .limit(limit as i64)
+                .load(&mut conn)
+                .map_err(|e| Error::Database(e.to_string()))?;
+            return Ok(rows
+                .into_iter()
+                .map(|faq| ScoredFaq { faq, score: 0.0 })
+                .collect());
+        }
+
+        // Terms are bound first, as $1..$n
+        let keyword = keyword_score_sql(SEARCH_TEXT, terms.len(), 1);
+        let mut param = terms.len();
+        let mut next_param = || {
+            param += 1;
+            param
+        };
+        let score = match embedding {
+            Some(_) => hybrid_score_sql(next_param(), "e.embedding", &keyword),
+            None => keyword.clone(),
+        };
+        let mut sql = format!(
+            "SELECT f.id, ({})::REAL AS score \
+             FROM faqs f LEFT JOIN faq_embeddings e ON e.faq_id = f.id \
+             WHERE f.is_active AND f.language = ${}",
+            score,
+            next_param()
+        );
+        if embedding.is_none() {
+            sql.push_str(&format!(" AND {} > 0", keyword));
+        }
+        sql.push_str(&format!(
+            " ORDER BY score DESC, f.view_count DESC LIMIT ${}",
+            next_param()
+        ));
+
+        let mut ranking = sql_query(sql).into_boxed::<Pg>();
+        for term in &terms {
+            ranking = ranking.bind::<Text, _>(contains_pattern(term));
+        }
+        if let Some(embedding) = embedding {
+            ranking = ranking.bind::<Array<Float4>, _>(embedding.to_vec());
+        }
+        let ranked: Vec<RankedId> = ranking
+            .bind::<Text, _>(language)
+            .bind::<BigInt, _>(limit as i64)
+            .load(&mut conn)
+            .map_err(|e| Error::Database(e.to_string()))?;
+
+        let ids: Vec<Uuid> = ranked.iter().map(|r| r.id).collect();
+        let rows: Vec<Faq> = faqs::table
+            .filter(faqs::id.eq_any(&ids))
+            .load(&mut conn)
+            .map_err(|e| Error::Database(e.to_string()))?;
+
+        Ok(in_rank_order(ranked, rows, |faq| faq.id)
+            .into_iter()
+            .map(|(faq, score)| ScoredFaq { faq, score })
+            .collect())
+    }
+
+    /// FAQs without an up-to-date embedding from `model`, with the hash of
+    /// their current content
+    ///
+    /// An FAQ needs (re-)embedding when it has no embedding, one made by
+    /// another model, or one of an older question or answer.
+    pub fn stale_embeddings(&self, model: &str, limit: usize) -> Result<Vec<(Faq, String)>> {
+        let mut conn = self.pool.conn()?;
+        let content_hash = md5(faqs::question.concat("\n").concat(faqs::answer));
+        faqs::table
+            .left_join(faq_embeddings::table)
+            .filter(
+                faq_embeddings::faq_id
+                    .is_null()
+                    .or(faq_embeddings::model.ne(model))
+                    .or(faq_embeddings::content_hash.ne(content_hash)),
+            )
+            .select((Faq::as_select(), content_hash))
+            .order(faqs::created_at.asc())
+            .limit(limit as i64)
+            .load(&mut conn)
+            .map_err(|e| Error::Database(e.to_string()))
+    }
+
+    pub fn increment_view_counts(&self, ids: &[Uuid]) -> Result<()> {
+        if ids.is_empty() {
+            return Ok(());
+        }
+
+        let mut conn = self.pool.conn()?;
+        diesel::update(faqs::table.filter(faqs::id.eq_any(ids)))
+            .set(faqs::view_count.eq(faqs::view_count + 1))
+            .execute(&mut conn)
+            .map_err(|e| Error::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Store the embedding of an FAQ, replacing any previous one
+    ///
+    /// `content_hash` is the hash [`stale_embeddings`](Self::stale_embeddings)
+    /// returned for the embedded content.
+    pub fn upsert_embedding(
+        &self,
+        faq_id: &Uuid,
+        embedding: &[f32],
+        model: &str,
+        content_hash: &str,
+    ) -> Result<()> {
+        let mut conn = self.pool.conn()?;
+        let now = Utc::now();
+        let row = NewFaqEmbedding {
+            faq_id: *faq_id,
+            embedding,
+            model,
+            content_hash,
+            created_at: now,
+            updated_at: now,
+        };
+
+        diesel::insert_into(faq_embeddings::table)
+            .values(&row)
+            .on_conflict(faq_embeddings::faq_id)
+            .do_update()
+            .set((
+                faq_embeddings::embedding.eq(embedding),
+                faq_embeddings::model.eq(model),
+                faq_embeddings::content_hash.eq(content_hash),
+                faq_embeddings::updated_at.eq(now),
+            ))
+            .execute(&mut conn)
+            .map_err(|e| Error::Database(e.to_string()))?;
+        Ok(())
+    }
+}
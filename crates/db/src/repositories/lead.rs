@@ -0,0 +1,120 @@
+//! Lead repository using Diesel ORM.
+
+use crate::models::{Lead, NewLead};
+use crate::pool::DbPool;
+use crate::schema::leads;
+use chrono::{DateTime, Utc};
+use common::{Error, Result};
+use diesel::prelude::*;
+use uuid::Uuid;
+
+/// A validated lead to store
+#[derive(Debug, Clone)]
+pub struct LeadInput {
+    /// Conversation the lead was captured in
+    pub conversation_id: Option<Uuid>,
+    pub name: String,
+    pub company: Option<String>,
+    pub phone: Option<String>,
+    pub email: Option<String>,
+    pub interested_products: Vec<String>,
+    pub notes: Option<String>,
+    /// Whether the customer agreed to be contacted by the sales team
+    pub consent: bool,
+}
+
+/// Filters applied when listing leads
+#[derive(Debug, Clone, Default)]
+pub struct LeadFilter {
+    pub status: Option<String>,
+    pub conversation_id: Option<Uuid>,
+    /// Only leads captured at or after this time
+    pub since: Option<DateTime<Utc>>,
+    /// Only leads captured before this time
+    pub until: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone)]
+pub struct LeadRepository {
+    pool: DbPool,
+}
+
+impl LeadRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Store a new lead, recording the time consent was given
+    pub fn create(&self, input: &LeadInput) -> Result<Lead> {
+        let mut conn = self.pool.conn()?;
+        let now = Utc::now();
+        let new_lead = NewLead {
+            id: Uuid::new_v4(),
+            conversation_id: input.conversation_id,
+            name: &input.name,
+            company: input.company.as_deref(),
+            phone: input.phone.as_deref(),
+            email: input.email.as_deref(),
+            interested_products: serde_json::json!(input.interested_products),
+            notes: input.notes.as_deref(),
+            consent: input.consent,
+            consent_at: input.consent.then_some(now),
+            status: "new",
+            metadata: serde_json::json!({}),
+            created_at: now,
+            updated_at: now,
+        };
+
+        diesel::insert_into(leads::table)
+            .values(&new_lead)
+            .returning(Lead::as_returning())
+            .get_result(&mut conn)
+            .map_err(|e| Error::Database(e.to_string()))
+    }
+
+    pub fn get(&self, id: &Uuid) -> Result<Option<Lead>> {
+        let mut conn = self.pool.conn()?;
+        leads::table
+            .find(id)
+            .first(&mut conn)
+            .optional()
+            .map_err(|e| Error::Database(e.to_string()))
+    }
+
+    /// Leads matching `filter`, newest first
+    pub fn list(&self, filter: &LeadFilter, limit: i64, offset: i64) -> Result<Vec<Lead>> {
+        let mut conn = self.pool.conn()?;
+        filtered(filter)
+            .order(leads::created_at.desc())
+            .limit(limit)
+            .offset(offset)
+            .load(&mut conn)
+            .map_err(|e| Error::Database(e.to_string()))
+    }
+
+    /// All leads matching `filter`, oldest first
+    pub fn export(&self, filter: &LeadFilter) -> Result<Vec<Lead>> {
+        let mut conn = self.pool.conn()?;
+        filtered(filter)
+            .order(leads::created_at.asc())
+            .load(&mut conn)
+            .map_err(|e| Error::Database(e.to_string()))
+    }
+}
+
+fn filtered(filter: &LeadFilter) -> leads::BoxedQuery<'static, diesel::pg::Pg> {
+    let mut sql = leads::table.into_boxed();
+    if let Some(status) = &filter.status {
+        sql = sql.filter(leads::status.eq(status.clone()));
+    }
+    if let Some(conversation_id) = filter.conversation_id {
+        sql = sql.filter(leads::conversation_id.eq(conversation_id));
+    }
+    if let Some(since) = filter.since {
+        sql = sql.filter(leads::created_at.ge(since));
+    }
+    if let Some(until) = filter.until {
+        sql = sql.filter(leads::created_at.lt(until));
+    }
+    sql
+}
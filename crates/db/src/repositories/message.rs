@@ -40,13 +40,23 @@ impl MessageRepository {
     }
 
     pub fn create(&self, conversation_id: Uuid, role: &str, content: &str) -> Result<Message> {
+        self.create_with_metadata(conversation_id, role, content, serde_json::json!({}))
+    }
+
+    pub fn create_with_metadata(
+        &self,
+        conversation_id: Uuid,
+        role: &str,
+        content: &str,
+        metadata: serde_json::Value,
+    ) -> Result<Message> {
         let mut conn = self.pool.conn()?;
         let new_msg = NewMessage {
             id: Uuid::new_v4(),
             conversation_id,
             role,
             content,
-            metadata: serde_json::json!({}),
+            metadata,
             created_at: Utc::now(),
         };
 
@@ -1,11 +1,28 @@
 //! Repository implementations for data access.
 
+pub mod appointment;
+pub mod brochure;
+pub mod company_info;
 pub mod conversation;
 pub mod document;
+pub mod faq;
 pub mod job;
+pub mod lead;
 pub mod message;
+pub mod product;
+pub mod quote;
+mod ranking;
 
+pub use appointment::{
+    AppointmentFilter, AppointmentRepository, HoldInput, SlotAvailability, SlotInput,
+};
+pub use brochure::{BrochureFilter, BrochureRepository};
+pub use company_info::CompanyInfoRepository;
 pub use conversation::ConversationRepository;
-pub use document::DocumentRepository;
+pub use document::{DocumentRepository, ScoredChunk};
+pub use faq::{FaqRepository, ScoredFaq};
 pub use job::JobRepository;
+pub use lead::{LeadFilter, LeadInput, LeadRepository};
 pub use message::MessageRepository;
+pub use product::{ProductFilter, ProductRepository, ScoredProduct};
+pub use quote::{QuoteDraft, QuoteRepository};
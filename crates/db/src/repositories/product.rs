@@ -0,0 +1,254 @@
+//! Product repository using Diesel ORM.
+//!
+//! Search combines the catalog filters with two ranking signals: cosine
+//! similarity against the stored product embeddings, and the share of query
+//! terms found in the product's name, description, category and features.
+
+use crate::models::{NewProductEmbedding, Product};
+use crate::pool::DbPool;
+use crate::repositories::ranking::{
+    contains_pattern, hybrid_score_sql, in_rank_order, keyword_score_sql, query_terms, RankedId,
+};
+use crate::schema::{product_embeddings, products};
+use bigdecimal::BigDecimal;
+use chrono::Utc;
+use common::{Error, Result};
+use diesel::dsl::sql_query;
+use diesel::pg::Pg;
+use diesel::prelude::*;
+use diesel::sql_types::{Array, BigInt, Bool, Float4, Numeric, Text};
+use uuid::Uuid;
+
+/// Text a query is matched against: name, description, category, features
+const SEARCH_TEXT: &str =
+    "lower(p.name || ' ' || p.description || ' ' || p.category || ' ' || p.features::text)";
+
+define_sql_function!(fn lower(x: Text) -> Text);
+
+/// Filters applied to a product search
+#[derive(Debug, Clone, Default)]
+pub struct ProductFilter {
+    /// Exact category, compared case-insensitively
+    pub category: Option<String>,
+    pub min_price: Option<f64>,
+    pub max_price: Option<f64>,
+    /// Only active (or only inactive) products; `None` matches both
+    pub is_active: Option<bool>,
+}
+
+/// A product with its search relevance
+#[derive(Debug, Clone)]
+pub struct ScoredProduct {
+    pub product: Product,
+    pub score: f32,
+}
+
+#[derive(Clone)]
+pub struct ProductRepository {
+    pool: DbPool,
+}
+
+impl ProductRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    pub fn get(&self, id: &Uuid) -> Result<Option<Product>> {
+        let mut conn = self.pool.conn()?;
+        products::table
+            .find(id)
+            .first(&mut conn)
+            .optional()
+            .map_err(|e| Error::Database(e.to_string()))
+    }
+
+    pub fn get_many(&self, ids: &[Uuid]) -> Result<Vec<Product>> {
+        let mut conn = self.pool.conn()?;
+        products::table
+            .filter(products::id.eq_any(ids))
+            .load(&mut conn)
+            .map_err(|e| Error::Database(e.to_string()))
+    }
+
+    /// Search products matching `filter`, ranked by relevance to `query`
+    ///
+    /// With `embedding`, products are ranked by embedding similarity blended
+    /// with keyword matches. Without it, only products matching at least one
+    /// query term are returned. An empty query lists products by name.
+    /// Ranking happens in SQL; only the top `limit` products are loaded.
+    pub fn search(
+        &self,
+        query: &str,
+        embedding: Option<&[f32]>,
+        filter: &ProductFilter,
+        limit: usize,
+    ) -> Result<Vec<ScoredProduct>> {
+        let mut conn = self.pool.conn()?;
+        let min_price = decimal(filter.min_price)?;
+        let max_price = decimal(filter.max_price)?;
+        let category = filter.category.as_deref().map(str::to_lowercase);
+
+        let terms = query_terms(query);
+        if terms.is_empty() {
+            let mut sql = products::table.into_boxed();
+            if let Some(category) = category {
+                sql = sql.filter(lower(products::category).eq(category));
+            }
+            if let Some(min_price) = min_price {
+                sql = sql.filter(products::price.ge(min_price));
+            }
+            if let Some(max_price) = max_price {
+                sql = sql.filter(products::price.le(max_price));
+            }
+            if let Some(is_active) = filter.is_active {
+                sql = sql.filter(products::is_active.eq(is_active));
+            }
+
+            let rows: Vec<Product> = sql
+                .order(products::name.asc())
+                .limit(limit as i64)
+                .load(&mut conn)
+                .map_err(|e| Error::Database(e.to_string()))?;
+            return Ok(rows
+                .into_iter()
+                .map(|product| ScoredProduct {
+                    product,
+                    score: 0.0,
+                })
+                .collect());
+        }
+
+        // Terms are bound first, as $1..$n
+        let keyword = keyword_score_sql(SEARCH_TEXT, terms.len(), 1);
+        let mut param = terms.len();
+        let mut next_param = || {
+            param += 1;
+            param
+        };
+        let score = match embedding {
+            Some(_) => hybrid_score_sql(next_param(), "e.embedding", &keyword),
+            None => keyword.clone(),
+        };
+        let mut sql = format!(
+            "SELECT p.id, ({})::REAL AS score \
+             FROM products p LEFT JOIN product_embeddings e ON e.product_id = p.id \
+             WHERE TRUE",
+            score
+        );
+        if embedding.is_none() {
+            sql.push_str(&format!(" AND {} > 0", keyword));
+        }
+        if category.is_some() {
+            sql.push_str(&format!(" AND lower(p.category) = ${}", next_param()));
+        }
+        if min_price.is_some() {
+            sql.push_str(&format!(" AND p.price >= ${}", next_param()));
+        }
+        if max_price.is_some() {
+            sql.push_str(&format!(" AND p.price <= ${}", next_param()));
+        }
+        if filter.is_active.is_some() {
+            sql.push_str(&format!(" AND p.is_active = ${}", next_param()));
+        }
+        sql.push_str(&format!(
+            " ORDER BY score DESC, p.name LIMIT ${}",
+            next_param()
+        ));
+
+        let mut ranking = sql_query(sql).into_boxed::<Pg>();
+        for term in &terms {
+            ranking = ranking.bind::<Text, _>(contains_pattern(term));
+        }
+        if let Some(embedding) = embedding {
+            ranking = ranking.bind::<Array<Float4>, _>(embedding.to_vec());
+        }
+        if let Some(category) = category {
+            ranking = ranking.bind::<Text, _>(category);
+        }
+        if let Some(min_price) = min_price {
+            ranking = ranking.bind::<Numeric, _>(min_price);
+        }
+        if let Some(max_price) = max_price {
+            ranking = ranking.bind::<Numeric, _>(max_price);
+        }
+        if let Some(is_active) = filter.is_active {
+            ranking = ranking.bind::<Bool, _>(is_active);
+        }
+        let ranked: Vec<RankedId> = ranking
+            .bind::<BigInt, _>(limit as i64)
+            .load(&mut conn)
+            .map_err(|e| Error::Database(e.to_string()))?;
+
+        let ids: Vec<Uuid> = ranked.iter().map(|r| r.id).collect();
+        let rows: Vec<Product> = products::table
+            .filter(products::id.eq_any(&ids))
+            .load(&mut conn)
+            .map_err(|e| Error::Database(e.to_string()))?;
+
+        Ok(in_rank_order(ranked, rows, |product| product.id)
+            .into_iter()
+            .map(|(product, score)| ScoredProduct { product, score })
+            .collect())
+    }
+
+    /// Products without an up-to-date embedding from `model`
+    ///
+    /// A product needs (re-)embedding when it has no embedding, one made by
+    /// another model, or one older than its last update.
+    pub fn stale_embeddings(&self, model: &str, limit: usize) -> Result<Vec<Product>> {
+        let mut conn = self.pool.conn()?;
+        products::table
+            .left_join(product_embeddings::table)
+            .filter(
+                product_embeddings::product_id
+                    .is_null()
+                    .or(product_embeddings::model.ne(model))
+                    .or(product_embeddings::updated_at.lt(products::updated_at)),
+            )
+            .select(Product::as_select())
+            .order(products::updated_at.asc())
+            .limit(limit as i64)
+            .load(&mut conn)
+            .map_err(|e| Error::Database(e.to_string()))
+    }
+
+    /// Store the embedding of a product, replacing any previous one
+    pub fn upsert_embedding(
+        &self,
+        product_id: &Uuid,
+        embedding: &[f32],
+        model: &str,
+    ) -> Result<()> {
+        let mut conn = self.pool.conn()?;
+        let now = Utc::now();
+        let row = NewProductEmbedding {
+            product_id: *product_id,
+            embedding,
+            model,
+            created_at: now,
+            updated_at: now,
+        };
+
+        diesel::insert_into(product_embeddings::table)
+            .values(&row)
+            .on_conflict(product_embeddings::product_id)
+            .do_update()
+            .set((
+                product_embeddings::embedding.eq(embedding),
+                product_embeddings::model.eq(model),
+                product_embeddings::updated_at.eq(now),
+            ))
+            .execute(&mut conn)
+            .map_err(|e| Error::Database(e.to_string()))?;
+        Ok(())
+    }
+}
+
+fn decimal(value: Option<f64>) -> Result<Option<BigDecimal>> {
+    value
+        .map(|v| {
+            BigDecimal::try_from(v)
+                .map_err(|_| Error::Validation(format!("Invalid price filter: {}", v)))
+        })
+        .transpose()
+}
@@ -0,0 +1,82 @@
+//! Quote repository using Diesel ORM.
+
+use crate::models::{NewQuote, Quote};
+use crate::pool::DbPool;
+use crate::schema::quotes;
+use bigdecimal::num_bigint::BigInt;
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use common::{Error, Result};
+use diesel::prelude::*;
+use uuid::Uuid;
+
+/// A computed quote to store as a draft
+///
+/// Amounts are in minor units (satang, cents) of `currency`.
+#[derive(Debug, Clone)]
+pub struct QuoteDraft {
+    pub currency: String,
+    pub items: serde_json::Value,
+    pub subtotal: i64,
+    pub discount_total: i64,
+    /// VAT rate in percent
+    pub vat_rate: f64,
+    pub vat_amount: i64,
+    pub total: i64,
+    pub notes: Option<String>,
+    pub valid_until: DateTime<Utc>,
+}
+
+#[derive(Clone)]
+pub struct QuoteRepository {
+    pool: DbPool,
+}
+
+impl QuoteRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    pub fn create_draft(&self, draft: &QuoteDraft) -> Result<Quote> {
+        let mut conn = self.pool.conn()?;
+        let now = Utc::now();
+        let vat_rate = BigDecimal::try_from(draft.vat_rate)
+            .map_err(|_| Error::Validation(format!("Invalid VAT rate: {}", draft.vat_rate)))?
+            .round(2);
+        let new_quote = NewQuote {
+            id: Uuid::new_v4(),
+            status: "draft",
+            currency: &draft.currency,
+            items: draft.items.clone(),
+            subtotal: from_minor_units(draft.subtotal),
+            discount_total: from_minor_units(draft.discount_total),
+            vat_rate,
+            vat_amount: from_minor_units(draft.vat_amount),
+            total: from_minor_units(draft.total),
+            notes: draft.notes.as_deref(),
+            valid_until: draft.valid_until,
+            metadata: serde_json::json!({}),
+            created_at: now,
+            updated_at: now,
+        };
+
+        diesel::insert_into(quotes::table)
+            .values(&new_quote)
+            .returning(Quote::as_returning())
+            .get_result(&mut conn)
+            .map_err(|e| Error::Database(e.to_string()))
+    }
+
+    pub fn get(&self, id: &Uuid) -> Result<Option<Quote>> {
+        let mut conn = self.pool.conn()?;
+        quotes::table
+            .find(id)
+            .first(&mut conn)
+            .optional()
+            .map_err(|e| Error::Database(e.to_string()))
+    }
+}
+
+fn from_minor_units(amount: i64) -> BigDecimal {
+    BigDecimal::new(BigInt::from(amount), 2)
+}
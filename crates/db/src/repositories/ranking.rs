@@ -0,0 +1,148 @@
+//! Relevance scoring shared by the search repositories.
+//!
+//! Searches rank rows in SQL and load only the best ones. The embedding
+//! similarity uses the `cosine_similarity` function from the migrations.
+
+use diesel::prelude::*;
+use diesel::sql_types::{Float4, Uuid as SqlUuid};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Weight of embedding similarity when the query has an embedding
+pub(crate) const SEMANTIC_WEIGHT: f32 = 0.7;
+
+/// A row's ID with its relevance, as ranked by a search query
+#[derive(Debug, QueryableByName)]
+pub(crate) struct RankedId {
+    #[diesel(sql_type = SqlUuid)]
+    pub id: Uuid,
+    #[diesel(sql_type = Float4)]
+    pub score: f32,
+}
+
+/// Length of the character n-grams Thai text is split into
+const THAI_NGRAM: usize = 3;
+
+/// Lowercased terms of a search query
+///
+/// Words are split on whitespace. Thai puts no spaces between words, so
+/// each run of Thai text is split further into overlapping character
+/// trigrams; a row then scores by how much of the run it contains.
+pub(crate) fn query_terms(query: &str) -> Vec<String> {
+    let mut terms: Vec<String> = Vec::new();
+    let mut push = |term: String| {
+        if !terms.contains(&term) {
+            terms.push(term);
+        }
+    };
+
+    for word in query.split_whitespace() {
+        let chars: Vec<char> = word.to_lowercase().chars().collect();
+        let mut rest = chars.as_slice();
+        while let Some(&first) = rest.first() {
+            let len = rest
+                .iter()
+                .position(|c| is_thai(*c) != is_thai(first))
+                .unwrap_or(rest.len());
+            let (run, tail) = rest.split_at(len);
+            if is_thai(first) && run.len() > THAI_NGRAM {
+                run.windows(THAI_NGRAM)
+                    .for_each(|gram| push(gram.iter().collect()));
+            } else {
+                push(run.iter().collect());
+            }
+            rest = tail;
+        }
+    }
+    terms
+}
+
+fn is_thai(c: char) -> bool {
+    ('\u{0E00}'..='\u{0E7F}').contains(&c)
+}
+
+/// `LIKE` pattern matching `term` anywhere, with its wildcards escaped
+pub(crate) fn contains_pattern(term: &str) -> String {
+    let escaped = term
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("%{}%", escaped)
+}
+
+/// SQL for the share of query terms found in `text`
+///
+/// `text` must be a lowercased SQL expression. The [`contains_pattern`] of
+/// each of the `terms` is bound as `$first`, `$first + 1`, ...
+pub(crate) fn keyword_score_sql(text: &str, terms: usize, first: usize) -> String {
+    let matches: Vec<String> = (first..first + terms)
+        .map(|param| {
+            format!(
+                "CASE WHEN {} LIKE ${} ESCAPE '\\' THEN 1 ELSE 0 END",
+                text, param
+            )
+        })
+        .collect();
+    format!("(({})::REAL / {})", matches.join(" + "), terms)
+}
+
+/// SQL blending the embedding similarity with `keyword`
+///
+/// The query embedding is bound as `$param`; `stored` is the row's
+/// embedding column, which may be NULL.
+pub(crate) fn hybrid_score_sql(param: usize, stored: &str, keyword: &str) -> String {
+    format!(
+        "{} * cosine_similarity(${}, {}) + {} * {}",
+        SEMANTIC_WEIGHT,
+        param,
+        stored,
+        1.0 - SEMANTIC_WEIGHT,
+        keyword
+    )
+}
+
+/// Pair `rows` with their scores, in the order of `ranked`
+pub(crate) fn in_rank_order<T>(
+    ranked: Vec<RankedId>,
+    rows: Vec<T>,
+    id: impl Fn(&T) -> Uuid,
+) -> Vec<(T, f32)> {
+    let mut rows: HashMap<Uuid, T> = rows.into_iter().map(|row| (id(&row), row)).collect();
+    ranked
+        .into_iter()
+        .filter_map(|ranked| rows.remove(&ranked.id).map(|row| (row, ranked.score)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Share of query terms found in `text`, as `keyword_score_sql` scores it
+    fn keyword_score(text: &str, terms: &[String]) -> f32 {
+        let matched = terms
+            .iter()
+            .filter(|term| text.contains(term.as_str()))
+            .count();
+        matched as f32 / terms.len() as f32
+    }
+
+    #[test]
+    fn test_query_terms_split_thai_runs() {
+        assert_eq!(query_terms("Pump  X"), ["pump", "x"]);
+        assert_eq!(query_terms("ราคา"), ["ราค", "าคา"]);
+        assert_eq!(query_terms("ปั๊ม500"), ["ปั๊", "ั๊ม", "500"]);
+        assert_eq!(query_terms("ส่ง"), ["ส่ง"]);
+
+        let text = "ปั๊มน้ำแรงดันสูง ราคาพิเศษ";
+        assert_eq!(keyword_score(text, &query_terms("ปั๊มน้ำ")), 1.0);
+        assert!(keyword_score(text, &query_terms("ราคาปั๊มน้ำ")) > 0.7);
+        assert_eq!(keyword_score(text, &query_terms("วาล์ว")), 0.0);
+    }
+
+    #[test]
+    fn test_contains_pattern_escapes_wildcards() {
+        assert_eq!(contains_pattern("50%_off"), "%50\\%\\_off%");
+        assert_eq!(contains_pattern("a\\b"), "%a\\\\b%");
+    }
+}
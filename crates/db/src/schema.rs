@@ -18,6 +18,47 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    appointment_slots (id) {
+        id -> Uuid,
+        #[max_length = 50]
+        kind -> Varchar,
+        starts_at -> Timestamptz,
+        ends_at -> Timestamptz,
+        capacity -> Int4,
+        #[max_length = 255]
+        location -> Nullable<Varchar>,
+        #[max_length = 255]
+        host -> Nullable<Varchar>,
+        is_active -> Bool,
+        metadata -> Jsonb,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    appointments (id) {
+        id -> Uuid,
+        slot_id -> Uuid,
+        conversation_id -> Nullable<Uuid>,
+        #[max_length = 255]
+        customer_name -> Varchar,
+        #[max_length = 20]
+        customer_phone -> Nullable<Varchar>,
+        #[max_length = 255]
+        customer_email -> Nullable<Varchar>,
+        notes -> Nullable<Text>,
+        #[max_length = 20]
+        status -> Varchar,
+        hold_expires_at -> Nullable<Timestamptz>,
+        confirmed_at -> Nullable<Timestamptz>,
+        metadata -> Jsonb,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     brochures (id) {
         id -> Uuid,
@@ -66,6 +107,12 @@ diesel::table! {
         agent_id -> Nullable<Varchar>,
         created_at -> Timestamptz,
         updated_at -> Timestamptz,
+        #[max_length = 20]
+        state -> Varchar,
+        #[max_length = 255]
+        operator_id -> Nullable<Varchar>,
+        handoff_reason -> Nullable<Text>,
+        handoff_requested_at -> Nullable<Timestamptz>,
     }
 }
 
@@ -110,6 +157,19 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    faq_embeddings (faq_id) {
+        faq_id -> Uuid,
+        embedding -> Array<Float4>,
+        #[max_length = 100]
+        model -> Varchar,
+        #[max_length = 32]
+        content_hash -> Varchar,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     faqs (id) {
         id -> Uuid,
@@ -142,6 +202,30 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    leads (id) {
+        id -> Uuid,
+        conversation_id -> Nullable<Uuid>,
+        #[max_length = 255]
+        name -> Varchar,
+        #[max_length = 255]
+        company -> Nullable<Varchar>,
+        #[max_length = 20]
+        phone -> Nullable<Varchar>,
+        #[max_length = 255]
+        email -> Nullable<Varchar>,
+        interested_products -> Jsonb,
+        notes -> Nullable<Text>,
+        consent -> Bool,
+        consent_at -> Nullable<Timestamptz>,
+        #[max_length = 20]
+        status -> Varchar,
+        metadata -> Jsonb,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     product_brochures (product_id, brochure_id) {
         product_id -> Uuid,
@@ -149,6 +233,17 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    product_embeddings (product_id) {
+        product_id -> Uuid,
+        embedding -> Array<Float4>,
+        #[max_length = 100]
+        model -> Varchar,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     products (id) {
         id -> Uuid,
@@ -170,21 +265,51 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    quotes (id) {
+        id -> Uuid,
+        #[max_length = 20]
+        status -> Varchar,
+        #[max_length = 10]
+        currency -> Varchar,
+        items -> Jsonb,
+        subtotal -> Numeric,
+        discount_total -> Numeric,
+        vat_rate -> Numeric,
+        vat_amount -> Numeric,
+        total -> Numeric,
+        notes -> Nullable<Text>,
+        valid_until -> Timestamptz,
+        metadata -> Jsonb,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::joinable!(appointments -> appointment_slots (slot_id));
 diesel::joinable!(document_chunks -> documents (document_id));
+diesel::joinable!(faq_embeddings -> faqs (faq_id));
 diesel::joinable!(messages -> conversations (conversation_id));
 diesel::joinable!(product_brochures -> brochures (brochure_id));
 diesel::joinable!(product_brochures -> products (product_id));
+diesel::joinable!(product_embeddings -> products (product_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
     agents,
+    appointment_slots,
+    appointments,
     brochures,
     company_info,
     conversations,
     document_chunks,
     documents,
+    faq_embeddings,
     faqs,
     jobs,
+    leads,
     messages,
     product_brochures,
+    product_embeddings,
     products,
+    quotes,
 );
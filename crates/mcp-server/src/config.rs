@@ -0,0 +1,184 @@
+//! MCP server configuration.
+//!
+//! Loaded from `config/mcp.toml` when present. Only tools listed in
+//! `allowed_tools` are served; without a config file that is the read-only
+//! set (`product_search`, `get_brochure`, `company_info`, `search`).
+
+use serde::Deserialize;
+use std::net::SocketAddr;
+use std::path::Path;
+
+/// MCP server configuration
+#[derive(Debug, Clone, Deserialize)]
+pub struct McpConfig {
+    /// Server name reported to clients
+    #[serde(default = "default_name")]
+    pub name: String,
+
+    /// Tools clients may list and call
+    #[serde(default = "default_allowed_tools")]
+    pub allowed_tools: Vec<String>,
+
+    #[serde(default)]
+    pub http: HttpConfig,
+}
+
+/// Streamable HTTP transport settings
+#[derive(Debug, Clone, Deserialize)]
+pub struct HttpConfig {
+    #[serde(default = "default_host")]
+    pub host: String,
+    #[serde(default = "default_port")]
+    pub port: u16,
+    /// Endpoint receiving JSON-RPC messages
+    #[serde(default = "default_path")]
+    pub path: String,
+}
+
+fn default_name() -> String {
+    "agentic-rust".to_string()
+}
+
+fn default_allowed_tools() -> Vec<String> {
+    ["product_search", "get_brochure", "company_info", "search"]
+        .into_iter()
+        .map(String::from)
+        .collect()
+}
+
+fn default_host() -> String {
+    "127.0.0.1".to_string()
+}
+
+fn default_port() -> u16 {
+    8090
+}
+
+fn default_path() -> String {
+    "/mcp".to_string()
+}
+
+impl Default for McpConfig {
+    fn default() -> Self {
+        Self {
+            name: default_name(),
+            allowed_tools: default_allowed_tools(),
+            http: HttpConfig::default(),
+        }
+    }
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        Self {
+            host: default_host(),
+            port: default_port(),
+            path: default_path(),
+        }
+    }
+}
+
+impl HttpConfig {
+    /// Resolve the host, which may be a name such as "localhost", to the
+    /// addresses to listen on
+    pub async fn resolve(&self) -> std::io::Result<Vec<SocketAddr>> {
+        Ok(tokio::net::lookup_host((self.host.as_str(), self.port))
+            .await?
+            .collect())
+    }
+}
+
+impl McpConfig {
+    /// Load configuration from a TOML file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> common::Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| common::Error::Config(format!("Failed to read MCP config: {}", e)))?;
+        Self::parse_toml(&content)
+    }
+
+    /// Load configuration from a TOML string
+    pub fn parse_toml(content: &str) -> common::Result<Self> {
+        toml::from_str(content)
+            .map_err(|e| common::Error::Config(format!("Failed to parse MCP config: {}", e)))
+    }
+
+    /// Load from `MCP_CONFIG`, the default paths, or use defaults
+    pub fn load() -> common::Result<Self> {
+        if let Ok(path) = std::env::var("MCP_CONFIG") {
+            return Self::from_file(path);
+        }
+
+        let config_paths = [
+            "config/mcp.toml",
+            "./mcp.toml",
+            "/etc/agentic-rust/mcp.toml",
+        ];
+        for path in config_paths {
+            if Path::new(path).exists() {
+                tracing::info!("Loaded MCP config from: {}", path);
+                return Self::from_file(path);
+            }
+        }
+
+        tracing::info!("Using default MCP configuration");
+        Ok(Self::default())
+    }
+
+    /// Whether clients may use the tool
+    pub fn is_allowed(&self, tool_name: &str) -> bool {
+        self.allowed_tools
+            .iter()
+            .any(|allowed| allowed == tool_name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config() {
+        let config = McpConfig::parse_toml(
+            r#"
+allowed_tools = ["search", "quote"]
+
+[http]
+port = 9000
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.name, "agentic-rust");
+        assert!(config.is_allowed("quote"));
+        assert!(!config.is_allowed("capture_lead"));
+        assert_eq!(config.http.host, "127.0.0.1");
+        assert_eq!(config.http.port, 9000);
+        assert_eq!(config.http.path, "/mcp");
+    }
+
+    #[tokio::test]
+    async fn test_http_host_resolves_names() {
+        let host = |host: &str| HttpConfig {
+            host: host.to_string(),
+            ..HttpConfig::default()
+        };
+
+        let addrs = host("localhost").resolve().await.unwrap();
+        assert!(!addrs.is_empty());
+        assert!(addrs.iter().all(|addr| addr.ip().is_loopback()));
+        assert_eq!(addrs[0].port(), 8090);
+
+        let addrs = host("10.0.0.5").resolve().await.unwrap();
+        assert!(!addrs[0].ip().is_loopback());
+    }
+
+    #[test]
+    fn test_default_allows_read_only_tools() {
+        let config = McpConfig::parse_toml("").unwrap();
+
+        assert!(config.is_allowed("product_search"));
+        assert!(config.is_allowed("search"));
+        assert!(!config.is_allowed("capture_lead"));
+        assert!(!config.is_allowed("book_appointment"));
+    }
+}
@@ -0,0 +1,307 @@
+//! JSON-RPC handling of MCP requests.
+//!
+//! Supports the handshake (`initialize`), `ping`, `tools/list` and
+//! `tools/call`. Arguments are checked against each tool's schema before it
+//! runs, and tool failures are returned as results with `isError` set so the
+//! client's model can see them. Only argument errors are described; other
+//! failure details stay in the server log.
+
+use agent::crew::{DynamicTool, ToolError};
+use agent::tools::Tool;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+use crate::config::McpConfig;
+
+/// Protocol revisions this server speaks, newest first
+pub const PROTOCOL_VERSIONS: [&str; 2] = ["2025-03-26", "2024-11-05"];
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+/// Serves a set of agent tools over MCP
+pub struct McpHandler {
+    name: String,
+    tools: Vec<DynamicTool>,
+}
+
+impl McpHandler {
+    /// Serve the tools allowed by `config`
+    pub fn new(config: &McpConfig, tools: Vec<Box<dyn Tool>>) -> Self {
+        let tools: Vec<DynamicTool> = tools
+            .into_iter()
+            .filter(|tool| config.is_allowed(&tool.definition().name))
+            .map(|tool| DynamicTool::from_tool(Arc::from(tool)))
+            .collect();
+
+        for allowed in &config.allowed_tools {
+            if !tools.iter().any(|tool| tool.name() == allowed) {
+                tracing::warn!(tool = %allowed, "Allowed tool does not exist");
+            }
+        }
+
+        Self {
+            name: config.name.clone(),
+            tools,
+        }
+    }
+
+    /// Names of the tools served
+    pub fn tool_names(&self) -> Vec<&str> {
+        self.tools.iter().map(|tool| tool.name()).collect()
+    }
+
+    /// Handle a message or batch, returning the response if one is due
+    ///
+    /// Notifications get no response.
+    pub async fn handle(&self, message: Value) -> Option<Value> {
+        match message {
+            Value::Array(batch) if !batch.is_empty() => {
+                let mut responses = Vec::new();
+                for message in batch {
+                    responses.extend(self.handle_one(message).await);
+                }
+                (!responses.is_empty()).then_some(Value::Array(responses))
+            }
+            message => self.handle_one(message).await,
+        }
+    }
+
+    /// Response to a message that is not valid JSON
+    pub fn parse_error(message: &str) -> Value {
+        error_response(Value::Null, RpcError::new(PARSE_ERROR, message))
+    }
+
+    async fn handle_one(&self, message: Value) -> Option<Value> {
+        let Some(method) = message.get("method").and_then(Value::as_str) else {
+            let id = message.get("id").cloned().unwrap_or(Value::Null);
+            return Some(error_response(
+                id,
+                RpcError::new(INVALID_REQUEST, "Invalid request"),
+            ));
+        };
+        let id = message.get("id").cloned()?;
+        let params = message.get("params").cloned().unwrap_or_else(|| json!({}));
+
+        Some(match self.dispatch(method, params).await {
+            Ok(result) => json!({"jsonrpc": "2.0", "id": id, "result": result}),
+            Err(error) => error_response(id, error),
+        })
+    }
+
+    async fn dispatch(&self, method: &str, params: Value) -> Result<Value, RpcError> {
+        match method {
+            "initialize" => Ok(self.initialize(&params)),
+            "ping" => Ok(json!({})),
+            "tools/list" => Ok(json!({
+                "tools": self.tools.iter().map(tool_json).collect::<Vec<_>>()
+            })),
+            "tools/call" => self.call_tool(params).await,
+            other => Err(RpcError::new(
+                METHOD_NOT_FOUND,
+                format!("Method not found: {}", other),
+            )),
+        }
+    }
+
+    fn initialize(&self, params: &Value) -> Value {
+        let requested = params["protocolVersion"].as_str().unwrap_or_default();
+        let version = PROTOCOL_VERSIONS
+            .into_iter()
+            .find(|version| *version == requested)
+            .unwrap_or(PROTOCOL_VERSIONS[0]);
+
+        json!({
+            "protocolVersion": version,
+            "capabilities": {"tools": {"listChanged": false}},
+            "serverInfo": {"name": self.name, "version": env!("CARGO_PKG_VERSION")}
+        })
+    }
+
+    async fn call_tool(&self, params: Value) -> Result<Value, RpcError> {
+        let name = params["name"]
+            .as_str()
+            .ok_or_else(|| RpcError::new(INVALID_PARAMS, "Missing tool name"))?;
+        let tool = self
+            .tools
+            .iter()
+            .find(|tool| tool.name() == name)
+            .ok_or_else(|| RpcError::new(INVALID_PARAMS, format!("Unknown tool: {}", name)))?;
+        let arguments = match params.get("arguments") {
+            Some(Value::Null) | None => json!({}),
+            Some(arguments) => arguments.clone(),
+        };
+
+        Ok(match tool.execute(arguments).await {
+            Ok(output) => json!({"content": [{"type": "text", "text": output}]}),
+            Err(e) => {
+                tracing::warn!(tool = name, error = %e, "MCP tool call failed");
+                json!({
+                    "content": [{"type": "text", "text": client_error(name, &e)}],
+                    "isError": true
+                })
+            }
+        })
+    }
+}
+
+/// Error text for the client
+///
+/// Problems with the caller's arguments are reported as is; anything else
+/// may carry internal details, so it is only logged.
+fn client_error(name: &str, error: &ToolError) -> String {
+    match error {
+        ToolError::InvalidInput(_) | ToolError::MissingArgument(_) => error.to_string(),
+        _ => format!("Tool '{}' failed", name),
+    }
+}
+
+fn tool_json(tool: &DynamicTool) -> Value {
+    let definition = tool.definition();
+    json!({
+        "name": definition.name,
+        "description": definition.description,
+        "inputSchema": definition.args_schema
+    })
+}
+
+fn error_response(id: Value, error: RpcError) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": {"code": error.code, "message": error.message}
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use agent::tools::{SearchTool, ToolDefinition, ToolResult};
+    use async_trait::async_trait;
+
+    struct BrokenTool;
+
+    #[async_trait]
+    impl Tool for BrokenTool {
+        fn definition(&self) -> ToolDefinition {
+            ToolDefinition {
+                name: "quote".to_string(),
+                description: "Always fails".to_string(),
+                parameters: json!({"type": "object"}),
+            }
+        }
+
+        async fn execute(&self, _args: Value) -> common::Result<ToolResult> {
+            Err(common::Error::Database("connection refused".to_string()))
+        }
+    }
+
+    fn handler(allowed: &[&str]) -> McpHandler {
+        let config = McpConfig {
+            allowed_tools: allowed.iter().map(|name| name.to_string()).collect(),
+            ..McpConfig::default()
+        };
+        McpHandler::new(
+            &config,
+            vec![Box::new(SearchTool::new()), Box::new(BrokenTool)],
+        )
+    }
+
+    async fn request(handler: &McpHandler, method: &str, params: Value) -> Value {
+        handler
+            .handle(json!({"jsonrpc": "2.0", "id": 7, "method": method, "params": params}))
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_initialize_and_list_allowed_tools() {
+        let handler = handler(&["search", "missing"]);
+
+        let response = request(
+            &handler,
+            "initialize",
+            json!({"protocolVersion": "2024-11-05", "capabilities": {}}),
+        )
+        .await;
+        assert_eq!(response["id"], 7);
+        assert_eq!(response["result"]["protocolVersion"], "2024-11-05");
+        assert_eq!(response["result"]["serverInfo"]["name"], "agentic-rust");
+
+        let notification = json!({"jsonrpc": "2.0", "method": "notifications/initialized"});
+        assert!(handler.handle(notification).await.is_none());
+
+        let response = request(&handler, "tools/list", json!({})).await;
+        let tools = response["result"]["tools"].as_array().unwrap();
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0]["name"], "search");
+        assert_eq!(tools[0]["inputSchema"]["required"], json!(["query"]));
+    }
+
+    #[tokio::test]
+    async fn test_call_tool() {
+        let handler = handler(&["search", "quote"]);
+
+        let response = request(
+            &handler,
+            "tools/call",
+            json!({"name": "search", "arguments": {"query": "pump", "limit": "2"}}),
+        )
+        .await;
+        assert_eq!(
+            response["result"],
+            json!({"content": [{"type": "text", "text": "Searched for 'pump' with limit 2"}]})
+        );
+
+        let response = request(&handler, "tools/call", json!({"name": "search"})).await;
+        assert_eq!(response["result"]["isError"], true);
+        assert!(response["result"]["content"][0]["text"]
+            .as_str()
+            .unwrap()
+            .starts_with("Invalid input"));
+
+        let response = request(&handler, "tools/call", json!({"name": "quote"})).await;
+        assert_eq!(response["result"]["isError"], true);
+        assert_eq!(
+            response["result"]["content"][0]["text"],
+            "Tool 'quote' failed"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rejects_unlisted_tools_and_methods() {
+        let handler = handler(&["search"]);
+
+        let response = request(&handler, "tools/call", json!({"name": "quote"})).await;
+        assert_eq!(response["error"]["code"], INVALID_PARAMS);
+        assert_eq!(response["error"]["message"], "Unknown tool: quote");
+
+        let response = request(&handler, "resources/list", json!({})).await;
+        assert_eq!(response["error"]["code"], METHOD_NOT_FOUND);
+
+        let batch = json!([
+            {"jsonrpc": "2.0", "id": 1, "method": "ping"},
+            {"jsonrpc": "2.0", "method": "notifications/cancelled"}
+        ]);
+        assert_eq!(
+            handler.handle(batch).await.unwrap(),
+            json!([{"jsonrpc": "2.0", "id": 1, "result": {}}])
+        );
+    }
+}
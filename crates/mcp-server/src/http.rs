@@ -0,0 +1,85 @@
+//! Streamable HTTP transport.
+//!
+//! Clients POST JSON-RPC messages to the endpoint and get the response back
+//! as `application/json`; notifications are acknowledged with `202 Accepted`.
+//! The server never opens an event stream, so `GET` is not allowed. When a
+//! token is set, requests must carry it as `Authorization: Bearer <token>`.
+
+use axum::{
+    extract::State,
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::post,
+    Json, Router,
+};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use subtle::ConstantTimeEq;
+
+use crate::handler::McpHandler;
+
+#[derive(Clone)]
+struct HttpState {
+    handler: Arc<McpHandler>,
+    token: Option<String>,
+}
+
+/// Create the router serving MCP at `path`
+pub fn create_router(handler: Arc<McpHandler>, path: &str, token: Option<String>) -> Router {
+    Router::new()
+        .route(path, post(post_message))
+        .with_state(HttpState { handler, token })
+}
+
+async fn post_message(
+    State(state): State<HttpState>,
+    headers: HeaderMap,
+    body: String,
+) -> Response {
+    if let Some(token) = &state.token {
+        let authorized = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .is_some_and(|presented| tokens_match(presented, token));
+        if !authorized {
+            return StatusCode::UNAUTHORIZED.into_response();
+        }
+    }
+
+    let message = match serde_json::from_str(&body) {
+        Ok(message) => message,
+        Err(e) => {
+            let error = McpHandler::parse_error(&e.to_string());
+            return (StatusCode::BAD_REQUEST, Json(error)).into_response();
+        }
+    };
+
+    match state.handler.handle(message).await {
+        Some(response) => Json(response).into_response(),
+        None => StatusCode::ACCEPTED.into_response(),
+    }
+}
+
+/// Compare tokens in constant time
+///
+/// Both are hashed first so that neither the contents nor the length of the
+/// expected token show in the comparison's timing.
+fn tokens_match(presented: &str, expected: &str) -> bool {
+    let presented = Sha256::digest(presented.as_bytes());
+    let expected = Sha256::digest(expected.as_bytes());
+    presented.ct_eq(&expected).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokens_match() {
+        assert!(tokens_match("secret", "secret"));
+        assert!(!tokens_match("secreT", "secret"));
+        assert!(!tokens_match("secret-but-longer", "secret"));
+        assert!(!tokens_match("", "secret"));
+    }
+}
@@ -0,0 +1,17 @@
+//! Model Context Protocol server for the agent tools.
+//!
+//! This crate provides:
+//! - A JSON-RPC handler serving `agent::tools` as MCP tools
+//! - stdio transport for assistants and IDEs that start the server themselves
+//! - Streamable HTTP transport for remote clients
+//! - Tool allowlisting from `config/mcp.toml`
+
+pub mod config;
+pub mod handler;
+pub mod http;
+pub mod stdio;
+
+pub use config::{HttpConfig, McpConfig};
+pub use handler::McpHandler;
+pub use http::create_router;
+pub use stdio::serve_stdio;
@@ -0,0 +1,33 @@
+//! stdio transport.
+//!
+//! Reads newline-delimited JSON-RPC messages from stdin and writes responses
+//! to stdout, one per line. Logs must go to stderr.
+
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+use crate::handler::McpHandler;
+
+/// Serve MCP over stdin/stdout until stdin closes
+pub async fn serve_stdio(handler: Arc<McpHandler>) -> std::io::Result<()> {
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    let mut stdout = tokio::io::stdout();
+
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = match serde_json::from_str(&line) {
+            Ok(message) => handler.handle(message).await,
+            Err(e) => Some(McpHandler::parse_error(&e.to_string())),
+        };
+        if let Some(response) = response {
+            stdout
+                .write_all(format!("{}\n", response).as_bytes())
+                .await?;
+            stdout.flush().await?;
+        }
+    }
+    Ok(())
+}
@@ -2,7 +2,10 @@
 
 use async_trait::async_trait;
 use common::models::{DocumentChunk, EmbeddedChunk};
-use common::Result;
+use common::{Error, Result};
+use rig::client::EmbeddingsClient;
+use rig::embeddings::EmbeddingModel as _;
+use rig::providers::openai;
 
 /// Trait for embedding models
 #[async_trait]
@@ -36,6 +39,66 @@ pub async fn embed_chunks<E: EmbeddingModel>(
         .collect())
 }
 
+/// Default OpenAI embedding model
+pub const DEFAULT_EMBEDDING_MODEL: &str = "text-embedding-3-small";
+
+/// OpenAI embedding model
+#[derive(Clone)]
+pub struct OpenAiEmbeddingModel {
+    model: openai::EmbeddingModel,
+}
+
+impl OpenAiEmbeddingModel {
+    /// Create an embedding model using `api_key`
+    pub fn new(api_key: &str, model: &str) -> Self {
+        Self {
+            model: openai::Client::new(api_key).embedding_model(model),
+        }
+    }
+
+    /// Create from `OPENAI_API_KEY` and `EMBEDDING_MODEL`
+    ///
+    /// Returns `None` when no API key is set.
+    pub fn from_env() -> Option<Self> {
+        let api_key = std::env::var("OPENAI_API_KEY").ok()?;
+        let model = std::env::var("EMBEDDING_MODEL")
+            .unwrap_or_else(|_| DEFAULT_EMBEDDING_MODEL.to_string());
+        Some(Self::new(&api_key, &model))
+    }
+
+    /// Name of the model, stored alongside the embeddings it makes
+    pub fn name(&self) -> &str {
+        &self.model.model
+    }
+}
+
+#[async_trait]
+impl EmbeddingModel for OpenAiEmbeddingModel {
+    async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        let mut embeddings = self.embed_batch(&[text]).await?;
+        embeddings
+            .pop()
+            .ok_or_else(|| Error::Embedding("No embedding returned".to_string()))
+    }
+
+    async fn embed_batch(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+        let embeddings = self
+            .model
+            .embed_texts(texts.iter().map(|t| t.to_string()))
+            .await
+            .map_err(|e| Error::Embedding(e.to_string()))?;
+
+        Ok(embeddings
+            .into_iter()
+            .map(|e| e.vec.into_iter().map(|v| v as f32).collect())
+            .collect())
+    }
+
+    fn dimension(&self) -> usize {
+        self.model.ndims()
+    }
+}
+
 /// Mock embedding model for testing
 #[cfg(test)]
 pub struct MockEmbeddingModel {
@@ -12,5 +12,6 @@ pub mod retriever;
 pub mod vector_store;
 
 pub use chunker::TextChunker;
-pub use retriever::Retriever;
-pub use vector_store::VectorStore;
+pub use embeddings::{EmbeddingModel, OpenAiEmbeddingModel};
+pub use retriever::{DocumentRetriever, Retriever};
+pub use vector_store::{QdrantVectorStore, VectorStore};
@@ -2,9 +2,17 @@
 
 use crate::embeddings::EmbeddingModel;
 use crate::vector_store::VectorStore;
+use async_trait::async_trait;
 use common::models::SearchResult;
 use common::Result;
 
+/// Object-safe retrieval interface, for holders of any retriever
+#[async_trait]
+pub trait DocumentRetriever: Send + Sync {
+    /// Retrieve up to `top_k` relevant chunks for a query, best first
+    async fn retrieve_top_k(&self, query: &str, top_k: usize) -> Result<Vec<SearchResult>>;
+}
+
 /// Document retriever that combines embedding and vector search
 pub struct Retriever<E: EmbeddingModel, V: VectorStore> {
     embedding_model: E,
@@ -38,14 +46,15 @@ impl<E: EmbeddingModel, V: VectorStore> Retriever<E, V> {
 
     /// Retrieve relevant documents for a query
     pub async fn retrieve(&self, query: &str) -> Result<Vec<SearchResult>> {
+        self.search(query, self.top_k).await
+    }
+
+    async fn search(&self, query: &str, top_k: usize) -> Result<Vec<SearchResult>> {
         // Generate embedding for query
         let query_embedding = self.embedding_model.embed(query).await?;
 
         // Search vector store
-        let results = self
-            .vector_store
-            .search(&query_embedding, self.top_k)
-            .await?;
+        let results = self.vector_store.search(&query_embedding, top_k).await?;
 
         // Filter by similarity threshold
         Ok(results
@@ -64,3 +73,10 @@ impl<E: EmbeddingModel, V: VectorStore> Retriever<E, V> {
         &self.vector_store
     }
 }
+
+#[async_trait]
+impl<E: EmbeddingModel, V: VectorStore> DocumentRetriever for Retriever<E, V> {
+    async fn retrieve_top_k(&self, query: &str, top_k: usize) -> Result<Vec<SearchResult>> {
+        self.search(query, top_k).await
+    }
+}
@@ -1,8 +1,17 @@
 //! Vector store abstraction for storing and querying embeddings.
 
 use async_trait::async_trait;
-use common::models::{EmbeddedChunk, SearchResult};
-use common::Result;
+use common::models::{DocumentChunk, EmbeddedChunk, SearchResult};
+use common::{Error, Result};
+use qdrant_client::qdrant::point_id::PointIdOptions;
+use qdrant_client::qdrant::{
+    Condition, CreateCollectionBuilder, DeletePointsBuilder, Distance, Filter, PointId,
+    PointStruct, SearchPointsBuilder, UpsertPointsBuilder, Value as QdrantValue,
+    VectorParamsBuilder,
+};
+use qdrant_client::{Payload, Qdrant};
+use std::collections::HashMap;
+use std::sync::Arc;
 
 /// Trait for vector store implementations
 #[async_trait]
@@ -91,10 +100,162 @@ impl VectorStore for InMemoryVectorStore {
     }
 }
 
+/// Default Qdrant collection for document chunks
+pub const DEFAULT_COLLECTION: &str = "documents";
+
+/// Qdrant-backed vector store
+///
+/// Each chunk is stored as a point keyed by the chunk ID, with the chunk's
+/// document ID, content, index and metadata in the payload.
+#[derive(Clone)]
+pub struct QdrantVectorStore {
+    client: Arc<Qdrant>,
+    collection: String,
+}
+
+impl QdrantVectorStore {
+    /// Connect to Qdrant at `url`, storing chunks in `collection`
+    pub fn new(url: &str, collection: impl Into<String>) -> Result<Self> {
+        let client = Qdrant::from_url(url)
+            .build()
+            .map_err(|e| Error::VectorStore(e.to_string()))?;
+        Ok(Self {
+            client: Arc::new(client),
+            collection: collection.into(),
+        })
+    }
+
+    /// Create from `QDRANT_URL` and `QDRANT_COLLECTION`
+    ///
+    /// Returns `None` when no URL is set.
+    pub fn from_env() -> Result<Option<Self>> {
+        let Ok(url) = std::env::var("QDRANT_URL") else {
+            return Ok(None);
+        };
+        let collection =
+            std::env::var("QDRANT_COLLECTION").unwrap_or_else(|_| DEFAULT_COLLECTION.to_string());
+        Self::new(&url, collection).map(Some)
+    }
+
+    /// Create the collection for `dimension`-sized vectors if it is missing
+    pub async fn ensure_collection(&self, dimension: usize) -> Result<()> {
+        let exists = self
+            .client
+            .collection_exists(&self.collection)
+            .await
+            .map_err(|e| Error::VectorStore(e.to_string()))?;
+        if exists {
+            return Ok(());
+        }
+
+        self.client
+            .create_collection(
+                CreateCollectionBuilder::new(&self.collection)
+                    .vectors_config(VectorParamsBuilder::new(dimension as u64, Distance::Cosine)),
+            )
+            .await
+            .map_err(|e| Error::VectorStore(e.to_string()))?;
+        Ok(())
+    }
+
+    fn chunk_payload(chunk: &DocumentChunk) -> Result<Payload> {
+        Payload::try_from(serde_json::json!({
+            "document_id": chunk.document_id.to_string(),
+            "content": chunk.content,
+            "chunk_index": chunk.chunk_index,
+            "metadata": chunk.metadata,
+        }))
+        .map_err(|e| Error::VectorStore(e.to_string()))
+    }
+
+    fn chunk_from_payload(
+        id: Option<PointId>,
+        payload: HashMap<String, QdrantValue>,
+    ) -> Option<DocumentChunk> {
+        let mut payload: serde_json::Map<String, serde_json::Value> = payload
+            .into_iter()
+            .map(|(key, value)| (key, value.into_json()))
+            .collect();
+        let id = match id?.point_id_options? {
+            PointIdOptions::Uuid(id) => uuid::Uuid::parse_str(&id).ok()?,
+            PointIdOptions::Num(_) => return None,
+        };
+
+        Some(DocumentChunk {
+            id,
+            document_id: uuid::Uuid::parse_str(payload.get("document_id")?.as_str()?).ok()?,
+            content: payload.get("content")?.as_str()?.to_string(),
+            chunk_index: payload.get("chunk_index")?.as_u64()? as usize,
+            metadata: payload.remove("metadata").unwrap_or_default(),
+        })
+    }
+}
+
+#[async_trait]
+impl VectorStore for QdrantVectorStore {
+    async fn add_chunks(&self, chunks: Vec<EmbeddedChunk>) -> Result<()> {
+        if chunks.is_empty() {
+            return Ok(());
+        }
+
+        let points = chunks
+            .into_iter()
+            .map(|embedded| {
+                Ok(PointStruct::new(
+                    embedded.chunk.id.to_string(),
+                    embedded.embedding,
+                    Self::chunk_payload(&embedded.chunk)?,
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        self.client
+            .upsert_points(UpsertPointsBuilder::new(&self.collection, points).wait(true))
+            .await
+            .map_err(|e| Error::VectorStore(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn search(&self, query_embedding: &[f32], top_k: usize) -> Result<Vec<SearchResult>> {
+        let response = self
+            .client
+            .search_points(
+                SearchPointsBuilder::new(&self.collection, query_embedding.to_vec(), top_k as u64)
+                    .with_payload(true),
+            )
+            .await
+            .map_err(|e| Error::VectorStore(e.to_string()))?;
+
+        Ok(response
+            .result
+            .into_iter()
+            .filter_map(|point| {
+                let score = point.score;
+                Self::chunk_from_payload(point.id, point.payload)
+                    .map(|chunk| SearchResult { chunk, score })
+            })
+            .collect())
+    }
+
+    async fn delete_by_document_id(&self, document_id: &uuid::Uuid) -> Result<()> {
+        self.client
+            .delete_points(
+                DeletePointsBuilder::new(&self.collection)
+                    .points(Filter::must([Condition::matches(
+                        "document_id",
+                        document_id.to_string(),
+                    )]))
+                    .wait(true),
+            )
+            .await
+            .map_err(|e| Error::VectorStore(e.to_string()))?;
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use common::models::DocumentChunk;
     use uuid::Uuid;
 
     #[tokio::test]
@@ -113,4 +274,22 @@ mod tests {
         assert_eq!(results.len(), 1);
         assert!((results[0].score - 1.0).abs() < 0.001);
     }
+
+    #[test]
+    fn test_qdrant_payload_round_trip() {
+        let mut chunk = DocumentChunk::new(Uuid::new_v4(), "Pump X datasheet", 3);
+        chunk.metadata = serde_json::json!({ "page": 2 });
+        let payload = QdrantVectorStore::chunk_payload(&chunk).unwrap();
+
+        let restored = QdrantVectorStore::chunk_from_payload(
+            Some(PointId::from(chunk.id.to_string())),
+            payload.into(),
+        )
+        .unwrap();
+        assert_eq!(restored.id, chunk.id);
+        assert_eq!(restored.document_id, chunk.document_id);
+        assert_eq!(restored.content, "Pump X datasheet");
+        assert_eq!(restored.chunk_index, 3);
+        assert_eq!(restored.metadata, serde_json::json!({ "page": 2 }));
+    }
 }
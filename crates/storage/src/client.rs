@@ -330,8 +330,20 @@ impl StorageClient {
         key: &str,
         opts: PresignedUrlOptions,
     ) -> StorageResult<String> {
+        let mut queries = HashMap::new();
+        if let Some(disposition) = opts.content_disposition {
+            queries.insert("response-content-disposition".to_string(), disposition);
+        }
+        if let Some(content_type) = opts.content_type {
+            queries.insert("response-content-type".to_string(), content_type);
+        }
+
         self.bucket(bucket)?
-            .presign_get(key, opts.expires_in, None)
+            .presign_get(
+                key,
+                opts.expires_in,
+                (!queries.is_empty()).then_some(queries),
+            )
             .await
             .map_err(|e| StorageError::PresignedUrl(e.to_string()))
     }
@@ -361,6 +373,26 @@ impl StorageClient {
             })
     }
 
+    /// Bucket and key of an object URL built by [`object_url`](Self::object_url)
+    ///
+    /// A value that is not a URL is read as `bucket/key`.
+    pub fn object_location(&self, url: &str) -> Option<(String, String)> {
+        let path = [
+            self.config.public_url.as_deref(),
+            Some(self.config.endpoint.as_str()),
+        ]
+        .into_iter()
+        .flatten()
+        .find_map(|base| url.strip_prefix(base.trim_end_matches('/')))
+        .or_else(|| (!url.contains("://")).then_some(url))?;
+
+        let (bucket, key) = path.trim_start_matches('/').split_once('/')?;
+        if bucket.is_empty() || key.is_empty() {
+            return None;
+        }
+        Some((bucket.to_string(), key.to_string()))
+    }
+
     pub async fn copy(
         &self,
         src_bucket: &str,
@@ -391,4 +423,30 @@ mod tests {
         assert!(config.path_style);
         assert_eq!(config.region, "us-east-1");
     }
+
+    #[test]
+    fn test_object_location() {
+        let client = StorageClient::new(
+            StorageConfig::rustfs("http://localhost:9000", "access", "secret")
+                .with_public_url("https://cdn.example.com"),
+        );
+
+        let url = client.object_url("brochures", "2024/12/a.pdf").unwrap();
+        assert_eq!(
+            client.object_location(&url),
+            Some(("brochures".to_string(), "2024/12/a.pdf".to_string()))
+        );
+        assert_eq!(
+            client.object_location("http://localhost:9000/products/x.png"),
+            Some(("products".to_string(), "x.png".to_string()))
+        );
+        assert_eq!(
+            client.object_location("brochures/a.pdf"),
+            Some(("brochures".to_string(), "a.pdf".to_string()))
+        );
+        assert_eq!(
+            client.object_location("https://other.example.com/a.pdf"),
+            None
+        );
+    }
 }
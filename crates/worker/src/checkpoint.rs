@@ -0,0 +1,67 @@
+//! Redis-backed checkpoint storage for ReAct executions.
+
+use agent::checkpoint::{CheckpointStore, ReActCheckpoint};
+use agent::ReActError;
+use async_trait::async_trait;
+use common::queue::{keys, CHECKPOINT_TTL_SECONDS};
+use deadpool_redis::{redis::AsyncCommands, Connection, Pool};
+use uuid::Uuid;
+
+/// Stores checkpoints under `agentic:checkpoint:{execution_id}`
+///
+/// The worker uses the chat job ID as execution ID, so a paused job can be
+/// resumed by job ID.
+#[derive(Clone)]
+pub struct RedisCheckpointStore {
+    pool: Pool,
+}
+
+impl RedisCheckpointStore {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+
+    async fn conn(&self) -> Result<Connection, ReActError> {
+        self.pool.get().await.map_err(checkpoint_error)
+    }
+}
+
+fn checkpoint_error(e: impl std::fmt::Display) -> ReActError {
+    ReActError::CheckpointError(e.to_string())
+}
+
+#[async_trait]
+impl CheckpointStore for RedisCheckpointStore {
+    async fn save(&self, checkpoint: &ReActCheckpoint) -> Result<(), ReActError> {
+        let json = serde_json::to_string(checkpoint).map_err(checkpoint_error)?;
+        self.conn()
+            .await?
+            .set_ex::<_, _, ()>(
+                keys::checkpoint(&checkpoint.execution_id),
+                json,
+                CHECKPOINT_TTL_SECONDS,
+            )
+            .await
+            .map_err(checkpoint_error)
+    }
+
+    async fn load(&self, execution_id: &Uuid) -> Result<Option<ReActCheckpoint>, ReActError> {
+        let json: Option<String> = self
+            .conn()
+            .await?
+            .get(keys::checkpoint(execution_id))
+            .await
+            .map_err(checkpoint_error)?;
+
+        json.map(|json| serde_json::from_str(&json).map_err(checkpoint_error))
+            .transpose()
+    }
+
+    async fn delete(&self, execution_id: &Uuid) -> Result<(), ReActError> {
+        self.conn()
+            .await?
+            .del::<_, ()>(keys::checkpoint(execution_id))
+            .await
+            .map_err(checkpoint_error)
+    }
+}
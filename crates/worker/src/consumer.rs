@@ -1,12 +1,34 @@
-use common::models::{EmbedDocumentJob, IndexDocumentJob, ProcessChatJob};
-use common::queue::{keys, queues, JobResult, RESULT_TTL_SECONDS};
+use crate::checkpoint::RedisCheckpointStore;
+use crate::delivery::{self, LeaseReaper};
+use crate::handoff::RedisHandoffQueue;
+use crate::progress::RedisProgressPublisher;
+use agent::checkpoint::{ApprovalDecision, CheckpointStore, ReActCheckpoint};
+use agent::tools::{create_db_sales_agent_tools, create_sales_agent_tools, HandoffTool, Tool};
+use agent::{ReActAgent, ReActConfig, ReActError, ReActOutcome};
+use common::models::{
+    ChatMessage, ConversationState, EmbedDocumentJob, IndexDocumentJob, MessageRole,
+    ProcessChatJob, ResumeChatJob,
+};
+use common::queue::{
+    keys, queues, JobResult, CHECKPOINT_TTL_SECONDS, COMPARE_AND_SET_STATUS_SCRIPT,
+    LEASE_TTL_SECONDS, RESULT_TTL_SECONDS,
+};
 use common::{Error, QueueJobStatus, Result};
-use deadpool_redis::{redis::AsyncCommands, Config, Connection, Pool, Runtime};
+use db::{ConversationRepository, DbPool};
+use deadpool_redis::redis::{AsyncCommands, Script};
+use deadpool_redis::{Config, Connection, Pool, Runtime};
+use rag_core::{DocumentRetriever, EmbeddingModel};
 use std::sync::Arc;
+use std::time::Duration;
+use storage::StorageClient;
 use tokio::sync::Semaphore;
+use uuid::Uuid;
 
 pub type RedisPool = Pool;
 
+/// Queues whose jobs run the agent, delivered at least once
+const AGENT_QUEUES: [&str; 2] = [queues::CHAT_QUEUE, queues::RESUME_QUEUE];
+
 pub fn create_pool(redis_url: &str) -> Result<RedisPool> {
     let cfg = Config::from_url(redis_url);
     cfg.create_pool(Some(Runtime::Tokio1))
@@ -15,6 +37,16 @@ pub fn create_pool(redis_url: &str) -> Result<RedisPool> {
 
 pub struct WorkerState {
     pub redis_pool: RedisPool,
+    /// Database backing the sales tools; they run without data when unset
+    pub db_pool: Option<DbPool>,
+    /// Object storage issuing brochure download links
+    pub storage_client: StorageClient,
+    /// Tools the chat agent must get approval for before calling
+    pub approval_tools: Vec<String>,
+    /// Knowledge base the chat agent retrieves context from, if configured
+    pub retriever: Option<Arc<dyn DocumentRetriever>>,
+    /// Embeds queries for semantic catalog search, if configured
+    pub embedding_model: Option<Arc<dyn EmbeddingModel>>,
 }
 
 pub struct JobConsumer {
@@ -34,6 +66,9 @@ impl JobConsumer {
         let semaphore = Arc::new(Semaphore::new(self.concurrency));
         tracing::info!(concurrency = self.concurrency, "consumer started");
 
+        let reaper = LeaseReaper::new(self.state.redis_pool.clone(), &AGENT_QUEUES);
+        tokio::spawn(reaper.run(Duration::from_secs(LEASE_TTL_SECONDS)));
+
         loop {
             let permit = semaphore.clone().acquire_owned().await.unwrap();
             let state = self.state.clone();
@@ -60,27 +95,87 @@ async fn conn(state: &WorkerState) -> Result<Connection> {
 
 async fn set_status(conn: &mut Connection, job_id: uuid::Uuid, status: &JobResult) -> Result<()> {
     let json = serde_json::to_string(status)?;
-    conn.set_ex::<_, _, ()>(keys::job_status(&job_id), &json, RESULT_TTL_SECONDS)
+    // Paused jobs live as long as their checkpoint
+    let ttl = match status.status {
+        QueueJobStatus::Paused => CHECKPOINT_TTL_SECONDS,
+        _ => RESULT_TTL_SECONDS,
+    };
+    conn.set_ex::<_, _, ()>(keys::job_status(&job_id), &json, ttl)
         .await
         .map_err(|e| Error::Queue(e.to_string()))
 }
 
+/// Current status of a job, if it has one
+async fn status(conn: &mut Connection, job_id: Uuid) -> Result<Option<JobResult>> {
+    let json: Option<String> = conn
+        .get(keys::job_status(&job_id))
+        .await
+        .map_err(|e| Error::Queue(e.to_string()))?;
+    json.map(|json| serde_json::from_str(&json).map_err(Into::into))
+        .transpose()
+}
+
+/// Move a job awaiting its resume to processing
+///
+/// A job already processing was redelivered after its worker died and may
+/// go on. Returns false, leaving the status alone, if the job is not paused,
+/// resuming or processing, e.g. because it has finished.
+async fn start_resume(conn: &mut Connection, job_id: Uuid) -> Result<bool> {
+    let key = keys::job_status(&job_id);
+    let current: Option<String> = conn
+        .get(&key)
+        .await
+        .map_err(|e| Error::Queue(e.to_string()))?;
+    let Some(current) = current else {
+        return Ok(false);
+    };
+    let status: JobResult = serde_json::from_str(&current)?;
+    match status.status {
+        QueueJobStatus::Paused | QueueJobStatus::Resuming => {}
+        QueueJobStatus::Processing => return Ok(true),
+        _ => return Ok(false),
+    }
+
+    let processing = serde_json::to_string(&JobResult {
+        job_id,
+        status: QueueJobStatus::Processing,
+        result: None,
+        error: None,
+        completed_at: None,
+    })?;
+    let swapped: i64 = Script::new(COMPARE_AND_SET_STATUS_SCRIPT)
+        .key(&key)
+        .arg(&current)
+        .arg(&processing)
+        .arg(RESULT_TTL_SECONDS)
+        .invoke_async(conn)
+        .await
+        .map_err(|e| Error::Queue(e.to_string()))?;
+    Ok(swapped == 1)
+}
+
 async fn process_next_job(state: &WorkerState) -> Result<()> {
     let mut c = conn(state).await?;
 
+    for queue in AGENT_QUEUES {
+        if let Some(job_json) = delivery::take(&mut c, queue).await? {
+            let result = tokio::select! {
+                result = process_agent_job(state, queue, &job_json) => result,
+                _ = delivery::keep_lease(&state.redis_pool, queue, &job_json) => unreachable!(),
+            };
+            delivery::release(&mut c, queue, &job_json).await?;
+            return result;
+        }
+    }
+
+    // Agent jobs are taken without blocking, so wait only briefly for others
     let result: Option<(String, String)> = c
-        .brpop(
-            &[queues::CHAT_QUEUE, queues::EMBED_QUEUE, queues::INDEX_QUEUE],
-            1.0,
-        )
+        .brpop(&[queues::EMBED_QUEUE, queues::INDEX_QUEUE], 0.5)
         .await
         .map_err(|e| Error::Queue(e.to_string()))?;
 
     if let Some((queue, job_json)) = result {
         match queue.as_str() {
-            q if q == queues::CHAT_QUEUE => {
-                process_chat_job(state, serde_json::from_str(&job_json)?).await?;
-            }
             q if q == queues::EMBED_QUEUE => {
                 process_embed_job(state, serde_json::from_str(&job_json)?).await?;
             }
@@ -93,10 +188,29 @@ async fn process_next_job(state: &WorkerState) -> Result<()> {
     Ok(())
 }
 
+async fn process_agent_job(state: &WorkerState, queue: &str, job_json: &str) -> Result<()> {
+    if queue == queues::CHAT_QUEUE {
+        process_chat_job(state, serde_json::from_str(job_json)?).await
+    } else {
+        process_resume_job(state, serde_json::from_str(job_json)?).await
+    }
+}
+
 async fn process_chat_job(state: &WorkerState, job: ProcessChatJob) -> Result<()> {
     tracing::info!(job_id = %job.job_id, "processing chat");
     let mut c = conn(state).await?;
 
+    // A redelivered job may have paused or finished before its worker died
+    if let Some(current) = status(&mut c, job.job_id).await? {
+        if !matches!(
+            current.status,
+            QueueJobStatus::Pending | QueueJobStatus::Processing
+        ) {
+            tracing::warn!(job_id = %job.job_id, "chat job already handled, skipping");
+            return Ok(());
+        }
+    }
+
     set_status(
         &mut c,
         job.job_id,
@@ -110,25 +224,233 @@ async fn process_chat_job(state: &WorkerState, job: ProcessChatJob) -> Result<()
     )
     .await?;
 
-    // TODO: RAG processing
-    tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+    // ... or be part-way through its run, which then goes on from there
+    let store = RedisCheckpointStore::new(state.redis_pool.clone());
+    if let Some(checkpoint) = store
+        .load(&job.job_id)
+        .await
+        .map_err(|e| Error::Queue(e.to_string()))?
+    {
+        tracing::info!(
+            job_id = %job.job_id,
+            iteration = checkpoint.iteration,
+            "continuing chat from checkpoint"
+        );
+        let outcome = chat_agent(state, job.job_id, job.conversation_id)
+            .run_checkpoint(checkpoint)
+            .await;
+        return finish_chat(state, &mut c, job.job_id, job.conversation_id, outcome).await;
+    }
 
-    set_status(
-        &mut c,
-        job.job_id,
-        &JobResult::completed(
+    if let Some(conversation_state) = operator_state(state, &job).await? {
+        set_status(
+            &mut c,
             job.job_id,
-            serde_json::json!({
-                "response": format!("Processed: {}", job.message),
-                "conversation_id": job.conversation_id,
-                "sources": []
-            }),
+            &JobResult::completed(
+                job.job_id,
+                serde_json::json!({
+                    "response": null,
+                    "conversation_id": job.conversation_id,
+                    "state": conversation_state,
+                }),
+            ),
+        )
+        .await?;
+        tracing::info!(job_id = %job.job_id, "chat left to operator");
+        return Ok(());
+    }
+
+    let checkpoint = ReActCheckpoint::new(&job.message)
+        .with_execution_id(job.job_id)
+        .with_conversation_id(job.conversation_id);
+    let outcome = chat_agent(state, job.job_id, job.conversation_id)
+        .run_checkpoint(checkpoint)
+        .await;
+
+    finish_chat(state, &mut c, job.job_id, job.conversation_id, outcome).await
+}
+
+async fn process_resume_job(state: &WorkerState, job: ResumeChatJob) -> Result<()> {
+    tracing::info!(
+        job_id = %job.job_id,
+        approve = job.approve,
+        reviewer = %job.reviewer,
+        "resuming chat"
+    );
+    let mut c = conn(state).await?;
+
+    if !start_resume(&mut c, job.job_id).await? {
+        tracing::warn!(job_id = %job.job_id, "job is not awaiting a resume, skipping");
+        return Ok(());
+    }
+
+    let store = RedisCheckpointStore::new(state.redis_pool.clone());
+    let Some(mut checkpoint) = store
+        .load(&job.job_id)
+        .await
+        .map_err(|e| Error::Queue(e.to_string()))?
+    else {
+        set_status(
+            &mut c,
+            job.job_id,
+            &JobResult::failed(job.job_id, "No checkpoint found for job"),
+        )
+        .await?;
+        return Err(Error::NotFound(format!(
+            "checkpoint for job {}",
+            job.job_id
+        )));
+    };
+
+    // A redelivered resume has no pending calls left once they were decided
+    let decision = if job.approve {
+        ApprovalDecision::Approve
+    } else {
+        ApprovalDecision::Reject { reason: job.reason }
+    };
+    checkpoint.record_review(job.reviewer, &decision);
+    let conversation_id = checkpoint.conversation_id;
+    let outcome = chat_agent(state, job.job_id, conversation_id)
+        .resume(checkpoint, decision)
+        .await;
+
+    finish_chat(state, &mut c, job.job_id, conversation_id, outcome).await
+}
+
+/// State of a conversation taken over by an operator, if it is
+///
+/// The bot does not answer such conversations; the customer's message is
+/// stored for the operator instead.
+async fn operator_state(
+    state: &WorkerState,
+    job: &ProcessChatJob,
+) -> Result<Option<ConversationState>> {
+    let (Some(pool), Some(conversation_id)) = (&state.db_pool, job.conversation_id) else {
+        return Ok(None);
+    };
+    let conversations = ConversationRepository::new(pool.clone());
+    let message = ChatMessage {
+        role: MessageRole::User,
+        content: job.message.clone(),
+    };
+
+    tokio::task::spawn_blocking(move || {
+        let current = conversations.state(&conversation_id)?;
+        if current == ConversationState::Bot {
+            return Ok(None);
+        }
+        conversations.add_message(&conversation_id, message)?;
+        Ok(Some(current))
+    })
+    .await
+    .map_err(|e| Error::Internal(e.to_string()))?
+}
+
+/// Chat agent checkpointing to Redis and publishing progress under the job ID
+fn chat_agent(state: &WorkerState, job_id: Uuid, conversation_id: Option<Uuid>) -> ReActAgent {
+    let mut config = ReActConfig::builder();
+    for tool in &state.approval_tools {
+        config = config.pause_before_tool(tool);
+    }
+
+    // TODO: configure the LLM client; the agent answers with a placeholder without one
+    let agent = ReActAgent::new(config.build())
+        .with_tools(sales_agent_tools(state, conversation_id))
+        .with_checkpoint_store(Arc::new(RedisCheckpointStore::new(
+            state.redis_pool.clone(),
+        )))
+        .with_listener(Arc::new(RedisProgressPublisher::new(
+            state.redis_pool.clone(),
+            job_id,
+        )));
+    match &state.retriever {
+        Some(retriever) => agent.with_retriever(retriever.clone()),
+        None => agent,
+    }
+}
+
+/// Sales tools, backed by the database when one is configured
+///
+/// Conversations can be handed off to live agents only when they are stored.
+fn sales_agent_tools(state: &WorkerState, conversation_id: Option<Uuid>) -> Vec<Box<dyn Tool>> {
+    let mut tools = match &state.db_pool {
+        Some(pool) => create_db_sales_agent_tools(
+            pool.clone(),
+            state.storage_client.clone(),
+            state.embedding_model.clone(),
+            state.retriever.clone(),
+            conversation_id,
         ),
-    )
-    .await?;
+        None => create_sales_agent_tools(),
+    };
+    if let (Some(pool), Some(conversation_id)) = (&state.db_pool, conversation_id) {
+        let queue = RedisHandoffQueue::new(state.redis_pool.clone(), pool.clone());
+        tools.push(Box::new(
+            HandoffTool::new(Arc::new(queue)).with_conversation_id(conversation_id),
+        ));
+    }
+    tools
+}
 
-    tracing::info!(job_id = %job.job_id, "chat completed");
-    Ok(())
+/// Store the outcome of a chat run as the job status
+///
+/// The checkpoint of a failed run is dropped, as the job is not retried.
+async fn finish_chat(
+    state: &WorkerState,
+    conn: &mut Connection,
+    job_id: Uuid,
+    conversation_id: Option<Uuid>,
+    outcome: std::result::Result<ReActOutcome, ReActError>,
+) -> Result<()> {
+    match outcome {
+        Ok(ReActOutcome::Completed(response)) => {
+            set_status(
+                conn,
+                job_id,
+                &JobResult::completed(
+                    job_id,
+                    serde_json::json!({
+                        "response": response.final_answer,
+                        "conversation_id": conversation_id,
+                        "sources": response.sources,
+                        "usage": response.usage,
+                    }),
+                ),
+            )
+            .await?;
+            tracing::info!(job_id = %job_id, "chat completed");
+            Ok(())
+        }
+        Ok(ReActOutcome::Paused(checkpoint)) => {
+            set_status(
+                conn,
+                job_id,
+                &JobResult::paused(
+                    job_id,
+                    serde_json::json!({
+                        "pending": checkpoint.pending,
+                        "iteration": checkpoint.iteration,
+                        "reviews": checkpoint.reviews,
+                    }),
+                ),
+            )
+            .await?;
+            tracing::info!(
+                job_id = %job_id,
+                tools = ?checkpoint.pending_tool_names(),
+                "chat paused for approval"
+            );
+            Ok(())
+        }
+        Err(e) => {
+            set_status(conn, job_id, &JobResult::failed(job_id, e.to_string())).await?;
+            let store = RedisCheckpointStore::new(state.redis_pool.clone());
+            if let Err(e) = store.delete(&job_id).await {
+                tracing::warn!(job_id = %job_id, error = %e, "failed to delete checkpoint");
+            }
+            Err(Error::Internal(e.to_string()))
+        }
+    }
 }
 
 async fn process_embed_job(state: &WorkerState, job: EmbedDocumentJob) -> Result<()> {
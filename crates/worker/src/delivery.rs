@@ -0,0 +1,142 @@
+//! At-least-once delivery of agent jobs.
+//!
+//! Chat and resume jobs run the agent for long enough that a worker may die
+//! in the middle of one. Instead of being popped, they are moved from their
+//! queue into a processing set, scored by a lease deadline the worker keeps
+//! extending while the job runs. [`LeaseReaper`] pushes jobs whose lease ran
+//! out back onto their queue; the worker that takes them next continues the
+//! run from its last checkpoint.
+
+use common::queue::{keys, LEASE_TTL_SECONDS};
+use common::{Error, Result};
+use deadpool_redis::redis::{AsyncCommands, Script};
+use deadpool_redis::{Connection, Pool};
+use std::time::Duration;
+
+/// Lua script moving the next job of queue KEYS[1] into processing set KEYS[2]
+///
+/// The job's lease runs ARGV[1] seconds from now, by the Redis clock.
+/// Returns the job, or nil when the queue is empty.
+const TAKE_SCRIPT: &str = r#"
+local job = redis.call('RPOP', KEYS[1])
+if job then
+    local now = tonumber(redis.call('TIME')[1])
+    redis.call('ZADD', KEYS[2], now + tonumber(ARGV[1]), job)
+end
+return job
+"#;
+
+/// Lua script extending the lease of job ARGV[2] in processing set KEYS[1]
+///
+/// Does nothing if the job is no longer processing.
+const RENEW_SCRIPT: &str = r#"
+local now = tonumber(redis.call('TIME')[1])
+return redis.call('ZADD', KEYS[1], 'XX', 'CH', now + tonumber(ARGV[1]), ARGV[2])
+"#;
+
+/// Lua script pushing the jobs of processing set KEYS[2] whose lease ran out
+/// back onto queue KEYS[1], to be taken next
+///
+/// Returns how many jobs were requeued.
+const REQUEUE_SCRIPT: &str = r#"
+local now = redis.call('TIME')[1]
+local jobs = redis.call('ZRANGEBYSCORE', KEYS[2], '-inf', now)
+for _, job in ipairs(jobs) do
+    redis.call('ZREM', KEYS[2], job)
+    redis.call('RPUSH', KEYS[1], job)
+end
+return #jobs
+"#;
+
+/// Take the next job of `queue` without blocking, leasing it to this worker
+pub async fn take(conn: &mut Connection, queue: &str) -> Result<Option<String>> {
+    Script::new(TAKE_SCRIPT)
+        .key(queue)
+        .key(keys::processing(queue))
+        .arg(LEASE_TTL_SECONDS)
+        .invoke_async(conn)
+        .await
+        .map_err(|e| Error::Queue(e.to_string()))
+}
+
+/// Extend the lease of a taken job every third of its TTL, forever
+///
+/// Meant to be raced against the job; a failed renewal is retried on the
+/// next tick.
+pub async fn keep_lease(pool: &Pool, queue: &str, job: &str) {
+    let mut ticker = tokio::time::interval(Duration::from_secs(LEASE_TTL_SECONDS / 3));
+    ticker.tick().await;
+    loop {
+        ticker.tick().await;
+        if let Err(e) = renew(pool, queue, job).await {
+            tracing::warn!(queue, error = %e, "failed to renew job lease");
+        }
+    }
+}
+
+async fn renew(pool: &Pool, queue: &str, job: &str) -> Result<()> {
+    let mut conn = pool.get().await.map_err(|e| Error::Queue(e.to_string()))?;
+    let _: i64 = Script::new(RENEW_SCRIPT)
+        .key(keys::processing(queue))
+        .arg(LEASE_TTL_SECONDS)
+        .arg(job)
+        .invoke_async(&mut conn)
+        .await
+        .map_err(|e| Error::Queue(e.to_string()))?;
+    Ok(())
+}
+
+/// Mark a taken job as done, whether it succeeded or failed
+pub async fn release(conn: &mut Connection, queue: &str, job: &str) -> Result<()> {
+    conn.zrem::<_, _, ()>(keys::processing(queue), job)
+        .await
+        .map_err(|e| Error::Queue(e.to_string()))
+}
+
+/// Requeues jobs whose worker stopped renewing their lease
+pub struct LeaseReaper {
+    pool: Pool,
+    queues: Vec<&'static str>,
+}
+
+impl LeaseReaper {
+    pub fn new(pool: Pool, queues: &[&'static str]) -> Self {
+        Self {
+            pool,
+            queues: queues.to_vec(),
+        }
+    }
+
+    /// Requeue every abandoned job, returning how many were requeued
+    pub async fn requeue_abandoned(&self) -> Result<usize> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::Queue(e.to_string()))?;
+        let mut requeued = 0;
+        for queue in &self.queues {
+            let count: usize = Script::new(REQUEUE_SCRIPT)
+                .key(*queue)
+                .key(keys::processing(queue))
+                .invoke_async(&mut conn)
+                .await
+                .map_err(|e| Error::Queue(e.to_string()))?;
+            requeued += count;
+        }
+        Ok(requeued)
+    }
+
+    /// Requeue now and then every `interval`, forever
+    pub async fn run(self, interval: Duration) {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            match self.requeue_abandoned().await {
+                Ok(0) => {}
+                Ok(count) => tracing::warn!(count, "requeued abandoned jobs"),
+                Err(e) => tracing::error!(error = %e, "requeueing abandoned jobs failed"),
+            }
+        }
+    }
+}
@@ -0,0 +1,61 @@
+//! Redis-backed handoff queue for escalations to live agents.
+
+use agent::tools::HandoffQueue;
+use async_trait::async_trait;
+use common::models::{ConversationState, HandoffRequest};
+use common::queue::queues;
+use common::{Error, Result};
+use db::{ConversationRepository, DbPool};
+use deadpool_redis::{redis::AsyncCommands, Pool};
+
+/// Marks conversations as waiting for a live agent and queues them on
+/// `agentic:handoff` for the operators
+#[derive(Clone)]
+pub struct RedisHandoffQueue {
+    pool: Pool,
+    conversations: ConversationRepository,
+}
+
+impl RedisHandoffQueue {
+    pub fn new(pool: Pool, db_pool: DbPool) -> Self {
+        Self {
+            pool,
+            conversations: ConversationRepository::new(db_pool),
+        }
+    }
+}
+
+#[async_trait]
+impl HandoffQueue for RedisHandoffQueue {
+    async fn request_handoff(&self, request: HandoffRequest) -> Result<ConversationState> {
+        let conversations = self.conversations.clone();
+        let conversation_id = request.conversation_id;
+        let reason = request.reason.clone();
+        // State of a conversation that could not be handed off
+        let unchanged = tokio::task::spawn_blocking(move || {
+            match conversations.request_handoff(&conversation_id, &reason)? {
+                Some(_) => Ok(None),
+                None => conversations.state(&conversation_id).map(Some),
+            }
+        })
+        .await
+        .map_err(|e| Error::Internal(e.to_string()))??;
+
+        // Already waiting for or handled by an operator
+        if let Some(state) = unchanged {
+            return Ok(state);
+        }
+
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::Queue(e.to_string()))?;
+        conn.lpush::<_, _, ()>(queues::HANDOFF_QUEUE, serde_json::to_string(&request)?)
+            .await
+            .map_err(|e| Error::Queue(e.to_string()))?;
+
+        tracing::info!(conversation_id = %conversation_id, "conversation handed off");
+        Ok(ConversationState::WaitingHuman)
+    }
+}
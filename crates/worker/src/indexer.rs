@@ -0,0 +1,135 @@
+//! Embedding of catalog rows for semantic search.
+//!
+//! Products and FAQs with a missing or outdated embedding are re-embedded
+//! in batches. The worker runs a pass at startup and then on an interval, so
+//! rows created or edited through any path are picked up.
+
+use common::{Error, Result};
+use db::{DbPool, FaqRepository, ProductRepository};
+use rag_core::EmbeddingModel;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Rows embedded per request to the embedding model
+const BATCH_SIZE: usize = 64;
+
+/// Keeps the product and FAQ embeddings up to date
+pub struct CatalogIndexer {
+    products: ProductRepository,
+    faqs: FaqRepository,
+    model: Arc<dyn EmbeddingModel>,
+    model_name: String,
+}
+
+impl CatalogIndexer {
+    /// Index with `model`, recording `model_name` on each embedding
+    pub fn new(
+        pool: DbPool,
+        model: Arc<dyn EmbeddingModel>,
+        model_name: impl Into<String>,
+    ) -> Self {
+        Self {
+            products: ProductRepository::new(pool.clone()),
+            faqs: FaqRepository::new(pool),
+            model,
+            model_name: model_name.into(),
+        }
+    }
+
+    /// Embed every stale product, returning how many were embedded
+    pub async fn index_products(&self) -> Result<usize> {
+        let mut indexed = 0;
+        loop {
+            let products = self.products.clone();
+            let model_name = self.model_name.clone();
+            let batch = tokio::task::spawn_blocking(move || {
+                products.stale_embeddings(&model_name, BATCH_SIZE)
+            })
+            .await
+            .map_err(|e| Error::Internal(e.to_string()))??;
+            if batch.is_empty() {
+                return Ok(indexed);
+            }
+
+            let texts: Vec<String> = batch.iter().map(|p| p.embedding_text()).collect();
+            let texts: Vec<&str> = texts.iter().map(String::as_str).collect();
+            let embeddings = self.model.embed_batch(&texts).await?;
+
+            let products = self.products.clone();
+            let model_name = self.model_name.clone();
+            let ids: Vec<_> = batch.iter().map(|p| p.id).collect();
+            tokio::task::spawn_blocking(move || {
+                ids.iter().zip(&embeddings).try_for_each(|(id, embedding)| {
+                    products.upsert_embedding(id, embedding, &model_name)
+                })
+            })
+            .await
+            .map_err(|e| Error::Internal(e.to_string()))??;
+
+            indexed += batch.len();
+            if batch.len() < BATCH_SIZE {
+                return Ok(indexed);
+            }
+        }
+    }
+
+    /// Embed every stale FAQ, returning how many were embedded
+    pub async fn index_faqs(&self) -> Result<usize> {
+        let mut indexed = 0;
+        loop {
+            let faqs = self.faqs.clone();
+            let model_name = self.model_name.clone();
+            let batch =
+                tokio::task::spawn_blocking(move || faqs.stale_embeddings(&model_name, BATCH_SIZE))
+                    .await
+                    .map_err(|e| Error::Internal(e.to_string()))??;
+            if batch.is_empty() {
+                return Ok(indexed);
+            }
+
+            let texts: Vec<String> = batch
+                .iter()
+                .map(|(faq, _)| format!("{}\n{}", faq.question, faq.answer))
+                .collect();
+            let texts: Vec<&str> = texts.iter().map(String::as_str).collect();
+            let embeddings = self.model.embed_batch(&texts).await?;
+
+            let faqs = self.faqs.clone();
+            let model_name = self.model_name.clone();
+            let count = batch.len();
+            tokio::task::spawn_blocking(move || {
+                batch
+                    .iter()
+                    .zip(&embeddings)
+                    .try_for_each(|((faq, content_hash), embedding)| {
+                        faqs.upsert_embedding(&faq.id, embedding, &model_name, content_hash)
+                    })
+            })
+            .await
+            .map_err(|e| Error::Internal(e.to_string()))??;
+
+            indexed += count;
+            if count < BATCH_SIZE {
+                return Ok(indexed);
+            }
+        }
+    }
+
+    /// Index now and then every `interval`, forever
+    pub async fn run(self, interval: Duration) {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            match self.index_products().await {
+                Ok(0) => {}
+                Ok(count) => tracing::info!(count, "embedded products"),
+                Err(e) => tracing::error!(error = %e, "product indexing failed"),
+            }
+            match self.index_faqs().await {
+                Ok(0) => {}
+                Ok(count) => tracing::info!(count, "embedded FAQs"),
+                Err(e) => tracing::error!(error = %e, "FAQ indexing failed"),
+            }
+        }
+    }
+}
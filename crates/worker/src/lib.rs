@@ -5,11 +5,28 @@
 //! - Job processors using apalis
 //! - Queue management
 //! - Job consumer for processing queued jobs
+//! - At-least-once delivery of agent jobs across worker restarts
+//! - Redis checkpoints for pausable agent runs
+//! - Live progress events for chat jobs
+//! - Handoff of conversations to live agents
+//! - Embedding of catalog rows for semantic search
+//! - Periodic expiry of appointment holds
 
+pub mod checkpoint;
 pub mod consumer;
+pub mod delivery;
+pub mod handoff;
+pub mod indexer;
 pub mod jobs;
+pub mod maintenance;
 pub mod processors;
+pub mod progress;
 pub mod queue;
 
+pub use checkpoint::RedisCheckpointStore;
 pub use consumer::{JobConsumer, WorkerState};
+pub use handoff::RedisHandoffQueue;
+pub use indexer::CatalogIndexer;
 pub use jobs::{EmbedDocumentJob, IndexDocumentJob, ProcessChatJob};
+pub use maintenance::HoldExpirer;
+pub use progress::RedisProgressPublisher;
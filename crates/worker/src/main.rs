@@ -1,6 +1,11 @@
+use db::DbPool;
+use rag_core::{EmbeddingModel, OpenAiEmbeddingModel, QdrantVectorStore, Retriever};
+use std::sync::Arc;
+use std::time::Duration;
+use storage::StorageClient;
 use tracing::info;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
-use worker::{consumer, JobConsumer, WorkerState};
+use worker::{consumer, CatalogIndexer, HoldExpirer, JobConsumer, WorkerState};
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -18,12 +23,67 @@ async fn main() -> anyhow::Result<()> {
     let redis_pool = consumer::create_pool(&redis_url)?;
     info!("Redis pool initialized");
 
+    let db_pool = match std::env::var("DATABASE_URL") {
+        Ok(database_url) => {
+            let pool = DbPool::new(&database_url, 4)?;
+            info!("Database pool initialized");
+            Some(pool)
+        }
+        Err(_) => None,
+    };
+
+    let storage_client = StorageClient::from_env()?;
+
     let concurrency: usize = std::env::var("WORKER_CONCURRENCY")
         .unwrap_or_else(|_| "4".into())
         .parse()
         .unwrap_or(4);
 
-    let state = WorkerState { redis_pool };
+    let approval_tools: Vec<String> = std::env::var("AGENT_APPROVAL_TOOLS")
+        .unwrap_or_default()
+        .split(',')
+        .map(|tool| tool.trim().to_string())
+        .filter(|tool| !tool.is_empty())
+        .collect();
+
+    if let Some(pool) = &db_pool {
+        let interval: u64 = std::env::var("HOLD_EXPIRY_INTERVAL_SECS")
+            .unwrap_or_else(|_| "60".into())
+            .parse()
+            .unwrap_or(60);
+        tokio::spawn(HoldExpirer::new(pool.clone()).run(Duration::from_secs(interval)));
+    }
+
+    let embedding_model = OpenAiEmbeddingModel::from_env();
+    if let (Some(model), Some(pool)) = (&embedding_model, &db_pool) {
+        let interval: u64 = std::env::var("CATALOG_INDEX_INTERVAL_SECS")
+            .unwrap_or_else(|_| "300".into())
+            .parse()
+            .unwrap_or(300);
+        let indexer = CatalogIndexer::new(pool.clone(), Arc::new(model.clone()), model.name());
+        tokio::spawn(indexer.run(Duration::from_secs(interval)));
+        info!(interval, "Catalog indexing enabled");
+    }
+
+    let retriever = match (embedding_model.clone(), QdrantVectorStore::from_env()?) {
+        (Some(model), Some(store)) => {
+            if model.dimension() > 0 {
+                store.ensure_collection(model.dimension()).await?;
+            }
+            info!("Knowledge base retrieval enabled");
+            Some(Arc::new(Retriever::new(model, store)) as _)
+        }
+        _ => None,
+    };
+
+    let state = WorkerState {
+        redis_pool,
+        db_pool,
+        storage_client,
+        approval_tools,
+        retriever,
+        embedding_model: embedding_model.map(|model| Arc::new(model) as _),
+    };
     let consumer = JobConsumer::new(state, concurrency);
 
     info!(concurrency, "worker started");
@@ -0,0 +1,43 @@
+//! Periodic upkeep of database state.
+//!
+//! Expired appointment holds no longer take a seat, but stay `held` until
+//! marked `expired`. The worker marks them on an interval so staff see the
+//! real status without a read having to write.
+
+use common::{Error, Result};
+use db::{AppointmentRepository, DbPool};
+use std::time::Duration;
+
+/// Marks appointment holds past their expiry as expired
+pub struct HoldExpirer {
+    appointments: AppointmentRepository,
+}
+
+impl HoldExpirer {
+    pub fn new(pool: DbPool) -> Self {
+        Self {
+            appointments: AppointmentRepository::new(pool),
+        }
+    }
+
+    /// Expire every overdue hold, returning how many were expired
+    pub async fn expire_holds(&self) -> Result<usize> {
+        let appointments = self.appointments.clone();
+        tokio::task::spawn_blocking(move || appointments.expire_holds())
+            .await
+            .map_err(|e| Error::Internal(e.to_string()))?
+    }
+
+    /// Expire now and then every `interval`, forever
+    pub async fn run(self, interval: Duration) {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            match self.expire_holds().await {
+                Ok(0) => {}
+                Ok(count) => tracing::info!(count, "expired appointment holds"),
+                Err(e) => tracing::error!(error = %e, "hold expiry failed"),
+            }
+        }
+    }
+}
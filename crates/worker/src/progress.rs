@@ -0,0 +1,69 @@
+//! Live progress reporting for chat jobs.
+
+use agent::events::{ReActEvent, ReActEventListener};
+use async_trait::async_trait;
+use common::queue::{keys, JobResult, RESULT_TTL_SECONDS};
+use common::QueueJobStatus;
+use deadpool_redis::{redis::AsyncCommands, Pool};
+use uuid::Uuid;
+
+/// Publishes agent step events of a job
+///
+/// Each event is published as JSON on `agentic:events:{job_id}`, and the
+/// job status is updated with a short progress message so clients polling
+/// `GET /chat/jobs/:job_id` see e.g. "Using product_search...". That endpoint
+/// is public, so the status gets only the message, the event kind and its
+/// timing; tool inputs and outputs stay on the channel.
+pub struct RedisProgressPublisher {
+    pool: Pool,
+    job_id: Uuid,
+}
+
+impl RedisProgressPublisher {
+    pub fn new(pool: Pool, job_id: Uuid) -> Self {
+        Self { pool, job_id }
+    }
+
+    async fn publish(&self, event: &ReActEvent) -> common::Result<()> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| common::Error::Queue(e.to_string()))?;
+
+        let payload = serde_json::to_string(event)?;
+        conn.publish::<_, _, ()>(keys::job_events(&self.job_id), &payload)
+            .await
+            .map_err(|e| common::Error::Queue(e.to_string()))?;
+
+        let status = JobResult {
+            job_id: self.job_id,
+            status: QueueJobStatus::Processing,
+            result: Some(serde_json::json!({
+                "progress": event.progress_message(),
+                "event": event.kind.name(),
+                "iteration": event.iteration,
+                "timestamp": event.timestamp,
+                "duration_ms": event.kind.duration_ms(),
+            })),
+            error: None,
+            completed_at: None,
+        };
+        conn.set_ex::<_, _, ()>(
+            keys::job_status(&self.job_id),
+            serde_json::to_string(&status)?,
+            RESULT_TTL_SECONDS,
+        )
+        .await
+        .map_err(|e| common::Error::Queue(e.to_string()))
+    }
+}
+
+#[async_trait]
+impl ReActEventListener for RedisProgressPublisher {
+    async fn on_event(&self, event: &ReActEvent) {
+        if let Err(e) = self.publish(event).await {
+            tracing::warn!(job_id = %self.job_id, error = %e, "failed to publish progress");
+        }
+    }
+}